@@ -1,13 +1,18 @@
 mod import;
+mod license;
 mod startup;
 
 pub use import::{ImportAsset, ImportAssetResponse};
+pub use license::check_redistribution;
 
 use crate::api;
-use crate::error::ApiResult;
+use crate::error::{ApiError, ApiResult};
 use bevy::prelude::*;
 use bevy_flurx::prelude::once;
-use homunculus_core::prelude::{AssetId, AssetRegistry, AssetType};
+use homunculus_core::prelude::{
+    AssetId, AssetRegistry, AssetType, VrmDiagnostics, VrmLicense, VrmaMetadata,
+};
+use homunculus_utils::vrm::read_vrm_diagnostics;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -17,6 +22,8 @@ pub struct AssetFilter {
     pub asset_type: Option<AssetType>,
     #[serde(rename = "mod")]
     pub mod_name: Option<String>,
+    /// Only assets carrying this tag.
+    pub tag: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +36,14 @@ pub struct AssetInfo {
     pub mod_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Duration, loopability, bones and root motion of VRMA animations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vrma: Option<VrmaMetadata>,
+    /// License meta of VRM models, known once they are imported or first attached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<VrmLicense>,
 }
 
 api!(
@@ -55,6 +70,47 @@ impl AssetsApi {
             })
             .await?
     }
+
+    /// Validates a VRM asset without rendering it.
+    ///
+    /// Fails with [`ApiError::AssetTypeMismatch`] for assets that are not VRMs.
+    pub async fn diagnostics(&self, asset_id: String) -> ApiResult<VrmDiagnostics> {
+        let id = AssetId::new(&asset_id);
+        let path = self
+            .0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(resolve_vrm_path).with(asset_id))
+                    .await
+            })
+            .await??;
+        // Reading the model can take a while, so keep it off the async runtime.
+        let read_path = path.clone();
+        tokio::task::spawn_blocking(move || read_vrm_diagnostics(&read_path))
+            .await
+            .map_err(|e| ApiError::FailedLoad(format!("VRM validation task panicked: {e}")))?
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => ApiError::AssetNotFound(id),
+                _ => ApiError::InvalidInput(format!("Failed to read {}: {e}", path.display())),
+            })
+    }
+}
+
+fn resolve_vrm_path(
+    In(asset_id): In<String>,
+    registry: Res<AssetRegistry>,
+) -> ApiResult<std::path::PathBuf> {
+    let id = AssetId::new(&asset_id);
+    let entry = registry
+        .get(&asset_id)
+        .ok_or_else(|| ApiError::AssetNotFound(id.clone()))?;
+    if entry.asset_type != AssetType::Vrm {
+        return Err(ApiError::AssetTypeMismatch {
+            id,
+            expected: AssetType::Vrm,
+            actual: entry.asset_type.clone(),
+        });
+    }
+    Ok(entry.absolute_path.clone())
 }
 
 fn resolve_file_path(
@@ -63,7 +119,7 @@ fn resolve_file_path(
 ) -> ApiResult<std::path::PathBuf> {
     let entry = registry
         .get(&asset_id)
-        .ok_or_else(|| crate::error::ApiError::AssetNotFound(AssetId::new(&asset_id)))?;
+        .ok_or_else(|| ApiError::AssetNotFound(AssetId::new(&asset_id)))?;
     Ok(entry.absolute_path.clone())
 }
 
@@ -76,21 +132,27 @@ fn list_assets(In(filter): In<AssetFilter>, registry: Res<AssetRegistry>) -> Vec
                 .as_ref()
                 .is_none_or(|t| t == &e.asset_type)
                 && filter.mod_name.as_ref().is_none_or(|m| m == &e.mod_name)
+                && filter.tag.as_ref().is_none_or(|t| e.tags.contains(t))
         })
         .map(|e| AssetInfo {
             id: e.id.clone(),
             asset_type: e.asset_type.clone(),
             mod_name: e.mod_name.clone(),
             description: e.description.clone(),
+            tags: e.tags.clone(),
+            vrma: e.vrma.clone(),
+            license: e.license.clone(),
         })
         .collect()
 }
 
-/// Plugin that restores imported assets from the database at startup.
+/// Plugin that restores imported assets from the database at startup and records
+/// the license of VRMs as they are attached.
 pub struct AssetsApiPlugin;
 
 impl Plugin for AssetsApiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, startup::restore_imported_assets);
+        app.add_systems(Startup, startup::restore_imported_assets)
+            .add_systems(Update, license::record_attached_licenses);
     }
 }
@@ -2,14 +2,19 @@
 //!
 //! Copies a local file into `~/.homunculus/assets/`, registers it in the
 //! [`AssetRegistry`], and persists the record to the `imported_assets` DB table.
+//! VRM models are validated on the way in and their license meta is stored with
+//! the record.
 
 use crate::assets::AssetsApi;
 use crate::error::{ApiError, ApiResult};
 use bevy::prelude::*;
 use bevy_flurx::prelude::once;
-use homunculus_core::prelude::{AssetEntry, AssetId, AssetRegistry, AssetType};
+use homunculus_core::prelude::{
+    AssetEntry, AssetId, AssetRegistry, AssetType, VrmDiagnostics, VrmLicense, read_vrma_metadata,
+};
 use homunculus_prefs::PrefsDatabase;
 use homunculus_utils::path::homunculus_dir;
+use homunculus_utils::vrm::read_vrm_diagnostics;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -35,6 +40,9 @@ pub struct ImportAsset {
 #[serde(rename_all = "camelCase")]
 pub struct ImportAssetResponse {
     pub asset_id: AssetId,
+    /// Validation report, for VRM models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<VrmDiagnostics>,
 }
 
 impl AssetsApi {
@@ -42,23 +50,52 @@ impl AssetsApi {
     ///
     /// Validates the source path, copies the file to managed storage,
     /// registers it in the ECS `AssetRegistry`, and persists the record.
+    /// VRM models are validated and the report is returned; problems are logged
+    /// but do not fail the import.
     pub async fn import(&self, args: ImportAsset) -> ApiResult<ImportAssetResponse> {
         let source = validate_source(&args.source_path)?;
         let dest = copy_to_managed_storage(&source, &args.asset_id)?;
+        let diagnostics = diagnose(&args, &dest).await;
 
         let dest_str = dest.to_string_lossy().to_string();
         let source_str = args.source_path.clone();
+        let license = diagnostics.as_ref().and_then(|d| d.license.clone());
 
         self.0
             .schedule(move |task| async move {
                 task.will(
                     Update,
-                    once::run(register_and_persist).with((args, dest_str, source_str)),
+                    once::run(register_and_persist).with((args, dest_str, source_str, license)),
                 )
                 .await
             })
             .await?
+            .map(|asset_id| ImportAssetResponse {
+                asset_id,
+                diagnostics,
+            })
+    }
+}
+
+/// Validates an imported VRM and logs what is wrong with it.
+async fn diagnose(args: &ImportAsset, dest: &Path) -> Option<VrmDiagnostics> {
+    if args.asset_type != AssetType::Vrm {
+        return None;
+    }
+    let path = dest.to_path_buf();
+    let diagnostics = tokio::task::spawn_blocking(move || read_vrm_diagnostics(&path))
+        .await
+        .inspect_err(|e| warn!("VRM validation task of {} panicked: {e}", args.asset_id))
+        .ok()?
+        .inspect_err(|e| warn!("Failed to validate VRM {}: {e}", args.asset_id))
+        .ok()?;
+    for error in &diagnostics.errors {
+        warn!("VRM {} is invalid: {error}", args.asset_id);
+    }
+    for warning in &diagnostics.warnings {
+        warn!("VRM {}: {warning}", args.asset_id);
     }
+    Some(diagnostics)
 }
 
 /// Validates that the source file exists and is a file.
@@ -107,12 +144,17 @@ fn derive_filename(source: &Path, asset_id: &str) -> String {
 }
 
 /// Bevy one-shot system: registers the asset in `AssetRegistry` and persists
-/// the record in the `imported_assets` table.
+/// the record, including any VRM license meta, in the `imported_assets` table.
 fn register_and_persist(
-    In((args, dest_path, source_path)): In<(ImportAsset, String, String)>,
+    In((args, dest_path, source_path, license)): In<(
+        ImportAsset,
+        String,
+        String,
+        Option<VrmLicense>,
+    )>,
     mut registry: ResMut<AssetRegistry>,
     prefs: NonSend<PrefsDatabase>,
-) -> ApiResult<ImportAssetResponse> {
+) -> ApiResult<AssetId> {
     let asset_id = AssetId::new(&args.asset_id);
     let dest = PathBuf::from(&dest_path);
     let filename = derive_filename(&PathBuf::from(&source_path), &args.asset_id);
@@ -120,9 +162,12 @@ fn register_and_persist(
     let entry = AssetEntry {
         id: asset_id.clone(),
         path: PathBuf::from(&filename),
+        vrma: read_vrma_metadata(&args.asset_type, &dest),
+        license: license.clone(),
         absolute_path: dest,
         asset_type: args.asset_type.clone(),
         description: args.description.clone(),
+        tags: Vec::new(),
         mod_name: "local".to_string(),
     };
     registry.register_imported(entry);
@@ -137,9 +182,10 @@ fn register_and_persist(
             args.description.as_deref(),
             Some(&source_path),
         )
+        .and_then(|()| prefs.set_imported_asset_license(&args.asset_id, license.as_ref()))
         .map_err(|e| ApiError::Sql(e.to_string()))?;
 
-    Ok(ImportAssetResponse { asset_id })
+    Ok(asset_id)
 }
 
 /// Serializes an `AssetType` to its lowercase string representation.
@@ -0,0 +1,155 @@
+//! Reads VRM license meta when a model is attached.
+//!
+//! Imported VRMs get their license at import time; MOD-provided VRMs (and VRMs
+//! imported before licenses were stored) get it the first time a persona wears them.
+//! Licenses of imported VRMs are also saved to the `imported_assets` table.
+//! [`check_redistribution`] tells whether a model may be bundled into an export.
+
+use crate::error::{ApiError, ApiResult};
+use crate::events::try_recv;
+use async_broadcast::Receiver;
+use bevy::prelude::*;
+use homunculus_core::prelude::{
+    AssetRegistry, AssetType, VrmAttachedEvent, VrmEvent, VrmEventReceiver, VrmLicense,
+};
+use homunculus_prefs::PrefsDatabase;
+use homunculus_utils::vrm::read_vrm_license;
+
+/// Fills in the license of attached VRM assets that do not have one yet.
+pub(super) fn record_attached_licenses(
+    mut rx: Local<Option<Receiver<VrmEvent<VrmAttachedEvent>>>>,
+    source: Option<Res<VrmEventReceiver<VrmAttachedEvent>>>,
+    mut registry: ResMut<AssetRegistry>,
+    prefs: NonSend<PrefsDatabase>,
+) {
+    let Some(source) = source else {
+        return;
+    };
+    let rx = rx.get_or_insert_with(|| source.new_receiver());
+    while let Some(event) = try_recv(rx) {
+        let asset_id = event.payload.asset_id;
+        let Some(entry) = registry.get_mut(&asset_id) else {
+            continue;
+        };
+        if entry.asset_type != AssetType::Vrm || entry.license.is_some() {
+            continue;
+        }
+        let license = match read_vrm_license(&entry.absolute_path) {
+            Ok(license) => license,
+            Err(e) => {
+                warn!("Failed to read the license of VRM {asset_id}: {e}");
+                continue;
+            }
+        };
+        if entry.mod_name == "local"
+            && let Err(e) = prefs.set_imported_asset_license(&asset_id, license.as_ref())
+        {
+            warn!("Failed to save the license of VRM {asset_id}: {e}");
+        }
+        entry.license = license;
+    }
+}
+
+/// Fails when the license of a model forbids redistributing it, unless the caller
+/// explicitly overrides it. Models whose meta does not say are allowed.
+pub fn check_redistribution(license: Option<&VrmLicense>, allow_override: bool) -> ApiResult {
+    match license {
+        Some(license) if license.allow_redistribution == Some(false) && !allow_override => Err(
+            ApiError::RedistributionForbidden(license.name.clone().unwrap_or_default()),
+        ),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homunculus_core::prelude::{AssetEntry, AssetId};
+    use std::path::PathBuf;
+
+    /// A `.glb` file holding only a JSON chunk.
+    fn glb(json: &str) -> Vec<u8> {
+        let mut json = json.as_bytes().to_vec();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bytes = b"glTF".to_vec();
+        bytes.extend(2u32.to_le_bytes());
+        bytes.extend((20 + json.len() as u32).to_le_bytes());
+        bytes.extend((json.len() as u32).to_le_bytes());
+        bytes.extend(b"JSON");
+        bytes.extend(json);
+        bytes
+    }
+
+    #[test]
+    fn records_the_license_of_attached_vrms() {
+        let path = std::env::temp_dir().join("homunculus_attached_license.vrm");
+        let json = r#"{"extensions": {"VRMC_vrm": {"meta": {
+            "name": "Elmer", "allowRedistribution": false
+        }}}}"#;
+        std::fs::write(&path, glb(json)).unwrap();
+
+        let (sender, receiver) = async_broadcast::broadcast(16);
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<AssetRegistry>()
+            .insert_resource(VrmEventReceiver(receiver))
+            .insert_non_send_resource(PrefsDatabase::open_in_memory())
+            .add_systems(Update, record_attached_licenses);
+        app.world_mut()
+            .resource_mut::<AssetRegistry>()
+            .register_imported(AssetEntry {
+                id: AssetId::new("vrm:local:elmer"),
+                path: PathBuf::from("elmer.vrm"),
+                absolute_path: path.clone(),
+                asset_type: AssetType::Vrm,
+                description: None,
+                tags: Vec::new(),
+                vrma: None,
+                license: None,
+                mod_name: "local".to_string(),
+            });
+        app.world()
+            .non_send_resource::<PrefsDatabase>()
+            .upsert_imported_asset("vrm:local:elmer", None, "elmer.vrm", "vrm", None, None)
+            .unwrap();
+        app.update();
+
+        sender
+            .try_broadcast(VrmEvent {
+                vrm: Entity::PLACEHOLDER,
+                payload: VrmAttachedEvent {
+                    asset_id: "vrm:local:elmer".to_string(),
+                },
+            })
+            .unwrap();
+        app.update();
+        std::fs::remove_file(&path).ok();
+
+        let registry = app.world().resource::<AssetRegistry>();
+        let license = registry.get("vrm:local:elmer").unwrap().license.clone();
+        assert_eq!(license.as_ref().unwrap().allow_redistribution, Some(false));
+        let stored = app
+            .world()
+            .non_send_resource::<PrefsDatabase>()
+            .list_imported_assets()
+            .unwrap();
+        assert_eq!(stored[0].license, license);
+    }
+
+    #[test]
+    fn redistribution_follows_the_license_unless_overridden() {
+        let license = |allow_redistribution| VrmLicense {
+            name: Some("Elmer".to_string()),
+            allow_redistribution,
+            ..default()
+        };
+        assert!(matches!(
+            check_redistribution(Some(&license(Some(false))), false),
+            Err(ApiError::RedistributionForbidden(name)) if name == "Elmer"
+        ));
+        assert!(check_redistribution(Some(&license(Some(false))), true).is_ok());
+        assert!(check_redistribution(Some(&license(Some(true))), false).is_ok());
+        assert!(check_redistribution(Some(&license(None)), false).is_ok());
+        assert!(check_redistribution(None, false).is_ok());
+    }
+}
@@ -5,7 +5,7 @@
 //! engine restarts.
 
 use bevy::prelude::*;
-use homunculus_core::prelude::{AssetEntry, AssetId, AssetRegistry, AssetType};
+use homunculus_core::prelude::{AssetEntry, AssetId, AssetRegistry, AssetType, read_vrma_metadata};
 use homunculus_prefs::PrefsDatabase;
 use homunculus_utils::path::homunculus_dir;
 use std::path::PathBuf;
@@ -39,9 +39,12 @@ pub fn restore_imported_assets(mut registry: ResMut<AssetRegistry>, prefs: NonSe
         let entry = AssetEntry {
             id: AssetId::new(&asset.id),
             path: filename,
+            vrma: read_vrma_metadata(&asset_type, &absolute_path),
+            license: asset.license,
             absolute_path,
             asset_type,
             description: asset.description,
+            tags: Vec::new(),
             mod_name: "local".to_string(),
         };
         registry.register_imported(entry);
@@ -69,6 +72,7 @@ mod tests {
         assert_eq!(parse_asset_type("sound"), Some(AssetType::Sound));
         assert_eq!(parse_asset_type("image"), Some(AssetType::Image));
         assert_eq!(parse_asset_type("html"), Some(AssetType::Html));
+        assert_eq!(parse_asset_type("timeline"), Some(AssetType::Timeline));
     }
 
     #[test]
@@ -98,7 +98,7 @@ impl AudioBgmApi {
 
 // --- SE Systems ---
 
-fn play_se(
+pub(crate) fn play_se(
     In((asset_id, volume, speed, panning)): In<(AssetId, f64, f64, f64)>,
     mut commands: Commands,
     asset_resolver: AssetResolver,
@@ -0,0 +1,728 @@
+//! Batched execution of API operations.
+//!
+//! A batch runs an ordered list of operations inside a single reactor task, so
+//! a choreographed reaction (expression, animation, webview, sound) needs one
+//! HTTP round trip instead of one per step. In [`BatchMode::SameFrame`] every
+//! step is applied in the same frame; in [`BatchMode::Sequential`] steps run one
+//! after another and may wait.
+//!
+//! Later steps can use the results of earlier ones: any `{"$ref": "<step id>"}`
+//! object in a step's `args` is replaced with that step's result before the step
+//! runs. A dotted path (`"panel.entity"`) selects a field of an object result.
+
+use crate::api;
+use crate::audio::play_se;
+use crate::error::{ApiError, ApiResult};
+use crate::signals::send::send_signal;
+use crate::vrm::expressions::{do_clear_expressions, do_modify_expressions, do_set_expressions};
+use crate::vrm::initialized;
+use crate::vrm::vrma::fetch_vrma;
+use crate::vrma::{Repeat, play, stop, wait_animation_finished};
+use crate::webview::close::close;
+#[cfg(not(feature = "gui"))]
+use crate::webview::headless::WEBVIEWS_UNSUPPORTED;
+#[cfg(feature = "gui")]
+use crate::webview::open::create_global_webview;
+use bevy::ecs::system::SystemInput;
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use homunculus_core::prelude::{AssetId, PersonaId, PersonaIndex, WebviewOpenOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Longest pause a single `wait` step may request.
+const MAX_WAIT_MS: u64 = 60_000;
+
+/// How long a batch waits for a VRMA to finish loading before failing the step.
+const VRMA_LOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+api!(
+    /// Runs ordered lists of operations in a single reactor task.
+    BatchApi
+);
+
+/// How the steps of a batch are scheduled.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BatchMode {
+    /// Apply every step in one frame, so the effects appear together.
+    ///
+    /// VRMA animations are loaded before that frame. `wait` steps and
+    /// `waitForCompletion` are rejected.
+    SameFrame,
+    /// Apply steps one after another, honoring `wait` steps and `waitForCompletion`.
+    #[default]
+    Sequential,
+}
+
+/// What happens to the remaining steps after a step fails.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BatchErrorPolicy {
+    /// Skip the remaining steps.
+    #[default]
+    Stop,
+    /// Run the remaining steps anyway.
+    Continue,
+}
+
+/// Body of `POST /batch`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+    #[serde(default)]
+    pub mode: BatchMode,
+    #[serde(default)]
+    pub on_error: BatchErrorPolicy,
+    pub steps: Vec<BatchStep>,
+}
+
+/// One operation of a batch.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchStep {
+    /// Name used by later steps to reference this step's result.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Operation name, e.g. `vrma.play` or `webview.open`.
+    pub op: String,
+    /// Operation arguments. `{"$ref": "<id>"}` objects are replaced with earlier results.
+    #[serde(default = "empty_args")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub args: Value,
+}
+
+fn empty_args() -> Value {
+    Value::Object(Default::default())
+}
+
+/// The operations a batch step can perform, keyed by [`BatchStep::op`].
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", content = "args", rename_all_fields = "camelCase")]
+pub enum BatchOperation {
+    /// Replaces all expression weights.
+    #[serde(rename = "expressions.set")]
+    SetExpressions {
+        persona: PersonaId,
+        weights: HashMap<String, f32>,
+    },
+    /// Merges expression weights into the current ones.
+    #[serde(rename = "expressions.modify")]
+    ModifyExpressions {
+        persona: PersonaId,
+        weights: HashMap<String, f32>,
+    },
+    #[serde(rename = "expressions.clear")]
+    ClearExpressions { persona: PersonaId },
+    #[serde(rename = "vrma.play")]
+    PlayVrma {
+        persona: PersonaId,
+        asset: AssetId,
+        #[serde(default)]
+        repeat: Option<Repeat>,
+        #[serde(default)]
+        transition_secs: Option<f64>,
+        #[serde(default)]
+        reset_spring_bones: Option<bool>,
+        #[serde(default)]
+        wait_for_completion: bool,
+    },
+    #[serde(rename = "vrma.stop")]
+    StopVrma { persona: PersonaId, asset: AssetId },
+    /// Opens a webview; the result is its entity.
+    #[serde(rename = "webview.open")]
+    OpenWebview(WebviewOpenOptions),
+    #[serde(rename = "webview.close")]
+    CloseWebview { entity: Entity },
+    #[serde(rename = "se.play")]
+    PlaySe {
+        asset: AssetId,
+        #[serde(default = "default_one")]
+        volume: f64,
+        #[serde(default = "default_one")]
+        speed: f64,
+        #[serde(default)]
+        panning: f64,
+    },
+    #[serde(rename = "signal.send")]
+    SendSignal {
+        signal: String,
+        #[serde(default)]
+        payload: Value,
+    },
+    /// Pauses a sequential batch.
+    #[serde(rename = "wait")]
+    Wait { ms: u64 },
+}
+
+fn default_one() -> f64 {
+    1.0
+}
+
+impl BatchOperation {
+    /// The persona and VRMA asset this operation needs loaded before it is applied.
+    fn vrma(&self) -> Option<(&PersonaId, &AssetId)> {
+        match self {
+            Self::PlayVrma { persona, asset, .. } | Self::StopVrma { persona, asset } => {
+                Some((persona, asset))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of one step.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum BatchStepStatus {
+    Ok,
+    Error,
+    /// Not run because an earlier step failed.
+    Skipped,
+}
+
+/// Result of one step, in request order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BatchStepResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub op: String,
+    pub status: BatchStepStatus,
+    /// The step's result, e.g. the entity of an opened webview.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<Object>))]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response of `POST /batch`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BatchResponse {
+    /// `true` if every step succeeded.
+    pub ok: bool,
+    pub results: Vec<BatchStepResult>,
+}
+
+impl BatchApi {
+    /// Runs the steps of `request` in one reactor task.
+    ///
+    /// Fails only if the request itself is invalid; step failures are reported
+    /// in the per-step results.
+    pub async fn execute(&self, request: BatchRequest) -> ApiResult<BatchResponse> {
+        validate(&request)?;
+        self.0
+            .schedule(move |task| async move {
+                match request.mode {
+                    BatchMode::SameFrame => run_same_frame(&task, request).await,
+                    BatchMode::Sequential => run_sequential(&task, request).await,
+                }
+            })
+            .await
+    }
+}
+
+fn validate(request: &BatchRequest) -> ApiResult {
+    if request.steps.is_empty() {
+        return Err(ApiError::InvalidInput("batch has no steps".to_string()));
+    }
+    let mut ids = HashSet::new();
+    for id in request.steps.iter().filter_map(|step| step.id.as_deref()) {
+        if id.is_empty() || id.contains('.') {
+            return Err(ApiError::InvalidInput(format!(
+                "invalid step id '{id}': must be non-empty and contain no '.'"
+            )));
+        }
+        if !ids.insert(id) {
+            return Err(ApiError::InvalidInput(format!("duplicate step id '{id}'")));
+        }
+    }
+    // Referenced arguments are checked again when the step runs.
+    for step in &request.steps {
+        if let Ok(BatchOperation::Wait { ms }) = parse_operation(&step.op, step.args.clone()) {
+            check_wait(ms)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_wait(ms: u64) -> ApiResult {
+    if MAX_WAIT_MS < ms {
+        return Err(ApiError::InvalidInput(format!(
+            "wait ms must not exceed {MAX_WAIT_MS}"
+        )));
+    }
+    Ok(())
+}
+
+async fn run_sequential(task: &ReactorTask, request: BatchRequest) -> BatchResponse {
+    let mut run = BatchRun::new(request.on_error);
+    for step in request.steps {
+        let Some(operation) = run.prepare(&step) else {
+            continue;
+        };
+        let outcome = run_operation(task, operation).await;
+        run.record(&step, outcome);
+    }
+    run.finish()
+}
+
+async fn run_operation(task: &ReactorTask, operation: BatchOperation) -> ApiResult<Value> {
+    if let BatchOperation::Wait { ms } = operation {
+        check_wait(ms)?;
+        task.will(Update, delay::time().with(Duration::from_millis(ms)))
+            .await;
+        return Ok(Value::Null);
+    }
+    if let Some((persona, asset)) = operation.vrma() {
+        load_vrma(task, persona.clone(), asset.clone()).await?;
+    }
+    let wait = match &operation {
+        BatchOperation::PlayVrma {
+            persona,
+            asset,
+            wait_for_completion: true,
+            ..
+        } => Some((persona.clone(), asset.clone())),
+        _ => None,
+    };
+    let result = task
+        .will(Update, once::run(apply_one).with(operation))
+        .await?;
+    if let Some((persona, asset)) = wait
+        && let Some(duration) = task
+            .will(Update, once::run(vrma_duration).with((persona, asset)))
+            .await
+    {
+        task.will(Update, delay::time().with(duration)).await;
+    }
+    Ok(result)
+}
+
+async fn run_same_frame(task: &ReactorTask, request: BatchRequest) -> BatchResponse {
+    // Load animations first so that playing them does not hold back the frame.
+    // Steps whose persona or asset is a reference are resolved when applied.
+    for step in &request.steps {
+        if let Ok(operation) = parse_operation(&step.op, step.args.clone())
+            && let Some((persona, asset)) = operation.vrma()
+        {
+            // A failure here is reported when the step is applied.
+            let _ = load_vrma(task, persona.clone(), asset.clone()).await;
+        }
+    }
+    let run = BatchRun::new(request.on_error);
+    task.will(Update, once::run(apply_all).with((run, request.steps)))
+        .await
+        .finish()
+}
+
+/// Resolves the persona and loads the VRMA under its VRM, waiting until it is ready.
+async fn load_vrma(task: &ReactorTask, persona: PersonaId, asset: AssetId) -> ApiResult {
+    let vrm = task
+        .will(Update, once::run(persona_entity).with(persona))
+        .await?;
+    let vrma = task
+        .will(Update, once::run(fetch_vrma).with((vrm, asset)))
+        .await
+        .ok_or(ApiError::EntityNotFound)?;
+    let loaded = task
+        .will(
+            Update,
+            wait::either(
+                wait::until(initialized).with(vrma),
+                delay::time().with(VRMA_LOAD_TIMEOUT),
+            ),
+        )
+        .await;
+    match loaded {
+        Either::Left(_) => Ok(()),
+        Either::Right(_) => Err(ApiError::Timeout(format!(
+            "VRMA did not finish loading within {}s",
+            VRMA_LOAD_TIMEOUT.as_secs()
+        ))),
+    }
+}
+
+/// Per-batch bookkeeping: step results and the outputs later steps can reference.
+struct BatchRun {
+    on_error: BatchErrorPolicy,
+    halted: bool,
+    results: Vec<BatchStepResult>,
+    outputs: HashMap<String, Value>,
+}
+
+impl BatchRun {
+    fn new(on_error: BatchErrorPolicy) -> Self {
+        Self {
+            on_error,
+            halted: false,
+            results: Vec::new(),
+            outputs: HashMap::new(),
+        }
+    }
+
+    /// Resolves the step's references and parses it.
+    ///
+    /// Returns `None`, after recording why, if the step must not run.
+    fn prepare(&mut self, step: &BatchStep) -> Option<BatchOperation> {
+        if self.halted {
+            self.results.push(BatchStepResult {
+                id: step.id.clone(),
+                op: step.op.clone(),
+                status: BatchStepStatus::Skipped,
+                result: None,
+                error: None,
+            });
+            return None;
+        }
+        let parsed = resolve_refs(step.args.clone(), &self.outputs)
+            .and_then(|args| parse_operation(&step.op, args));
+        match parsed {
+            Ok(operation) => Some(operation),
+            Err(e) => {
+                self.record(step, Err(e));
+                None
+            }
+        }
+    }
+
+    fn record(&mut self, step: &BatchStep, outcome: ApiResult<Value>) {
+        let result = match outcome {
+            Ok(value) => {
+                if let Some(id) = &step.id {
+                    self.outputs.insert(id.clone(), value.clone());
+                }
+                BatchStepResult {
+                    id: step.id.clone(),
+                    op: step.op.clone(),
+                    status: BatchStepStatus::Ok,
+                    result: Some(value),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                self.halted = self.on_error == BatchErrorPolicy::Stop;
+                BatchStepResult {
+                    id: step.id.clone(),
+                    op: step.op.clone(),
+                    status: BatchStepStatus::Error,
+                    result: None,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        self.results.push(result);
+    }
+
+    fn finish(self) -> BatchResponse {
+        BatchResponse {
+            ok: self
+                .results
+                .iter()
+                .all(|result| result.status == BatchStepStatus::Ok),
+            results: self.results,
+        }
+    }
+}
+
+fn parse_operation(op: &str, args: Value) -> ApiResult<BatchOperation> {
+    serde_json::from_value(serde_json::json!({ "op": op, "args": args }))
+        .map_err(|e| ApiError::InvalidInput(format!("invalid '{op}' step: {e}")))
+}
+
+/// Replaces every `{"$ref": "<id>[.<field>...]"}` object in `args` with the referenced output.
+fn resolve_refs(args: Value, outputs: &HashMap<String, Value>) -> ApiResult<Value> {
+    match args {
+        Value::Object(object) => {
+            if object.len() == 1
+                && let Some(Value::String(reference)) = object.get("$ref")
+            {
+                return lookup_ref(reference, outputs);
+            }
+            object
+                .into_iter()
+                .map(|(key, value)| Ok((key, resolve_refs(value, outputs)?)))
+                .collect::<ApiResult<_>>()
+                .map(Value::Object)
+        }
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| resolve_refs(item, outputs))
+            .collect::<ApiResult<_>>()
+            .map(Value::Array),
+        other => Ok(other),
+    }
+}
+
+fn lookup_ref(reference: &str, outputs: &HashMap<String, Value>) -> ApiResult<Value> {
+    let mut path = reference.split('.');
+    let id = path.next().unwrap_or_default();
+    let mut value = outputs.get(id).ok_or_else(|| {
+        ApiError::InvalidInput(format!(
+            "reference '{reference}': no earlier successful step with id '{id}'"
+        ))
+    })?;
+    for field in path {
+        value = value.get(field).ok_or_else(|| {
+            ApiError::InvalidInput(format!("reference '{reference}': no field '{field}'"))
+        })?;
+    }
+    Ok(value.clone())
+}
+
+fn apply_one(In(operation): In<BatchOperation>, world: &mut World) -> ApiResult<Value> {
+    apply(world, operation)
+}
+
+fn apply_all(In((mut run, steps)): In<(BatchRun, Vec<BatchStep>)>, world: &mut World) -> BatchRun {
+    for step in steps {
+        let Some(operation) = run.prepare(&step) else {
+            continue;
+        };
+        let outcome = match operation {
+            BatchOperation::Wait { .. }
+            | BatchOperation::PlayVrma {
+                wait_for_completion: true,
+                ..
+            } => Err(ApiError::InvalidInput(
+                "waiting is only supported in sequential mode".to_string(),
+            )),
+            operation => apply(world, operation),
+        };
+        run.record(&step, outcome);
+    }
+    run
+}
+
+/// Applies one operation immediately, running the same systems as the dedicated endpoints.
+fn apply(world: &mut World, operation: BatchOperation) -> ApiResult<Value> {
+    match operation {
+        BatchOperation::SetExpressions { persona, weights } => {
+            let vrm = resolve_persona(world, &persona)?;
+            run_system(world, do_set_expressions, (vrm, weights))?;
+        }
+        BatchOperation::ModifyExpressions { persona, weights } => {
+            let vrm = resolve_persona(world, &persona)?;
+            run_system(world, do_modify_expressions, (vrm, weights))?;
+        }
+        BatchOperation::ClearExpressions { persona } => {
+            let vrm = resolve_persona(world, &persona)?;
+            run_system(world, do_clear_expressions, vrm)?;
+        }
+        BatchOperation::PlayVrma {
+            persona,
+            asset,
+            repeat,
+            transition_secs,
+            reset_spring_bones,
+            ..
+        } => {
+            let vrma = loaded_vrma(world, persona, asset)?;
+            let args = bevy_vrm1::prelude::PlayVrma {
+                vrma,
+                transition_duration: Duration::from_secs_f64(transition_secs.unwrap_or_default()),
+                repeat: repeat.unwrap_or(Repeat::Never).into(),
+                reset_spring_bones: reset_spring_bones.unwrap_or(true),
+            };
+            run_system(world, play, args)?;
+        }
+        BatchOperation::StopVrma { persona, asset } => {
+            let vrma = loaded_vrma(world, persona, asset)?;
+            run_system(world, stop, vrma)?;
+        }
+        #[cfg(feature = "gui")]
+        BatchOperation::OpenWebview(options) => {
+            let webview = run_system(world, create_global_webview, options)??;
+            return Ok(serde_json::to_value(webview).unwrap_or_default());
+        }
+        #[cfg(not(feature = "gui"))]
+        BatchOperation::OpenWebview(_) => {
+            return Err(ApiError::Unsupported(WEBVIEWS_UNSUPPORTED.to_string()));
+        }
+        BatchOperation::CloseWebview { entity } => {
+            run_system(world, close, entity)?;
+        }
+        BatchOperation::PlaySe {
+            asset,
+            volume,
+            speed,
+            panning,
+        } => {
+            run_system(world, play_se, (asset, volume, speed, panning))??;
+        }
+        BatchOperation::SendSignal { signal, payload } => {
+            run_system(world, send_signal, (signal, payload))??;
+        }
+        BatchOperation::Wait { .. } => {}
+    }
+    Ok(Value::Null)
+}
+
+/// Runs `system` on `world` with `input`, as the dedicated endpoints do through the reactor.
+pub(crate) fn run_system<I, O, M, S>(
+    world: &mut World,
+    system: S,
+    input: I::Inner<'_>,
+) -> ApiResult<O>
+where
+    I: SystemInput + 'static,
+    O: 'static,
+    S: IntoSystem<I, O, M> + 'static,
+{
+    world
+        .run_system_cached_with(system, input)
+        .map_err(|e| ApiError::FailedRunSystem(e.to_string()))
+}
+
+fn resolve_persona(world: &World, persona: &PersonaId) -> ApiResult<Entity> {
+    world
+        .resource::<PersonaIndex>()
+        .get(persona)
+        .ok_or(ApiError::EntityNotFound)
+}
+
+fn persona_entity(In(persona): In<PersonaId>, index: Res<PersonaIndex>) -> ApiResult<Entity> {
+    index.get(&persona).ok_or(ApiError::EntityNotFound)
+}
+
+/// Returns the VRMA entity for `asset` under the persona's VRM if it has finished loading.
+fn loaded_vrma(world: &mut World, persona: PersonaId, asset: AssetId) -> ApiResult<Entity> {
+    let vrm = resolve_persona(world, &persona)?;
+    let vrma =
+        run_system(world, fetch_vrma, (vrm, asset.clone()))?.ok_or(ApiError::EntityNotFound)?;
+    if run_system(world, initialized, vrma)? {
+        Ok(vrma)
+    } else {
+        Err(ApiError::Conflict(format!(
+            "VRMA '{asset}' is not loaded yet; pass the persona and asset literally so it is loaded before the batch is applied"
+        )))
+    }
+}
+
+/// The length of the persona's VRMA animation for `asset`, if it is loaded.
+fn vrma_duration(
+    In((persona, asset)): In<(PersonaId, AssetId)>,
+    world: &mut World,
+) -> Option<Duration> {
+    let vrma = loaded_vrma(world, persona, asset).ok()?;
+    run_system(world, wait_animation_finished, (vrma, true))
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn step(id: Option<&str>, op: &str, args: Value) -> BatchStep {
+        BatchStep {
+            id: id.map(str::to_string),
+            op: op.to_string(),
+            args,
+        }
+    }
+
+    #[test]
+    fn refs_are_replaced_with_earlier_outputs() {
+        let outputs = HashMap::from([
+            ("panel".to_string(), json!(42)),
+            ("reply".to_string(), json!({"answer": {"text": "hi"}})),
+        ]);
+        let args = json!({
+            "entity": {"$ref": "panel"},
+            "payload": [{"$ref": "reply.answer.text"}, {"$ref": "x", "extra": 1}],
+        });
+        assert_eq!(
+            resolve_refs(args, &outputs).unwrap(),
+            json!({"entity": 42, "payload": ["hi", {"$ref": "x", "extra": 1}]})
+        );
+    }
+
+    #[test]
+    fn unknown_refs_are_errors() {
+        let outputs = HashMap::from([("panel".to_string(), json!(42))]);
+        assert!(resolve_refs(json!({"$ref": "missing"}), &outputs).is_err());
+        assert!(resolve_refs(json!({"$ref": "panel.entity"}), &outputs).is_err());
+    }
+
+    #[test]
+    fn operations_parse_from_op_and_args() {
+        let operation = parse_operation(
+            "vrma.play",
+            json!({"persona": "elmer", "asset": "vrma:wave", "repeat": {"type": "forever"}}),
+        )
+        .unwrap();
+        assert!(matches!(
+            operation,
+            BatchOperation::PlayVrma {
+                repeat: Some(Repeat::Forever),
+                wait_for_completion: false,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_operation("webview.close", json!({"entity": 7})).unwrap(),
+            BatchOperation::CloseWebview { .. }
+        ));
+        assert!(parse_operation("teleport", json!({})).is_err());
+    }
+
+    #[test]
+    fn stop_policy_skips_remaining_steps() {
+        let mut run = BatchRun::new(BatchErrorPolicy::Stop);
+        let steps = [
+            step(Some("a"), "signal.send", json!({"signal": "x"})),
+            step(None, "signal.send", json!({"payload": 1})),
+            step(None, "signal.send", json!({"signal": "y"})),
+        ];
+        for step in &steps {
+            if run.prepare(step).is_some() {
+                run.record(step, Ok(Value::Null));
+            }
+        }
+        let response = run.finish();
+        let statuses: Vec<_> = response.results.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                BatchStepStatus::Ok,
+                BatchStepStatus::Error,
+                BatchStepStatus::Skipped
+            ]
+        );
+        assert!(!response.ok);
+    }
+
+    #[test]
+    fn duplicate_ids_are_rejected() {
+        let request = BatchRequest {
+            mode: BatchMode::SameFrame,
+            on_error: BatchErrorPolicy::Continue,
+            steps: vec![
+                step(Some("a"), "wait", json!({"ms": 1})),
+                step(Some("a"), "wait", json!({"ms": 1})),
+            ],
+        };
+        assert!(validate(&request).is_err());
+    }
+
+    #[test]
+    fn long_waits_are_rejected() {
+        let request = BatchRequest {
+            mode: BatchMode::Sequential,
+            on_error: BatchErrorPolicy::Continue,
+            steps: vec![step(None, "wait", json!({"ms": MAX_WAIT_MS + 1}))],
+        };
+        assert!(validate(&request).is_err());
+    }
+}
@@ -22,7 +22,7 @@ impl EffectsApi {
     }
 }
 
-fn stamp(
+pub(crate) fn stamp(
     In((asset_id, options)): In<(AssetId, Option<StampOptions>)>,
     mut commands: Commands,
     asset_resolver: AssetResolver,
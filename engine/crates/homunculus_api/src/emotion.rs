@@ -0,0 +1,437 @@
+//! Emotions for personas.
+//!
+//! [`EmotionApi::set`] sets a named emotion or a valence/arousal point with an intensity.
+//! The emotion holds, then fades back to neutral, and drives the persona's expressions
+//! through a mapping table. Each VRM model can store its own table; emotions it does not
+//! map use the expression preset of the same name, when the model has it.
+//!
+//! Only the expressions an emotion maps to are touched, so explicit expression weights
+//! for other expressions stay in place.
+
+mod model;
+
+use crate::api;
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use bevy_vrm1::prelude::{ExpressionEntityMap, ModifyExpressions};
+use homunculus_core::prelude::{
+    AssetIdComponent, EmotionChangeEvent, OutputLog, Persona, VrmEvent, VrmEventSender,
+};
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use model::{
+    affect_from_emotions, default_mapping, emotions_from_affect, expression_weights,
+    resolve_mapping, strength,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// The emotion name that fades the current emotion out immediately.
+const NEUTRAL: &str = "neutral";
+
+/// Emotion name to expression weights, e.g. `{"happy": {"happy": 1.0}}`.
+pub type EmotionMapping = HashMap<String, HashMap<String, f32>>;
+
+/// Request arguments for setting a persona's emotion.
+///
+/// Set either `emotion` or `valence`/`arousal`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EmotionArgs {
+    /// `happy`, `relaxed`, `sad`, `angry`, `surprised`, an emotion from the model's mapping,
+    /// or `neutral` to clear the emotion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emotion: Option<String>,
+    /// Pleasantness, from -1.0 (unpleasant) to 1.0 (pleasant).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valence: Option<f32>,
+    /// Activation, from -1.0 (calm) to 1.0 (excited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arousal: Option<f32>,
+    /// Strength of the emotion (0.0–1.0). Defaults to `1.0`.
+    #[serde(default = "default_intensity")]
+    pub intensity: f32,
+    /// Seconds the emotion stays at full strength before fading. Defaults to `0`.
+    #[serde(default)]
+    pub hold_secs: f32,
+    /// Seconds to fade back to neutral; `null` keeps the emotion until it is changed.
+    /// Defaults to `3`.
+    #[serde(default = "default_decay_secs")]
+    pub decay_secs: Option<f32>,
+}
+
+fn default_intensity() -> f32 {
+    1.0
+}
+
+fn default_decay_secs() -> Option<f32> {
+    Some(3.0)
+}
+
+/// A persona's current emotion.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EmotionState {
+    /// Current intensity of each emotion (0.0–1.0). Empty when neutral.
+    pub emotions: HashMap<String, f32>,
+    /// Pleasantness, from -1.0 (unpleasant) to 1.0 (pleasant).
+    pub valence: f32,
+    /// Activation, from -1.0 (calm) to 1.0 (excited).
+    pub arousal: f32,
+}
+
+impl EmotionState {
+    fn new(emotions: HashMap<String, f32>) -> Self {
+        let affect = affect_from_emotions(&emotions);
+        Self {
+            emotions,
+            valence: affect.x,
+            arousal: affect.y,
+        }
+    }
+}
+
+/// The emotion a persona feels, fading over time.
+#[derive(Component, Debug)]
+pub(crate) struct Emotion {
+    /// Emotion weights at full strength.
+    peak: HashMap<String, f32>,
+    hold_secs: f32,
+    decay_secs: Option<f32>,
+    elapsed: f32,
+    /// Emotion to expression weights, limited to the model's expressions.
+    table: EmotionMapping,
+    /// Expression weights this emotion applied last.
+    applied: HashMap<String, f32>,
+}
+
+impl Emotion {
+    fn strength(&self) -> f32 {
+        strength(self.elapsed, self.hold_secs, self.decay_secs)
+    }
+
+    fn emotions(&self) -> HashMap<String, f32> {
+        let strength = self.strength();
+        self.peak
+            .iter()
+            .map(|(name, weight)| (name.clone(), weight * strength))
+            .filter(|(_, weight)| 0.0 < *weight)
+            .collect()
+    }
+
+    /// The current emotion, after fading.
+    pub(crate) fn state(&self) -> EmotionState {
+        EmotionState::new(self.emotions())
+    }
+}
+
+api!(
+    /// Sets and reads persona emotions.
+    EmotionApi
+);
+
+impl EmotionApi {
+    /// Sets the persona's emotion, replacing the current one.
+    pub async fn set(&self, vrm: Entity, args: EmotionArgs) -> ApiResult<EmotionState> {
+        validate(&args)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(set_emotion).with((vrm, args)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Returns the persona's current emotion.
+    pub async fn get(&self, vrm: Entity) -> ApiResult<EmotionState> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(get_emotion).with(vrm)).await
+            })
+            .await?
+    }
+
+    /// Returns the emotion mapping of the persona's VRM model, including the defaults.
+    pub async fn mapping(&self, vrm: Entity) -> ApiResult<EmotionMapping> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(get_mapping).with(vrm)).await
+            })
+            .await?
+    }
+
+    /// Stores an emotion mapping for the persona's VRM model.
+    ///
+    /// Emotions in `mapping` replace their default entries; others keep the defaults.
+    pub async fn set_mapping(
+        &self,
+        vrm: Entity,
+        mapping: EmotionMapping,
+    ) -> ApiResult<EmotionMapping> {
+        if mapping
+            .values()
+            .flat_map(|targets| targets.values())
+            .any(|weight| !(0.0..=1.0).contains(weight))
+        {
+            return Err(ApiError::InvalidInput(
+                "expression weights must be between 0 and 1".to_string(),
+            ));
+        }
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_mapping).with((vrm, mapping)))
+                    .await
+            })
+            .await?
+    }
+}
+
+fn validate(args: &EmotionArgs) -> ApiResult {
+    let has_affect = args.valence.is_some() || args.arousal.is_some();
+    if args.emotion.is_some() == has_affect {
+        return Err(ApiError::InvalidInput(
+            "set either emotion or valence/arousal".to_string(),
+        ));
+    }
+    if [args.valence, args.arousal]
+        .into_iter()
+        .flatten()
+        .any(|value| !(-1.0..=1.0).contains(&value))
+    {
+        return Err(ApiError::InvalidInput(
+            "valence and arousal must be between -1 and 1".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&args.intensity) {
+        return Err(ApiError::InvalidInput(
+            "intensity must be between 0 and 1".to_string(),
+        ));
+    }
+    if !args.hold_secs.is_finite() || args.hold_secs < 0.0 {
+        return Err(ApiError::InvalidInput(
+            "holdSecs must be zero or positive".to_string(),
+        ));
+    }
+    if args
+        .decay_secs
+        .is_some_and(|secs| !secs.is_finite() || secs < 0.0)
+    {
+        return Err(ApiError::InvalidInput(
+            "decaySecs must be zero or positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The default mapping overlaid with the one stored for the VRM model, if any.
+fn load_mapping(prefs: &PrefsDatabase, asset_id: Option<&AssetIdComponent>) -> EmotionMapping {
+    let mut mapping = default_mapping();
+    if let Some(asset_id) = asset_id
+        && let Ok(Some(stored)) =
+            prefs.load_as::<EmotionMapping>(&PrefsKeys::emotion_mapping(&asset_id.0.to_string()))
+    {
+        mapping.extend(stored);
+    }
+    mapping
+}
+
+fn set_emotion(
+    In((vrm, args)): In<(Entity, EmotionArgs)>,
+    mut commands: Commands,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<(Option<&AssetIdComponent>, Option<&ExpressionEntityMap>), With<Persona>>,
+    emotions: Query<&Emotion>,
+    tx: Option<Res<VrmEventSender<EmotionChangeEvent>>>,
+) -> ApiResult<EmotionState> {
+    let (asset_id, expressions) = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mapping = load_mapping(&prefs, asset_id);
+    let peak = match &args.emotion {
+        Some(name) if name == NEUTRAL => HashMap::new(),
+        Some(name) if mapping.contains_key(name) => HashMap::from([(name.clone(), args.intensity)]),
+        Some(name) => {
+            return Err(ApiError::InvalidInput(format!("unknown emotion: {name}")));
+        }
+        None => {
+            let affect = Vec2::new(args.valence.unwrap_or(0.0), args.arousal.unwrap_or(0.0));
+            emotions_from_affect(affect)
+                .into_iter()
+                .map(|(name, weight)| (name, weight * args.intensity))
+                .collect()
+        }
+    };
+    let available: HashSet<String> = expressions
+        .map(|map| map.iter().map(|(name, _)| name.to_string()).collect())
+        .unwrap_or_default();
+    let previous = emotions
+        .get(vrm)
+        .map(|emotion| emotion.applied.clone())
+        .unwrap_or_default();
+
+    let mut emotion = Emotion {
+        peak,
+        hold_secs: args.hold_secs,
+        decay_secs: args.decay_secs,
+        elapsed: 0.0,
+        table: resolve_mapping(&mapping, &available),
+        applied: HashMap::new(),
+    };
+    let state = emotion.state();
+    emotion.applied = apply_expressions(&mut commands, vrm, &previous, &emotion);
+    if state.emotions.is_empty() {
+        commands.entity(vrm).try_remove::<Emotion>();
+    } else {
+        commands.entity(vrm).try_insert(emotion);
+    }
+    send_change(&tx, vrm, &state);
+    Ok(state)
+}
+
+fn get_emotion(
+    In(vrm): In<Entity>,
+    personas: Query<Option<&Emotion>, With<Persona>>,
+) -> ApiResult<EmotionState> {
+    let emotion = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    Ok(emotion.map(Emotion::state).unwrap_or_default())
+}
+
+fn get_mapping(
+    In(vrm): In<Entity>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<Option<&AssetIdComponent>, With<Persona>>,
+) -> ApiResult<EmotionMapping> {
+    let asset_id = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    Ok(load_mapping(&prefs, asset_id))
+}
+
+fn put_mapping(
+    In((vrm, mapping)): In<(Entity, EmotionMapping)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<Option<&AssetIdComponent>, With<Persona>>,
+) -> ApiResult<EmotionMapping> {
+    let asset_id = personas
+        .get(vrm)
+        .map_err(|_| ApiError::EntityNotFound)?
+        .ok_or_else(|| ApiError::Conflict("No VRM attached to this persona".to_string()))?;
+    prefs
+        .save_as(
+            &PrefsKeys::emotion_mapping(&asset_id.0.to_string()),
+            &mapping,
+        )
+        .map_err(|e| ApiError::FailedSave(e.to_string()))?;
+    Ok(load_mapping(&prefs, Some(asset_id)))
+}
+
+/// Fades every emotion with a decay and returns it to neutral once it has faded out.
+fn fade_emotions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut emotions: Query<(Entity, &mut Emotion)>,
+    tx: Option<Res<VrmEventSender<EmotionChangeEvent>>>,
+) {
+    for (vrm, mut emotion) in emotions.iter_mut() {
+        if emotion.decay_secs.is_none() {
+            continue;
+        }
+        let before = emotion.strength();
+        emotion.elapsed += time.delta_secs();
+        if emotion.strength() == before {
+            continue;
+        }
+        let previous = std::mem::take(&mut emotion.applied);
+        emotion.applied = apply_expressions(&mut commands, vrm, &previous, &emotion);
+        if emotion.strength() <= 0.0 {
+            commands.entity(vrm).try_remove::<Emotion>();
+            send_change(&tx, vrm, &EmotionState::default());
+        }
+    }
+}
+
+/// Sets the expression weights for the emotion, and zero for expressions it no longer uses.
+///
+/// Returns the weights applied.
+fn apply_expressions(
+    commands: &mut Commands,
+    vrm: Entity,
+    previous: &HashMap<String, f32>,
+    emotion: &Emotion,
+) -> HashMap<String, f32> {
+    let weights = expression_weights(&emotion.emotions(), &emotion.table);
+    let released = previous
+        .keys()
+        .filter(|name| !weights.contains_key(*name))
+        .map(|name| (name.as_str(), 0.0));
+    let updates: Vec<_> = weights
+        .iter()
+        .map(|(name, weight)| (name.as_str(), *weight))
+        .chain(released)
+        .collect();
+    if !updates.is_empty() {
+        commands.trigger(ModifyExpressions::from_iter(vrm, updates));
+    }
+    weights
+}
+
+fn send_change(
+    tx: &Option<Res<VrmEventSender<EmotionChangeEvent>>>,
+    vrm: Entity,
+    state: &EmotionState,
+) {
+    if let Some(tx) = tx {
+        tx.try_broadcast(VrmEvent {
+            vrm,
+            payload: EmotionChangeEvent {
+                emotions: state.emotions.clone(),
+                valence: state.valence,
+                arousal: state.arousal,
+            },
+        })
+        .output_log_if_error("Failed to broadcast EmotionChangeEvent");
+    }
+}
+
+pub(super) struct EmotionApiPlugin;
+
+impl Plugin for EmotionApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, fade_emotions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(emotion: Option<&str>, valence: Option<f32>) -> EmotionArgs {
+        EmotionArgs {
+            emotion: emotion.map(str::to_string),
+            valence,
+            arousal: None,
+            intensity: 1.0,
+            hold_secs: 0.0,
+            decay_secs: Some(3.0),
+        }
+    }
+
+    #[test]
+    fn emotion_or_affect_must_be_set_but_not_both() {
+        assert!(validate(&args(Some("happy"), None)).is_ok());
+        assert!(validate(&args(None, Some(0.5))).is_ok());
+        assert!(validate(&args(None, None)).is_err());
+        assert!(validate(&args(Some("happy"), Some(0.5))).is_err());
+        assert!(validate(&args(None, Some(1.5))).is_err());
+    }
+
+    #[test]
+    fn emotion_args_default_to_a_three_second_fade() {
+        let args: EmotionArgs = serde_json::from_str(r#"{"emotion": "happy"}"#).unwrap();
+        assert_eq!(args.intensity, 1.0);
+        assert_eq!(args.hold_secs, 0.0);
+        assert_eq!(args.decay_secs, Some(3.0));
+
+        let held: EmotionArgs =
+            serde_json::from_str(r#"{"emotion": "happy", "decaySecs": null}"#).unwrap();
+        assert_eq!(held.decay_secs, None);
+    }
+}
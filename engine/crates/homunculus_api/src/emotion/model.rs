@@ -0,0 +1,184 @@
+//! Named emotions on the valence/arousal plane, decay, and expression weights.
+
+use super::EmotionMapping;
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// The built-in emotions and where each sits on the valence/arousal plane.
+///
+/// The names match the VRM 1.0 emotion presets.
+pub(super) const EMOTIONS: [(&str, Vec2); 5] = [
+    ("happy", Vec2::new(0.8, 0.5)),
+    ("relaxed", Vec2::new(0.6, -0.6)),
+    ("sad", Vec2::new(-0.7, -0.5)),
+    ("angry", Vec2::new(-0.7, 0.7)),
+    ("surprised", Vec2::new(0.1, 0.9)),
+];
+
+/// Expressions tried for a preset the model lacks, as used by VRM 0.x models.
+const ALIASES: [(&str, &str); 3] = [("happy", "joy"), ("sad", "sorrow"), ("relaxed", "fun")];
+
+/// Weights below this are dropped when converting valence and arousal to emotions.
+const MIN_WEIGHT: f32 = 0.05;
+
+/// Emotion weights for a valence/arousal point.
+///
+/// Each emotion is weighted by how closely its direction matches the point, and the
+/// strongest one gets the point's distance from neutral (at most 1).
+pub(super) fn emotions_from_affect(affect: Vec2) -> HashMap<String, f32> {
+    let strength = affect.length().min(1.0);
+    if strength <= f32::EPSILON {
+        return HashMap::new();
+    }
+    let direction = affect.normalize();
+    let similarities: Vec<_> = EMOTIONS
+        .iter()
+        .map(|(name, position)| (*name, direction.dot(position.normalize()).max(0.0).powi(2)))
+        .collect();
+    let max = similarities
+        .iter()
+        .map(|(_, similarity)| *similarity)
+        .fold(0.0, f32::max);
+    similarities
+        .into_iter()
+        .map(|(name, similarity)| (name.to_string(), similarity / max * strength))
+        .filter(|(_, weight)| MIN_WEIGHT <= *weight)
+        .collect()
+}
+
+/// The valence/arousal point for a set of emotion weights, clamped to the unit circle.
+///
+/// Emotions that are not built in do not move the point.
+pub(super) fn affect_from_emotions(emotions: &HashMap<String, f32>) -> Vec2 {
+    EMOTIONS
+        .iter()
+        .filter_map(|(name, position)| emotions.get(*name).map(|weight| *position * *weight))
+        .sum::<Vec2>()
+        .clamp_length_max(1.0)
+}
+
+/// How strong an emotion still is `elapsed` seconds after it was set.
+///
+/// It stays at full strength for `hold_secs`, then fades linearly to zero over
+/// `decay_secs`. Without `decay_secs` it never fades.
+pub(super) fn strength(elapsed: f32, hold_secs: f32, decay_secs: Option<f32>) -> f32 {
+    let Some(decay_secs) = decay_secs else {
+        return 1.0;
+    };
+    let fading = elapsed - hold_secs;
+    if fading <= 0.0 {
+        1.0
+    } else if decay_secs <= 0.0 {
+        0.0
+    } else {
+        (1.0 - fading / decay_secs).max(0.0)
+    }
+}
+
+/// Maps every built-in emotion to the expression preset of the same name.
+pub(super) fn default_mapping() -> EmotionMapping {
+    EMOTIONS
+        .iter()
+        .map(|(name, _)| (name.to_string(), HashMap::from([(name.to_string(), 1.0)])))
+        .collect()
+}
+
+/// Limits a mapping to the expressions the model has, falling back to VRM 0.x names.
+pub(super) fn resolve_mapping(
+    mapping: &EmotionMapping,
+    available: &HashSet<String>,
+) -> EmotionMapping {
+    mapping
+        .iter()
+        .map(|(emotion, targets)| {
+            let resolved = targets
+                .iter()
+                .filter_map(|(expression, weight)| {
+                    if available.contains(expression) {
+                        return Some((expression.clone(), *weight));
+                    }
+                    ALIASES
+                        .iter()
+                        .find(|(preset, alias)| preset == expression && available.contains(*alias))
+                        .map(|(_, alias)| (alias.to_string(), *weight))
+                })
+                .collect();
+            (emotion.clone(), resolved)
+        })
+        .collect()
+}
+
+/// Expression weights for a set of emotion weights, summed and clamped to 1.
+pub(super) fn expression_weights(
+    emotions: &HashMap<String, f32>,
+    table: &EmotionMapping,
+) -> HashMap<String, f32> {
+    let mut weights = HashMap::new();
+    for (emotion, intensity) in emotions {
+        for (expression, weight) in table.get(emotion).into_iter().flatten() {
+            *weights.entry(expression.clone()).or_insert(0.0) += intensity * weight;
+        }
+    }
+    weights.values_mut().for_each(|w| *w = w.clamp(0.0, 1.0));
+    weights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pleasant_excitement_is_mostly_happy() {
+        let emotions = emotions_from_affect(Vec2::new(0.8, 0.5));
+        assert!((emotions["happy"] - 0.943).abs() < 1e-3);
+        assert!(!emotions.contains_key("sad"));
+        assert!(emotions.values().all(|w| *w <= emotions["happy"]));
+
+        assert!(emotions_from_affect(Vec2::ZERO).is_empty());
+    }
+
+    #[test]
+    fn affect_follows_the_emotion_weights() {
+        let affect = affect_from_emotions(&HashMap::from([("sad".to_string(), 0.5)]));
+        assert_eq!(affect, Vec2::new(-0.35, -0.25));
+
+        let custom = affect_from_emotions(&HashMap::from([("sleepy".to_string(), 1.0)]));
+        assert_eq!(custom, Vec2::ZERO);
+    }
+
+    #[test]
+    fn emotions_hold_then_fade() {
+        assert_eq!(strength(1.0, 2.0, Some(4.0)), 1.0);
+        assert_eq!(strength(4.0, 2.0, Some(4.0)), 0.5);
+        assert_eq!(strength(9.0, 2.0, Some(4.0)), 0.0);
+        assert_eq!(strength(2.5, 2.0, Some(0.0)), 0.0);
+        assert_eq!(strength(100.0, 0.0, None), 1.0);
+    }
+
+    #[test]
+    fn mappings_use_the_presets_a_model_has() {
+        let available = HashSet::from(["joy".to_string(), "angry".to_string()]);
+        let table = resolve_mapping(&default_mapping(), &available);
+        assert_eq!(table["happy"], HashMap::from([("joy".to_string(), 1.0)]));
+        assert_eq!(table["angry"], HashMap::from([("angry".to_string(), 1.0)]));
+        assert!(table["surprised"].is_empty());
+    }
+
+    #[test]
+    fn expression_weights_sum_and_clamp() {
+        let table: EmotionMapping = HashMap::from([
+            (
+                "happy".to_string(),
+                HashMap::from([("happy".to_string(), 1.0)]),
+            ),
+            (
+                "excited".to_string(),
+                HashMap::from([("happy".to_string(), 0.6), ("surprised".to_string(), 0.5)]),
+            ),
+        ]);
+        let emotions = HashMap::from([("happy".to_string(), 0.7), ("excited".to_string(), 1.0)]);
+        let weights = expression_weights(&emotions, &table);
+        assert_eq!(weights["happy"], 1.0);
+        assert_eq!(weights["surprised"], 0.5);
+    }
+}
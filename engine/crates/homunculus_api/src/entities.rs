@@ -1,17 +1,28 @@
 //! Provides generics APIs for [`Entity`](bevy::prelude::Entity) in Bevy.
 
 use crate::api;
+use bevy::prelude::*;
 
 mod find;
 mod move_to;
 mod name;
+pub mod path_tween;
 pub mod transform;
 pub mod tween;
 
 pub use move_to::MoveTarget;
+pub use path_tween::{PathCurve, PathSpace, TweenPathArgs};
 pub use tween::{
     EasingFunction, TweenPositionArgs, TweenPositionViewportArgs, TweenRotationArgs,
     TweenRotationAxisArgs, TweenScaleArgs,
 };
 
 api!(EntitiesApi);
+
+pub(super) struct EntitiesApiPlugin;
+
+impl Plugin for EntitiesApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(path_tween::PathTweenPlugin);
+    }
+}
@@ -0,0 +1,506 @@
+//! Tweens that move an entity along a path.
+//!
+//! The path is a polyline, a Catmull-Rom spline through the points or a chain of cubic
+//! Bézier segments. It is sampled once and walked by arc length, so the entity moves at
+//! a constant speed before easing is applied, however unevenly the points are spaced.
+
+use crate::entities::EntitiesApi;
+use crate::entities::tween::EasingFunction;
+use crate::error::{ApiError, ApiResult};
+use crate::events::EngineEventBus;
+use crate::vrma::Repeat;
+use bevy::math::curve::Curve;
+use bevy::math::curve::easing::EaseFunction;
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use homunculus_core::prelude::{Coordinate, GlobalViewport, Persona};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+/// Samples taken per curve segment when building the arc-length table.
+const SAMPLES_PER_SEGMENT: usize = 16;
+
+/// How the path passes through its points.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PathCurve {
+    /// Straight lines between consecutive points.
+    #[default]
+    Polyline,
+    /// A smooth curve through every point.
+    CatmullRom,
+    /// Cubic Bézier segments: an anchor, two control points, an anchor, and so on.
+    /// Needs `3n + 1` points.
+    Bezier,
+}
+
+/// Coordinate space of the path points.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum PathSpace {
+    /// Bevy world coordinates.
+    #[default]
+    World,
+    /// Global viewport pixels (0,0 = top-left of the primary monitor).
+    Viewport,
+}
+
+/// Request arguments for moving an entity along a path.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TweenPathArgs {
+    /// Path points in `space` coordinates.
+    #[cfg_attr(feature = "openapi", schema(value_type = Vec<[f32; 2]>))]
+    pub points: Vec<Vec2>,
+    #[serde(default)]
+    pub curve: PathCurve,
+    #[serde(default)]
+    pub space: PathSpace,
+    /// World z of the path. Defaults to the entity's current z; ignored for viewport paths.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub z: Option<f32>,
+    /// Start the path at the entity's current position instead of jumping to the first point.
+    #[serde(default)]
+    pub from_current: bool,
+    /// Duration of one pass along the path in milliseconds.
+    pub duration_ms: u64,
+    /// Easing applied to the distance travelled along the path.
+    #[serde(default)]
+    pub easing: EasingFunction,
+    /// Turn the entity's front (+Z) toward the direction of travel.
+    #[serde(default)]
+    pub orient_to_path: bool,
+    /// How many passes to make. Defaults to a single pass.
+    #[serde(default = "default_repeat")]
+    pub repeat: Repeat,
+    /// Travel back along the path on every other pass instead of jumping to the start.
+    #[serde(default)]
+    pub ping_pong: bool,
+    /// Resolve only once the last pass ends. Not allowed with `repeat: forever`.
+    #[serde(default)]
+    pub wait: bool,
+}
+
+fn default_repeat() -> Repeat {
+    Repeat::Never
+}
+
+/// Sampled path points with their cumulative distance from the first point.
+#[derive(Debug, Clone)]
+struct ArcPath {
+    points: Vec<Vec3>,
+    lengths: Vec<f32>,
+}
+
+impl ArcPath {
+    fn new(points: Vec<Vec3>) -> Self {
+        let mut lengths = Vec::with_capacity(points.len());
+        let mut total = 0.0;
+        for (i, point) in points.iter().enumerate() {
+            if 0 < i {
+                total += points[i - 1].distance(*point);
+            }
+            lengths.push(total);
+        }
+        Self { points, lengths }
+    }
+
+    /// The position at `fraction` of the path length, and the unit direction of travel there.
+    ///
+    /// The direction is zero on a path without length.
+    fn at(&self, fraction: f32) -> (Vec3, Vec3) {
+        let total = self.lengths.last().copied().unwrap_or_default();
+        if self.points.len() < 2 || total <= f32::EPSILON {
+            return (self.points.first().copied().unwrap_or_default(), Vec3::ZERO);
+        }
+        let distance = fraction.clamp(0.0, 1.0) * total;
+        let i = self
+            .lengths
+            .partition_point(|&length| length < distance)
+            .clamp(1, self.points.len() - 1);
+        let (a, b) = (self.points[i - 1], self.points[i]);
+        let segment = self.lengths[i] - self.lengths[i - 1];
+        let t = if segment > 0.0 {
+            (distance - self.lengths[i - 1]) / segment
+        } else {
+            0.0
+        };
+        (a.lerp(b, t), (b - a).normalize_or_zero())
+    }
+}
+
+/// Turns the control points into a dense polyline that follows the curve.
+fn sample_curve(curve: PathCurve, points: &[Vec3]) -> Result<Vec<Vec3>, String> {
+    if points.len() < 2 {
+        return Err("a path needs at least 2 points".to_string());
+    }
+    match curve {
+        PathCurve::Polyline => Ok(points.to_vec()),
+        PathCurve::CatmullRom => {
+            let last = points.len() - 1;
+            let mut sampled = Vec::with_capacity(last * SAMPLES_PER_SEGMENT + 1);
+            for i in 0..last {
+                let p0 = points[i.saturating_sub(1)];
+                let p3 = points[(i + 2).min(last)];
+                for s in 0..SAMPLES_PER_SEGMENT {
+                    let t = s as f32 / SAMPLES_PER_SEGMENT as f32;
+                    sampled.push(catmull_rom(p0, points[i], points[i + 1], p3, t));
+                }
+            }
+            sampled.push(points[last]);
+            Ok(sampled)
+        }
+        PathCurve::Bezier => {
+            if (points.len() - 1) % 3 != 0 {
+                return Err("a bezier path needs 3n + 1 points".to_string());
+            }
+            let mut sampled = Vec::with_capacity(points.len() / 3 * SAMPLES_PER_SEGMENT + 1);
+            for segment in points.windows(4).step_by(3) {
+                for s in 0..SAMPLES_PER_SEGMENT {
+                    let t = s as f32 / SAMPLES_PER_SEGMENT as f32;
+                    sampled.push(cubic_bezier(segment, t));
+                }
+            }
+            sampled.push(points[points.len() - 1]);
+            Ok(sampled)
+        }
+    }
+}
+
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let (t2, t3) = (t * t, t * t * t);
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+fn cubic_bezier(p: &[Vec3], t: f32) -> Vec3 {
+    let u = 1.0 - t;
+    p[0] * (u * u * u) + p[1] * (3.0 * u * u * t) + p[2] * (3.0 * u * t * t) + p[3] * (t * t * t)
+}
+
+/// An entity moving along a path.
+#[derive(Component, Debug)]
+pub(crate) struct PathTween {
+    path: ArcPath,
+    duration: Duration,
+    elapsed: Duration,
+    ease: EaseFunction,
+    /// Rotation the path direction is applied on top of, when orienting to the path.
+    orient_base: Option<Quat>,
+    repeat: Repeat,
+    ping_pong: bool,
+    /// Passes completed so far.
+    passes: u32,
+}
+
+/// Where a path tween is after a frame.
+#[derive(Debug, PartialEq)]
+struct PathStep {
+    /// Fraction of the path length the entity is at.
+    fraction: f32,
+    /// Whether the entity is travelling from the end back to the start.
+    reversed: bool,
+    /// Passes that ended this frame with another one following.
+    looped: u32,
+    /// Whether the last pass ended.
+    finished: bool,
+}
+
+impl PathTween {
+    fn advance(&mut self, delta: Duration) -> PathStep {
+        self.elapsed += delta;
+        let mut looped = 0;
+        let mut finished = false;
+        while self.duration <= self.elapsed {
+            self.passes += 1;
+            if !has_next_pass(self.repeat, self.passes) {
+                self.elapsed = self.duration;
+                finished = true;
+                break;
+            }
+            self.elapsed -= self.duration;
+            looped += 1;
+        }
+        let pass = if finished {
+            self.passes - 1
+        } else {
+            self.passes
+        };
+        let reversed = self.ping_pong && pass % 2 == 1;
+        let t = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        let eased = self.ease.sample_clamped(t.min(1.0));
+        PathStep {
+            fraction: if reversed { 1.0 - eased } else { eased },
+            reversed,
+            looped,
+            finished,
+        }
+    }
+}
+
+fn has_next_pass(repeat: Repeat, completed: u32) -> bool {
+    match repeat {
+        Repeat::Forever => true,
+        Repeat::Never => false,
+        Repeat::Count { count } => completed < count,
+    }
+}
+
+impl EntitiesApi {
+    /// Moves the entity along a path, replacing a path tween in progress.
+    ///
+    /// Each pass that loops emits `path-tween-loop`; the end of the tween emits
+    /// `path-tween-finish`.
+    pub async fn tween_path(&self, entity: Entity, args: TweenPathArgs) -> ApiResult {
+        if args.duration_ms == 0 {
+            return Err(ApiError::InvalidInput(
+                "duration must be greater than 0".into(),
+            ));
+        }
+        if args.wait && args.repeat == Repeat::Forever {
+            return Err(ApiError::InvalidInput(
+                "wait cannot be used with a path that repeats forever".into(),
+            ));
+        }
+        let should_wait = args.wait;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(start_path_tween).with((entity, args)))
+                    .await?;
+                if should_wait {
+                    task.will(Update, wait::until(path_tween_ended).with(entity))
+                        .await;
+                }
+                Ok(())
+            })
+            .await?
+    }
+}
+
+fn start_path_tween(
+    In((entity, args)): In<(Entity, TweenPathArgs)>,
+    mut commands: Commands,
+    coordinate: Coordinate,
+    transforms: Query<(&Transform, Has<PathTween>)>,
+    personas: Query<&Persona>,
+    mut bus: Option<ResMut<EngineEventBus>>,
+) -> ApiResult {
+    let (tf, replacing) = transforms
+        .get(entity)
+        .map_err(|_| ApiError::EntityNotFound)?;
+    let mut points = Vec::with_capacity(args.points.len() + 1);
+    if args.from_current {
+        points.push(tf.translation);
+    }
+    for point in &args.points {
+        points.push(match args.space {
+            PathSpace::World => point.extend(args.z.unwrap_or(tf.translation.z)),
+            PathSpace::Viewport => coordinate
+                .to_world_2d_by_global(GlobalViewport(*point))
+                .ok_or(ApiError::FailedToWorldPosition)?
+                .extend(tf.translation.z),
+        });
+    }
+    let sampled = sample_curve(args.curve, &points).map_err(ApiError::InvalidInput)?;
+
+    if replacing {
+        publish_finish(&mut bus, &personas, entity, tf.translation, true);
+    }
+    let ease: EaseFunction = args.easing.into();
+    commands.entity(entity).try_insert(PathTween {
+        path: ArcPath::new(sampled),
+        duration: Duration::from_millis(args.duration_ms),
+        elapsed: Duration::ZERO,
+        ease,
+        orient_base: args.orient_to_path.then_some(tf.rotation),
+        repeat: args.repeat,
+        ping_pong: args.ping_pong,
+        passes: 0,
+    });
+    Ok(())
+}
+
+fn path_tween_ended(In(entity): In<Entity>, tweens: Query<(), With<PathTween>>) -> bool {
+    !tweens.contains(entity)
+}
+
+/// Moves every entity with a [`PathTween`] and finishes the tweens whose last pass ended.
+fn advance_path_tweens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tweens: Query<(Entity, &mut PathTween, &mut Transform)>,
+    personas: Query<&Persona>,
+    mut bus: Option<ResMut<EngineEventBus>>,
+) {
+    for (entity, mut tween, mut tf) in tweens.iter_mut() {
+        let step = tween.advance(time.delta());
+        let (position, direction) = tween.path.at(step.fraction);
+        tf.translation = position;
+        if let Some(base) = tween.orient_base
+            && direction != Vec3::ZERO
+        {
+            let direction = if step.reversed { -direction } else { direction };
+            tf.rotation = Quat::from_rotation_arc(Vec3::Z, direction) * base;
+        }
+        let last_looped = tween.passes - u32::from(step.finished);
+        for i in 0..step.looped {
+            let pass = last_looped - step.looped + i + 1;
+            publish(
+                &mut bus,
+                &personas,
+                entity,
+                "path-tween-loop",
+                json!({ "entity": entity, "pass": pass }),
+            );
+        }
+        if step.finished {
+            publish_finish(&mut bus, &personas, entity, position, false);
+            commands.entity(entity).try_remove::<PathTween>();
+        }
+    }
+}
+
+fn publish_finish(
+    bus: &mut Option<ResMut<EngineEventBus>>,
+    personas: &Query<&Persona>,
+    entity: Entity,
+    position: Vec3,
+    cancelled: bool,
+) {
+    publish(
+        bus,
+        personas,
+        entity,
+        "path-tween-finish",
+        json!({ "entity": entity, "position": position, "cancelled": cancelled }),
+    );
+}
+
+fn publish(
+    bus: &mut Option<ResMut<EngineEventBus>>,
+    personas: &Query<&Persona>,
+    entity: Entity,
+    event: &str,
+    data: serde_json::Value,
+) {
+    if let Some(bus) = bus
+        && bus.has_subscribers()
+    {
+        let persona_id = personas.get(entity).map(|persona| persona.id.clone()).ok();
+        bus.publish(event, persona_id, None, data);
+    }
+}
+
+pub(super) struct PathTweenPlugin;
+
+impl Plugin for PathTweenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, advance_path_tweens);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tween(repeat: Repeat, ping_pong: bool) -> PathTween {
+        PathTween {
+            path: ArcPath::new(vec![Vec3::ZERO, Vec3::X]),
+            duration: Duration::from_secs(1),
+            elapsed: Duration::ZERO,
+            ease: EaseFunction::Linear,
+            orient_base: None,
+            repeat,
+            ping_pong,
+            passes: 0,
+        }
+    }
+
+    #[test]
+    fn arc_path_moves_at_constant_speed() {
+        let path = ArcPath::new(vec![Vec3::ZERO, Vec3::X, Vec3::new(1.0, 3.0, 0.0)]);
+        let (position, direction) = path.at(0.125);
+        assert!(position.distance(Vec3::new(0.5, 0.0, 0.0)) < 1e-5);
+        assert_eq!(direction, Vec3::X);
+
+        let (position, direction) = path.at(0.625);
+        assert!(position.distance(Vec3::new(1.0, 1.5, 0.0)) < 1e-5);
+        assert_eq!(direction, Vec3::Y);
+
+        assert_eq!(path.at(2.0).0, Vec3::new(1.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn curves_pass_through_their_anchors() {
+        let points = [
+            Vec3::ZERO,
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+        ];
+        let sampled = sample_curve(PathCurve::CatmullRom, &points).unwrap();
+        assert_eq!(sampled.len(), 2 * SAMPLES_PER_SEGMENT + 1);
+        assert_eq!(sampled[0], points[0]);
+        assert!(sampled[SAMPLES_PER_SEGMENT].distance(points[1]) < 1e-5);
+        assert_eq!(*sampled.last().unwrap(), points[2]);
+
+        let hop = [
+            Vec3::ZERO,
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ];
+        let sampled = sample_curve(PathCurve::Bezier, &hop).unwrap();
+        assert_eq!(sampled[0], hop[0]);
+        assert!(sampled[SAMPLES_PER_SEGMENT / 2].distance(Vec3::new(0.5, 0.75, 0.0)) < 1e-5);
+        assert_eq!(*sampled.last().unwrap(), hop[3]);
+    }
+
+    #[test]
+    fn invalid_paths_are_rejected() {
+        assert!(sample_curve(PathCurve::Polyline, &[Vec3::ZERO]).is_err());
+        assert!(sample_curve(PathCurve::Bezier, &[Vec3::ZERO, Vec3::X, Vec3::Y]).is_err());
+    }
+
+    #[test]
+    fn single_pass_finishes_at_the_end() {
+        let mut tween = tween(Repeat::Never, false);
+        let step = tween.advance(Duration::from_millis(500));
+        assert_eq!(step.fraction, 0.5);
+        assert!(!step.finished);
+
+        let step = tween.advance(Duration::from_millis(700));
+        assert_eq!(step.fraction, 1.0);
+        assert_eq!(step.looped, 0);
+        assert!(step.finished);
+    }
+
+    #[test]
+    fn ping_pong_travels_back_and_counts_passes() {
+        let mut tween = tween(Repeat::Count { count: 2 }, true);
+        let step = tween.advance(Duration::from_millis(1250));
+        assert_eq!(step.looped, 1);
+        assert!(step.reversed);
+        assert_eq!(step.fraction, 0.75);
+        assert!(!step.finished);
+
+        let step = tween.advance(Duration::from_secs(1));
+        assert!(step.finished);
+        assert!(step.reversed);
+        assert_eq!(step.fraction, 0.0);
+    }
+
+    #[test]
+    fn looping_forever_never_finishes() {
+        let mut tween = tween(Repeat::Forever, false);
+        let step = tween.advance(Duration::from_millis(3500));
+        assert_eq!(step.looped, 3);
+        assert_eq!(step.fraction, 0.5);
+        assert!(!step.finished);
+    }
+}
@@ -198,7 +198,7 @@ use bevy_tweening::{
 use homunculus_core::prelude::{Coordinate, GlobalViewport};
 use std::time::Duration;
 
-fn apply_position_tween(
+pub(crate) fn apply_position_tween(
     In((entity, args)): In<(Entity, TweenPositionArgs)>,
     transforms: Query<&Transform>,
     mut commands: Commands,
@@ -229,7 +229,7 @@ fn apply_position_tween(
     Ok(())
 }
 
-fn apply_position_tween_viewport(
+pub(crate) fn apply_position_tween_viewport(
     In((entity, args)): In<(Entity, TweenPositionViewportArgs)>,
     coordinate: Coordinate,
     transforms: Query<&Transform>,
@@ -267,7 +267,7 @@ fn apply_position_tween_viewport(
     Ok(())
 }
 
-fn apply_rotation_tween(
+pub(crate) fn apply_rotation_tween(
     In((entity, args)): In<(Entity, TweenRotationArgs)>,
     transforms: Query<&Transform>,
     mut commands: Commands,
@@ -355,7 +355,7 @@ fn apply_rotation_axis_tween(
     Ok(())
 }
 
-fn apply_scale_tween(
+pub(crate) fn apply_scale_tween(
     In((entity, args)): In<(Entity, TweenScaleArgs)>,
     transforms: Query<&Transform>,
     mut commands: Commands,
@@ -44,6 +44,14 @@ pub enum ApiError {
     BgmNotPlaying,
     #[error("Cannot resume: BGM is not paused")]
     BgmNotPaused,
+    #[error("No timeline is playing")]
+    TimelineNotPlaying,
+    #[error("Expression preset '{0}' not found")]
+    ExpressionPresetNotFound(String),
+    #[error("Spring bone preset '{0}' not found")]
+    SpringBonePresetNotFound(String),
+    #[error("Pose '{0}' not found")]
+    PosePresetNotFound(String),
     #[error("Asset not found: {0}")]
     AssetNotFound(AssetId),
     #[error("Asset type mismatch for '{id}': expected {expected:?}, got {actual:?}")]
@@ -56,6 +64,14 @@ pub enum ApiError {
     Conflict(String),
     #[error("Too many requests: {0}")]
     TooManyRequests(String),
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    #[error("Failed to run system: {0}")]
+    FailedRunSystem(String),
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+    #[error("The license of '{0}' does not allow redistribution")]
+    RedistributionForbidden(String),
 }
 
 pub trait ApiResultExt {
@@ -97,18 +113,25 @@ pub mod axum {
                 ApiError::EntityNotFound
                 | ApiError::WebviewNotFound(_)
                 | ApiError::ModNotFound(_)
+                | ApiError::ExpressionPresetNotFound(_)
+                | ApiError::SpringBonePresetNotFound(_)
+                | ApiError::PosePresetNotFound(_)
                 | ApiError::AssetNotFound(_)
                 | ApiError::NotFoundPreferences(_) => axum::http::StatusCode::NOT_FOUND,
                 ApiError::InvalidInput(_) | ApiError::AssetTypeMismatch { .. } => {
                     axum::http::StatusCode::BAD_REQUEST
                 }
-                ApiError::BgmNotPlaying | ApiError::BgmNotPaused | ApiError::Conflict(_) => {
-                    axum::http::StatusCode::CONFLICT
-                }
+                ApiError::BgmNotPlaying
+                | ApiError::BgmNotPaused
+                | ApiError::TimelineNotPlaying
+                | ApiError::Conflict(_) => axum::http::StatusCode::CONFLICT,
                 ApiError::MissingShadowPanel | ApiError::MissingName(_) => {
                     axum::http::StatusCode::BAD_REQUEST
                 }
                 ApiError::TooManyRequests(_) => axum::http::StatusCode::TOO_MANY_REQUESTS,
+                ApiError::Timeout(_) => axum::http::StatusCode::GATEWAY_TIMEOUT,
+                ApiError::Unsupported(_) => axum::http::StatusCode::NOT_IMPLEMENTED,
+                ApiError::RedistributionForbidden(_) => axum::http::StatusCode::FORBIDDEN,
                 _ => axum::http::StatusCode::INTERNAL_SERVER_ERROR,
             };
             (
@@ -0,0 +1,449 @@
+//! Unified engine event bus.
+//!
+//! Bridges every per-type [`VrmEventSender`](homunculus_core::prelude::VrmEventSender)
+//! channel and all user signals into a single broadcast stream of [`EngineEvent`]s,
+//! so consumers can follow the whole engine over one connection instead of opening
+//! a stream per event type or persona.
+
+use async_broadcast::{InactiveReceiver, Receiver, RecvError, Sender};
+use bevy::prelude::*;
+use bevy::tasks::futures_lite::Stream;
+use bevy::tasks::futures_lite::stream::unfold;
+use bevy_flurx::prelude::*;
+use homunculus_core::prelude::{
+    EmotionChangeEvent, ExpressionChangeEvent, OnClickEvent, OnDragEndEvent, OnDragEvent,
+    OnDragStartEvent, OnPointerCancelEvent, OnPointerMoveEvent, OnPointerOutEvent,
+    OnPointerOverEvent, OnPointerPressedEvent, OnPointerReleasedEvent, Persona, PersonaChangeEvent,
+    PersonaDeletedEvent, PersonaDespawnedEvent, PersonaId, PersonaSpawnedEvent,
+    PersonaStateChangeEvent, ProximityEnterEvent, ProximityLeaveEvent, VrmAttachedEvent,
+    VrmDetachedEvent, VrmEvent, VrmEventReceiver, VrmaFinishEvent, VrmaPlayEvent, WalkArriveEvent,
+    WalkCancelEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::error::ApiResult;
+use crate::signals::{SignalPattern, SignalRecord, SignalsChannels, now_millis};
+
+/// Number of events buffered per subscriber before the oldest are dropped.
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+/// The `event` name of user signals on the bus.
+pub const SIGNAL_EVENT: &str = "signal";
+
+/// Names of the engine events carried by the bus, in addition to [`SIGNAL_EVENT`].
+///
+/// Most match the SSE event names of `GET /personas/{id}/events` and `GET /personas/stream`;
+/// the `timeline-*` events are only published on the bus.
+pub const ENGINE_EVENTS: &[&str] = &[
+    "drag-start",
+    "drag",
+    "drag-end",
+    "pointer-press",
+    "pointer-click",
+    "pointer-move",
+    "pointer-release",
+    "pointer-over",
+    "pointer-out",
+    "pointer-cancel",
+    "state-change",
+    "expression-change",
+    "vrma-play",
+    "vrma-finish",
+    "vrm-attached",
+    "vrm-detached",
+    "persona-change",
+    "persona-spawned",
+    "persona-despawned",
+    "persona-deleted",
+    "proximity-enter",
+    "proximity-leave",
+    "walk-arrive",
+    "walk-cancel",
+    "timeline-play",
+    "timeline-cue",
+    "timeline-finish",
+    "path-tween-loop",
+    "path-tween-finish",
+    "emotion-change",
+];
+
+/// An event on the engine event bus.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EngineEvent {
+    /// Bus-wide sequence number, increasing by one per published event.
+    ///
+    /// On an unfiltered subscription, a jump in `seq` means events were missed.
+    pub seq: u64,
+    /// Unix time in milliseconds when the event was published.
+    pub timestamp: i64,
+    /// The event name, e.g. `drag-start` or `signal`.
+    pub event: String,
+    /// The persona the event concerns, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona_id: Option<PersonaId>,
+    /// The signal channel name, for `signal` events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal: Option<String>,
+    /// The event payload.
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub data: serde_json::Value,
+}
+
+/// Selects which events a subscription receives. Omitted fields match everything.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+    /// Event names to receive (see [`ENGINE_EVENTS`] and [`SIGNAL_EVENT`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<String>>,
+    /// Only receive events concerning these personas. Events without a persona are excluded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub personas: Option<Vec<PersonaId>>,
+    /// Signal channel names or wildcard patterns to receive `signal` events for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signals: Option<Vec<String>>,
+}
+
+impl EventFilter {
+    /// Validates the filter and compiles its signal patterns.
+    pub fn compile(self) -> Result<CompiledEventFilter, String> {
+        if let Some(unknown) = self.events.iter().flatten().find(|event| {
+            event.as_str() != SIGNAL_EVENT && !ENGINE_EVENTS.contains(&event.as_str())
+        }) {
+            return Err(format!("unknown event '{unknown}'"));
+        }
+        let signals = self
+            .signals
+            .map(|signals| {
+                signals
+                    .iter()
+                    .map(|signal| SignalPattern::parse(signal))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        Ok(CompiledEventFilter {
+            events: self.events,
+            personas: self.personas,
+            signals,
+        })
+    }
+}
+
+/// An [`EventFilter`] ready to be matched against events.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledEventFilter {
+    events: Option<Vec<String>>,
+    personas: Option<Vec<PersonaId>>,
+    signals: Option<Vec<SignalPattern>>,
+}
+
+impl CompiledEventFilter {
+    /// Returns `true` if `event` passes the filter.
+    pub fn matches(&self, event: &EngineEvent) -> bool {
+        if let Some(events) = &self.events
+            && !events.contains(&event.event)
+        {
+            return false;
+        }
+        if let Some(personas) = &self.personas
+            && !event
+                .persona_id
+                .as_ref()
+                .is_some_and(|id| personas.contains(id))
+        {
+            return false;
+        }
+        if let (Some(patterns), Some(signal)) = (&self.signals, &event.signal) {
+            return patterns.iter().any(|pattern| pattern.matches(signal));
+        }
+        true
+    }
+}
+
+/// An item of an event bus subscription.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventBusItem {
+    Event(EngineEvent),
+    /// The subscriber fell behind and this many events were dropped.
+    Lagged(u64),
+}
+
+#[derive(Resource)]
+pub(crate) struct EngineEventBus {
+    sender: Sender<EngineEvent>,
+    receiver: InactiveReceiver<EngineEvent>,
+    next_seq: u64,
+}
+
+impl Default for EngineEventBus {
+    fn default() -> Self {
+        let (mut sender, receiver) = async_broadcast::broadcast(EVENT_BUS_CAPACITY);
+        sender.set_overflow(true);
+        Self {
+            sender,
+            receiver: receiver.deactivate(),
+            next_seq: 1,
+        }
+    }
+}
+
+impl EngineEventBus {
+    pub(crate) fn has_subscribers(&self) -> bool {
+        0 < self.sender.receiver_count()
+    }
+
+    pub(crate) fn publish(
+        &mut self,
+        event: &str,
+        persona_id: Option<PersonaId>,
+        signal: Option<String>,
+        data: serde_json::Value,
+    ) {
+        let event = EngineEvent {
+            seq: self.next_seq,
+            timestamp: now_millis(),
+            event: event.to_string(),
+            persona_id,
+            signal,
+            data,
+        };
+        self.next_seq += 1;
+        let _ = self.sender.try_broadcast(event);
+    }
+}
+
+api!(
+    /// Provides access to the unified engine event bus.
+    EventsApi
+);
+
+impl EventsApi {
+    /// Subscribes to every event published on the bus from now on.
+    pub async fn subscribe(self) -> ApiResult<impl Stream<Item = EventBusItem>> {
+        self.0
+            .schedule(move |task| async move {
+                let rx = task.will(Update, once::run(obtain_receiver)).await;
+                unfold(rx, |mut rx| async move {
+                    match rx.recv().await {
+                        Ok(event) => Some((EventBusItem::Event(event), rx)),
+                        Err(RecvError::Overflowed(missed)) => {
+                            Some((EventBusItem::Lagged(missed), rx))
+                        }
+                        Err(RecvError::Closed) => None,
+                    }
+                })
+            })
+            .await
+    }
+}
+
+fn obtain_receiver(bus: Res<EngineEventBus>) -> Receiver<EngineEvent> {
+    bus.receiver.activate_cloned()
+}
+
+pub(super) struct EventsApiPlugin;
+
+impl Plugin for EventsApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EngineEventBus>().add_systems(
+            PostUpdate,
+            (
+                (
+                    bridge::<OnDragStartEvent>,
+                    bridge::<OnDragEvent>,
+                    bridge::<OnDragEndEvent>,
+                    bridge::<OnPointerPressedEvent>,
+                    bridge::<OnClickEvent>,
+                    bridge::<OnPointerMoveEvent>,
+                    bridge::<OnPointerReleasedEvent>,
+                    bridge::<OnPointerOverEvent>,
+                    bridge::<OnPointerOutEvent>,
+                    bridge::<OnPointerCancelEvent>,
+                ),
+                (
+                    bridge::<PersonaStateChangeEvent>,
+                    bridge::<ExpressionChangeEvent>,
+                    bridge::<VrmaPlayEvent>,
+                    bridge::<VrmaFinishEvent>,
+                    bridge::<VrmAttachedEvent>,
+                    bridge::<VrmDetachedEvent>,
+                    bridge::<PersonaChangeEvent>,
+                    bridge::<PersonaSpawnedEvent>,
+                    bridge::<PersonaDespawnedEvent>,
+                    bridge::<PersonaDeletedEvent>,
+                    bridge::<ProximityEnterEvent>,
+                    bridge::<ProximityLeaveEvent>,
+                    bridge::<WalkArriveEvent>,
+                    bridge::<WalkCancelEvent>,
+                    bridge::<EmotionChangeEvent>,
+                ),
+                bridge_signals,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// An engine event type carried by the bus under a fixed event name.
+trait BusEvent: Serialize + Clone + Send + Sync + 'static {
+    const NAME: &'static str;
+}
+
+macro_rules! bus_events {
+    ($($event:ty => $name:literal),* $(,)?) => {
+        $(
+            impl BusEvent for $event {
+                const NAME: &'static str = $name;
+            }
+        )*
+    };
+}
+
+bus_events! {
+    OnDragStartEvent => "drag-start",
+    OnDragEvent => "drag",
+    OnDragEndEvent => "drag-end",
+    OnPointerPressedEvent => "pointer-press",
+    OnClickEvent => "pointer-click",
+    OnPointerMoveEvent => "pointer-move",
+    OnPointerReleasedEvent => "pointer-release",
+    OnPointerOverEvent => "pointer-over",
+    OnPointerOutEvent => "pointer-out",
+    OnPointerCancelEvent => "pointer-cancel",
+    PersonaStateChangeEvent => "state-change",
+    ExpressionChangeEvent => "expression-change",
+    VrmaPlayEvent => "vrma-play",
+    VrmaFinishEvent => "vrma-finish",
+    VrmAttachedEvent => "vrm-attached",
+    VrmDetachedEvent => "vrm-detached",
+    PersonaChangeEvent => "persona-change",
+    PersonaSpawnedEvent => "persona-spawned",
+    PersonaDespawnedEvent => "persona-despawned",
+    PersonaDeletedEvent => "persona-deleted",
+    ProximityEnterEvent => "proximity-enter",
+    ProximityLeaveEvent => "proximity-leave",
+    WalkArriveEvent => "walk-arrive",
+    WalkCancelEvent => "walk-cancel",
+    EmotionChangeEvent => "emotion-change",
+}
+
+/// Republishes one `VrmEvent<E>` channel onto the bus.
+///
+/// The persona is resolved from the event's entity, falling back to a `personaId`
+/// field in the payload for entities that no longer exist (deleted or despawned personas).
+fn bridge<E: BusEvent>(
+    mut rx: Local<Option<Receiver<VrmEvent<E>>>>,
+    source: Option<Res<VrmEventReceiver<E>>>,
+    mut bus: ResMut<EngineEventBus>,
+    personas: Query<&Persona>,
+) {
+    let Some(source) = source else {
+        return;
+    };
+    let rx = rx.get_or_insert_with(|| source.new_receiver());
+    while let Some(vrm_event) = try_recv(rx) {
+        if !bus.has_subscribers() {
+            continue;
+        }
+        let Ok(data) = serde_json::to_value(&vrm_event.payload) else {
+            continue;
+        };
+        let persona_id = personas
+            .get(vrm_event.vrm)
+            .map(|persona| persona.id.clone())
+            .ok()
+            .or_else(|| {
+                data.get("personaId")
+                    .and_then(|id| serde_json::from_value(id.clone()).ok())
+            });
+        bus.publish(E::NAME, persona_id, None, data);
+    }
+}
+
+fn bridge_signals(
+    mut rx: Local<Option<Receiver<(String, SignalRecord)>>>,
+    channels: Res<SignalsChannels>,
+    mut bus: ResMut<EngineEventBus>,
+) {
+    let rx = rx.get_or_insert_with(|| channels.tap());
+    while let Some((signal, record)) = try_recv(rx) {
+        if bus.has_subscribers() {
+            bus.publish(SIGNAL_EVENT, None, Some(signal), record.payload);
+        }
+    }
+}
+
+/// Receives the next buffered message, skipping over any that were dropped on overflow.
+pub(crate) fn try_recv<T: Clone>(rx: &mut Receiver<T>) -> Option<T> {
+    loop {
+        match rx.try_recv() {
+            Ok(value) => return Some(value),
+            Err(async_broadcast::TryRecvError::Overflowed(_)) => continue,
+            Err(_) => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &str, persona: Option<&str>, signal: Option<&str>) -> EngineEvent {
+        EngineEvent {
+            seq: 1,
+            timestamp: 0,
+            event: name.to_string(),
+            persona_id: persona.map(PersonaId::new),
+            signal: signal.map(str::to_string),
+            data: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = EventFilter::default().compile().unwrap();
+        assert!(filter.matches(&event("drag", Some("elmer"), None)));
+        assert!(filter.matches(&event("signal", None, Some("stt/result"))));
+    }
+
+    #[test]
+    fn filters_by_event_and_persona() {
+        let filter = EventFilter {
+            events: Some(vec!["drag".to_string()]),
+            personas: Some(vec![PersonaId::new("elmer")]),
+            signals: None,
+        }
+        .compile()
+        .unwrap();
+        assert!(filter.matches(&event("drag", Some("elmer"), None)));
+        assert!(!filter.matches(&event("drag", Some("other"), None)));
+        assert!(!filter.matches(&event("drag", None, None)));
+        assert!(!filter.matches(&event("vrma-play", Some("elmer"), None)));
+    }
+
+    #[test]
+    fn filters_signals_by_pattern() {
+        let filter = EventFilter {
+            signals: Some(vec!["stt/#".to_string()]),
+            ..Default::default()
+        }
+        .compile()
+        .unwrap();
+        assert!(filter.matches(&event("signal", None, Some("stt/result"))));
+        assert!(!filter.matches(&event("signal", None, Some("chat"))));
+        assert!(filter.matches(&event("drag", Some("elmer"), None)));
+    }
+
+    #[test]
+    fn rejects_unknown_events() {
+        let filter = EventFilter {
+            events: Some(vec!["dragging".to_string()]),
+            ..Default::default()
+        };
+        assert!(filter.compile().is_err());
+    }
+}
@@ -0,0 +1,493 @@
+//! Spatial interaction between personas.
+//!
+//! Moves a persona next to another ([`InteractionApi::approach`]), turns it toward
+//! another ([`InteractionApi::face`]) and lines personas up along the bottom of the
+//! screen ([`InteractionApi::formation`]). While running, the engine pushes
+//! overlapping personas apart and reports `proximity-enter` / `proximity-leave`
+//! events when two personas come within [`InteractionSettings::proximity_radius`].
+
+mod spacing;
+
+use crate::api;
+use crate::entities::EasingFunction;
+use crate::error::{ApiError, ApiResult};
+use bevy::math::curve::easing::EaseFunction;
+use bevy::prelude::*;
+use bevy::window::{Monitor, PrimaryMonitor};
+use bevy_flurx::prelude::*;
+use bevy_tweening::lens::{TransformPositionLens, TransformRotationLens};
+use bevy_tweening::{EaseMethod, Tween, TweenAnim};
+use bevy_vrm1::vrm::Vrm;
+use homunculus_core::prelude::{Coordinate, GlobalViewport, Persona, PersonaId, PersonaIndex};
+use serde::{Deserialize, Serialize};
+use spacing::{separate_personas, track_proximity};
+use std::time::Duration;
+
+/// How far a facing persona turns away from the viewer, in radians.
+///
+/// A three-quarter turn keeps the face visible while clearly pointing at the other persona.
+const FACE_YAW: f32 = std::f32::consts::FRAC_PI_3;
+
+/// Request arguments for moving a persona next to another.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ApproachArgs {
+    /// The persona to approach.
+    pub target: PersonaId,
+    /// Horizontal distance to stop at, in world units. Defaults to `0.6`.
+    #[serde(default = "default_approach_distance")]
+    pub distance: f32,
+    /// Defaults to `1000`. `0` moves instantly.
+    #[serde(default = "default_approach_duration")]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+    /// Turn toward the target before moving. Defaults to `true`.
+    #[serde(default = "default_true")]
+    pub face: bool,
+    #[serde(default)]
+    pub wait: bool,
+}
+
+/// Request arguments for turning a persona toward another.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FaceArgs {
+    /// The persona to face.
+    pub target: PersonaId,
+    /// Defaults to `300`. `0` turns instantly.
+    #[serde(default = "default_face_duration")]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+    #[serde(default)]
+    pub wait: bool,
+}
+
+/// Request arguments for lining personas up along the bottom of the primary monitor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FormationArgs {
+    /// Personas from left to right. Defaults to every persona with a VRM, ordered by ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub personas: Option<Vec<PersonaId>>,
+    /// Distance from the bottom edge of the screen in pixels.
+    #[serde(default)]
+    pub margin: f32,
+    /// Defaults to `1000`. `0` moves instantly.
+    #[serde(default = "default_approach_duration")]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+    #[serde(default)]
+    pub wait: bool,
+}
+
+impl Default for FormationArgs {
+    fn default() -> Self {
+        Self {
+            personas: None,
+            margin: 0.0,
+            duration_ms: default_approach_duration(),
+            easing: EasingFunction::default(),
+            wait: false,
+        }
+    }
+}
+
+/// Engine-wide settings for separation and proximity detection.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct InteractionSettings {
+    /// Push overlapping personas apart.
+    pub separation: bool,
+    /// Horizontal gap separation keeps between personas, in world units.
+    pub personal_space: f32,
+    /// Distance at which `proximity-enter` fires, in world units.
+    ///
+    /// `proximity-leave` fires once the personas are 10% further apart than this.
+    pub proximity_radius: f32,
+}
+
+impl Default for InteractionSettings {
+    fn default() -> Self {
+        Self {
+            separation: true,
+            personal_space: 0.4,
+            proximity_radius: 1.0,
+        }
+    }
+}
+
+impl InteractionSettings {
+    fn validate(&self) -> ApiResult {
+        if !self.personal_space.is_finite() || self.personal_space < 0.0 {
+            return Err(ApiError::InvalidInput(
+                "personalSpace must be zero or positive".to_string(),
+            ));
+        }
+        if !self.proximity_radius.is_finite() || self.proximity_radius <= 0.0 {
+            return Err(ApiError::InvalidInput(
+                "proximityRadius must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_approach_distance() -> f32 {
+    0.6
+}
+
+fn default_approach_duration() -> u64 {
+    1000
+}
+
+fn default_face_duration() -> u64 {
+    300
+}
+
+fn default_true() -> bool {
+    true
+}
+
+api!(
+    /// Moves, turns and arranges personas relative to each other.
+    InteractionApi
+);
+
+impl InteractionApi {
+    /// Moves the persona to `distance` beside the target, on the side it is already on.
+    ///
+    /// The persona takes the target's height on screen and keeps its own depth.
+    pub async fn approach(&self, vrm: Entity, args: ApproachArgs) -> ApiResult {
+        if !args.distance.is_finite() || args.distance < 0.0 {
+            return Err(ApiError::InvalidInput(
+                "distance must be zero or positive".to_string(),
+            ));
+        }
+        let wait = args.wait.then_some(args.duration_ms);
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(approach).with((vrm, args)))
+                    .await?;
+                if let Some(ms) = wait {
+                    task.will(Update, delay::time().with(Duration::from_millis(ms)))
+                        .await;
+                }
+                Ok(())
+            })
+            .await?
+    }
+
+    /// Turns the persona toward the target, around the vertical axis.
+    pub async fn face(&self, vrm: Entity, args: FaceArgs) -> ApiResult {
+        let wait = args.wait.then_some(args.duration_ms);
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(face).with((vrm, args))).await?;
+                if let Some(ms) = wait {
+                    task.will(Update, delay::time().with(Duration::from_millis(ms)))
+                        .await;
+                }
+                Ok(())
+            })
+            .await?
+    }
+
+    /// Spreads personas evenly along the bottom of the primary monitor.
+    pub async fn formation(&self, args: FormationArgs) -> ApiResult {
+        if !args.margin.is_finite() {
+            return Err(ApiError::InvalidInput("margin must be finite".to_string()));
+        }
+        let wait = args.wait.then_some(args.duration_ms);
+        self.0
+            .schedule(move |task| async move {
+                let targets = task
+                    .will(Update, once::run(formation_targets).with(args.clone()))
+                    .await?;
+                task.will(
+                    Update,
+                    once::run(move_personas).with((targets, args.duration_ms, args.easing)),
+                )
+                .await;
+                if let Some(ms) = wait {
+                    task.will(Update, delay::time().with(Duration::from_millis(ms)))
+                        .await;
+                }
+                Ok(())
+            })
+            .await?
+    }
+
+    /// Returns the separation and proximity settings.
+    pub async fn settings(&self) -> ApiResult<InteractionSettings> {
+        self.0
+            .schedule(move |task| async move { task.will(Update, once::run(get_settings)).await })
+            .await
+    }
+
+    /// Replaces the separation and proximity settings.
+    pub async fn set_settings(
+        &self,
+        settings: InteractionSettings,
+    ) -> ApiResult<InteractionSettings> {
+        settings.validate()?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_settings).with(settings))
+                    .await
+            })
+            .await
+    }
+}
+
+fn get_settings(settings: Res<InteractionSettings>) -> InteractionSettings {
+    settings.clone()
+}
+
+fn put_settings(
+    In(settings): In<InteractionSettings>,
+    mut current: ResMut<InteractionSettings>,
+) -> InteractionSettings {
+    *current = settings.clone();
+    settings
+}
+
+fn approach(
+    In((vrm, args)): In<(Entity, ApproachArgs)>,
+    index: Res<PersonaIndex>,
+    mut transforms: Query<&mut Transform>,
+    mut commands: Commands,
+) -> ApiResult {
+    let other = resolve_other(&index, vrm, &args.target)?;
+    let other_tf = *transforms
+        .get(other)
+        .map_err(|_| ApiError::EntityNotFound)?;
+    let mut tf = transforms
+        .get_mut(vrm)
+        .map_err(|_| ApiError::EntityNotFound)?;
+
+    let x = approach_x(tf.translation.x, other_tf.translation.x, args.distance);
+    let target = Vec3::new(x, other_tf.translation.y, tf.translation.z);
+    if args.face
+        && let Some(yaw) = facing_yaw(x, other_tf.translation.x)
+    {
+        tf.rotation = Quat::from_rotation_y(yaw);
+    }
+    move_to(
+        &mut commands,
+        vrm,
+        &mut tf,
+        target,
+        args.duration_ms,
+        args.easing,
+    );
+    Ok(())
+}
+
+fn face(
+    In((vrm, args)): In<(Entity, FaceArgs)>,
+    index: Res<PersonaIndex>,
+    mut transforms: Query<&mut Transform>,
+    mut commands: Commands,
+) -> ApiResult {
+    let other = resolve_other(&index, vrm, &args.target)?;
+    let other_x = transforms
+        .get(other)
+        .map_err(|_| ApiError::EntityNotFound)?
+        .translation
+        .x;
+    let mut tf = transforms
+        .get_mut(vrm)
+        .map_err(|_| ApiError::EntityNotFound)?;
+    let Some(yaw) = facing_yaw(tf.translation.x, other_x) else {
+        return Ok(());
+    };
+    let target = Quat::from_rotation_y(yaw);
+    if args.duration_ms == 0 {
+        tf.rotation = target;
+        return Ok(());
+    }
+    let tween = Tween::new(
+        ease_method(args.easing),
+        Duration::from_millis(args.duration_ms),
+        TransformRotationLens {
+            start: tf.rotation,
+            end: target,
+        },
+    );
+    commands.entity(vrm).try_insert(TweenAnim::new(tween));
+    Ok(())
+}
+
+/// Computes where each persona of a formation goes, in world coordinates.
+fn formation_targets(
+    In(args): In<FormationArgs>,
+    index: Res<PersonaIndex>,
+    personas: Query<(Entity, &Persona), With<Vrm>>,
+    primary_monitor: Query<&Monitor, With<PrimaryMonitor>>,
+    coordinate: Coordinate,
+) -> ApiResult<Vec<(Entity, Vec2)>> {
+    let entities = match args.personas {
+        Some(ids) => ids
+            .iter()
+            .map(|id| {
+                index
+                    .get(id)
+                    .filter(|e| personas.contains(*e))
+                    .ok_or(ApiError::EntityNotFound)
+            })
+            .collect::<ApiResult<Vec<_>>>()?,
+        None => {
+            let mut all: Vec<_> = personas.iter().collect();
+            all.sort_by(|(_, a), (_, b)| a.id.0.cmp(&b.id.0));
+            all.into_iter().map(|(entity, _)| entity).collect()
+        }
+    };
+    let monitor = primary_monitor
+        .single()
+        .map_err(|_| ApiError::FailedToWorldPosition)?;
+    let scale = monitor.scale_factor as f32;
+    let pos = monitor.physical_position.as_vec2() / scale;
+    let size = monitor.physical_size().as_vec2() / scale;
+    let bounds = Rect::from_corners(pos, pos + size);
+
+    formation_slots(bounds, entities.len(), args.margin)
+        .into_iter()
+        .zip(entities)
+        .map(|(slot, entity)| {
+            let world = coordinate
+                .to_world_2d_by_global(GlobalViewport(slot))
+                .ok_or(ApiError::FailedToWorldPosition)?;
+            Ok((entity, world))
+        })
+        .collect()
+}
+
+fn move_personas(
+    In((targets, duration_ms, easing)): In<(Vec<(Entity, Vec2)>, u64, EasingFunction)>,
+    mut transforms: Query<&mut Transform>,
+    mut commands: Commands,
+) {
+    for (entity, target) in targets {
+        if let Ok(mut tf) = transforms.get_mut(entity) {
+            let target = target.extend(tf.translation.z);
+            move_to(&mut commands, entity, &mut tf, target, duration_ms, easing);
+        }
+    }
+}
+
+fn resolve_other(index: &PersonaIndex, vrm: Entity, target: &PersonaId) -> ApiResult<Entity> {
+    let other = index.get(target).ok_or(ApiError::EntityNotFound)?;
+    if other == vrm {
+        return Err(ApiError::InvalidInput(
+            "a persona cannot interact with itself".to_string(),
+        ));
+    }
+    Ok(other)
+}
+
+/// Moves the entity to `target`, tweening when `duration_ms` is non-zero.
+fn move_to(
+    commands: &mut Commands,
+    entity: Entity,
+    tf: &mut Transform,
+    target: Vec3,
+    duration_ms: u64,
+    easing: EasingFunction,
+) {
+    if duration_ms == 0 {
+        tf.translation = target;
+        return;
+    }
+    let tween = Tween::new(
+        ease_method(easing),
+        Duration::from_millis(duration_ms),
+        TransformPositionLens {
+            start: tf.translation,
+            end: target,
+        },
+    );
+    commands.entity(entity).try_insert(TweenAnim::new(tween));
+}
+
+fn ease_method(easing: EasingFunction) -> EaseMethod {
+    let ease_function: EaseFunction = easing.into();
+    ease_function.into()
+}
+
+/// The x coordinate `distance` away from `other_x`, on the side `self_x` is on.
+fn approach_x(self_x: f32, other_x: f32, distance: f32) -> f32 {
+    if self_x <= other_x {
+        other_x - distance
+    } else {
+        other_x + distance
+    }
+}
+
+/// The yaw that turns a persona at `self_x` toward `other_x`, or `None` if they are
+/// in line and there is no side to turn to.
+fn facing_yaw(self_x: f32, other_x: f32) -> Option<f32> {
+    let dx = other_x - self_x;
+    (dx.abs() > f32::EPSILON).then(|| FACE_YAW.copysign(dx))
+}
+
+/// Evenly spaced slots along the bottom of `bounds`, `margin` pixels above its edge.
+fn formation_slots(bounds: Rect, count: usize, margin: f32) -> Vec<Vec2> {
+    let step = bounds.width() / count as f32;
+    (0..count)
+        .map(|i| {
+            Vec2::new(
+                bounds.min.x + step * (i as f32 + 0.5),
+                bounds.max.y - margin,
+            )
+        })
+        .collect()
+}
+
+pub(super) struct InteractionApiPlugin;
+
+impl Plugin for InteractionApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionSettings>()
+            .add_systems(Update, (separate_personas, track_proximity).chain());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approach_stops_on_the_near_side() {
+        assert_eq!(approach_x(-2.0, 1.0, 0.5), 0.5);
+        assert_eq!(approach_x(3.0, 1.0, 0.5), 1.5);
+    }
+
+    #[test]
+    fn facing_turns_toward_the_other() {
+        assert_eq!(facing_yaw(0.0, 1.0), Some(FACE_YAW));
+        assert_eq!(facing_yaw(0.0, -1.0), Some(-FACE_YAW));
+        assert_eq!(facing_yaw(1.0, 1.0), None);
+    }
+
+    #[test]
+    fn formation_spreads_along_the_bottom() {
+        let bounds = Rect::new(0.0, 0.0, 900.0, 600.0);
+        let slots = formation_slots(bounds, 3, 20.0);
+        assert_eq!(
+            slots,
+            vec![
+                Vec2::new(150.0, 580.0),
+                Vec2::new(450.0, 580.0),
+                Vec2::new(750.0, 580.0),
+            ]
+        );
+        assert!(formation_slots(bounds, 0, 0.0).is_empty());
+    }
+}
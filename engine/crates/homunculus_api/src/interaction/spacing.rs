@@ -0,0 +1,275 @@
+//! Separation and proximity detection between personas.
+
+use super::InteractionSettings;
+use crate::entities::path_tween::PathTween;
+use crate::locomotion::WALKING_STATE;
+use bevy::prelude::*;
+use bevy_tweening::TweenAnim;
+use bevy_vrm1::vrm::Vrm;
+use homunculus_core::prelude::{
+    OutputLog, Persona, PersonaId, PersonaState, ProximityEnterEvent, ProximityLeaveEvent,
+    VrmEvent, VrmEventSender,
+};
+use homunculus_drag::physics::FALLING_STATE;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Maximum speed at which separation moves a persona, in world units per second.
+const SEPARATION_SPEED: f32 = 1.5;
+
+/// `proximity-leave` fires once a pair is this much further apart than the radius,
+/// so personas standing right at the edge do not flicker in and out.
+const LEAVE_FACTOR: f32 = 1.1;
+
+/// A persona as seen by the separation and proximity passes.
+pub(super) struct Body<K> {
+    pub key: K,
+    pub position: Vec2,
+    /// Whether separation may move this persona. Fixed personas still push others.
+    pub movable: bool,
+}
+
+/// Pushes overlapping personas apart horizontally, a little every frame.
+///
+/// Personas that are being dragged, sitting, walking, falling or tweening (including along
+/// a path) are not moved.
+pub(super) fn separate_personas(
+    settings: Res<InteractionSettings>,
+    time: Res<Time>,
+    mut personas: Query<
+        (
+            Entity,
+            &mut Transform,
+            &PersonaState,
+            Has<TweenAnim>,
+            Has<PathTween>,
+        ),
+        With<Vrm>,
+    >,
+) {
+    if !settings.separation {
+        return;
+    }
+    let bodies: Vec<_> = personas
+        .iter()
+        .map(|(entity, tf, state, tweening, on_path)| Body {
+            key: entity,
+            position: tf.translation.truncate(),
+            movable: !tweening
+                && !on_path
+                && !matches!(
+                    state.0.as_str(),
+                    PersonaState::DRAG | PersonaState::SITTING | WALKING_STATE | FALLING_STATE
+                ),
+        })
+        .collect();
+    let max_step = SEPARATION_SPEED * time.delta_secs();
+    for (body, push) in bodies
+        .iter()
+        .zip(separation_pushes(&bodies, settings.personal_space))
+    {
+        if push == 0.0 {
+            continue;
+        }
+        if let Ok((_, mut tf, ..)) = personas.get_mut(body.key) {
+            tf.translation.x += push.clamp(-max_step, max_step);
+        }
+    }
+}
+
+/// Pairs of personas within the proximity radius, with the IDs seen last frame so
+/// leave events can still name a persona that has been despawned.
+#[derive(Default)]
+pub(super) struct ProximityState {
+    near: HashSet<(Entity, Entity)>,
+    ids: HashMap<Entity, PersonaId>,
+}
+
+/// Emits `proximity-enter` and `proximity-leave` on both personas of a pair.
+pub(super) fn track_proximity(
+    mut state: Local<ProximityState>,
+    settings: Res<InteractionSettings>,
+    personas: Query<(Entity, &Persona, &Transform), With<Vrm>>,
+    tx_enter: Option<Res<VrmEventSender<ProximityEnterEvent>>>,
+    tx_leave: Option<Res<VrmEventSender<ProximityLeaveEvent>>>,
+) {
+    let bodies: Vec<_> = personas
+        .iter()
+        .map(|(entity, _, tf)| Body {
+            key: entity,
+            position: tf.translation.truncate(),
+            movable: true,
+        })
+        .collect();
+    let (entered, left) = proximity_changes(&state.near, &bodies, settings.proximity_radius);
+
+    for &(a, b) in &left {
+        state.near.remove(&(a, b));
+        if let Some(tx) = &tx_leave {
+            for (vrm, other) in [(a, b), (b, a)] {
+                if let Some(other) = state.ids.get(&other)
+                    && personas.contains(vrm)
+                {
+                    tx.try_broadcast(VrmEvent {
+                        vrm,
+                        payload: ProximityLeaveEvent {
+                            other: other.clone(),
+                        },
+                    })
+                    .output_log_if_error("Failed to broadcast ProximityLeaveEvent");
+                }
+            }
+        }
+    }
+
+    state.ids = personas
+        .iter()
+        .map(|(entity, persona, _)| (entity, persona.id.clone()))
+        .collect();
+    for &(a, b, distance) in &entered {
+        state.near.insert((a, b));
+        if let Some(tx) = &tx_enter {
+            for (vrm, other) in [(a, b), (b, a)] {
+                if let Some(other) = state.ids.get(&other) {
+                    tx.try_broadcast(VrmEvent {
+                        vrm,
+                        payload: ProximityEnterEvent {
+                            other: other.clone(),
+                            distance,
+                        },
+                    })
+                    .output_log_if_error("Failed to broadcast ProximityEnterEvent");
+                }
+            }
+        }
+    }
+}
+
+/// Horizontal displacement that resolves every overlap, per body.
+///
+/// Two bodies overlap when both their horizontal and vertical distance are below
+/// `personal_space`. A movable pair shares the correction; a movable body next to a
+/// fixed one takes all of it. Bodies at the same x are split by their order.
+pub(super) fn separation_pushes<K>(bodies: &[Body<K>], personal_space: f32) -> Vec<f32> {
+    let mut pushes = vec![0.0; bodies.len()];
+    for i in 0..bodies.len() {
+        for j in (i + 1)..bodies.len() {
+            let (a, b) = (&bodies[i], &bodies[j]);
+            let delta = b.position - a.position;
+            if delta.x.abs() >= personal_space || delta.y.abs() >= personal_space {
+                continue;
+            }
+            let overlap = personal_space - delta.x.abs();
+            let direction = if delta.x >= 0.0 { 1.0 } else { -1.0 };
+            let (share_a, share_b) = match (a.movable, b.movable) {
+                (true, true) => (overlap / 2.0, overlap / 2.0),
+                (true, false) => (overlap, 0.0),
+                (false, true) => (0.0, overlap),
+                (false, false) => (0.0, 0.0),
+            };
+            pushes[i] -= direction * share_a;
+            pushes[j] += direction * share_b;
+        }
+    }
+    pushes
+}
+
+/// Pairs that came within `radius`, with their distance, and pairs in `near` that
+/// moved apart or are gone. Pairs are ordered `(smaller, larger)`.
+pub(super) fn proximity_changes<K>(
+    near: &HashSet<(K, K)>,
+    bodies: &[Body<K>],
+    radius: f32,
+) -> (Vec<(K, K, f32)>, Vec<(K, K)>)
+where
+    K: Copy + Ord + Hash,
+{
+    let mut entered = Vec::new();
+    let mut current = HashSet::new();
+    for i in 0..bodies.len() {
+        for j in (i + 1)..bodies.len() {
+            let (a, b) = (&bodies[i], &bodies[j]);
+            let pair = (a.key.min(b.key), a.key.max(b.key));
+            let distance = a.position.distance(b.position);
+            if near.contains(&pair) {
+                if distance <= radius * LEAVE_FACTOR {
+                    current.insert(pair);
+                }
+            } else if distance <= radius {
+                entered.push((pair.0, pair.1, distance));
+            }
+        }
+    }
+    let left = near
+        .iter()
+        .filter(|pair| !current.contains(*pair))
+        .copied()
+        .collect();
+    (entered, left)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_pushes(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-5, "{actual:?} != {expected:?}");
+        }
+    }
+
+    fn body(key: u32, x: f32, movable: bool) -> Body<u32> {
+        Body {
+            key,
+            position: Vec2::new(x, 0.0),
+            movable,
+        }
+    }
+
+    #[test]
+    fn overlapping_personas_share_the_push() {
+        let pushes = separation_pushes(&[body(0, 0.0, true), body(1, 0.2, true)], 0.4);
+        assert_pushes(pushes, &[-0.1, 0.1]);
+    }
+
+    #[test]
+    fn fixed_personas_push_but_do_not_move() {
+        let pushes = separation_pushes(&[body(0, 0.0, false), body(1, -0.1, true)], 0.4);
+        assert_pushes(pushes, &[0.0, -0.3]);
+    }
+
+    #[test]
+    fn distant_or_stacked_personas_are_left_alone() {
+        let apart = separation_pushes(&[body(0, 0.0, true), body(1, 0.5, true)], 0.4);
+        assert_eq!(apart, vec![0.0, 0.0]);
+
+        let mut above = body(1, 0.0, true);
+        above.position.y = 1.0;
+        let stacked = separation_pushes(&[body(0, 0.0, true), above], 0.4);
+        assert_eq!(stacked, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn proximity_enters_and_leaves_with_hysteresis() {
+        let mut near = HashSet::new();
+        let (entered, left) =
+            proximity_changes(&near, &[body(2, 0.0, true), body(1, 0.8, true)], 1.0);
+        assert_eq!(entered.len(), 1);
+        assert_eq!((entered[0].0, entered[0].1), (1, 2));
+        assert!((entered[0].2 - 0.8).abs() < 1e-5);
+        assert!(left.is_empty());
+        near.insert((1, 2));
+
+        let (entered, left) =
+            proximity_changes(&near, &[body(2, 0.0, true), body(1, 1.05, true)], 1.0);
+        assert!(entered.is_empty());
+        assert!(left.is_empty());
+
+        let (_, left) = proximity_changes(&near, &[body(2, 0.0, true), body(1, 1.2, true)], 1.0);
+        assert_eq!(left, vec![(1, 2)]);
+
+        let (_, left) = proximity_changes(&near, &[body(2, 0.0, true)], 1.0);
+        assert_eq!(left, vec![(1, 2)]);
+    }
+}
@@ -36,32 +36,47 @@
 mod app;
 pub mod assets;
 mod audio;
+pub mod batch;
 mod cameras;
 mod display;
 mod effects;
+pub mod emotion;
 pub mod entities;
 mod error;
+pub mod events;
+pub mod interaction;
+pub mod locomotion;
 pub mod mods;
 pub mod persona;
+pub mod physics;
 pub mod preferences;
 pub mod processes;
 mod reactor;
+pub mod rpc;
 mod settings;
 mod shadow_panel;
 mod signals;
 mod speech;
 pub mod stt;
+pub mod timeline;
 pub mod vrm;
 mod vrma;
 mod webview;
 
 use crate::assets::AssetsApiPlugin;
+use crate::emotion::EmotionApiPlugin;
+use crate::entities::EntitiesApiPlugin;
+use crate::events::EventsApiPlugin;
+use crate::interaction::InteractionApiPlugin;
+use crate::locomotion::LocomotionApiPlugin;
 use crate::persona::PersonaApiPlugin;
 use crate::prelude::{ShadowPanelApiPlugin, WebviewApiPlugin};
 use crate::processes::ProcessesApiPlugin;
 use crate::reactor::ApiReactorPlugin;
 use crate::signals::SignalsApiPlugin;
 use crate::stt::SttPttPlugin;
+use crate::timeline::TimelineApiPlugin;
+use crate::vrm::VrmApiPlugin;
 use bevy::app::PluginGroupBuilder;
 use bevy::prelude::*;
 
@@ -82,7 +97,9 @@ pub mod prelude {
         reactor::*,
         settings::*,
         shadow_panel::*,
-        signals::{SignalInfo, SignalsApi},
+        signals::{
+            SignalInfo, SignalPattern, SignalRecord, SignalRequest, SignalRetention, SignalsApi,
+        },
         speech::*,
         vrm::*,
         vrma::*,
@@ -103,8 +120,15 @@ pub mod prelude {
 /// - `WebviewApiPlugin`: WebView management and control
 /// - `GptApiPlugin`: AI chat integration
 /// - `SignalsApiPlugin`: Signal-based pub/sub messaging
+/// - `EventsApiPlugin`: Unified engine event bus
 /// - `ShadowPanelApiPlugin`: Shadow rendering control
 /// - `PersonaApiPlugin`: Persona startup restoration
+/// - `TimelineApiPlugin`: Persona timeline playback
+/// - `InteractionApiPlugin`: Persona separation and proximity events
+/// - `LocomotionApiPlugin`: Persona walking
+/// - `EntitiesApiPlugin`: Path tweens
+/// - `EmotionApiPlugin`: Persona emotions and their fading
+/// - `VrmApiPlugin`: Expression transitions, auto-blink, micro-expressions and spring bone wind
 pub struct HomunculusApiPlugin;
 
 impl PluginGroup for HomunculusApiPlugin {
@@ -114,11 +138,18 @@ impl PluginGroup for HomunculusApiPlugin {
             .add(ApiReactorPlugin)
             .add(WebviewApiPlugin)
             .add(SignalsApiPlugin)
+            .add(EventsApiPlugin)
             .add(ShadowPanelApiPlugin)
             .add(PersonaApiPlugin)
             .add(AssetsApiPlugin)
             .add(SttPttPlugin)
             .add(ProcessesApiPlugin)
+            .add(TimelineApiPlugin)
+            .add(InteractionApiPlugin)
+            .add(LocomotionApiPlugin)
+            .add(EntitiesApiPlugin)
+            .add(EmotionApiPlugin)
+            .add(VrmApiPlugin)
             .build()
     }
 }
@@ -0,0 +1,359 @@
+//! Walking locomotion for personas.
+//!
+//! [`LocomotionApi::walk`] moves a persona to a target at a constant speed instead of
+//! teleporting or sliding it. While walking, the persona loops a walk VRMA, turns
+//! toward the direction of travel and is in the `walking` state. On arrival it turns
+//! back, stops the walk VRMA, blends to an idle VRMA and returns to `idle`.
+//!
+//! Targets are clamped onto the parts of the app windows that a display shows, and
+//! paths stay inside them: a walk from one window to another follows the edges the
+//! windows share.
+
+mod path;
+
+use crate::api;
+use crate::entities::MoveTarget;
+use crate::error::{ApiError, ApiResult};
+use crate::vrm::initialized;
+use crate::vrm::vrma::fetch_vrma;
+use crate::vrma::Repeat;
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use bevy_vrm1::prelude::{PlayVrma, StopVrma};
+use homunculus_core::prelude::{
+    AssetId, Coordinate, GlobalViewport, OutputLog, PersonaState, VrmEvent, VrmEventSender,
+    WalkArriveEvent, WalkCancelEvent,
+};
+use homunculus_screen::prelude::GlobalDisplays;
+use path::{clamp_to_windows, plan_path, step_along, visible_areas};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// The persona state while walking.
+pub(crate) const WALKING_STATE: &str = "walking";
+
+/// Walk VRMA from the bundled assets, used when a walk does not name one.
+const DEFAULT_WALK_VRMA: &str = "vrma:walk";
+
+/// Idle VRMA from the bundled assets, used when a walk does not name one.
+const DEFAULT_IDLE_VRMA: &str = "vrma:idle-maid";
+
+/// How far a walking persona turns toward the direction of travel, in radians.
+const WALK_YAW: f32 = std::f32::consts::FRAC_PI_2;
+
+/// Request arguments for walking a persona to a target.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct WalkArgs {
+    pub target: MoveTarget,
+    /// Walking speed in world units per second. Defaults to `0.8`.
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    /// VRMA looped while walking. Defaults to the bundled `vrma:walk`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub walk_vrma: Option<AssetId>,
+    /// VRMA blended to on arrival. Defaults to the bundled `vrma:idle-maid`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idle_vrma: Option<AssetId>,
+    /// Blend time between the walk and idle animations in seconds. Defaults to `0.3`.
+    #[serde(default = "default_transition_secs")]
+    pub transition_secs: f64,
+    /// Resolve only once the walk ends.
+    #[serde(default)]
+    pub wait: bool,
+}
+
+fn default_speed() -> f32 {
+    0.8
+}
+
+fn default_transition_secs() -> f64 {
+    0.3
+}
+
+/// A persona walking along a planned path.
+#[derive(Component, Debug)]
+struct Walker {
+    /// Remaining waypoints in world coordinates; the last one is the target.
+    waypoints: VecDeque<Vec3>,
+    speed: f32,
+    /// Rotation before the walk started, restored on arrival.
+    rest_rotation: Quat,
+    walk_vrma: Option<Entity>,
+    idle_vrma: Option<Entity>,
+    transition: Duration,
+}
+
+/// A walk ready to start: its path and loaded animations.
+struct WalkPlan {
+    waypoints: VecDeque<Vec3>,
+    speed: f32,
+    walk_vrma: Option<Entity>,
+    idle_vrma: Option<Entity>,
+    transition: Duration,
+}
+
+api!(
+    /// Walks personas across the desktop.
+    LocomotionApi
+);
+
+impl LocomotionApi {
+    /// Walks the persona to the target, replacing a walk in progress.
+    ///
+    /// Progress ends with a `walk-arrive` or `walk-cancel` event.
+    pub async fn walk(&self, vrm: Entity, args: WalkArgs) -> ApiResult {
+        if !args.speed.is_finite() || args.speed <= 0.0 {
+            return Err(ApiError::InvalidInput("speed must be positive".to_string()));
+        }
+        if !args.transition_secs.is_finite() || args.transition_secs < 0.0 {
+            return Err(ApiError::InvalidInput(
+                "transitionSecs must be zero or positive".to_string(),
+            ));
+        }
+        self.0
+            .schedule(move |task| async move {
+                let mut vrmas = [None, None];
+                let assets = [
+                    (args.walk_vrma, DEFAULT_WALK_VRMA),
+                    (args.idle_vrma, DEFAULT_IDLE_VRMA),
+                ];
+                for (slot, (asset, default)) in vrmas.iter_mut().zip(assets) {
+                    let requested = asset.is_some();
+                    let asset = asset.unwrap_or_else(|| AssetId::new(default));
+                    let vrma = task
+                        .will(Update, once::run(fetch_vrma).with((vrm, asset)))
+                        .await;
+                    let vrma = match vrma {
+                        Some(vrma) => vrma,
+                        // Without the bundled assets the persona walks unanimated.
+                        None if !requested => continue,
+                        None => return Err(ApiError::EntityNotFound),
+                    };
+                    task.will(Update, wait::until(initialized).with(vrma)).await;
+                    *slot = Some(vrma);
+                }
+                let waypoints = task
+                    .will(Update, once::run(plan_walk).with((vrm, args.target)))
+                    .await?;
+                let plan = WalkPlan {
+                    waypoints,
+                    speed: args.speed,
+                    walk_vrma: vrmas[0],
+                    idle_vrma: vrmas[1],
+                    transition: Duration::from_secs_f64(args.transition_secs),
+                };
+                task.will(Update, once::run(start_walk).with((vrm, plan)))
+                    .await;
+                if args.wait {
+                    task.will(Update, wait::until(walk_ended).with(vrm)).await;
+                }
+                Ok(())
+            })
+            .await?
+    }
+
+    /// Stops the persona where it is. The persona turns back and returns to `idle`.
+    pub async fn stop(&self, vrm: Entity) -> ApiResult {
+        self.0
+            .schedule(
+                move |task| async move { task.will(Update, once::run(stop_walk).with(vrm)).await },
+            )
+            .await?
+    }
+}
+
+/// Plans the walk path in global viewport coordinates and converts it to world waypoints.
+///
+/// When the persona is not in any app window (e.g. headless), it walks straight to a
+/// world target.
+fn plan_walk(
+    In((vrm, target)): In<(Entity, MoveTarget)>,
+    coordinate: Coordinate,
+    transforms: Query<&Transform>,
+) -> ApiResult<VecDeque<Vec3>> {
+    let start = transforms
+        .get(vrm)
+        .map_err(|_| ApiError::EntityNotFound)?
+        .translation;
+    let target_global = match &target {
+        MoveTarget::Viewport { position } => Some(*position),
+        MoveTarget::World { position, z } => coordinate
+            .to_global_by_world(position.extend(z.unwrap_or(start.z)))
+            .map(|global| global.0),
+    };
+    let start_global = coordinate.to_global_by_world(start).map(|global| global.0);
+
+    match (start_global, target_global) {
+        (Some(start_global), Some(target_global)) => {
+            let displays: Vec<Rect> = GlobalDisplays::find_all()
+                .iter()
+                .map(|display| display.frame)
+                .collect();
+            let windows = visible_areas(&coordinate.windows.rects(), &displays);
+            let target_global = clamp_to_windows(target_global, &windows);
+            plan_path(start_global, target_global, &windows)
+                .into_iter()
+                .map(|point| {
+                    let world = coordinate
+                        .to_world_2d_by_global(GlobalViewport(point))
+                        .ok_or(ApiError::FailedToWorldPosition)?;
+                    Ok(world.extend(start.z))
+                })
+                .collect()
+        }
+        _ => match target {
+            MoveTarget::World { position, z } => {
+                Ok(VecDeque::from([position.extend(z.unwrap_or(start.z))]))
+            }
+            MoveTarget::Viewport { .. } => Err(ApiError::FailedToWorldPosition),
+        },
+    }
+}
+
+fn start_walk(
+    In((vrm, plan)): In<(Entity, WalkPlan)>,
+    mut commands: Commands,
+    walkers: Query<(&Walker, &Transform)>,
+    transforms: Query<&Transform>,
+    tx_cancel: Option<Res<VrmEventSender<WalkCancelEvent>>>,
+) {
+    let rest_rotation = match walkers.get(vrm) {
+        Ok((walker, tf)) => {
+            send_cancel(&tx_cancel, vrm, "replaced", tf.translation);
+            walker.rest_rotation
+        }
+        Err(_) => transforms
+            .get(vrm)
+            .map(|tf| tf.rotation)
+            .unwrap_or_default(),
+    };
+    if let Some(vrma) = plan.walk_vrma {
+        commands.trigger(PlayVrma {
+            vrma,
+            repeat: Repeat::Forever.into(),
+            transition_duration: plan.transition,
+            reset_spring_bones: false,
+        });
+    }
+    commands.entity(vrm).try_insert((
+        Walker {
+            waypoints: plan.waypoints,
+            speed: plan.speed,
+            rest_rotation,
+            walk_vrma: plan.walk_vrma,
+            idle_vrma: plan.idle_vrma,
+            transition: plan.transition,
+        },
+        PersonaState::from(WALKING_STATE),
+    ));
+}
+
+fn stop_walk(
+    In(vrm): In<Entity>,
+    mut commands: Commands,
+    walkers: Query<(&Walker, &Transform)>,
+    tx_cancel: Option<Res<VrmEventSender<WalkCancelEvent>>>,
+) -> ApiResult {
+    let (walker, tf) = walkers
+        .get(vrm)
+        .map_err(|_| ApiError::Conflict("persona is not walking".to_string()))?;
+    send_cancel(&tx_cancel, vrm, "stopped", tf.translation);
+    rest(&mut commands, vrm, walker, *tf);
+    Ok(())
+}
+
+fn walk_ended(In(vrm): In<Entity>, walkers: Query<(), With<Walker>>) -> bool {
+    !walkers.contains(vrm)
+}
+
+/// Moves every walker along its path and finishes the walks that arrived.
+///
+/// A walk is cancelled as `interrupted` when something else changes the persona
+/// state, such as a drag.
+fn advance_walkers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut walkers: Query<(Entity, &mut Walker, &mut Transform, &PersonaState)>,
+    tx_arrive: Option<Res<VrmEventSender<WalkArriveEvent>>>,
+    tx_cancel: Option<Res<VrmEventSender<WalkCancelEvent>>>,
+) {
+    for (vrm, mut walker, mut tf, state) in walkers.iter_mut() {
+        if state.0 != WALKING_STATE {
+            send_cancel(&tx_cancel, vrm, "interrupted", tf.translation);
+            commands.entity(vrm).try_remove::<Walker>();
+            continue;
+        }
+        if let Some(next) = walker.waypoints.front() {
+            let dx = next.x - tf.translation.x;
+            if dx.abs() > f32::EPSILON {
+                tf.rotation = Quat::from_rotation_y(WALK_YAW.copysign(dx)) * walker.rest_rotation;
+            }
+        }
+        let distance = walker.speed * time.delta_secs();
+        tf.translation = step_along(tf.translation, &mut walker.waypoints, distance);
+        if walker.waypoints.is_empty() {
+            if let Some(tx) = &tx_arrive {
+                tx.try_broadcast(VrmEvent {
+                    vrm,
+                    payload: WalkArriveEvent {
+                        position: tf.translation,
+                    },
+                })
+                .output_log_if_error("Failed to broadcast WalkArriveEvent");
+            }
+            rest(&mut commands, vrm, &walker, *tf);
+        }
+    }
+}
+
+/// Ends a walk: turns the persona back, stops the walk VRMA, blends to the idle VRMA and
+/// returns to `idle`.
+fn rest(commands: &mut Commands, vrm: Entity, walker: &Walker, tf: Transform) {
+    if let Some(vrma) = walker.walk_vrma {
+        commands.trigger(StopVrma { entity: vrma });
+    }
+    if let Some(vrma) = walker.idle_vrma {
+        commands.trigger(PlayVrma {
+            vrma,
+            repeat: Repeat::Forever.into(),
+            transition_duration: walker.transition,
+            reset_spring_bones: false,
+        });
+    }
+    commands.entity(vrm).try_remove::<Walker>().try_insert((
+        Transform {
+            rotation: walker.rest_rotation,
+            ..tf
+        },
+        PersonaState::default(),
+    ));
+}
+
+fn send_cancel(
+    tx: &Option<Res<VrmEventSender<WalkCancelEvent>>>,
+    vrm: Entity,
+    reason: &str,
+    position: Vec3,
+) {
+    if let Some(tx) = tx {
+        tx.try_broadcast(VrmEvent {
+            vrm,
+            payload: WalkCancelEvent {
+                reason: reason.to_string(),
+                position,
+            },
+        })
+        .output_log_if_error("Failed to broadcast WalkCancelEvent");
+    }
+}
+
+pub(super) struct LocomotionApiPlugin;
+
+impl Plugin for LocomotionApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, advance_walkers);
+    }
+}
@@ -0,0 +1,234 @@
+//! Walk path planning across app windows and per-frame stepping along a path.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// The parts of `windows` that lie on a display, so that walks never end off-screen.
+///
+/// Returns `windows` as-is when the displays are unknown.
+pub(super) fn visible_areas(windows: &[Rect], displays: &[Rect]) -> Vec<Rect> {
+    if displays.is_empty() {
+        return windows.to_vec();
+    }
+    windows
+        .iter()
+        .flat_map(|window| displays.iter().map(|display| window.intersect(*display)))
+        .filter(|area| !area.is_empty())
+        .collect()
+}
+
+/// Clamps `point` into the window nearest to it. Points in a window are unchanged.
+///
+/// Returns `point` as-is when there are no windows.
+pub(super) fn clamp_to_windows(point: Vec2, windows: &[Rect]) -> Vec2 {
+    windows
+        .iter()
+        .map(|rect| point.clamp(rect.min, rect.max))
+        .min_by(|a, b| {
+            a.distance_squared(point)
+                .total_cmp(&b.distance_squared(point))
+        })
+        .unwrap_or(point)
+}
+
+/// Plans a walk from `start` to `target` in global viewport coordinates.
+///
+/// When the two points are in different windows, the path goes through the fewest
+/// windows, crossing from one to the next at the nearest point of the edge they share,
+/// so the persona never walks through space no window shows. Each leg of the path lies
+/// inside one window. The returned waypoints end with `target`.
+pub(super) fn plan_path(start: Vec2, target: Vec2, windows: &[Rect]) -> Vec<Vec2> {
+    let (Some(from), Some(to)) = (window_index(windows, start), window_index(windows, target))
+    else {
+        return vec![target];
+    };
+    let Some(route) = route(windows, from, to) else {
+        // The windows are not connected; there is no edge to walk through.
+        return vec![target];
+    };
+    let mut waypoints = Vec::new();
+    let mut position = start;
+    for pair in route.windows(2) {
+        if let Some(crossing) = shared_edge(windows[pair[0]], windows[pair[1]]) {
+            position = position.clamp(crossing.min, crossing.max);
+            waypoints.push(position);
+        }
+    }
+    waypoints.push(target);
+    waypoints
+}
+
+fn window_index(windows: &[Rect], point: Vec2) -> Option<usize> {
+    windows.iter().position(|rect| rect.contains(point))
+}
+
+/// The edge (or overlap) two windows share, or `None` if they touch at most at a corner.
+fn shared_edge(a: Rect, b: Rect) -> Option<Rect> {
+    let min = a.min.max(b.min);
+    let max = a.max.min(b.max);
+    let touching = min.x <= max.x && min.y <= max.y;
+    (touching && (min.x < max.x || min.y < max.y)).then_some(Rect { min, max })
+}
+
+/// The windows from `from` to `to` through the fewest touching windows, found by a
+/// breadth-first search.
+fn route(windows: &[Rect], from: usize, to: usize) -> Option<Vec<usize>> {
+    let mut previous = vec![None; windows.len()];
+    let mut queue = VecDeque::from([from]);
+    previous[from] = Some(from);
+    while let Some(i) = queue.pop_front() {
+        if i == to {
+            let mut route = vec![to];
+            while let Some(&last) = route.last()
+                && last != from
+            {
+                route.push(previous[last]?);
+            }
+            route.reverse();
+            return Some(route);
+        }
+        for next in 0..windows.len() {
+            if previous[next].is_none() && shared_edge(windows[i], windows[next]).is_some() {
+                previous[next] = Some(i);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Moves `position` up to `distance` along `waypoints`, dropping the waypoints it reaches.
+pub(super) fn step_along(
+    mut position: Vec3,
+    waypoints: &mut VecDeque<Vec3>,
+    distance: f32,
+) -> Vec3 {
+    let mut remaining = distance;
+    while let Some(&next) = waypoints.front() {
+        let to_next = position.distance(next);
+        if to_next > remaining {
+            return position + (next - position) * (remaining / to_next);
+        }
+        remaining -= to_next;
+        position = next;
+        waypoints.pop_front();
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows() -> Vec<Rect> {
+        vec![
+            Rect::new(1920.0, 200.0, 3840.0, 1280.0),
+            Rect::new(0.0, 0.0, 1920.0, 1080.0),
+        ]
+    }
+
+    #[test]
+    fn targets_off_screen_are_pulled_onto_the_nearest_window() {
+        let d = windows();
+        assert_eq!(
+            clamp_to_windows(Vec2::new(-50.0, 500.0), &d),
+            Vec2::new(0.0, 500.0)
+        );
+        assert_eq!(
+            clamp_to_windows(Vec2::new(3000.0, 1500.0), &d),
+            Vec2::new(3000.0, 1280.0)
+        );
+        assert_eq!(
+            clamp_to_windows(Vec2::new(10.0, 10.0), &[]),
+            Vec2::new(10.0, 10.0)
+        );
+    }
+
+    #[test]
+    fn windows_are_cut_to_the_displays() {
+        let displays = [
+            Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            Rect::new(1920.0, 0.0, 3840.0, 1080.0),
+        ];
+        let areas = visible_areas(&windows(), &displays);
+        assert_eq!(
+            areas,
+            vec![
+                Rect::new(1920.0, 200.0, 3840.0, 1080.0),
+                Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            ]
+        );
+        assert_eq!(
+            clamp_to_windows(Vec2::new(3000.0, 1200.0), &areas),
+            Vec2::new(3000.0, 1080.0)
+        );
+        assert_eq!(visible_areas(&windows(), &[]), windows());
+    }
+
+    #[test]
+    fn walks_within_one_window_go_straight() {
+        let path = plan_path(Vec2::new(100.0, 900.0), Vec2::new(800.0, 900.0), &windows());
+        assert_eq!(path, vec![Vec2::new(800.0, 900.0)]);
+    }
+
+    #[test]
+    fn walks_across_windows_go_through_the_shared_edge() {
+        let path = plan_path(
+            Vec2::new(100.0, 100.0),
+            Vec2::new(3000.0, 1200.0),
+            &windows(),
+        );
+        assert_eq!(
+            path,
+            vec![Vec2::new(1920.0, 200.0), Vec2::new(3000.0, 1200.0)]
+        );
+
+        let back = plan_path(
+            Vec2::new(3000.0, 1200.0),
+            Vec2::new(100.0, 100.0),
+            &windows(),
+        );
+        assert_eq!(
+            back,
+            vec![Vec2::new(1920.0, 1080.0), Vec2::new(100.0, 100.0)]
+        );
+    }
+
+    #[test]
+    fn walks_follow_the_edges_of_stacked_and_disconnected_windows() {
+        let stacked = [
+            Rect::new(0.0, 0.0, 1920.0, 1080.0),
+            Rect::new(1920.0, 1080.0, 3840.0, 2160.0),
+            Rect::new(0.0, 1080.0, 1920.0, 2160.0),
+        ];
+        let path = plan_path(Vec2::new(100.0, 500.0), Vec2::new(3000.0, 2000.0), &stacked);
+        assert_eq!(
+            path,
+            vec![
+                Vec2::new(100.0, 1080.0),
+                Vec2::new(1920.0, 1080.0),
+                Vec2::new(3000.0, 2000.0)
+            ]
+        );
+
+        // Windows touching only at a corner are not connected either.
+        let apart = [
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Rect::new(100.0, 100.0, 200.0, 200.0),
+        ];
+        let path = plan_path(Vec2::new(50.0, 50.0), Vec2::new(150.0, 150.0), &apart);
+        assert_eq!(path, vec![Vec2::new(150.0, 150.0)]);
+    }
+
+    #[test]
+    fn stepping_follows_waypoints_in_order() {
+        let mut waypoints = VecDeque::from([Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 0.0)]);
+        let position = step_along(Vec3::ZERO, &mut waypoints, 1.5);
+        assert_eq!(position, Vec3::new(1.0, 0.5, 0.0));
+        assert_eq!(waypoints.len(), 1);
+
+        let position = step_along(position, &mut waypoints, 10.0);
+        assert_eq!(position, Vec3::new(1.0, 2.0, 0.0));
+        assert!(waypoints.is_empty());
+    }
+}
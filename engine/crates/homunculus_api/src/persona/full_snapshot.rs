@@ -1,12 +1,16 @@
+use crate::emotion::{Emotion, EmotionState};
 use crate::error::ApiResult;
 use crate::persona::PersonaApi;
 use crate::vrm::expressions::{ExpressionInfo, ExpressionsResponse};
+use crate::vrm::look::{Gaze, look_at_state};
 use crate::vrm::snapshot::LookAtState;
 use crate::vrma::VrmaInfo;
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
 use bevy_vrm1::prelude::*;
-use homunculus_core::prelude::{AssetIdComponent, LinkedPersona, Persona, PersonaState};
+use homunculus_core::prelude::{
+    AssetIdComponent, AssetRegistry, LinkedPersona, Persona, PersonaState, VrmLicense,
+};
 use serde::{Deserialize, Serialize};
 
 /// Full snapshot of a persona including transform, linked webviews, and VRM state.
@@ -26,6 +30,8 @@ pub struct PersonaFullSnapshot {
     /// Entity IDs of linked webviews (as strings to avoid JS 64-bit precision loss).
     #[cfg_attr(feature = "openapi", schema(value_type = Vec<String>))]
     pub linked_webviews: Vec<String>,
+    /// Current emotion, after fading.
+    pub emotion: EmotionState,
     /// VRM-specific data, or `null` if no VRM is attached.
     pub vrm: Option<VrmInfo>,
 }
@@ -41,6 +47,8 @@ pub struct VrmInfo {
     pub look_at: Option<LookAtState>,
     #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub spring_bones: serde_json::Value,
+    /// License meta of the model, or `null` if it has not been read.
+    pub license: Option<VrmLicense>,
 }
 
 impl PersonaApi {
@@ -57,7 +65,7 @@ fn snapshot_all(
     personas: Query<(Entity, &Persona, &PersonaState, &Transform)>,
     vrm_handles: Query<&VrmHandle>,
     asset_ids: Query<&AssetIdComponent>,
-    look_ats: Query<&LookAt>,
+    look_ats: Query<(Option<&LookAt>, Option<&Gaze>)>,
     expr_maps: Query<&ExpressionEntityMap>,
     expr_components: Query<(
         &Transform,
@@ -69,6 +77,8 @@ fn snapshot_all(
     vrma_query: Query<(Entity, &Name, &VrmaAnimationPlayers), With<Vrma>>,
     players: Query<&AnimationPlayer>,
     linked_personas: Query<(Entity, &LinkedPersona)>,
+    emotions: Query<&Emotion>,
+    registry: Res<AssetRegistry>,
 ) -> Vec<PersonaFullSnapshot> {
     personas
         .iter()
@@ -84,13 +94,20 @@ fn snapshot_all(
                 &children_query,
                 &vrma_query,
                 &players,
-            );
+            )
+            .map(|vrm| VrmInfo {
+                license: registry
+                    .get(&vrm.asset_id)
+                    .and_then(|entry| entry.license.clone()),
+                ..vrm
+            });
 
             PersonaFullSnapshot {
                 persona: persona.clone(),
                 state: state.0.clone(),
                 transform: *transform,
                 linked_webviews,
+                emotion: emotions.get(entity).map(Emotion::state).unwrap_or_default(),
                 vrm,
             }
         })
@@ -115,7 +132,7 @@ fn build_vrm_info(
     entity: Entity,
     vrm_handles: &Query<&VrmHandle>,
     asset_ids: &Query<&AssetIdComponent>,
-    look_ats: &Query<&LookAt>,
+    look_ats: &Query<(Option<&LookAt>, Option<&Gaze>)>,
     expr_maps: &Query<&ExpressionEntityMap>,
     expr_components: &Query<(
         &Transform,
@@ -136,10 +153,10 @@ fn build_vrm_info(
 
     let expressions = collect_expressions(expr_maps.get(entity).ok(), expr_components);
     let animations = collect_playing_animations(entity, children_query, vrma_query, players);
-    let look_at = look_ats.get(entity).ok().map(|la| match la {
-        LookAt::Cursor => LookAtState::Cursor,
-        LookAt::Target(target) => LookAtState::Target { entity: *target },
-    });
+    let look_at = look_ats
+        .get(entity)
+        .ok()
+        .and_then(|(look_at, gaze)| look_at_state(gaze, look_at));
 
     Some(VrmInfo {
         asset_id,
@@ -147,6 +164,7 @@ fn build_vrm_info(
         animations,
         look_at,
         spring_bones: serde_json::Value::Object(Default::default()),
+        license: None,
     })
 }
 
@@ -1,9 +1,10 @@
 use crate::error::{ApiError, ApiResult};
 use crate::persona::{PersonaApi, PersonaSnapshot};
 use crate::prelude::initialized;
+use crate::vrm::look::look_at_cursor;
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
-use bevy_vrm1::prelude::{BodyTracking, Cameras, LookAt, VrmHandle};
+use bevy_vrm1::prelude::{BodyTracking, Cameras, VrmHandle};
 use homunculus_core::prelude::{
     AssetResolver, Persona, PersonaDespawnedEvent, PersonaId, PersonaIndex, PersonaSpawnedEvent,
     PersonaState, VrmAttachedEvent, VrmEvent, VrmEventSender,
@@ -116,10 +117,10 @@ fn attach_vrm_to_entity(
 
     commands.entity(entity).try_insert((
         VrmHandle(handle),
-        LookAt::Cursor,
         BodyTracking::default(),
         cameras.all_layers(),
     ));
+    look_at_cursor(&mut commands, entity);
 
     Ok(())
 }
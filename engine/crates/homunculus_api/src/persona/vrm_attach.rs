@@ -2,9 +2,10 @@ use crate::error::{ApiError, ApiResult};
 use crate::persona::vrm_detach::detach_core;
 use crate::persona::{PersonaApi, PersonaSnapshot};
 use crate::prelude::initialized;
+use crate::vrm::look::look_at_cursor;
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
-use bevy_vrm1::prelude::{BodyTracking, Cameras, LookAt, Vrm, VrmHandle};
+use bevy_vrm1::prelude::{BodyTracking, Cameras, Vrm, VrmHandle};
 use homunculus_core::prelude::{
     AssetResolver, Persona, PersonaChangeEvent, PersonaId, PersonaIndex, PersonaState,
     VrmAttachedEvent, VrmDetachedEvent, VrmEvent, VrmEventSender,
@@ -127,10 +128,10 @@ fn attach_phase(
     };
     commands.entity(entity).try_insert((
         VrmHandle(handle),
-        LookAt::Cursor,
         BodyTracking::default(),
         cameras.all_layers(),
     ));
+    look_at_cursor(&mut commands, entity);
 }
 
 /// Persists persona to DB and broadcasts a change event.
@@ -0,0 +1,115 @@
+//! Gravity and throwing for released personas.
+//!
+//! The simulation lives in [`homunculus_drag::physics`]; this module reads and
+//! persists its settings.
+
+use crate::api;
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use homunculus_core::prelude::Persona;
+use homunculus_drag::physics::{PHYSICS_KEY, PhysicsSettings};
+use homunculus_prefs::PrefsDatabase;
+use serde::{Deserialize, Serialize};
+
+/// Request body for overriding physics for one persona.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaPhysicsArgs {
+    /// Whether the persona falls when released; `null` follows the global setting.
+    pub enabled: Option<bool>,
+}
+
+api!(
+    /// Configures gravity and throwing for released personas.
+    PhysicsApi
+);
+
+impl PhysicsApi {
+    /// Returns the physics settings.
+    pub async fn settings(&self) -> ApiResult<PhysicsSettings> {
+        self.0
+            .schedule(move |task| async move { task.will(Update, once::run(get_settings)).await })
+            .await
+    }
+
+    /// Replaces and persists the physics settings.
+    pub async fn set_settings(&self, settings: PhysicsSettings) -> ApiResult<PhysicsSettings> {
+        validate(&settings)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_settings).with(settings))
+                    .await
+            })
+            .await?
+    }
+
+    /// Overrides whether one persona falls when released.
+    pub async fn set_persona(
+        &self,
+        vrm: Entity,
+        args: PersonaPhysicsArgs,
+    ) -> ApiResult<PhysicsSettings> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_persona).with((vrm, args.enabled)))
+                    .await
+            })
+            .await?
+    }
+}
+
+fn validate(settings: &PhysicsSettings) -> ApiResult {
+    if !settings.gravity.is_finite() || settings.gravity <= 0.0 {
+        return Err(ApiError::InvalidInput(
+            "gravity must be positive".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&settings.bounciness) {
+        return Err(ApiError::InvalidInput(
+            "bounciness must be between 0 and 1".to_string(),
+        ));
+    }
+    if !settings.max_throw_speed.is_finite() || settings.max_throw_speed < 0.0 {
+        return Err(ApiError::InvalidInput(
+            "maxThrowSpeed must be zero or positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn get_settings(settings: Res<PhysicsSettings>) -> PhysicsSettings {
+    settings.clone()
+}
+
+fn put_settings(
+    In(settings): In<PhysicsSettings>,
+    mut current: ResMut<PhysicsSettings>,
+    prefs: NonSend<PrefsDatabase>,
+) -> ApiResult<PhysicsSettings> {
+    prefs
+        .save_as(PHYSICS_KEY, &settings)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))?;
+    *current = settings.clone();
+    Ok(settings)
+}
+
+fn put_persona(
+    In((vrm, enabled)): In<(Entity, Option<bool>)>,
+    personas: Query<&Persona>,
+    mut current: ResMut<PhysicsSettings>,
+    prefs: NonSend<PrefsDatabase>,
+) -> ApiResult<PhysicsSettings> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mut settings = current.clone();
+    match enabled {
+        Some(enabled) => settings.personas.insert(persona.id.clone(), enabled),
+        None => settings.personas.remove(&persona.id),
+    };
+    prefs
+        .save_as(PHYSICS_KEY, &settings)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))?;
+    *current = settings.clone();
+    Ok(settings)
+}
@@ -0,0 +1,410 @@
+//! MOD service RPC proxy.
+//!
+//! Resolves `(modName, method)` pairs against the shared [`RpcRegistry`] and
+//! forwards the JSON body to the MOD service's local HTTP server. Used by both
+//! the `POST /rpc/call` HTTP endpoint and the MCP server's dynamic RPC tools.
+//!
+//! Request bodies are validated against the method's `inputSchema` before they
+//! leave the engine; responses can optionally be checked against `outputSchema`.
+
+use homunculus_core::rpc_registry::{MethodValidators, RpcRegistry};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Default per-method proxy timeout (30 s).
+pub const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Errors returned while proxying an RPC call to a MOD service.
+#[derive(Debug, thiserror::Error)]
+pub enum RpcError {
+    #[error("registry lock poisoned")]
+    LockPoisoned,
+    #[error("Mod '{0}' is not registered")]
+    NotRegistered(String),
+    #[error("Method '{method}' not found in mod '{mod_name}'")]
+    MethodNotFound { mod_name: String, method: String },
+    #[error("Mod '{mod_name}' method '{method}' timed out after {timeout_ms}ms")]
+    Timeout {
+        mod_name: String,
+        method: String,
+        timeout_ms: u64,
+    },
+    #[error("Mod '{mod_name}' refused connection: {message}")]
+    ConnectionRefused { mod_name: String, message: String },
+    #[error("Mod '{mod_name}' proxy error: {message}")]
+    Proxy { mod_name: String, message: String },
+    #[error("Failed to read mod response: {0}")]
+    ReadResponse(String),
+    #[error(
+        "Request body does not match the input schema of method '{method}' in mod '{mod_name}'"
+    )]
+    InvalidInput {
+        mod_name: String,
+        method: String,
+        violations: Vec<SchemaViolation>,
+    },
+    #[error("Response of method '{method}' in mod '{mod_name}' does not match its output schema")]
+    InvalidOutput {
+        mod_name: String,
+        method: String,
+        violations: Vec<SchemaViolation>,
+    },
+}
+
+impl RpcError {
+    /// Returns the schema violations carried by validation errors.
+    pub fn violations(&self) -> Option<&[SchemaViolation]> {
+        match self {
+            RpcError::InvalidInput { violations, .. }
+            | RpcError::InvalidOutput { violations, .. } => Some(violations),
+            _ => None,
+        }
+    }
+}
+
+/// A single JSON Schema violation found in a request or response body.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaViolation {
+    /// JSON pointer (RFC 6901) to the offending value; empty for the root.
+    pub pointer: String,
+    /// Human-readable description of the violation.
+    pub message: String,
+}
+
+/// The address, timeout and schema validators resolved for a single RPC call.
+#[derive(Debug, Clone)]
+pub struct RpcTarget {
+    pub port: u16,
+    pub timeout_ms: u64,
+    pub validators: MethodValidators,
+}
+
+/// The raw response relayed from a MOD service.
+#[derive(Debug, Clone)]
+pub struct RpcResponse {
+    /// HTTP status code returned by the MOD service.
+    pub status: u16,
+    /// Raw response body (JSON by convention).
+    pub body: Vec<u8>,
+}
+
+impl RpcResponse {
+    /// Returns `true` if the MOD service answered with a 2xx status.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the body as UTF-8 text, replacing invalid sequences.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+/// Validates mod+method in the registry and returns the proxy target.
+///
+/// A MOD that is pre-registered (port allocated) but has not yet published its
+/// methods accepts any method name.
+pub fn resolve_target(
+    reg: &RpcRegistry,
+    mod_name: &str,
+    method: &str,
+) -> Result<RpcTarget, RpcError> {
+    let entry = reg
+        .get(mod_name)
+        .ok_or_else(|| RpcError::NotRegistered(mod_name.to_string()))?;
+    if !entry.methods.is_empty() && !entry.methods.contains_key(method) {
+        return Err(RpcError::MethodNotFound {
+            mod_name: mod_name.to_string(),
+            method: method.to_string(),
+        });
+    }
+    let meta = entry.methods.get(method);
+    Ok(RpcTarget {
+        port: entry.port,
+        timeout_ms: meta.and_then(|m| m.timeout).unwrap_or(DEFAULT_TIMEOUT_MS),
+        validators: entry.validators(method).cloned().unwrap_or_default(),
+    })
+}
+
+/// Resolves the target in `registry`, validates `body` against its input
+/// schema, and forwards it to the MOD service.
+///
+/// An absent body is validated as `{}`, matching how MOD services treat an
+/// empty request. When `validate_output` is set, successful responses are
+/// checked against the method's output schema.
+pub async fn call(
+    registry: &Arc<RwLock<RpcRegistry>>,
+    mod_name: &str,
+    method: &str,
+    body: Option<serde_json::Value>,
+    validate_output: bool,
+) -> Result<RpcResponse, RpcError> {
+    let target = {
+        let reg = registry.read().map_err(|_| RpcError::LockPoisoned)?;
+        resolve_target(&reg, mod_name, method)?
+    };
+    if let Some(validator) = &target.validators.input {
+        let instance = body
+            .clone()
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+        validate(validator, &instance).map_err(|violations| RpcError::InvalidInput {
+            mod_name: mod_name.to_string(),
+            method: method.to_string(),
+            violations,
+        })?;
+    }
+    let output_validator = target.validators.output.clone();
+    let response = forward(target, mod_name, method, body).await?;
+    if validate_output
+        && response.is_success()
+        && let Some(validator) = &output_validator
+    {
+        check_output(validator, &response).map_err(|violations| RpcError::InvalidOutput {
+            mod_name: mod_name.to_string(),
+            method: method.to_string(),
+            violations,
+        })?;
+    }
+    Ok(response)
+}
+
+/// Validates `instance` against a compiled schema, collecting every violation.
+pub fn validate(
+    validator: &jsonschema::Validator,
+    instance: &serde_json::Value,
+) -> Result<(), Vec<SchemaViolation>> {
+    let violations: Vec<SchemaViolation> = validator
+        .iter_errors(instance)
+        .map(|e| SchemaViolation {
+            pointer: e.instance_path().as_str().to_string(),
+            message: e.to_string(),
+        })
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn check_output(
+    validator: &jsonschema::Validator,
+    response: &RpcResponse,
+) -> Result<(), Vec<SchemaViolation>> {
+    let instance: serde_json::Value = serde_json::from_slice(&response.body).map_err(|e| {
+        vec![SchemaViolation {
+            pointer: String::new(),
+            message: format!("Response is not valid JSON: {e}"),
+        }]
+    })?;
+    validate(validator, &instance)
+}
+
+/// Forwards the request body to the MOD service and returns its response.
+pub async fn forward(
+    target: RpcTarget,
+    mod_name: &str,
+    method: &str,
+    body: Option<serde_json::Value>,
+) -> Result<RpcResponse, RpcError> {
+    let url = format!("http://127.0.0.1:{}/{method}", target.port);
+    let mut request = reqwest::Client::new().post(&url);
+    request = match body {
+        Some(value) => request.json(&value),
+        None => request
+            .header("content-type", "application/json")
+            .header("content-length", "0"),
+    };
+    let timeout = Duration::from_millis(target.timeout_ms);
+    let response = match tokio::time::timeout(timeout, request.send()).await {
+        Err(_) => {
+            return Err(RpcError::Timeout {
+                mod_name: mod_name.to_string(),
+                method: method.to_string(),
+                timeout_ms: target.timeout_ms,
+            });
+        }
+        Ok(Err(e)) if e.is_connect() => {
+            return Err(RpcError::ConnectionRefused {
+                mod_name: mod_name.to_string(),
+                message: e.to_string(),
+            });
+        }
+        Ok(Err(e)) => {
+            return Err(RpcError::Proxy {
+                mod_name: mod_name.to_string(),
+                message: e.to_string(),
+            });
+        }
+        Ok(Ok(response)) => response,
+    };
+    let status = response.status().as_u16();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| RpcError::ReadResponse(e.to_string()))?;
+    Ok(RpcResponse {
+        status,
+        body: body.to_vec(),
+    })
+}
+
+#[cfg(feature = "axum")]
+mod axum_impl {
+    use super::{RpcError, RpcResponse};
+    use axum::Json;
+    use axum::http::StatusCode;
+    use axum::response::{AppendHeaders, IntoResponse, Response};
+
+    impl RpcError {
+        /// HTTP status used when this error is returned from `POST /rpc/call`.
+        pub fn status_code(&self) -> StatusCode {
+            match self {
+                RpcError::LockPoisoned => StatusCode::INTERNAL_SERVER_ERROR,
+                RpcError::NotRegistered(_) => StatusCode::SERVICE_UNAVAILABLE,
+                RpcError::MethodNotFound { .. } => StatusCode::NOT_FOUND,
+                RpcError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+                RpcError::InvalidInput { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                RpcError::ConnectionRefused { .. }
+                | RpcError::Proxy { .. }
+                | RpcError::ReadResponse(_)
+                | RpcError::InvalidOutput { .. } => StatusCode::BAD_GATEWAY,
+            }
+        }
+    }
+
+    impl IntoResponse for RpcError {
+        fn into_response(self) -> Response {
+            let body = match self.violations() {
+                Some(violations) => serde_json::json!({
+                    "error": self.to_string(),
+                    "violations": violations,
+                }),
+                None => serde_json::json!({ "error": self.to_string() }),
+            };
+            (self.status_code(), Json(body)).into_response()
+        }
+    }
+
+    impl IntoResponse for RpcResponse {
+        fn into_response(self) -> Response {
+            let status =
+                StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (
+                status,
+                AppendHeaders([(axum::http::header::CONTENT_TYPE, "application/json")]),
+                self.body,
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homunculus_core::rpc_registry::RpcMethodMeta;
+    use std::collections::HashMap;
+
+    fn schema() -> serde_json::Map<String, serde_json::Value> {
+        serde_json::json!({
+            "type": "object",
+            "required": ["text"],
+            "properties": {
+                "text": { "type": "string" },
+                "speed": { "type": "number", "minimum": 0 }
+            }
+        })
+        .as_object()
+        .cloned()
+        .unwrap()
+    }
+
+    fn validator() -> jsonschema::Validator {
+        jsonschema::validator_for(&serde_json::Value::Object(schema())).unwrap()
+    }
+
+    fn registry_with(methods: &[(&str, Option<u64>)]) -> RpcRegistry {
+        let mut reg = RpcRegistry::default();
+        let methods = methods
+            .iter()
+            .map(|(name, timeout)| {
+                (
+                    name.to_string(),
+                    RpcMethodMeta {
+                        timeout: *timeout,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        reg.register("visual".to_string(), 54321, methods).unwrap();
+        reg
+    }
+
+    #[test]
+    fn resolve_unknown_mod_is_not_registered() {
+        let reg = RpcRegistry::default();
+        assert!(matches!(
+            resolve_target(&reg, "visual", "show"),
+            Err(RpcError::NotRegistered(_))
+        ));
+    }
+
+    #[test]
+    fn resolve_unknown_method_is_not_found() {
+        let reg = registry_with(&[("show", None)]);
+        assert!(matches!(
+            resolve_target(&reg, "visual", "hide"),
+            Err(RpcError::MethodNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn resolve_uses_method_timeout() {
+        let reg = registry_with(&[("show", Some(15000))]);
+        let target = resolve_target(&reg, "visual", "show").unwrap();
+        assert_eq!(target.port, 54321);
+        assert_eq!(target.timeout_ms, 15000);
+        assert!(target.validators.input.is_none());
+    }
+
+    #[test]
+    fn validate_accepts_matching_body() {
+        let body = serde_json::json!({ "text": "hello", "speed": 1.2 });
+        assert!(validate(&validator(), &body).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_pointer_of_each_violation() {
+        let body = serde_json::json!({ "speed": -1 });
+        let violations = validate(&validator(), &body).unwrap_err();
+        let pointers: Vec<&str> = violations.iter().map(|v| v.pointer.as_str()).collect();
+        assert_eq!(violations.len(), 2);
+        assert!(
+            pointers.contains(&""),
+            "missing `text` is reported at the root"
+        );
+        assert!(pointers.contains(&"/speed"));
+    }
+
+    #[test]
+    fn check_output_rejects_non_json_response() {
+        let response = RpcResponse {
+            status: 200,
+            body: b"not json".to_vec(),
+        };
+        let violations = check_output(&validator(), &response).unwrap_err();
+        assert_eq!(violations[0].pointer, "");
+    }
+
+    #[test]
+    fn resolve_pre_registered_mod_accepts_any_method() {
+        let reg = registry_with(&[]);
+        let target = resolve_target(&reg, "visual", "anything").unwrap();
+        assert_eq!(target.timeout_ms, DEFAULT_TIMEOUT_MS);
+    }
+}
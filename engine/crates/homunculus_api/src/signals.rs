@@ -1,14 +1,27 @@
+mod history;
 mod list;
-mod send;
+mod pattern;
+mod request;
+mod retention;
+pub(crate) mod send;
 mod stream;
 
-use async_broadcast::{Receiver, Sender};
+pub use pattern::SignalPattern;
+pub use request::{DEFAULT_REQUEST_TIMEOUT_MS, SignalRequest};
+
+use std::collections::VecDeque;
+
+use async_broadcast::{InactiveReceiver, Receiver, Sender};
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
+use homunculus_prefs::{PrefsDatabase, StoredSignalMessage, StoredSignalRetention};
 use serde::{Deserialize, Serialize};
 
 use crate::api;
 
+/// Upper bound on the number of messages a single signal may retain in memory.
+pub const MAX_RETAINED_MESSAGES: usize = 10_000;
+
 /// Information about an active signal channel.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -18,12 +31,213 @@ pub struct SignalInfo {
     pub signal: String,
     /// The number of active subscribers.
     pub subscribers: usize,
+    /// The number of active subscribers to wildcard patterns matching this signal.
+    #[serde(default)]
+    pub pattern_subscribers: usize,
+    /// The retention policy, if history is kept for this signal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<SignalRetention>,
 }
 
-#[derive(Resource, Debug, Clone, Deref, DerefMut, Default)]
-pub(crate) struct SignalsChannels(
-    HashMap<String, (Sender<serde_json::Value>, Receiver<serde_json::Value>)>,
-);
+/// A message sent on a signal channel.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SignalRecord {
+    /// Sequence number, increasing by one per message on the same signal.
+    ///
+    /// Pass the last seen value as `since` to replay only newer messages.
+    pub seq: u64,
+    /// Unix time in milliseconds when the message was sent.
+    pub timestamp: i64,
+    /// The message payload.
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub payload: serde_json::Value,
+}
+
+/// Opt-in history retention for a signal.
+///
+/// At least one of `max_messages` and `max_age_secs` must be set; when both are,
+/// a message is dropped as soon as either limit is exceeded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SignalRetention {
+    /// Keep at most this many messages (capped at 10,000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_messages: Option<usize>,
+    /// Keep messages for at most this many seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+    /// Whether the history is stored in the preferences database and restored on startup.
+    #[serde(default)]
+    pub persist: bool,
+}
+
+impl SignalRetention {
+    fn validate(&self) -> Result<(), String> {
+        match (self.max_messages, self.max_age_secs) {
+            (None, None) => Err("either maxMessages or maxAgeSecs must be set".to_string()),
+            (Some(0), _) => Err("maxMessages must be greater than 0".to_string()),
+            (Some(n), _) if MAX_RETAINED_MESSAGES < n => Err(format!(
+                "maxMessages must not exceed {MAX_RETAINED_MESSAGES}"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn to_stored(&self, signal: &str) -> StoredSignalRetention {
+        StoredSignalRetention {
+            signal: signal.to_string(),
+            max_messages: self.max_messages.map(|v| v as u64),
+            max_age_secs: self.max_age_secs,
+        }
+    }
+}
+
+impl From<&SignalRecord> for StoredSignalMessage {
+    fn from(record: &SignalRecord) -> Self {
+        Self {
+            seq: record.seq,
+            timestamp: record.timestamp,
+            payload: record.payload.clone(),
+        }
+    }
+}
+
+/// A single signal channel together with its retained history.
+#[derive(Debug)]
+pub(crate) struct SignalChannel {
+    pub(crate) sender: Sender<SignalRecord>,
+    pub(crate) receiver: Receiver<SignalRecord>,
+    pub(crate) retention: Option<SignalRetention>,
+    pub(crate) history: VecDeque<SignalRecord>,
+    next_seq: u64,
+    /// Retained messages not yet written to the preferences database.
+    unpersisted: Vec<SignalRecord>,
+}
+
+impl SignalChannel {
+    fn new(next_seq: u64) -> Self {
+        let (mut sender, receiver) = async_broadcast::broadcast(100);
+        sender.set_overflow(true);
+        Self {
+            sender,
+            receiver,
+            retention: None,
+            history: VecDeque::new(),
+            next_seq,
+            unpersisted: Vec::new(),
+        }
+    }
+
+    fn send_blocking(
+        &mut self,
+        value: serde_json::Value,
+    ) -> Result<SignalRecord, async_broadcast::SendError<serde_json::Value>> {
+        let record = SignalRecord {
+            seq: self.next_seq,
+            timestamp: now_millis(),
+            payload: value,
+        };
+        self.next_seq += 1;
+        if let Some(retention) = &self.retention {
+            if retention.persist {
+                self.unpersisted.push(record.clone());
+            }
+            self.history.push_back(record.clone());
+            self.prune(now_millis());
+        }
+        self.sender
+            .broadcast_blocking(record.clone())
+            .map_err(|e| async_broadcast::SendError(e.0.payload))?;
+        Ok(record)
+    }
+
+    /// Drops retained messages that exceed the retention limits.
+    pub(crate) fn prune(&mut self, now: i64) {
+        let Some(retention) = &self.retention else {
+            self.history.clear();
+            return;
+        };
+        let max_messages = retention
+            .max_messages
+            .unwrap_or(MAX_RETAINED_MESSAGES)
+            .min(MAX_RETAINED_MESSAGES);
+        while max_messages < self.history.len() {
+            self.history.pop_front();
+        }
+        if let Some(max_age_secs) = retention.max_age_secs {
+            let oldest = now.saturating_sub(max_age_secs.saturating_mul(1000) as i64);
+            while self
+                .history
+                .front()
+                .is_some_and(|record| record.timestamp < oldest)
+            {
+                self.history.pop_front();
+            }
+        }
+    }
+
+    /// The sequence number of the oldest message that is still retained.
+    fn oldest_retained_seq(&self) -> u64 {
+        self.history
+            .front()
+            .map(|record| record.seq)
+            .unwrap_or(self.next_seq)
+    }
+}
+
+/// A subscription target for every signal whose name matches a wildcard pattern.
+#[derive(Debug)]
+pub(crate) struct PatternChannel {
+    pattern: SignalPattern,
+    pub(crate) sender: Sender<(String, SignalRecord)>,
+    pub(crate) receiver: Receiver<(String, SignalRecord)>,
+}
+
+impl PatternChannel {
+    fn new(pattern: SignalPattern) -> Self {
+        let (mut sender, receiver) = async_broadcast::broadcast(100);
+        sender.set_overflow(true);
+        Self {
+            pattern,
+            sender,
+            receiver,
+        }
+    }
+
+    /// The number of active subscribers, excluding the receiver kept by [`SignalsChannels`].
+    pub(crate) fn subscribers(&self) -> usize {
+        self.sender.receiver_count().saturating_sub(1)
+    }
+}
+
+#[derive(Resource, Debug, Deref, DerefMut)]
+pub(crate) struct SignalsChannels {
+    #[deref]
+    channels: HashMap<String, SignalChannel>,
+    /// Wildcard subscriptions keyed by their pattern string.
+    patterns: HashMap<String, PatternChannel>,
+    /// Receives every message on every signal; used to bridge signals into the engine event bus.
+    ///
+    /// Unlike pattern subscriptions, it is not reported in [`SignalInfo`].
+    tap: Sender<(String, SignalRecord)>,
+    tap_receiver: InactiveReceiver<(String, SignalRecord)>,
+}
+
+impl Default for SignalsChannels {
+    fn default() -> Self {
+        let (mut tap, tap_receiver) = async_broadcast::broadcast(256);
+        tap.set_overflow(true);
+        Self {
+            channels: HashMap::default(),
+            patterns: HashMap::default(),
+            tap,
+            tap_receiver: tap_receiver.deactivate(),
+        }
+    }
+}
 
 api!(
     /// Provides access to the signals API.
@@ -36,7 +250,9 @@ pub(super) struct SignalsApiPlugin;
 
 impl Plugin for SignalsApiPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SignalsChannels>();
+        app.init_resource::<SignalsChannels>()
+            .add_systems(Startup, restore_signal_history)
+            .add_systems(Last, persist_signal_history);
     }
 }
 
@@ -46,19 +262,142 @@ impl SignalsChannels {
         signal: impl Into<String>,
         value: serde_json::Value,
     ) -> Result<(), async_broadcast::SendError<serde_json::Value>> {
-        let (sender, _) = self.channel(signal);
-        sender.broadcast_blocking(value)?;
+        let signal = signal.into();
+        let record = self.channel(signal.clone()).send_blocking(value)?;
+        if 0 < self.tap.receiver_count() {
+            let _ = self.tap.try_broadcast((signal.clone(), record.clone()));
+        }
+        self.prune_patterns();
+        for pattern in self.patterns.values() {
+            if !pattern.pattern.matches(&signal) {
+                continue;
+            }
+            pattern
+                .sender
+                .broadcast_blocking((signal.clone(), record.clone()))
+                .map_err(|e| async_broadcast::SendError(e.0.1.payload))?;
+        }
         Ok(())
     }
 
-    pub fn channel(
-        &mut self,
-        signal: impl Into<String>,
-    ) -> &mut (Sender<serde_json::Value>, Receiver<serde_json::Value>) {
-        self.0.entry(signal.into()).or_insert_with(|| {
-            let (mut sender, receiver) = async_broadcast::broadcast(100);
-            sender.set_overflow(true);
-            (sender, receiver)
-        })
+    pub fn channel(&mut self, signal: impl Into<String>) -> &mut SignalChannel {
+        self.channels
+            .entry(signal.into())
+            .or_insert_with(|| SignalChannel::new(1))
+    }
+
+    pub(crate) fn pattern_channel(&mut self, pattern: SignalPattern) -> &mut PatternChannel {
+        self.prune_patterns();
+        self.patterns
+            .entry(pattern.as_str().to_string())
+            .or_insert_with(|| PatternChannel::new(pattern))
+    }
+
+    /// Drops pattern subscriptions whose subscribers have all disconnected.
+    fn prune_patterns(&mut self) {
+        self.patterns.retain(|_, pattern| 0 < pattern.subscribers());
+    }
+
+    /// Returns a receiver for every message sent on any signal.
+    pub(crate) fn tap(&self) -> Receiver<(String, SignalRecord)> {
+        self.tap_receiver.activate_cloned()
+    }
+
+    /// The number of pattern subscribers that receive messages sent on `signal`.
+    pub(crate) fn pattern_subscribers(&self, signal: &str) -> usize {
+        self.patterns
+            .values()
+            .filter(|pattern| pattern.pattern.matches(signal))
+            .map(PatternChannel::subscribers)
+            .sum()
+    }
+}
+
+pub(crate) fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Restores persisted retention policies and their history from the preferences database.
+fn restore_signal_history(mut channels: ResMut<SignalsChannels>, prefs: NonSend<PrefsDatabase>) {
+    let retentions = match prefs.list_signal_retentions() {
+        Ok(retentions) => retentions,
+        Err(e) => {
+            error!("Failed to load signal retention policies: {e}");
+            return;
+        }
+    };
+    let now = now_millis();
+    for stored in retentions {
+        let history = prefs
+            .load_signal_history(&stored.signal)
+            .unwrap_or_else(|e| {
+                error!("Failed to load history of signal '{}': {e}", stored.signal);
+                Vec::new()
+            });
+        let next_seq = history.last().map(|m| m.seq + 1).unwrap_or(1);
+        let mut channel = SignalChannel::new(next_seq);
+        channel.retention = Some(SignalRetention {
+            max_messages: stored.max_messages.map(|v| v as usize),
+            max_age_secs: stored.max_age_secs,
+            persist: true,
+        });
+        channel.history = history
+            .into_iter()
+            .map(|message| SignalRecord {
+                seq: message.seq,
+                timestamp: message.timestamp,
+                payload: message.payload,
+            })
+            .collect();
+        channel.prune(now);
+        channels.insert(stored.signal, channel);
+    }
+}
+
+/// Writes newly retained messages of persistent signals to the preferences database.
+fn persist_signal_history(mut channels: ResMut<SignalsChannels>, prefs: NonSend<PrefsDatabase>) {
+    if channels
+        .values()
+        .all(|channel| channel.unpersisted.is_empty())
+    {
+        return;
+    }
+    for (signal, channel) in channels.iter_mut() {
+        if channel.unpersisted.is_empty() {
+            continue;
+        }
+        let messages: Vec<StoredSignalMessage> = channel
+            .unpersisted
+            .drain(..)
+            .map(|record| StoredSignalMessage::from(&record))
+            .collect();
+        if let Err(e) = prefs.append_signal_messages(signal, &messages) {
+            error!("Failed to persist messages of signal '{signal}': {e}");
+        }
+        if let Err(e) = prefs.prune_signal_history(signal, channel.oldest_retained_seq()) {
+            error!("Failed to prune history of signal '{signal}': {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_channels_without_subscribers_are_pruned() {
+        let mut channels = SignalsChannels::default();
+        let pattern = SignalPattern::parse("chat.*").unwrap();
+        let receiver = channels.pattern_channel(pattern).receiver.new_receiver();
+        channels
+            .send_blocking("chat.user", serde_json::json!("hi"))
+            .unwrap();
+        assert_eq!(channels.pattern_subscribers("chat.user"), 1);
+
+        drop(receiver);
+        channels
+            .send_blocking("chat.user", serde_json::json!("bye"))
+            .unwrap();
+        assert!(channels.patterns.is_empty());
     }
 }
@@ -0,0 +1,40 @@
+use crate::prelude::{ApiResult, SignalsApi};
+use crate::signals::{SignalRecord, SignalsChannels, now_millis};
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+
+impl SignalsApi {
+    /// Returns the retained messages of a signal, oldest first.
+    ///
+    /// If `since` is given, only messages with a greater sequence number are returned.
+    /// Signals without a retention policy have an empty history.
+    pub async fn history(
+        self,
+        signal: impl Into<String>,
+        since: Option<u64>,
+    ) -> ApiResult<Vec<SignalRecord>> {
+        let signal = signal.into();
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(signal_history).with((signal, since)))
+                    .await
+            })
+            .await
+    }
+}
+
+fn signal_history(
+    In((signal, since)): In<(String, Option<u64>)>,
+    mut channels: ResMut<SignalsChannels>,
+) -> Vec<SignalRecord> {
+    let Some(channel) = channels.get_mut(&signal) else {
+        return Vec::new();
+    };
+    channel.prune(now_millis());
+    channel
+        .history
+        .iter()
+        .filter(|record| since.is_none_or(|since| since < record.seq))
+        .cloned()
+        .collect()
+}
@@ -15,13 +15,15 @@ impl SignalsApi {
 fn list_signals(channels: Res<SignalsChannels>) -> ApiResult<Vec<SignalInfo>> {
     let signals = channels
         .iter()
-        .map(|(name, (sender, _))| {
+        .map(|(name, channel)| {
             // receiver_count() includes the initial Receiver held in SignalsChannels,
             // so subtract 1 to get the actual SSE subscriber count.
-            let subscribers = sender.receiver_count().saturating_sub(1);
+            let subscribers = channel.sender.receiver_count().saturating_sub(1);
             SignalInfo {
                 signal: name.clone(),
                 subscribers,
+                pattern_subscribers: channels.pattern_subscribers(name),
+                retention: channel.retention.clone(),
             }
         })
         .collect();
@@ -0,0 +1,119 @@
+//! Wildcard patterns over signal channel names.
+//!
+//! A channel name is split into segments at `.`, `/` and `:`. In a pattern,
+//! a `*` segment matches exactly one segment and a trailing `#` segment matches
+//! zero or more segments, so `persona.*` matches `persona.spawned` and `stt/#`
+//! matches `stt`, `stt/result` and `stt/result/final`. Separators must match
+//! literally: `persona.*` does not match `persona/spawned`.
+
+const SEPARATORS: [char; 3] = ['.', '/', ':'];
+
+/// A parsed signal channel pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalPattern {
+    source: String,
+    segments: Vec<String>,
+    separators: Vec<char>,
+}
+
+impl SignalPattern {
+    /// Returns `true` if `signal` contains a `*` or `#` segment and should be
+    /// treated as a pattern rather than an exact channel name.
+    pub fn is_pattern(signal: &str) -> bool {
+        split(signal).0.iter().any(|s| *s == "*" || *s == "#")
+    }
+
+    /// Parses a pattern, rejecting a `#` that is not the last segment.
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let (segments, separators) = split(pattern);
+        if let Some(i) = segments.iter().position(|s| *s == "#")
+            && i != segments.len() - 1
+        {
+            return Err(format!(
+                "invalid signal pattern '{pattern}': '#' must be the last segment"
+            ));
+        }
+        Ok(Self {
+            source: pattern.to_string(),
+            segments: segments.into_iter().map(str::to_string).collect(),
+            separators,
+        })
+    }
+
+    /// The pattern as originally written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns `true` if `signal` matches this pattern.
+    pub fn matches(&self, signal: &str) -> bool {
+        let (segments, separators) = split(signal);
+        let multi = self.segments.last().is_some_and(|s| s == "#");
+        let fixed = if multi {
+            self.segments.len() - 1
+        } else {
+            self.segments.len()
+        };
+        if segments.len() < fixed || (!multi && segments.len() != fixed) {
+            return false;
+        }
+        let segments_match = self.segments[..fixed]
+            .iter()
+            .zip(&segments)
+            .all(|(p, s)| p == "*" || p == s);
+        // Separators between the fixed segments, plus the one before `#` if the
+        // signal has any segments beyond them.
+        let compared = if multi && fixed < segments.len() {
+            self.separators.len()
+        } else {
+            fixed.saturating_sub(1)
+        };
+        segments_match && self.separators[..compared] == separators[..compared]
+    }
+}
+
+fn split(s: &str) -> (Vec<&str>, Vec<char>) {
+    let separators = s.chars().filter(|c| SEPARATORS.contains(c)).collect();
+    (s.split(SEPARATORS).collect(), separators)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, signal: &str) -> bool {
+        SignalPattern::parse(pattern).unwrap().matches(signal)
+    }
+
+    #[test]
+    fn single_segment_wildcard() {
+        assert!(matches("persona.*", "persona.spawned"));
+        assert!(!matches("persona.*", "persona.spawned.elmer"));
+        assert!(!matches("persona.*", "persona"));
+        assert!(!matches("persona.*", "persona/spawned"));
+        assert!(matches("*:exited", "process:exited"));
+    }
+
+    #[test]
+    fn multi_segment_wildcard() {
+        assert!(matches("stt/#", "stt"));
+        assert!(matches("stt/#", "stt/result"));
+        assert!(matches("stt/#", "stt/result/final"));
+        assert!(!matches("stt/#", "stt.result"));
+        assert!(!matches("stt/#", "tts/result"));
+        assert!(matches("#", "anything.at/all"));
+    }
+
+    #[test]
+    fn hash_must_be_last() {
+        assert!(SignalPattern::parse("stt/#/final").is_err());
+    }
+
+    #[test]
+    fn detects_patterns() {
+        assert!(SignalPattern::is_pattern("persona.*"));
+        assert!(SignalPattern::is_pattern("stt/#"));
+        assert!(!SignalPattern::is_pattern("process:exited"));
+        assert!(!SignalPattern::is_pattern("rating*"));
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use crate::error::ApiError;
+use crate::prelude::{ApiResult, SignalsApi};
+use bevy::tasks::futures_lite::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// Time to wait for a reply when a [`SignalRequest`] does not specify one.
+pub const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
+
+/// Upper bound on the time a request may wait for its reply.
+const MAX_REQUEST_TIMEOUT_MS: u64 = 300_000;
+
+/// A request sent with [`SignalsApi::request`].
+///
+/// Subscribers of the signal receive `{ correlationId, replyTo, payload }` and answer
+/// by sending `{ correlationId, payload }` to the `replyTo` signal.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SignalRequest {
+    /// The request payload delivered to subscribers.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub payload: serde_json::Value,
+    /// How long to wait for a reply, in milliseconds (default 30,000, max 300,000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl SignalsApi {
+    /// Sends a request on a signal and waits for the first reply carrying its correlation ID.
+    ///
+    /// Replies are read from the `<signal>:reply` signal; replies with another
+    /// correlation ID are ignored. Returns the `payload` of the matching reply, or
+    /// [`ApiError::Timeout`] if none arrives in time.
+    pub async fn request(
+        self,
+        signal: impl Into<String>,
+        request: SignalRequest,
+    ) -> ApiResult<serde_json::Value> {
+        let timeout_ms = request.timeout_ms.unwrap_or(DEFAULT_REQUEST_TIMEOUT_MS);
+        if MAX_REQUEST_TIMEOUT_MS < timeout_ms {
+            return Err(ApiError::InvalidInput(format!(
+                "timeoutMs must not exceed {MAX_REQUEST_TIMEOUT_MS}"
+            )));
+        }
+        let signal = signal.into();
+        let reply_to = format!("{signal}:reply");
+        let correlation_id = uuid::Uuid::new_v4().to_string();
+
+        // Subscribe before sending so a fast reply cannot be missed.
+        let replies = self.clone().stream(reply_to.clone()).await?;
+        self.send(
+            signal,
+            serde_json::json!({
+                "correlationId": correlation_id,
+                "replyTo": reply_to,
+                "payload": request.payload,
+            }),
+        )
+        .await?;
+
+        let wait_reply = async {
+            let mut replies = std::pin::pin!(replies);
+            while let Some(reply) = replies.next().await {
+                if reply
+                    .get("correlationId")
+                    .and_then(serde_json::Value::as_str)
+                    == Some(correlation_id.as_str())
+                {
+                    return Some(reply.get("payload").cloned().unwrap_or_default());
+                }
+            }
+            None
+        };
+        match tokio::time::timeout(Duration::from_millis(timeout_ms), wait_reply).await {
+            Ok(Some(payload)) => Ok(payload),
+            _ => Err(ApiError::Timeout(format!(
+                "no reply on '{reply_to}' within {timeout_ms} ms"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::error::ApiError;
+use crate::prelude::{ApiResult, SignalsApi};
+use crate::signals::{SignalRetention, SignalsChannels, now_millis};
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use homunculus_prefs::{PrefsDatabase, StoredSignalMessage};
+
+impl SignalsApi {
+    /// Enables history retention for a signal, replacing any existing policy.
+    ///
+    /// Already retained messages are kept if they satisfy the new limits.
+    /// When `persist` is set, the policy and the retained messages are stored in
+    /// the preferences database; otherwise any previously stored history is removed.
+    pub async fn set_retention(
+        self,
+        signal: impl Into<String>,
+        retention: SignalRetention,
+    ) -> ApiResult {
+        retention.validate().map_err(ApiError::InvalidInput)?;
+        let signal = signal.into();
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(set_retention).with((signal, retention)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Disables history retention for a signal and discards its history,
+    /// including any copy stored in the preferences database.
+    pub async fn clear_retention(self, signal: impl Into<String>) -> ApiResult {
+        let signal = signal.into();
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(clear_retention).with(signal))
+                    .await
+            })
+            .await?
+    }
+}
+
+fn set_retention(
+    In((signal, retention)): In<(String, SignalRetention)>,
+    mut channels: ResMut<SignalsChannels>,
+    prefs: NonSend<PrefsDatabase>,
+) -> ApiResult {
+    let persist = retention.persist;
+    let stored = retention.to_stored(&signal);
+    let channel = channels.channel(signal.clone());
+    channel.retention = Some(retention);
+    channel.unpersisted.clear();
+    channel.prune(now_millis());
+
+    if !persist {
+        return prefs
+            .delete_signal_retention(&signal)
+            .map_err(|e| ApiError::Sql(e.to_string()));
+    }
+    prefs
+        .save_signal_retention(&stored)
+        .map_err(|e| ApiError::Sql(e.to_string()))?;
+    let messages: Vec<StoredSignalMessage> = channel
+        .history
+        .iter()
+        .map(StoredSignalMessage::from)
+        .collect();
+    prefs
+        .append_signal_messages(&signal, &messages)
+        .map_err(|e| ApiError::Sql(e.to_string()))?;
+    prefs
+        .prune_signal_history(&signal, channel.oldest_retained_seq())
+        .map_err(|e| ApiError::Sql(e.to_string()))
+}
+
+fn clear_retention(
+    In(signal): In<String>,
+    mut channels: ResMut<SignalsChannels>,
+    prefs: NonSend<PrefsDatabase>,
+) -> ApiResult {
+    if let Some(channel) = channels.get_mut(&signal) {
+        channel.retention = None;
+        channel.history.clear();
+        channel.unpersisted.clear();
+    }
+    prefs
+        .delete_signal_retention(&signal)
+        .map_err(|e| ApiError::Sql(e.to_string()))
+}
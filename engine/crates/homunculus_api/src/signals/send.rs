@@ -19,7 +19,7 @@ impl SignalsApi {
     }
 }
 
-fn send_signal(
+pub(crate) fn send_signal(
     In((signal, payload)): In<(String, serde_json::Value)>,
     mut channels: ResMut<SignalsChannels>,
 ) -> ApiResult {
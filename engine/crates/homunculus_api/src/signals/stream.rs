@@ -1,9 +1,10 @@
+use crate::error::ApiError;
 use crate::prelude::ApiResult;
-use crate::signals::{SignalsApi, SignalsChannels};
+use crate::signals::{SignalPattern, SignalRecord, SignalsApi, SignalsChannels, now_millis};
 use async_broadcast::Receiver;
 use bevy::prelude::*;
-use bevy::tasks::futures_lite::Stream;
-use bevy::tasks::futures_lite::stream::unfold;
+use bevy::tasks::futures_lite::stream::{iter, unfold};
+use bevy::tasks::futures_lite::{Stream, StreamExt};
 use bevy_flurx::prelude::*;
 
 impl SignalsApi {
@@ -11,13 +12,50 @@ impl SignalsApi {
         self,
         signal: impl Into<String>,
     ) -> ApiResult<impl Stream<Item = serde_json::Value>> {
+        let records = self.stream_since(signal, None).await?;
+        Ok(records.map(|record| record.payload))
+    }
+
+    /// Streams the messages of a signal together with their sequence numbers.
+    ///
+    /// If `since` is given, retained messages with a sequence number greater than
+    /// `since` are replayed before live messages. The replay snapshot and the live
+    /// subscription are taken in the same frame, so no message is missed or duplicated.
+    pub async fn stream_since(
+        self,
+        signal: impl Into<String>,
+        since: Option<u64>,
+    ) -> ApiResult<impl Stream<Item = SignalRecord>> {
         let signal = signal.into();
+        self.0
+            .schedule(move |task| async move {
+                let (replay, rx) = task
+                    .will(Update, once::run(obtain_receiver).with((signal, since)))
+                    .await;
+                iter(replay).chain(unfold(rx, |mut rx| async move {
+                    let v = rx.recv().await.ok()?;
+                    Some((v, rx))
+                }))
+            })
+            .await
+    }
+
+    /// Streams the messages of every signal whose name matches a wildcard pattern,
+    /// paired with the name of the signal they were sent on.
+    ///
+    /// See [`SignalPattern`] for the pattern syntax. Sequence numbers are per signal,
+    /// so pattern subscriptions do not support replay.
+    pub async fn stream_pattern(
+        self,
+        pattern: impl Into<String>,
+    ) -> ApiResult<impl Stream<Item = (String, SignalRecord)>> {
+        let pattern = SignalPattern::parse(&pattern.into()).map_err(ApiError::InvalidInput)?;
         self.0
             .schedule(move |task| async move {
                 let rx = task
-                    .will(Update, once::run(obtain_receiver).with(signal))
+                    .will(Update, once::run(obtain_pattern_receiver).with(pattern))
                     .await;
-                unfold(rx.clone(), |mut rx| async move {
+                unfold(rx, |mut rx| async move {
                     let v = rx.recv().await.ok()?;
                     Some((v, rx))
                 })
@@ -27,8 +65,28 @@ impl SignalsApi {
 }
 
 fn obtain_receiver(
-    In(signal): In<String>,
+    In((signal, since)): In<(String, Option<u64>)>,
+    mut channels: ResMut<SignalsChannels>,
+) -> (Vec<SignalRecord>, Receiver<SignalRecord>) {
+    let channel = channels.channel(signal);
+    let replay = match since {
+        Some(since) => {
+            channel.prune(now_millis());
+            channel
+                .history
+                .iter()
+                .filter(|record| since < record.seq)
+                .cloned()
+                .collect()
+        }
+        None => Vec::new(),
+    };
+    (replay, channel.receiver.new_receiver())
+}
+
+fn obtain_pattern_receiver(
+    In(pattern): In<SignalPattern>,
     mut channels: ResMut<SignalsChannels>,
-) -> Receiver<serde_json::Value> {
-    channels.channel(signal).1.new_receiver()
+) -> Receiver<(String, SignalRecord)> {
+    channels.pattern_channel(pattern).receiver.new_receiver()
 }
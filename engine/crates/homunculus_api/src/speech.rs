@@ -1,4 +1,4 @@
-mod timeline;
+pub(crate) mod timeline;
 
 pub use timeline::*;
 
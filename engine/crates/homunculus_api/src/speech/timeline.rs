@@ -7,6 +7,9 @@ use homunculus_speech::{Mora, Moras, Speak, SpeakQueue};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 
+/// Default blend time between adjacent keyframes, in seconds.
+pub(crate) const DEFAULT_TRANSITION_DURATION: f32 = 0.05;
+
 /// A single keyframe in a speech timeline.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -67,7 +70,9 @@ impl SpeechApi {
         }
 
         // Convert keyframes to Moras
-        let transition_duration = options.transition_duration.unwrap_or(0.05);
+        let transition_duration = options
+            .transition_duration
+            .unwrap_or(DEFAULT_TRANSITION_DURATION);
         let moras = keyframes_to_moras(keyframes, transition_duration);
 
         self.0
@@ -93,7 +98,10 @@ impl SpeechApi {
     }
 }
 
-fn keyframes_to_moras(keyframes: Vec<TimelineKeyframe>, transition_duration: f32) -> Moras {
+pub(crate) fn keyframes_to_moras(
+    keyframes: Vec<TimelineKeyframe>,
+    transition_duration: f32,
+) -> Moras {
     let queue: VecDeque<Mora> = keyframes
         .into_iter()
         .map(|kf| Mora {
@@ -104,7 +112,7 @@ fn keyframes_to_moras(keyframes: Vec<TimelineKeyframe>, transition_duration: f32
     Moras::new(queue, transition_duration)
 }
 
-fn enqueue_timeline_speak(
+pub(crate) fn enqueue_timeline_speak(
     In((vrm, wav, moras, finish_signal)): In<(Entity, Vec<u8>, Moras, Option<Sender<()>>)>,
     mut query: Query<&mut SpeakQueue>,
 ) -> ApiResult {
@@ -0,0 +1,244 @@
+//! Declarative choreography for personas.
+//!
+//! A [`TimelineScript`] lists cues (animations, expressions, speech, effects, sounds,
+//! tweens and signals) at offsets in seconds. Playing one attaches a player to the
+//! persona that fires each cue on the engine clock, so every track runs in parallel
+//! and stays in sync with the frame. Scripts are given inline or registered as
+//! `timeline` assets.
+
+mod format;
+mod player;
+
+pub use format::{TimelineAction, TimelineCue, TimelineScript, TimelineTrack};
+
+use crate::api;
+use crate::error::{ApiError, ApiResult};
+use crate::vrm::initialized;
+use crate::vrm::vrma::fetch_vrma;
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use homunculus_core::prelude::{AssetId, AssetRegistry, AssetType};
+use player::{
+    TimelinePlayer, advance_timelines, insert_player, pause_player, player_status, seek_player,
+    stop_player,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Request to play a timeline on a persona. Give exactly one of `asset` or `timeline`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PlayTimeline {
+    /// A registered `timeline` asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset: Option<AssetId>,
+    /// An inline timeline script.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<TimelineScript>,
+    /// Restart from the beginning each time the timeline ends.
+    #[serde(default, rename = "loop")]
+    pub looping: bool,
+}
+
+/// Playback state of a persona's timeline.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineStatus {
+    /// The asset ID of the timeline, or `inline`.
+    pub timeline: String,
+    /// Seconds since the start of the timeline (or of the current loop).
+    pub elapsed: f32,
+    /// Length of the timeline in seconds.
+    pub duration: f32,
+    pub paused: bool,
+    #[serde(rename = "loop")]
+    pub looping: bool,
+}
+
+api!(
+    /// Plays, pauses, seeks and stops persona timelines.
+    TimelineApi
+);
+
+impl TimelineApi {
+    /// Starts a timeline on the persona's VRM, replacing the one it is playing.
+    ///
+    /// VRMA animations and speech audio used by the timeline are loaded first, so
+    /// cues fire on time.
+    pub async fn play(&self, vrm: Entity, request: PlayTimeline) -> ApiResult<TimelineStatus> {
+        let looping = request.looping;
+        let (name, script) = match (request.asset, request.timeline) {
+            (Some(asset), None) => {
+                let mut paths = self
+                    .asset_paths(vec![asset.clone()], AssetType::Timeline)
+                    .await?;
+                let (_, path) = paths.remove(0);
+                let script = read_script(&asset, &path).await?;
+                (asset.to_string(), script)
+            }
+            (None, Some(script)) => ("inline".to_string(), script),
+            _ => {
+                return Err(ApiError::InvalidInput(
+                    "give exactly one of asset or timeline".to_string(),
+                ));
+            }
+        };
+        script.validate()?;
+        if looping && script.length() <= 0.0 {
+            return Err(ApiError::InvalidInput(
+                "a looping timeline must have a positive duration".to_string(),
+            ));
+        }
+        // Files are read here rather than in a system so that they do not stall the frame,
+        // and on the blocking pool so that they do not stall the async runtime.
+        let mut audio = HashMap::new();
+        for (asset, path) in self
+            .asset_paths(script.speech_assets(), AssetType::Sound)
+            .await?
+        {
+            let wav = read_wav(&asset, &path).await?;
+            audio.insert(asset, wav);
+        }
+        self.0
+            .schedule(move |task| async move {
+                for asset in script.vrma_assets() {
+                    let vrma = task
+                        .will(Update, once::run(fetch_vrma).with((vrm, asset)))
+                        .await
+                        .ok_or(ApiError::EntityNotFound)?;
+                    task.will(Update, wait::until(initialized).with(vrma)).await;
+                }
+                let player = TimelinePlayer::new(name, script, looping, audio);
+                task.will(Update, once::run(insert_player).with((vrm, player)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Returns the persona's timeline playback state, or `None` if it is not playing one.
+    pub async fn status(&self, vrm: Entity) -> ApiResult<Option<TimelineStatus>> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(player_status).with(vrm)).await
+            })
+            .await
+    }
+
+    /// Stops the persona's timeline. Actions already started run to completion.
+    pub async fn stop(&self, vrm: Entity) -> ApiResult {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(stop_player).with(vrm)).await
+            })
+            .await?
+    }
+
+    /// Pauses the timeline clock and the VRMA animations the timeline started.
+    pub async fn pause(&self, vrm: Entity) -> ApiResult<TimelineStatus> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(pause_player).with((vrm, true)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Resumes a paused timeline.
+    pub async fn resume(&self, vrm: Entity) -> ApiResult<TimelineStatus> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(pause_player).with((vrm, false)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Moves the timeline to `at` seconds. Cues before `at` are skipped; cues from `at`
+    /// on fire again, even if they already fired.
+    pub async fn seek(&self, vrm: Entity, at: f32) -> ApiResult<TimelineStatus> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(seek_player).with((vrm, at)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Looks up the files of registered assets, checking that each has the expected type.
+    async fn asset_paths(
+        &self,
+        assets: Vec<AssetId>,
+        expected: AssetType,
+    ) -> ApiResult<Vec<(AssetId, PathBuf)>> {
+        if assets.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(asset_paths).with((assets, expected)))
+                    .await
+            })
+            .await?
+    }
+}
+
+fn asset_paths(
+    In((assets, expected)): In<(Vec<AssetId>, AssetType)>,
+    registry: Res<AssetRegistry>,
+) -> ApiResult<Vec<(AssetId, PathBuf)>> {
+    assets
+        .into_iter()
+        .map(|asset| {
+            let entry = registry
+                .get(&asset)
+                .ok_or_else(|| ApiError::AssetNotFound(asset.clone()))?;
+            if entry.asset_type != expected {
+                return Err(ApiError::AssetTypeMismatch {
+                    id: asset,
+                    expected: expected.clone(),
+                    actual: entry.asset_type.clone(),
+                });
+            }
+            let path = entry.absolute_path.clone();
+            Ok((asset, path))
+        })
+        .collect()
+}
+
+/// Reads and parses a `timeline` asset: YAML for `.yaml`/`.yml` files, JSON otherwise.
+async fn read_script(asset: &AssetId, path: &Path) -> ApiResult<TimelineScript> {
+    let bytes = read_asset(asset, path).await?;
+    let yaml = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"));
+    let parsed = if yaml {
+        serde_norway::from_slice(&bytes).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+    };
+    parsed.map_err(|e| ApiError::InvalidInput(format!("Invalid timeline '{asset}': {e}")))
+}
+
+/// Reads the WAV data of a sound asset spoken by a `speak` cue.
+async fn read_wav(asset: &AssetId, path: &Path) -> ApiResult<Vec<u8>> {
+    let wav = read_asset(asset, path).await?;
+    if wav.len() < 4 || &wav[..4] != b"RIFF" {
+        return Err(ApiError::InvalidInput(format!(
+            "speak cues need WAV audio, but '{asset}' has no RIFF header"
+        )));
+    }
+    Ok(wav)
+}
+
+/// Reads an asset file on the blocking thread pool.
+async fn read_asset(asset: &AssetId, path: &Path) -> ApiResult<Vec<u8>> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || std::fs::read(path))
+        .await
+        .map_err(|e| ApiError::FailedLoad(format!("{asset}: read task panicked: {e}")))?
+        .map_err(|e| ApiError::FailedLoad(format!("{asset}: {e}")))
+}
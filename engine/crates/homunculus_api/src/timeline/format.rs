@@ -0,0 +1,369 @@
+use crate::entities::EasingFunction;
+use crate::error::{ApiError, ApiResult};
+use crate::speech::TimelineKeyframe;
+use crate::vrma::Repeat;
+use bevy::prelude::{Quat, Vec2, Vec3};
+use homunculus_core::prelude::AssetId;
+use homunculus_effects::prelude::StampOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default on-screen duration of a stamp effect, in seconds.
+const DEFAULT_STAMP_SECS: f32 = 0.8;
+
+/// Shortest non-zero timeline length, in seconds: one frame at 60 fps.
+const MIN_DURATION_SECS: f32 = 1.0 / 60.0;
+
+/// A declarative choreography for a persona.
+///
+/// Every cue fires at its own `at` offset on the engine clock, so tracks run in parallel;
+/// tracks only group related cues (body, face, voice, ...) for authoring and progress events.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineScript {
+    /// Total length in seconds. Defaults to the end of the last cue, including tween,
+    /// speech and stamp durations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+    pub tracks: Vec<TimelineTrack>,
+}
+
+/// A named group of cues.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineTrack {
+    /// Name reported in `timeline-cue` events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub cues: Vec<TimelineCue>,
+}
+
+/// An action fired at a point in time.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TimelineCue {
+    /// Offset from the start of the timeline, in seconds.
+    pub at: f32,
+    #[serde(flatten)]
+    pub action: TimelineAction,
+}
+
+/// What a [`TimelineCue`] does when it fires.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TimelineAction {
+    /// Play a VRMA animation.
+    #[serde(rename_all = "camelCase")]
+    Vrma {
+        asset: AssetId,
+        #[serde(default)]
+        repeat: Option<Repeat>,
+        #[serde(default)]
+        transition_secs: Option<f64>,
+        #[serde(default)]
+        reset_spring_bones: Option<bool>,
+    },
+    /// Stop a VRMA animation.
+    StopVrma { asset: AssetId },
+    /// Merge expression weights into the current ones, or replace them all with `replace`.
+    Expressions {
+        weights: HashMap<String, f32>,
+        #[serde(default)]
+        replace: bool,
+    },
+    /// Clear all expression overrides.
+    ClearExpressions,
+    /// Speak a WAV sound asset with lip-sync keyframes.
+    #[serde(rename_all = "camelCase")]
+    Speak {
+        asset: AssetId,
+        keyframes: Vec<TimelineKeyframe>,
+        #[serde(default)]
+        transition_duration: Option<f32>,
+    },
+    /// Show a stamp effect.
+    Stamp {
+        asset: AssetId,
+        #[serde(default)]
+        #[cfg_attr(feature = "openapi", schema(value_type = Option<Object>))]
+        options: Option<StampOptions>,
+    },
+    /// Play a sound effect.
+    Sound {
+        asset: AssetId,
+        #[serde(default = "default_one")]
+        volume: f64,
+        #[serde(default = "default_one")]
+        speed: f64,
+        #[serde(default)]
+        panning: f64,
+    },
+    /// Tween the persona to a world position or a viewport position; give exactly one.
+    #[serde(rename_all = "camelCase")]
+    Move {
+        #[serde(default)]
+        #[cfg_attr(feature = "openapi", schema(value_type = Option<[f32; 3]>))]
+        position: Option<Vec3>,
+        #[serde(default)]
+        #[cfg_attr(feature = "openapi", schema(value_type = Option<[f32; 2]>))]
+        viewport: Option<Vec2>,
+        duration_ms: u64,
+        #[serde(default)]
+        easing: EasingFunction,
+    },
+    /// Tween the persona's rotation.
+    #[serde(rename_all = "camelCase")]
+    Rotate {
+        #[cfg_attr(feature = "openapi", schema(value_type = [f32; 4]))]
+        target: Quat,
+        duration_ms: u64,
+        #[serde(default)]
+        easing: EasingFunction,
+    },
+    /// Tween the persona's scale.
+    #[serde(rename_all = "camelCase")]
+    Scale {
+        #[cfg_attr(feature = "openapi", schema(value_type = [f32; 3]))]
+        target: Vec3,
+        duration_ms: u64,
+        #[serde(default)]
+        easing: EasingFunction,
+    },
+    /// Send a signal, e.g. to drive a MOD's UI in sync with the timeline.
+    Signal {
+        signal: String,
+        #[serde(default)]
+        #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+        payload: serde_json::Value,
+    },
+}
+
+fn default_one() -> f64 {
+    1.0
+}
+
+impl TimelineAction {
+    /// The action's `type` tag, as reported in `timeline-cue` events.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Vrma { .. } => "vrma",
+            Self::StopVrma { .. } => "stopVrma",
+            Self::Expressions { .. } => "expressions",
+            Self::ClearExpressions => "clearExpressions",
+            Self::Speak { .. } => "speak",
+            Self::Stamp { .. } => "stamp",
+            Self::Sound { .. } => "sound",
+            Self::Move { .. } => "move",
+            Self::Rotate { .. } => "rotate",
+            Self::Scale { .. } => "scale",
+            Self::Signal { .. } => "signal",
+        }
+    }
+
+    /// How long the action runs after it fires, in seconds, where that is known up front.
+    ///
+    /// VRMA clip lengths are not known until the asset loads, so they count as instant.
+    fn span(&self) -> f32 {
+        match self {
+            Self::Speak { keyframes, .. } => keyframes.iter().map(|k| k.duration).sum(),
+            Self::Stamp { options, .. } => options
+                .as_ref()
+                .and_then(|o| o.duration_secs)
+                .map_or(DEFAULT_STAMP_SECS, |secs| secs as f32),
+            Self::Move { duration_ms, .. }
+            | Self::Rotate { duration_ms, .. }
+            | Self::Scale { duration_ms, .. } => *duration_ms as f32 / 1000.0,
+            _ => 0.0,
+        }
+    }
+}
+
+impl TimelineScript {
+    /// Checks offsets, durations and action arguments before anything is played.
+    pub fn validate(&self) -> ApiResult {
+        if self.tracks.iter().all(|track| track.cues.is_empty()) {
+            return Err(ApiError::InvalidInput(
+                "timeline must contain at least one cue".to_string(),
+            ));
+        }
+        if let Some(duration) = self.duration
+            && !(duration.is_finite() && 0.0 <= duration)
+        {
+            return Err(ApiError::InvalidInput(
+                "timeline duration must be a non-negative number".to_string(),
+            ));
+        }
+        let length = self.length();
+        if 0.0 < length && length < MIN_DURATION_SECS {
+            return Err(ApiError::InvalidInput(format!(
+                "timeline duration must be 0 or at least {MIN_DURATION_SECS}s"
+            )));
+        }
+        for cue in self.tracks.iter().flat_map(|track| &track.cues) {
+            if !(cue.at.is_finite() && 0.0 <= cue.at) {
+                return Err(ApiError::InvalidInput(format!(
+                    "cue offset must be a non-negative number, got {}",
+                    cue.at
+                )));
+            }
+            match &cue.action {
+                TimelineAction::Move { duration_ms: 0, .. }
+                | TimelineAction::Rotate { duration_ms: 0, .. }
+                | TimelineAction::Scale { duration_ms: 0, .. } => {
+                    return Err(ApiError::InvalidInput(format!(
+                        "{} cue at {}s: durationMs must be greater than 0",
+                        cue.action.kind(),
+                        cue.at
+                    )));
+                }
+                TimelineAction::Move {
+                    position, viewport, ..
+                } if position.is_some() == viewport.is_some() => {
+                    return Err(ApiError::InvalidInput(format!(
+                        "move cue at {}s: give exactly one of position or viewport",
+                        cue.at
+                    )));
+                }
+                TimelineAction::Speak { keyframes, .. }
+                    if keyframes.iter().any(|k| k.duration < 0.0) =>
+                {
+                    return Err(ApiError::InvalidInput(format!(
+                        "speak cue at {}s: keyframe duration must not be negative",
+                        cue.at
+                    )));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// The playback length: the explicit `duration`, or the end of the last cue.
+    pub fn length(&self) -> f32 {
+        self.duration.unwrap_or_else(|| {
+            self.tracks
+                .iter()
+                .flat_map(|track| &track.cues)
+                .map(|cue| cue.at + cue.action.span())
+                .fold(0.0, f32::max)
+        })
+    }
+
+    /// VRMA assets the timeline plays or stops, deduplicated.
+    pub(crate) fn vrma_assets(&self) -> Vec<AssetId> {
+        let mut assets = Vec::new();
+        for cue in self.tracks.iter().flat_map(|track| &track.cues) {
+            if let TimelineAction::Vrma { asset, .. } | TimelineAction::StopVrma { asset } =
+                &cue.action
+                && !assets.contains(asset)
+            {
+                assets.push(asset.clone());
+            }
+        }
+        assets
+    }
+
+    /// Sound assets spoken by `speak` cues, deduplicated.
+    pub(crate) fn speech_assets(&self) -> Vec<AssetId> {
+        let mut assets = Vec::new();
+        for cue in self.tracks.iter().flat_map(|track| &track.cues) {
+            if let TimelineAction::Speak { asset, .. } = &cue.action
+                && !assets.contains(asset)
+            {
+                assets.push(asset.clone());
+            }
+        }
+        assets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn script(value: serde_json::Value) -> TimelineScript {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn parses_cues_of_every_track() {
+        let timeline = script(json!({
+            "tracks": [
+                {"name": "body", "cues": [
+                    {"at": 0.0, "type": "vrma", "asset": "vrma:wave", "transitionSecs": 0.2},
+                    {"at": 1.2, "type": "move", "viewport": [400.0, 300.0], "durationMs": 500, "easing": "backOut"}
+                ]},
+                {"name": "face", "cues": [
+                    {"at": 0.3, "type": "expressions", "weights": {"happy": 1.0}}
+                ]}
+            ]
+        }));
+        assert_eq!(timeline.tracks.len(), 2);
+        assert!(matches!(
+            timeline.tracks[0].cues[1].action,
+            TimelineAction::Move {
+                position: None,
+                viewport: Some(Vec2 { x: 400.0, y: 300.0 }),
+                duration_ms: 500,
+                easing: EasingFunction::BackOut,
+            }
+        ));
+        assert_eq!(timeline.vrma_assets(), vec![AssetId::new("vrma:wave")]);
+        assert!(timeline.validate().is_ok());
+    }
+
+    #[test]
+    fn length_covers_the_longest_cue() {
+        let timeline = script(json!({
+            "tracks": [{"cues": [
+                {"at": 0.5, "type": "speak", "asset": "se:hello", "keyframes": [
+                    {"duration": 0.25, "targets": {"aa": 1.0}},
+                    {"duration": 0.25}
+                ]},
+                {"at": 1.2, "type": "stamp", "asset": "image:star"},
+                {"at": 0.1, "type": "scale", "target": [2.0, 2.0, 2.0], "durationMs": 300}
+            ]}]
+        }));
+        assert!((timeline.length() - 2.0).abs() < 1e-6);
+
+        let explicit = TimelineScript {
+            duration: Some(5.0),
+            ..timeline
+        };
+        assert_eq!(explicit.length(), 5.0);
+    }
+
+    #[test]
+    fn rejects_invalid_cues() {
+        let empty = script(json!({"tracks": [{"cues": []}]}));
+        assert!(empty.validate().is_err());
+
+        let negative = script(json!({
+            "tracks": [{"cues": [{"at": -1.0, "type": "clearExpressions"}]}]
+        }));
+        assert!(negative.validate().is_err());
+
+        let both = script(json!({
+            "tracks": [{"cues": [{
+                "at": 0.0, "type": "move",
+                "position": [0.0, 0.0, 0.0], "viewport": [0.0, 0.0], "durationMs": 100
+            }]}]
+        }));
+        assert!(both.validate().is_err());
+
+        let instant = script(json!({
+            "tracks": [{"cues": [{"at": 0.0, "type": "rotate", "target": [0.0, 0.0, 0.0, 1.0], "durationMs": 0}]}]
+        }));
+        assert!(instant.validate().is_err());
+
+        let tiny = script(json!({
+            "duration": 1e-30,
+            "tracks": [{"cues": [{"at": 0.0, "type": "clearExpressions"}]}]
+        }));
+        assert!(tiny.validate().is_err());
+    }
+}
@@ -0,0 +1,497 @@
+use crate::audio::play_se;
+use crate::batch::run_system;
+use crate::effects::stamp;
+use crate::entities::tween::{
+    apply_position_tween, apply_position_tween_viewport, apply_rotation_tween, apply_scale_tween,
+};
+use crate::entities::{
+    TweenPositionArgs, TweenPositionViewportArgs, TweenRotationArgs, TweenScaleArgs,
+};
+use crate::error::{ApiError, ApiResult};
+use crate::events::EngineEventBus;
+use crate::signals::send::send_signal;
+use crate::speech::timeline::{
+    DEFAULT_TRANSITION_DURATION, enqueue_timeline_speak, keyframes_to_moras,
+};
+use crate::timeline::{TimelineAction, TimelineScript, TimelineStatus};
+use crate::vrm::expressions::{do_clear_expressions, do_modify_expressions, do_set_expressions};
+use crate::vrm::vrma::fetch_vrma;
+use crate::vrma::{Repeat, play, set_vrma_speed, stop};
+use bevy::prelude::*;
+use bevy_vrm1::prelude::PlayVrma;
+use homunculus_core::prelude::{AssetId, Persona};
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The timeline a persona is playing, advanced every frame by [`advance_timelines`].
+#[derive(Component)]
+pub(crate) struct TimelinePlayer {
+    name: String,
+    cues: Vec<ScheduledCue>,
+    next: usize,
+    elapsed: f32,
+    duration: f32,
+    paused: bool,
+    looping: bool,
+    /// WAV data of the `speak` cues, read when the timeline started.
+    audio: HashMap<AssetId, Vec<u8>>,
+    /// VRMAs started by the timeline, frozen while it is paused.
+    vrmas: Vec<Entity>,
+}
+
+/// A cue flattened out of its track.
+#[derive(Clone)]
+struct ScheduledCue {
+    at: f32,
+    track: Option<String>,
+    action: TimelineAction,
+}
+
+impl TimelinePlayer {
+    pub(crate) fn new(
+        name: String,
+        script: TimelineScript,
+        looping: bool,
+        audio: HashMap<AssetId, Vec<u8>>,
+    ) -> Self {
+        let duration = script.length();
+        let mut cues: Vec<ScheduledCue> = script
+            .tracks
+            .into_iter()
+            .flat_map(|track| {
+                let name = track.name;
+                track.cues.into_iter().map(move |cue| ScheduledCue {
+                    at: cue.at,
+                    track: name.clone(),
+                    action: cue.action,
+                })
+            })
+            .collect();
+        // Stable, so cues at the same offset fire in track order.
+        cues.sort_by(|a, b| a.at.total_cmp(&b.at));
+        Self {
+            name,
+            cues,
+            next: 0,
+            elapsed: 0.0,
+            duration,
+            paused: false,
+            looping,
+            audio,
+            vrmas: Vec::new(),
+        }
+    }
+
+    pub(crate) fn status(&self) -> TimelineStatus {
+        TimelineStatus {
+            timeline: self.name.clone(),
+            elapsed: self.elapsed,
+            duration: self.duration,
+            paused: self.paused,
+            looping: self.looping,
+        }
+    }
+
+    /// Advances the clock by `delta` seconds and returns the cues that became due,
+    /// along with whether the timeline has finished.
+    fn advance(&mut self, delta: f32) -> (Vec<ScheduledCue>, bool) {
+        let mut due = Vec::new();
+        if self.paused {
+            return (due, false);
+        }
+        self.elapsed += delta;
+        loop {
+            while let Some(cue) = self.cues.get(self.next)
+                && cue.at <= self.elapsed
+            {
+                due.push(cue.clone());
+                self.next += 1;
+            }
+            if self.elapsed < self.duration {
+                return (due, false);
+            }
+            // Cues past an explicit duration never fire.
+            if !self.looping || self.duration <= 0.0 {
+                self.elapsed = self.duration;
+                return (due, true);
+            }
+            // Wraps at most once per frame, however short the loop is.
+            self.elapsed %= self.duration;
+            self.next = 0;
+        }
+    }
+
+    /// Moves the clock to `at`; cues before it are skipped, cues from it on fire again.
+    pub(crate) fn seek(&mut self, at: f32) -> ApiResult {
+        if !(at.is_finite() && 0.0 <= at && at <= self.duration) {
+            return Err(ApiError::InvalidInput(format!(
+                "seek position must be between 0 and {}",
+                self.duration
+            )));
+        }
+        self.elapsed = at;
+        self.next = self.cues.partition_point(|cue| cue.at < at);
+        Ok(())
+    }
+}
+
+pub(crate) fn insert_player(
+    In((vrm, player)): In<(Entity, TimelinePlayer)>,
+    world: &mut World,
+) -> ApiResult<TimelineStatus> {
+    if world.get_entity(vrm).is_err() {
+        return Err(ApiError::EntityNotFound);
+    }
+    finish(world, vrm, "replaced");
+    let status = player.status();
+    world.entity_mut(vrm).insert(player);
+    publish(world, vrm, "timeline-play", json!(status));
+    Ok(status)
+}
+
+pub(crate) fn player_status(
+    In(vrm): In<Entity>,
+    players: Query<&TimelinePlayer>,
+) -> Option<TimelineStatus> {
+    players.get(vrm).ok().map(TimelinePlayer::status)
+}
+
+pub(crate) fn stop_player(In(vrm): In<Entity>, world: &mut World) -> ApiResult {
+    if finish(world, vrm, "stopped") {
+        Ok(())
+    } else {
+        Err(ApiError::TimelineNotPlaying)
+    }
+}
+
+pub(crate) fn pause_player(
+    In((vrm, paused)): In<(Entity, bool)>,
+    world: &mut World,
+) -> ApiResult<TimelineStatus> {
+    let mut player = world
+        .get_mut::<TimelinePlayer>(vrm)
+        .ok_or(ApiError::TimelineNotPlaying)?;
+    player.paused = paused;
+    let (status, vrmas) = (player.status(), player.vrmas.clone());
+    let speed = if paused { 0.0 } else { 1.0 };
+    for vrma in vrmas {
+        run_system(world, set_vrma_speed, (vrma, speed))?;
+    }
+    Ok(status)
+}
+
+pub(crate) fn seek_player(
+    In((vrm, at)): In<(Entity, f32)>,
+    mut players: Query<&mut TimelinePlayer>,
+) -> ApiResult<TimelineStatus> {
+    let mut player = players
+        .get_mut(vrm)
+        .map_err(|_| ApiError::TimelineNotPlaying)?;
+    player.seek(at)?;
+    Ok(player.status())
+}
+
+/// Fires the cues that became due this frame and removes finished timelines.
+pub(super) fn advance_timelines(world: &mut World) {
+    let delta = world.resource::<Time>().delta_secs();
+    let mut ticks = Vec::new();
+    let mut players = world.query::<(Entity, &mut TimelinePlayer)>();
+    for (vrm, mut player) in players.iter_mut(world) {
+        if player.paused {
+            continue;
+        }
+        let (due, finished) = player.advance(delta);
+        if !due.is_empty() || finished {
+            ticks.push((vrm, due, finished));
+        }
+    }
+    for (vrm, due, finished) in ticks {
+        for cue in due {
+            let outcome = fire(world, vrm, cue.action.clone());
+            if let Err(e) = &outcome {
+                warn!(
+                    "Timeline {} cue at {}s failed: {e}",
+                    cue.action.kind(),
+                    cue.at
+                );
+            }
+            let elapsed = world
+                .get::<TimelinePlayer>(vrm)
+                .map_or(cue.at, |player| player.elapsed);
+            publish(
+                world,
+                vrm,
+                "timeline-cue",
+                json!({
+                    "track": cue.track,
+                    "type": cue.action.kind(),
+                    "at": cue.at,
+                    "elapsed": elapsed,
+                    "error": outcome.err().map(|e| e.to_string()),
+                }),
+            );
+        }
+        if finished {
+            finish(world, vrm, "completed");
+        }
+    }
+}
+
+/// Removes the persona's timeline, if any, and reports why it ended.
+fn finish(world: &mut World, vrm: Entity, reason: &str) -> bool {
+    let Some(player) = world
+        .get_entity_mut(vrm)
+        .ok()
+        .and_then(|mut entity| entity.take::<TimelinePlayer>())
+    else {
+        return false;
+    };
+    if player.paused {
+        for &vrma in &player.vrmas {
+            let _ = run_system(world, set_vrma_speed, (vrma, 1.0));
+        }
+    }
+    let mut status = json!(player.status());
+    status["reason"] = json!(reason);
+    publish(world, vrm, "timeline-finish", status);
+    true
+}
+
+/// Applies one cue to the persona, running the same systems as the dedicated endpoints.
+fn fire(world: &mut World, vrm: Entity, action: TimelineAction) -> ApiResult {
+    match action {
+        TimelineAction::Vrma {
+            asset,
+            repeat,
+            transition_secs,
+            reset_spring_bones,
+        } => {
+            let vrma = vrma_entity(world, vrm, asset)?;
+            let args = PlayVrma {
+                vrma,
+                transition_duration: Duration::from_secs_f64(transition_secs.unwrap_or_default()),
+                repeat: repeat.unwrap_or(Repeat::Never).into(),
+                reset_spring_bones: reset_spring_bones.unwrap_or(true),
+            };
+            run_system(world, play, args)?;
+            if let Some(mut player) = world.get_mut::<TimelinePlayer>(vrm)
+                && !player.vrmas.contains(&vrma)
+            {
+                player.vrmas.push(vrma);
+            }
+        }
+        TimelineAction::StopVrma { asset } => {
+            let vrma = vrma_entity(world, vrm, asset)?;
+            run_system(world, stop, vrma)?;
+        }
+        TimelineAction::Expressions { weights, replace } => {
+            if replace {
+                run_system(world, do_set_expressions, (vrm, weights))?;
+            } else {
+                run_system(world, do_modify_expressions, (vrm, weights))?;
+            }
+        }
+        TimelineAction::ClearExpressions => {
+            run_system(world, do_clear_expressions, vrm)?;
+        }
+        TimelineAction::Speak {
+            asset,
+            keyframes,
+            transition_duration,
+        } => {
+            let wav = world
+                .get::<TimelinePlayer>(vrm)
+                .and_then(|player| player.audio.get(&asset).cloned())
+                .ok_or(ApiError::AssetNotFound(asset))?;
+            let moras = keyframes_to_moras(
+                keyframes,
+                transition_duration.unwrap_or(DEFAULT_TRANSITION_DURATION),
+            );
+            run_system(world, enqueue_timeline_speak, (vrm, wav, moras, None))??;
+        }
+        TimelineAction::Stamp { asset, options } => {
+            run_system(world, stamp, (asset, options))??;
+        }
+        TimelineAction::Sound {
+            asset,
+            volume,
+            speed,
+            panning,
+        } => {
+            run_system(world, play_se, (asset, volume, speed, panning))??;
+        }
+        TimelineAction::Move {
+            position,
+            viewport,
+            duration_ms,
+            easing,
+        } => match (position, viewport) {
+            (Some(target), None) => {
+                let args = TweenPositionArgs {
+                    target,
+                    duration_ms,
+                    easing,
+                    wait: false,
+                };
+                run_system(world, apply_position_tween, (vrm, args))??;
+            }
+            (None, Some(position)) => {
+                let args = TweenPositionViewportArgs {
+                    position,
+                    duration_ms,
+                    easing,
+                    wait: false,
+                };
+                run_system(world, apply_position_tween_viewport, (vrm, args))??;
+            }
+            _ => {
+                return Err(ApiError::InvalidInput(
+                    "give exactly one of position or viewport".to_string(),
+                ));
+            }
+        },
+        TimelineAction::Rotate {
+            target,
+            duration_ms,
+            easing,
+        } => {
+            let args = TweenRotationArgs {
+                target,
+                duration_ms,
+                easing,
+                wait: false,
+            };
+            run_system(world, apply_rotation_tween, (vrm, args))??;
+        }
+        TimelineAction::Scale {
+            target,
+            duration_ms,
+            easing,
+        } => {
+            let args = TweenScaleArgs {
+                target,
+                duration_ms,
+                easing,
+                wait: false,
+            };
+            run_system(world, apply_scale_tween, (vrm, args))??;
+        }
+        TimelineAction::Signal { signal, payload } => {
+            run_system(world, send_signal, (signal, payload))??;
+        }
+    }
+    Ok(())
+}
+
+fn vrma_entity(world: &mut World, vrm: Entity, asset: AssetId) -> ApiResult<Entity> {
+    run_system(world, fetch_vrma, (vrm, asset))?.ok_or(ApiError::EntityNotFound)
+}
+
+fn publish(world: &mut World, vrm: Entity, event: &str, data: serde_json::Value) {
+    let persona_id = world.get::<Persona>(vrm).map(|persona| persona.id.clone());
+    if let Some(mut bus) = world.get_resource_mut::<EngineEventBus>()
+        && bus.has_subscribers()
+    {
+        bus.publish(event, persona_id, None, data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn player(looping: bool) -> TimelinePlayer {
+        let script: TimelineScript = serde_json::from_value(json!({
+            "duration": 2.0,
+            "tracks": [
+                {"name": "body", "cues": [
+                    {"at": 0.0, "type": "clearExpressions"},
+                    {"at": 1.5, "type": "signal", "signal": "late"}
+                ]},
+                {"name": "face", "cues": [
+                    {"at": 0.5, "type": "expressions", "weights": {"happy": 1.0}}
+                ]}
+            ]
+        }))
+        .unwrap();
+        TimelinePlayer::new("test".to_string(), script, looping, HashMap::new())
+    }
+
+    fn kinds(cues: &[ScheduledCue]) -> Vec<&'static str> {
+        cues.iter().map(|cue| cue.action.kind()).collect()
+    }
+
+    #[test]
+    fn fires_cues_of_all_tracks_in_time_order() {
+        let mut player = player(false);
+        let (due, finished) = player.advance(0.6);
+        assert_eq!(kinds(&due), ["clearExpressions", "expressions"]);
+        assert!(!finished);
+
+        let (due, finished) = player.advance(1.0);
+        assert_eq!(kinds(&due), ["signal"]);
+        assert!(!finished);
+
+        let (due, finished) = player.advance(1.0);
+        assert!(due.is_empty());
+        assert!(finished);
+        assert_eq!(player.elapsed, 2.0);
+    }
+
+    #[test]
+    fn pause_stops_the_clock() {
+        let mut player = player(false);
+        player.paused = true;
+        let (due, finished) = player.advance(5.0);
+        assert!(due.is_empty());
+        assert!(!finished);
+        assert_eq!(player.elapsed, 0.0);
+    }
+
+    #[test]
+    fn seek_skips_earlier_cues_and_rearms_later_ones() {
+        let mut player = player(false);
+        player.advance(1.6);
+        player.seek(0.5).unwrap();
+        let (due, _) = player.advance(0.0);
+        assert_eq!(kinds(&due), ["expressions"]);
+        assert!(player.seek(3.0).is_err());
+    }
+
+    #[test]
+    fn looping_wraps_around() {
+        let mut player = player(true);
+        let (due, finished) = player.advance(2.1);
+        assert_eq!(
+            kinds(&due),
+            [
+                "clearExpressions",
+                "expressions",
+                "signal",
+                "clearExpressions"
+            ]
+        );
+        assert!(!finished);
+        assert!((player.elapsed - 0.1).abs() < 1e-5);
+    }
+
+    #[test]
+    fn long_frames_wrap_a_loop_once() {
+        let mut player = player(true);
+        let (due, finished) = player.advance(9.0);
+        assert_eq!(
+            kinds(&due),
+            [
+                "clearExpressions",
+                "expressions",
+                "signal",
+                "clearExpressions",
+                "expressions"
+            ]
+        );
+        assert!(!finished);
+        assert!((player.elapsed - 1.0).abs() < 1e-5);
+    }
+}
@@ -1,25 +1,65 @@
 mod bone;
+mod bone_layer;
 pub(crate) mod expressions;
-mod look;
+mod gesture;
+mod ik;
+pub(crate) mod look;
+mod pose;
 mod position;
 pub(crate) mod snapshot;
 mod spring_bones;
-mod vrma;
+pub(crate) mod vrma;
 
 pub use crate::entities::transform::*;
-pub use expressions::{ExpressionInfo, ExpressionsResponse};
+pub use expressions::{
+    ApplyExpressionPreset, AutoExpressionSettings, ExpressionInfo, ExpressionPresets,
+    ExpressionTransition, ExpressionsResponse,
+};
+pub use gesture::{Gesture, PerformGesture};
+pub use ik::{IkPoint, IkTarget, IkTargets};
+pub use look::{LookAtPoint, LookAtSettings};
+pub use pose::{BonePose, BoneRotation, PosePresets, PoseTransition, SetBoneRotation, SetPose};
 pub use position::PositionResponse;
 pub use snapshot::{LookAtState, VrmSnapshot};
 pub use spring_bones::{
-    SpringBoneChain, SpringBoneChainsResponse, SpringBoneProps, SpringBonePropsUpdate,
+    PersonaWindArgs, SPRING_BONE_PRESETS, SpringBoneChain, SpringBoneChainsResponse,
+    SpringBoneProps, SpringBonePropsUpdate, SpringBoneSettings, WindSettings,
 };
 
 use crate::api;
 use bevy::prelude::*;
+use bevy::transform::TransformSystems;
 use bevy_vrm1::prelude::Initialized;
+use bone_layer::BoneLayerSystems;
 
 api!(VrmApi);
 
 pub fn initialized(In(entity): In<Entity>, vrmas: Query<&Initialized>) -> bool {
     vrmas.get(entity).is_ok()
 }
+
+pub(super) struct VrmApiPlugin;
+
+impl Plugin for VrmApiPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(
+            PostUpdate,
+            (
+                BoneLayerSystems::Gaze,
+                BoneLayerSystems::Pose,
+                BoneLayerSystems::Ik,
+            )
+                .chain()
+                .after(bevy::animation::AnimationSystems)
+                .before(TransformSystems::Propagate),
+        )
+        .add_plugins((
+            expressions::ExpressionsPlugin,
+            gesture::GesturePlugin,
+            ik::IkPlugin,
+            look::LookPlugin,
+            pose::PosePlugin,
+            spring_bones::SpringBonesPlugin,
+        ));
+    }
+}
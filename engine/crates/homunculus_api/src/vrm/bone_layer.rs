@@ -0,0 +1,99 @@
+//! Rotations layered on top of the animated pose of VRM bones.
+//!
+//! Layers are written after animations run and before transforms propagate. A bone that
+//! no animation writes still holds last frame's layer, so it is taken off first.
+
+use bevy::prelude::*;
+
+/// Systems that layer rotations on bones, ordered after animations and before
+/// transform propagation. Later layers win on bones that several layers rotate.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum BoneLayerSystems {
+    /// Head and neck turns following the gaze.
+    Gaze,
+    /// Bone rotations set through the API.
+    Pose,
+    /// Arms and legs reaching their inverse kinematics targets.
+    Ik,
+}
+
+/// The rotation layered on a bone last frame.
+#[derive(Component, Debug)]
+pub(crate) struct BoneLayer {
+    delta: Quat,
+    result: Quat,
+}
+
+/// The bone's rotation without last frame's layer.
+pub(crate) fn base_rotation(rotation: Quat, layer: Option<&BoneLayer>) -> Quat {
+    match layer {
+        Some(layer) if rotation == layer.result => layer.delta.inverse() * rotation,
+        _ => rotation,
+    }
+}
+
+/// Global transform of `entity` from this frame's local transforms.
+///
+/// Global transforms are only propagated after animations have been applied, so the
+/// local ones are composed up the hierarchy instead.
+pub(crate) fn animated_global(
+    entity: Entity,
+    parents: &Query<&ChildOf>,
+    bones: &Query<(&mut Transform, Option<&mut BoneLayer>)>,
+) -> Option<GlobalTransform> {
+    let (tf, _) = bones.get(entity).ok()?;
+    match parents.get(entity) {
+        Ok(child_of) => {
+            Some(animated_global(child_of.parent(), parents, bones)?.mul_transform(*tf))
+        }
+        Err(_) => Some(GlobalTransform::from(*tf)),
+    }
+}
+
+/// Rotates the bone by `delta`, in its parent's space, on top of `base`.
+pub(crate) fn apply_layer(
+    commands: &mut Commands,
+    bone: Entity,
+    tf: &mut Transform,
+    layer: Option<Mut<BoneLayer>>,
+    base: Quat,
+    delta: Quat,
+) {
+    tf.rotation = delta * base;
+    match layer {
+        Some(mut layer) => {
+            layer.delta = delta;
+            layer.result = tf.rotation;
+        }
+        None => {
+            commands.entity(bone).try_insert(BoneLayer {
+                delta,
+                result: tf.rotation,
+            });
+        }
+    }
+}
+
+/// Takes the layer off the bone.
+pub(crate) fn release_layer(
+    commands: &mut Commands,
+    bone: Entity,
+    tf: &mut Transform,
+    layer: &BoneLayer,
+) {
+    tf.rotation = base_rotation(tf.rotation, Some(layer));
+    commands.entity(bone).try_remove::<BoneLayer>();
+}
+
+/// Takes the layers off the given bones.
+pub(crate) fn release_layers(
+    commands: &mut Commands,
+    bones: impl IntoIterator<Item = Entity>,
+    layered: &mut Query<(&mut Transform, &BoneLayer)>,
+) {
+    for bone in bones {
+        if let Ok((mut tf, layer)) = layered.get_mut(bone) {
+            release_layer(commands, bone, &mut tf, layer);
+        }
+    }
+}
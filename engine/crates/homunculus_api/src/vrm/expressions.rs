@@ -1,11 +1,22 @@
+mod auto;
+mod presets;
+mod transition;
+
+pub use auto::AutoExpressionSettings;
+pub use presets::ExpressionPresets;
+pub use transition::ExpressionTransition;
+
+use crate::entities::tween::EasingFunction;
 use crate::error::ApiResult;
 use crate::vrm::VrmApi;
+use auto::{load_auto_expressions, run_auto_expressions};
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
 use bevy_vrm1::prelude::*;
 use homunculus_core::prelude::{ExpressionChangeEvent, OutputLog, VrmEvent, VrmEventSender};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use transition::{ExpressionTween, TransitionEnd, advance_expression_tweens, current_weights};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -25,6 +36,21 @@ pub struct ExpressionsResponse {
     pub expressions: Vec<ExpressionInfo>,
 }
 
+/// Request arguments for applying an expression preset.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyExpressionPreset {
+    /// Duration of the transition in milliseconds; `0` applies the preset instantly.
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+    /// Release every other expression override, as `set` does. Defaults to `false`.
+    #[serde(default)]
+    pub replace: bool,
+}
+
 impl VrmApi {
     pub async fn list_expressions(&self, vrm: Entity) -> ApiResult<ExpressionsResponse> {
         self.0
@@ -77,6 +103,167 @@ impl VrmApi {
             })
             .await
     }
+
+    /// Replaces all expression weights, easing from the current weights over the transition.
+    ///
+    /// Expressions not in `weights` fade to zero and are released at the end.
+    pub async fn set_expressions_with(
+        &self,
+        vrm: Entity,
+        weights: HashMap<String, f32>,
+        transition: ExpressionTransition,
+    ) -> ApiResult {
+        let weights = clamp_weights(weights);
+        let end = TransitionEnd::Replace(weights.clone());
+        self.start_transition(vrm, weights, transition, end).await
+    }
+
+    /// Moves the given expression weights over the transition, leaving the others alone.
+    pub async fn modify_expressions_with(
+        &self,
+        vrm: Entity,
+        weights: HashMap<String, f32>,
+        transition: ExpressionTransition,
+    ) -> ApiResult {
+        let weights = clamp_weights(weights);
+        self.start_transition(vrm, weights, transition, TransitionEnd::Keep)
+            .await
+    }
+
+    /// Fades every expression override to zero over the transition, then returns
+    /// control to VRMA animation.
+    pub async fn clear_expressions_with(
+        &self,
+        vrm: Entity,
+        transition: ExpressionTransition,
+    ) -> ApiResult {
+        self.start_transition(vrm, HashMap::new(), transition, TransitionEnd::Clear)
+            .await
+    }
+
+    async fn start_transition(
+        &self,
+        vrm: Entity,
+        weights: HashMap<String, f32>,
+        transition: ExpressionTransition,
+        end: TransitionEnd,
+    ) -> ApiResult {
+        self.0
+            .schedule(move |task| async move {
+                task.will(
+                    Update,
+                    once::run(start_expression_tween).with((vrm, weights, transition, end)),
+                )
+                .await;
+            })
+            .await
+    }
+
+    /// Returns the persona's expression presets.
+    pub async fn expression_presets(&self, vrm: Entity) -> ApiResult<ExpressionPresets> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(presets::get_presets).with(vrm))
+                    .await
+            })
+            .await?
+    }
+
+    /// Stores a named combination of expression weights for the persona.
+    pub async fn save_expression_preset(
+        &self,
+        vrm: Entity,
+        name: String,
+        weights: HashMap<String, f32>,
+    ) -> ApiResult<ExpressionPresets> {
+        presets::validate_preset(&name, &weights)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(
+                    Update,
+                    once::run(presets::put_preset).with((vrm, name, weights)),
+                )
+                .await
+            })
+            .await?
+    }
+
+    /// Deletes one of the persona's expression presets.
+    pub async fn delete_expression_preset(
+        &self,
+        vrm: Entity,
+        name: String,
+    ) -> ApiResult<ExpressionPresets> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(presets::delete_preset).with((vrm, name)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Applies one of the persona's expression presets.
+    pub async fn apply_expression_preset(
+        &self,
+        vrm: Entity,
+        name: String,
+        args: ApplyExpressionPreset,
+    ) -> ApiResult {
+        let transition = ExpressionTransition {
+            duration_ms: args.duration_ms,
+            easing: args.easing,
+        };
+        self.0
+            .schedule(move |task| async move {
+                let weights = task
+                    .will(Update, once::run(presets::get_preset).with((vrm, name)))
+                    .await?;
+                let end = if args.replace {
+                    TransitionEnd::Replace(weights.clone())
+                } else {
+                    TransitionEnd::Keep
+                };
+                task.will(
+                    Update,
+                    once::run(start_expression_tween).with((vrm, weights, transition, end)),
+                )
+                .await;
+                Ok(())
+            })
+            .await?
+    }
+
+    /// Returns the persona's auto-blink and micro-expression settings.
+    pub async fn auto_expressions(&self, vrm: Entity) -> ApiResult<AutoExpressionSettings> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(auto::get_settings).with(vrm))
+                    .await
+            })
+            .await?
+    }
+
+    /// Replaces and persists the persona's auto-blink and micro-expression settings.
+    pub async fn set_auto_expressions(
+        &self,
+        vrm: Entity,
+        settings: AutoExpressionSettings,
+    ) -> ApiResult<AutoExpressionSettings> {
+        auto::validate_settings(&settings)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(auto::put_settings).with((vrm, settings)))
+                    .await
+            })
+            .await?
+    }
+}
+
+fn clamp_weights(weights: HashMap<String, f32>) -> HashMap<String, f32> {
+    weights
+        .into_iter()
+        .map(|(k, v)| (k, v.clamp(0.0, 1.0)))
+        .collect()
 }
 
 pub(crate) fn override_type_to_string(ty: &ExpressionOverrideType) -> &'static str {
@@ -139,7 +326,7 @@ fn list_expressions(
     ExpressionsResponse { expressions }
 }
 
-fn do_set_expressions(
+pub(crate) fn do_set_expressions(
     In((vrm, weights)): In<(Entity, HashMap<String, f32>)>,
     mut commands: Commands,
     tx: Option<Res<VrmEventSender<ExpressionChangeEvent>>>,
@@ -149,6 +336,7 @@ fn do_set_expressions(
         .map(|(k, v)| (k, v.clamp(0.0, 1.0)))
         .collect();
 
+    commands.entity(vrm).try_remove::<ExpressionTween>();
     commands.trigger(SetExpressions::from_iter(
         vrm,
         clamped.iter().map(|(k, &v)| (k.as_str(), v)),
@@ -165,7 +353,7 @@ fn do_set_expressions(
     }
 }
 
-fn do_modify_expressions(
+pub(crate) fn do_modify_expressions(
     In((vrm, weights)): In<(Entity, HashMap<String, f32>)>,
     mut commands: Commands,
     tx: Option<Res<VrmEventSender<ExpressionChangeEvent>>>,
@@ -175,6 +363,7 @@ fn do_modify_expressions(
         .map(|(k, v)| (k, v.clamp(0.0, 1.0)))
         .collect();
 
+    commands.entity(vrm).try_remove::<ExpressionTween>();
     commands.trigger(ModifyExpressions::from_iter(
         vrm,
         clamped.iter().map(|(k, &v)| (k.as_str(), v)),
@@ -217,11 +406,12 @@ fn do_modify_mouth(
     }
 }
 
-fn do_clear_expressions(
+pub(crate) fn do_clear_expressions(
     In(vrm): In<Entity>,
     mut commands: Commands,
     tx: Option<Res<VrmEventSender<ExpressionChangeEvent>>>,
 ) {
+    commands.entity(vrm).try_remove::<ExpressionTween>();
     commands.trigger(ClearExpressions { entity: vrm });
 
     if let Some(tx) = tx {
@@ -234,3 +424,68 @@ fn do_clear_expressions(
         .output_log_if_error("Failed to broadcast ExpressionChangeEvent");
     }
 }
+
+/// Starts an expression transition, or applies it at once when it is instant.
+///
+/// Either way, a transition already running on the persona stops where it is.
+fn start_expression_tween(
+    In((vrm, weights, transition, end)): In<(
+        Entity,
+        HashMap<String, f32>,
+        ExpressionTransition,
+        TransitionEnd,
+    )>,
+    mut commands: Commands,
+    maps: Query<&ExpressionEntityMap>,
+    expressions: Query<(&Transform, Option<&ExpressionOverride>)>,
+    tx: Option<Res<VrmEventSender<ExpressionChangeEvent>>>,
+) {
+    let changed = if end == TransitionEnd::Clear {
+        HashMap::new()
+    } else {
+        weights.clone()
+    };
+    if transition.is_instant() {
+        commands.entity(vrm).try_remove::<ExpressionTween>();
+        let pairs = weights.iter().map(|(k, &v)| (k.as_str(), v));
+        match end {
+            TransitionEnd::Keep => commands.trigger(ModifyExpressions::from_iter(vrm, pairs)),
+            TransitionEnd::Replace(_) => commands.trigger(SetExpressions::from_iter(vrm, pairs)),
+            TransitionEnd::Clear => commands.trigger(ClearExpressions { entity: vrm }),
+        }
+    } else {
+        let (current, overridden) = maps
+            .get(vrm)
+            .map(|map| current_weights(map, &expressions))
+            .unwrap_or_default();
+        commands.entity(vrm).try_insert(ExpressionTween::new(
+            weights, &current, overridden, transition, end,
+        ));
+    }
+
+    if let Some(tx) = tx {
+        tx.try_broadcast(VrmEvent {
+            vrm,
+            payload: ExpressionChangeEvent {
+                expressions: changed,
+            },
+        })
+        .output_log_if_error("Failed to broadcast ExpressionChangeEvent");
+    }
+}
+
+pub(super) struct ExpressionsPlugin;
+
+impl Plugin for ExpressionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                load_auto_expressions,
+                advance_expression_tweens,
+                run_auto_expressions,
+            )
+                .chain(),
+        );
+    }
+}
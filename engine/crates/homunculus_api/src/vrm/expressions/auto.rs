@@ -0,0 +1,520 @@
+//! Engine-driven blinking and micro-expressions.
+//!
+//! Blinks are scaled down or skipped while other expressions override blinking, and
+//! micro-expressions wait while the face is already showing an expression or while a
+//! mouth expression is playing that they would override. Once a blink or
+//! micro-expression ends, its override is released (or, for a blink, put back to what
+//! it was) so animations drive the expression again.
+
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use bevy_vrm1::prelude::*;
+use homunculus_core::prelude::Persona;
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use rand::random_range;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+const BLINK: &str = "blink";
+
+/// Expressions that close the eyes; they never block a blink.
+const BLINK_EXPRESSIONS: [&str; 3] = [BLINK, "blinkLeft", "blinkRight"];
+
+/// Mouth shapes driven by lip-sync.
+const MOUTH_EXPRESSIONS: [&str; 5] = ["aa", "ih", "ou", "ee", "oh"];
+
+/// Weights below this count as not showing.
+const VISIBLE_WEIGHT: f32 = 0.01;
+
+/// Auto-blink and micro-expression settings for one persona.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoExpressionSettings {
+    /// Blink at random intervals.
+    pub blink: bool,
+    /// Average number of blinks per minute.
+    pub blinks_per_minute: f32,
+    /// How long one blink takes, in milliseconds.
+    pub blink_duration_ms: u64,
+    /// Flash faint expressions at random intervals.
+    pub micro_expressions: bool,
+    /// Average number of micro-expressions per minute.
+    pub micro_expressions_per_minute: f32,
+    /// Peak weight of a micro-expression (0.0–1.0).
+    pub micro_expression_intensity: f32,
+    /// Expressions a micro-expression picks from.
+    pub micro_expression_names: Vec<String>,
+}
+
+impl Default for AutoExpressionSettings {
+    fn default() -> Self {
+        Self {
+            blink: false,
+            blinks_per_minute: 15.0,
+            blink_duration_ms: 150,
+            micro_expressions: false,
+            micro_expressions_per_minute: 4.0,
+            micro_expression_intensity: 0.25,
+            micro_expression_names: ["happy", "relaxed", "surprised"].map(String::from).to_vec(),
+        }
+    }
+}
+
+pub(super) fn validate_settings(settings: &AutoExpressionSettings) -> ApiResult {
+    for (name, rate) in [
+        ("blinksPerMinute", settings.blinks_per_minute),
+        (
+            "microExpressionsPerMinute",
+            settings.micro_expressions_per_minute,
+        ),
+    ] {
+        if !(0.0..=120.0).contains(&rate) {
+            return Err(ApiError::InvalidInput(format!(
+                "{name} must be between 0 and 120"
+            )));
+        }
+    }
+    if !(50..=1000).contains(&settings.blink_duration_ms) {
+        return Err(ApiError::InvalidInput(
+            "blinkDurationMs must be between 50 and 1000".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&settings.micro_expression_intensity) {
+        return Err(ApiError::InvalidInput(
+            "microExpressionIntensity must be between 0 and 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Where a persona is in its blink and micro-expression schedule.
+#[derive(Component, Debug, Default)]
+pub(super) struct AutoExpressionState {
+    until_blink: f32,
+    /// Seconds into the current blink.
+    blink: Option<f32>,
+    /// Blink weight written last, to notice when something else sets the blink.
+    written_blink: f32,
+    /// The blink override before the current blink started, put back when it ends.
+    blink_restore: Option<f32>,
+    until_micro: f32,
+    micro: Option<Micro>,
+}
+
+#[derive(Debug)]
+struct Micro {
+    name: String,
+    elapsed: f32,
+    duration: f32,
+}
+
+impl AutoExpressionState {
+    fn new(settings: &AutoExpressionSettings) -> Self {
+        Self {
+            until_blink: next_interval(settings.blinks_per_minute),
+            until_micro: next_interval(settings.micro_expressions_per_minute),
+            ..default()
+        }
+    }
+}
+
+/// An expression as the scheduler sees it.
+struct Face {
+    name: String,
+    weight: f32,
+    /// Whether the weight comes from an override rather than the animation.
+    overridden: bool,
+    override_blink: OverrideKind,
+    override_mouth: OverrideKind,
+}
+
+/// A change to one expression's override.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Update {
+    Set(f32),
+    /// Hand the expression back to the animation.
+    Release,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OverrideKind {
+    None,
+    Block,
+    Blend,
+}
+
+impl From<&ExpressionOverrideType> for OverrideKind {
+    fn from(ty: &ExpressionOverrideType) -> Self {
+        match ty {
+            ExpressionOverrideType::None => Self::None,
+            ExpressionOverrideType::Block => Self::Block,
+            ExpressionOverrideType::Blend => Self::Blend,
+        }
+    }
+}
+
+/// Seconds until the next event at `per_minute` on average, jittered by ±50%.
+fn next_interval(per_minute: f32) -> f32 {
+    if per_minute <= 0.0 {
+        return f32::INFINITY;
+    }
+    60.0 / per_minute * random_range(0.5..1.5)
+}
+
+/// Blink weight `t` (0.0–1.0) through a blink: quick to close, slower to open.
+fn blink_curve(t: f32) -> f32 {
+    const CLOSED_AT: f32 = 0.4;
+    if t < CLOSED_AT {
+        t / CLOSED_AT
+    } else {
+        ((1.0 - t) / (1.0 - CLOSED_AT)).max(0.0)
+    }
+}
+
+/// How much of a blink the showing expressions allow, following their `overrideBlink`.
+///
+/// `block` suppresses the blink entirely; `blend` scales it by one minus the weight.
+fn blink_factor(faces: &[Face]) -> f32 {
+    faces
+        .iter()
+        .filter(|face| {
+            VISIBLE_WEIGHT <= face.weight && !BLINK_EXPRESSIONS.contains(&face.name.as_str())
+        })
+        .fold(1.0, |factor, face| match face.override_blink {
+            OverrideKind::None => factor,
+            OverrideKind::Block => 0.0,
+            OverrideKind::Blend => factor * (1.0 - face.weight),
+        })
+}
+
+/// Expressions a micro-expression can use now.
+///
+/// None while another expression is showing. While the mouth is moving, only
+/// expressions that leave the mouth alone qualify.
+fn micro_candidates<'a>(names: &'a [String], faces: &[Face]) -> Vec<&'a str> {
+    let ignored =
+        |name: &str| BLINK_EXPRESSIONS.contains(&name) || MOUTH_EXPRESSIONS.contains(&name);
+    if faces
+        .iter()
+        .any(|face| VISIBLE_WEIGHT <= face.weight && !ignored(&face.name))
+    {
+        return Vec::new();
+    }
+    let speaking = faces.iter().any(|face| {
+        VISIBLE_WEIGHT <= face.weight && MOUTH_EXPRESSIONS.contains(&face.name.as_str())
+    });
+    names
+        .iter()
+        .filter(|name| {
+            faces.iter().any(|face| {
+                &face.name == *name && (!speaking || face.override_mouth == OverrideKind::None)
+            })
+        })
+        .map(String::as_str)
+        .collect()
+}
+
+fn step_blink(
+    settings: &AutoExpressionSettings,
+    state: &mut AutoExpressionState,
+    faces: &[Face],
+    delta: f32,
+) -> Option<Update> {
+    if !settings.blink {
+        return state.blink.take().map(|_| end_blink(state));
+    }
+    if state.blink.is_none() {
+        state.until_blink -= delta;
+        if 0.0 < state.until_blink {
+            return None;
+        }
+        state.until_blink = next_interval(settings.blinks_per_minute);
+        let current = faces.iter().find(|face| face.name == BLINK)?;
+        // Something else owns the eyelids, or the face forbids blinking right now.
+        if VISIBLE_WEIGHT < (current.weight - state.written_blink).abs()
+            || blink_factor(faces) <= 0.0
+        {
+            return None;
+        }
+        state.blink = Some(0.0);
+        state.blink_restore = current.overridden.then_some(current.weight);
+    }
+    let elapsed = state.blink.unwrap_or_default() + delta;
+    let t = elapsed / (settings.blink_duration_ms as f32 / 1000.0);
+    if 1.0 <= t {
+        state.blink = None;
+        return Some(end_blink(state));
+    }
+    state.blink = Some(elapsed);
+    let weight = blink_curve(t) * blink_factor(faces);
+    state.written_blink = weight;
+    Some(Update::Set(weight))
+}
+
+/// Puts the blink override back to what it was before the blink.
+fn end_blink(state: &mut AutoExpressionState) -> Update {
+    match state.blink_restore.take() {
+        Some(weight) => {
+            state.written_blink = weight;
+            Update::Set(weight)
+        }
+        None => {
+            state.written_blink = 0.0;
+            Update::Release
+        }
+    }
+}
+
+fn step_micro(
+    settings: &AutoExpressionSettings,
+    state: &mut AutoExpressionState,
+    faces: &[Face],
+    delta: f32,
+) -> Option<(String, Update)> {
+    if !settings.micro_expressions {
+        return state
+            .micro
+            .take()
+            .map(|micro| (micro.name, Update::Release));
+    }
+    if let Some(micro) = &mut state.micro {
+        micro.elapsed += delta;
+        let t = micro.elapsed / micro.duration;
+        if 1.0 <= t {
+            return state
+                .micro
+                .take()
+                .map(|micro| (micro.name, Update::Release));
+        }
+        let weight = (t * PI).sin() * settings.micro_expression_intensity;
+        return Some((micro.name.clone(), Update::Set(weight)));
+    }
+    state.until_micro -= delta;
+    if 0.0 < state.until_micro {
+        return None;
+    }
+    state.until_micro = next_interval(settings.micro_expressions_per_minute);
+    let candidates = micro_candidates(&settings.micro_expression_names, faces);
+    if candidates.is_empty() {
+        return None;
+    }
+    let name = candidates[random_range(0..candidates.len())].to_string();
+    state.micro = Some(Micro {
+        name: name.clone(),
+        elapsed: 0.0,
+        duration: random_range(0.4..0.8),
+    });
+    Some((name, Update::Set(0.0)))
+}
+
+/// Gives personas their stored settings, or the defaults, once their expressions load.
+pub(super) fn load_auto_expressions(
+    mut commands: Commands,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<(Entity, &Persona), Added<ExpressionEntityMap>>,
+) {
+    for (vrm, persona) in personas.iter() {
+        let settings = prefs
+            .load_as::<AutoExpressionSettings>(&PrefsKeys::auto_expressions(persona.id.as_ref()))
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        commands
+            .entity(vrm)
+            .try_insert((AutoExpressionState::new(&settings), settings));
+    }
+}
+
+/// Advances every persona's blinks and micro-expressions.
+pub(super) fn run_auto_expressions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut personas: Query<(
+        Entity,
+        &AutoExpressionSettings,
+        &mut AutoExpressionState,
+        &ExpressionEntityMap,
+    )>,
+    expressions: Query<(
+        &Transform,
+        Option<&ExpressionOverride>,
+        Option<&ExpressionOverrideSettings>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    for (vrm, settings, mut state, map) in personas.iter_mut() {
+        let faces: Vec<Face> = map
+            .iter()
+            .filter_map(|(name, &entity)| {
+                let (tf, maybe_override, maybe_settings) = expressions.get(entity).ok()?;
+                let (override_blink, override_mouth) = match maybe_settings {
+                    Some(s) => ((&s.override_blink).into(), (&s.override_mouth).into()),
+                    None => (OverrideKind::None, OverrideKind::None),
+                };
+                Some(Face {
+                    name: name.to_string(),
+                    weight: maybe_override.map_or(tf.translation.x, |o| o.0),
+                    overridden: maybe_override.is_some(),
+                    override_blink,
+                    override_mouth,
+                })
+            })
+            .collect();
+
+        let mut updates = Vec::new();
+        if let Some(update) = step_blink(settings, &mut state, &faces, delta) {
+            updates.push((BLINK.to_string(), update));
+        }
+        updates.extend(step_micro(settings, &mut state, &faces, delta));
+        let mut weights = Vec::new();
+        for (name, update) in updates {
+            match update {
+                Update::Set(weight) => weights.push((name, weight)),
+                Update::Release => {
+                    if let Some((_, &entity)) = map.iter().find(|(n, _)| n.to_string() == name) {
+                        commands.entity(entity).try_remove::<ExpressionOverride>();
+                    }
+                }
+            }
+        }
+        if !weights.is_empty() {
+            commands.trigger(ModifyExpressions::from_iter(
+                vrm,
+                weights
+                    .iter()
+                    .map(|(name, weight)| (name.as_str(), *weight)),
+            ));
+        }
+    }
+}
+
+pub(super) fn get_settings(
+    In(vrm): In<Entity>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<(&Persona, Option<&AutoExpressionSettings>)>,
+) -> ApiResult<AutoExpressionSettings> {
+    let (persona, settings) = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    if let Some(settings) = settings {
+        return Ok(settings.clone());
+    }
+    prefs
+        .load_as::<AutoExpressionSettings>(&PrefsKeys::auto_expressions(persona.id.as_ref()))
+        .map(Option::unwrap_or_default)
+        .map_err(|e| ApiError::FailedLoad(e.to_string()))
+}
+
+pub(super) fn put_settings(
+    In((vrm, settings)): In<(Entity, AutoExpressionSettings)>,
+    mut commands: Commands,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<(&Persona, Has<AutoExpressionState>)>,
+) -> ApiResult<AutoExpressionSettings> {
+    let (persona, scheduled) = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    prefs
+        .save_as(&PrefsKeys::auto_expressions(persona.id.as_ref()), &settings)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))?;
+    if !scheduled {
+        commands
+            .entity(vrm)
+            .try_insert(AutoExpressionState::new(&settings));
+    }
+    commands.entity(vrm).try_insert(settings.clone());
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn face(name: &str, weight: f32, blink: OverrideKind, mouth: OverrideKind) -> Face {
+        Face {
+            name: name.to_string(),
+            weight,
+            overridden: false,
+            override_blink: blink,
+            override_mouth: mouth,
+        }
+    }
+
+    #[test]
+    fn blinks_close_fast_and_open_slowly() {
+        assert_eq!(blink_curve(0.0), 0.0);
+        assert_eq!(blink_curve(0.4), 1.0);
+        assert!((blink_curve(0.7) - 0.5).abs() < 1e-5);
+        assert_eq!(blink_curve(1.0), 0.0);
+    }
+
+    #[test]
+    fn blinks_follow_override_blink() {
+        use OverrideKind::{Blend, Block, None as Free};
+        let blended = [
+            face("happy", 0.6, Blend, Free),
+            face("blink", 1.0, Block, Free),
+            face("sad", 0.0, Block, Free),
+        ];
+        assert!((blink_factor(&blended) - 0.4).abs() < 1e-5);
+
+        let blocked = [face("surprised", 0.3, Block, Free)];
+        assert_eq!(blink_factor(&blocked), 0.0);
+    }
+
+    #[test]
+    fn micro_expressions_wait_for_a_neutral_face_and_spare_speech() {
+        use OverrideKind::{Blend, Block, None as Free};
+        let names = ["happy", "surprised"].map(String::from).to_vec();
+
+        let neutral = [
+            face("happy", 0.0, Free, Block),
+            face("surprised", 0.0, Free, Free),
+            face("blink", 1.0, Free, Free),
+        ];
+        assert_eq!(
+            micro_candidates(&names, &neutral),
+            vec!["happy", "surprised"]
+        );
+
+        let speaking = [
+            face("happy", 0.0, Free, Block),
+            face("surprised", 0.0, Free, Free),
+            face("aa", 0.8, Free, Free),
+        ];
+        assert_eq!(micro_candidates(&names, &speaking), vec!["surprised"]);
+
+        let smiling = [
+            face("happy", 0.5, Free, Free),
+            face("surprised", 0.0, Free, Free),
+        ];
+        assert!(micro_candidates(&names, &smiling).is_empty());
+    }
+
+    #[test]
+    fn finished_blinks_release_or_restore_the_override() {
+        use OverrideKind::None as Free;
+        let settings = AutoExpressionSettings {
+            blink: true,
+            ..default()
+        };
+        let mut state = AutoExpressionState::default();
+        let open = [face("blink", 0.0, Free, Free)];
+        assert!(matches!(
+            step_blink(&settings, &mut state, &open, 0.0),
+            Some(Update::Set(_))
+        ));
+        assert_eq!(
+            step_blink(&settings, &mut state, &open, 1.0),
+            Some(Update::Release)
+        );
+
+        let mut state = AutoExpressionState::default();
+        let held_open = [Face {
+            overridden: true,
+            ..face("blink", 0.0, Free, Free)
+        }];
+        step_blink(&settings, &mut state, &held_open, 0.0);
+        assert_eq!(
+            step_blink(&settings, &mut state, &held_open, 1.0),
+            Some(Update::Set(0.0))
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Named combinations of expression weights, stored per persona.
+
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use homunculus_core::prelude::Persona;
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use std::collections::HashMap;
+
+/// Preset name to expression weights, e.g. `{"smug": {"happy": 0.4, "blinkLeft": 1.0}}`.
+pub type ExpressionPresets = HashMap<String, HashMap<String, f32>>;
+
+/// Maximum length of a preset name.
+const MAX_NAME_LEN: usize = 64;
+
+pub(super) fn validate_preset(name: &str, weights: &HashMap<String, f32>) -> ApiResult {
+    if name.is_empty() || MAX_NAME_LEN < name.len() {
+        return Err(ApiError::InvalidInput(format!(
+            "preset name must be 1 to {MAX_NAME_LEN} characters"
+        )));
+    }
+    if weights.values().any(|weight| !(0.0..=1.0).contains(weight)) {
+        return Err(ApiError::InvalidInput(
+            "expression weights must be between 0 and 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn load_presets(prefs: &PrefsDatabase, persona: &Persona) -> ApiResult<ExpressionPresets> {
+    prefs
+        .load_as::<ExpressionPresets>(&PrefsKeys::expression_presets(persona.id.as_ref()))
+        .map(Option::unwrap_or_default)
+        .map_err(|e| ApiError::FailedLoad(e.to_string()))
+}
+
+fn save_presets(
+    prefs: &PrefsDatabase,
+    persona: &Persona,
+    presets: &ExpressionPresets,
+) -> ApiResult {
+    prefs
+        .save_as(&PrefsKeys::expression_presets(persona.id.as_ref()), presets)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))
+}
+
+pub(super) fn get_presets(
+    In(vrm): In<Entity>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<ExpressionPresets> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    load_presets(&prefs, persona)
+}
+
+/// Returns the weights of one preset.
+pub(super) fn get_preset(
+    In((vrm, name)): In<(Entity, String)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<HashMap<String, f32>> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    load_presets(&prefs, persona)?
+        .remove(&name)
+        .ok_or(ApiError::ExpressionPresetNotFound(name))
+}
+
+pub(super) fn put_preset(
+    In((vrm, name, weights)): In<(Entity, String, HashMap<String, f32>)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<ExpressionPresets> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mut presets = load_presets(&prefs, persona)?;
+    presets.insert(name, weights);
+    save_presets(&prefs, persona, &presets)?;
+    Ok(presets)
+}
+
+pub(super) fn delete_preset(
+    In((vrm, name)): In<(Entity, String)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<ExpressionPresets> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mut presets = load_presets(&prefs, persona)?;
+    if presets.remove(&name).is_none() {
+        return Err(ApiError::ExpressionPresetNotFound(name));
+    }
+    save_presets(&prefs, persona, &presets)?;
+    Ok(presets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_need_a_name_and_unit_weights() {
+        let smug = HashMap::from([("happy".to_string(), 0.4), ("blinkLeft".to_string(), 1.0)]);
+        assert!(validate_preset("smug", &smug).is_ok());
+        assert!(validate_preset("", &smug).is_err());
+        assert!(validate_preset(&"x".repeat(65), &smug).is_err());
+
+        let too_much = HashMap::from([("happy".to_string(), 1.5)]);
+        assert!(validate_preset("smug", &too_much).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Eased transitions between expression weights.
+
+use crate::entities::tween::EasingFunction;
+use bevy::math::curve::easing::EaseFunction;
+use bevy::prelude::*;
+use bevy_vrm1::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How expression weights move to their new values.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ExpressionTransition {
+    /// Duration of the transition in milliseconds; `0` changes the weights instantly.
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+}
+
+impl ExpressionTransition {
+    pub(crate) fn is_instant(&self) -> bool {
+        self.duration_ms == 0
+    }
+}
+
+/// What a transition leaves behind once the weights have arrived.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TransitionEnd {
+    /// Keep every override, as `modify` does.
+    Keep,
+    /// Keep only these weights, as `set` does.
+    Replace(HashMap<String, f32>),
+    /// Return every expression to animation control.
+    Clear,
+}
+
+/// Expression weights moving from where they were to their targets.
+#[derive(Component, Debug)]
+pub(crate) struct ExpressionTween {
+    from: HashMap<String, f32>,
+    to: HashMap<String, f32>,
+    elapsed: f32,
+    duration: f32,
+    ease: EaseFunction,
+    end: TransitionEnd,
+}
+
+impl ExpressionTween {
+    /// Moves `to`'s expressions from their `current` weights. With [`TransitionEnd::Replace`]
+    /// and [`TransitionEnd::Clear`], expressions in `overridden` that are not in `to` fade
+    /// to zero.
+    pub(crate) fn new(
+        to: HashMap<String, f32>,
+        current: &HashMap<String, f32>,
+        overridden: impl IntoIterator<Item = String>,
+        transition: ExpressionTransition,
+        end: TransitionEnd,
+    ) -> Self {
+        let mut to = to;
+        if !matches!(end, TransitionEnd::Keep) {
+            for name in overridden {
+                to.entry(name).or_insert(0.0);
+            }
+        }
+        let from = to
+            .keys()
+            .map(|name| (name.clone(), current.get(name).copied().unwrap_or(0.0)))
+            .collect();
+        Self {
+            from,
+            to,
+            elapsed: 0.0,
+            duration: transition.duration_ms as f32 / 1000.0,
+            ease: transition.easing.into(),
+            end,
+        }
+    }
+
+    /// Advances the transition and returns the weights to apply, and whether it is done.
+    fn advance(&mut self, delta: f32) -> (HashMap<String, f32>, bool) {
+        self.elapsed += delta;
+        let t = if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        };
+        let eased = self.ease.sample_clamped(t);
+        let weights = self
+            .to
+            .iter()
+            .map(|(name, to)| {
+                let from = self.from.get(name).copied().unwrap_or(0.0);
+                (name.clone(), (from + (to - from) * eased).clamp(0.0, 1.0))
+            })
+            .collect();
+        (weights, 1.0 <= t)
+    }
+}
+
+/// Current weight of every expression, and the names of those with an override.
+pub(crate) fn current_weights(
+    map: &ExpressionEntityMap,
+    expressions: &Query<(&Transform, Option<&ExpressionOverride>)>,
+) -> (HashMap<String, f32>, Vec<String>) {
+    let mut weights = HashMap::new();
+    let mut overridden = Vec::new();
+    for (name, &entity) in map.iter() {
+        let Ok((tf, maybe_override)) = expressions.get(entity) else {
+            continue;
+        };
+        let weight = match maybe_override {
+            Some(ExpressionOverride(w)) => {
+                overridden.push(name.to_string());
+                *w
+            }
+            None => tf.translation.x,
+        };
+        weights.insert(name.to_string(), weight.clamp(0.0, 1.0));
+    }
+    (weights, overridden)
+}
+
+/// Applies every running transition's weights, and finishes those that have arrived.
+pub(crate) fn advance_expression_tweens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tweens: Query<(Entity, &mut ExpressionTween)>,
+) {
+    for (vrm, mut tween) in tweens.iter_mut() {
+        let (weights, finished) = tween.advance(time.delta_secs());
+        commands.trigger(ModifyExpressions::from_iter(
+            vrm,
+            weights
+                .iter()
+                .map(|(name, weight)| (name.as_str(), *weight)),
+        ));
+        if !finished {
+            continue;
+        }
+        match &tween.end {
+            TransitionEnd::Keep => {}
+            TransitionEnd::Replace(weights) => {
+                commands.trigger(SetExpressions::from_iter(
+                    vrm,
+                    weights
+                        .iter()
+                        .map(|(name, weight)| (name.as_str(), *weight)),
+                ));
+            }
+            TransitionEnd::Clear => {
+                commands.trigger(ClearExpressions { entity: vrm });
+            }
+        }
+        commands.entity(vrm).try_remove::<ExpressionTween>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights(entries: &[(&str, f32)]) -> HashMap<String, f32> {
+        entries
+            .iter()
+            .map(|(name, weight)| (name.to_string(), *weight))
+            .collect()
+    }
+
+    #[test]
+    fn weights_ease_from_current_to_target() {
+        let mut tween = ExpressionTween::new(
+            weights(&[("happy", 1.0)]),
+            &weights(&[("happy", 0.2), ("sad", 0.5)]),
+            ["sad".to_string()],
+            ExpressionTransition {
+                duration_ms: 1000,
+                easing: EasingFunction::Linear,
+            },
+            TransitionEnd::Keep,
+        );
+        let (halfway, finished) = tween.advance(0.5);
+        assert!((halfway["happy"] - 0.6).abs() < 1e-5);
+        assert!(!halfway.contains_key("sad"));
+        assert!(!finished);
+
+        let (end, finished) = tween.advance(0.6);
+        assert_eq!(end["happy"], 1.0);
+        assert!(finished);
+    }
+
+    #[test]
+    fn replacing_fades_other_overrides_out() {
+        let mut tween = ExpressionTween::new(
+            weights(&[("happy", 1.0)]),
+            &weights(&[("sad", 0.8)]),
+            ["sad".to_string()],
+            ExpressionTransition {
+                duration_ms: 200,
+                easing: EasingFunction::QuadraticIn,
+            },
+            TransitionEnd::Replace(weights(&[("happy", 1.0)])),
+        );
+        let (start, _) = tween.advance(0.0);
+        assert_eq!(start, weights(&[("happy", 0.0), ("sad", 0.8)]));
+        let (end, finished) = tween.advance(0.2);
+        assert_eq!(end, weights(&[("happy", 1.0), ("sad", 0.0)]));
+        assert!(finished);
+    }
+}
@@ -0,0 +1,616 @@
+//! Procedural gestures: short bone motions generated on the fly and played through the
+//! pose layer.
+//!
+//! Each gesture is a function of its progress, so it needs no animation asset and adapts
+//! to any duration and intensity. Bones the model lacks (e.g. optional shoulders) are
+//! skipped.
+
+use crate::entities::tween::EasingFunction;
+use crate::error::{ApiError, ApiResult};
+use crate::vrm::VrmApi;
+use crate::vrm::pose::{BonePose, BoneRotation, Pose, PoseChange, PoseTransition, apply_change};
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use bevy_vrm1::prelude::ChildSearcher;
+use bevy_vrm1::vrm::VrmBone;
+use homunculus_core::prelude::PersonaState;
+use homunculus_drag::physics::FALLING_STATE;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::{PI, TAU};
+
+/// Shortest and longest gesture, in milliseconds.
+const DURATION_RANGE_MS: std::ops::RangeInclusive<u64> = 100..=10_000;
+/// How bones ease back to their previous rotation when a gesture ends.
+const RETURN_TRANSITION: PoseTransition = PoseTransition {
+    duration_ms: 250,
+    easing: EasingFunction::Linear,
+};
+/// Share of a gesture spent easing in, and again easing out.
+const EASE_SHARE: f32 = 0.2;
+
+/// A procedural gesture.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum Gesture {
+    /// Nods twice.
+    Nod,
+    /// Shakes the head from side to side.
+    HeadShake,
+    /// Raises both shoulders and tilts the head.
+    Shrug,
+    /// Raises the right arm and waves.
+    Wave,
+    /// Bows from the waist.
+    Bow,
+    /// Crouches, hops up and lands.
+    Jump,
+    /// Tilts the head curiously.
+    Tilt,
+}
+
+/// Request arguments for performing a gesture.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PerformGesture {
+    /// How pronounced the gesture is, from `0` to `2`. Defaults to `1`.
+    #[serde(default = "default_intensity")]
+    pub intensity: f32,
+    /// Length of the gesture in milliseconds (100-10000). Defaults to the gesture's own.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Resolve only once the gesture ends.
+    #[serde(default)]
+    pub wait: bool,
+}
+
+impl Default for PerformGesture {
+    fn default() -> Self {
+        Self {
+            intensity: default_intensity(),
+            duration_ms: None,
+            wait: false,
+        }
+    }
+}
+
+fn default_intensity() -> f32 {
+    1.0
+}
+
+fn validate_args(args: &PerformGesture) -> ApiResult {
+    if !(0.0..=2.0).contains(&args.intensity) {
+        return Err(ApiError::InvalidInput(
+            "intensity must be between 0 and 2".to_string(),
+        ));
+    }
+    if args
+        .duration_ms
+        .is_some_and(|ms| !DURATION_RANGE_MS.contains(&ms))
+    {
+        return Err(ApiError::InvalidInput(
+            "durationMs must be between 100 and 10000".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// A gesture at one moment: bone rotations in the persona's space, in degrees, and how
+/// far the persona is lifted off its position, in meters.
+#[derive(Debug, Clone, PartialEq)]
+struct GestureFrame {
+    rotations: Vec<(&'static str, [f32; 3])>,
+    lift: f32,
+}
+
+impl Gesture {
+    fn default_duration_ms(self) -> u64 {
+        match self {
+            Gesture::Nod => 1000,
+            Gesture::HeadShake => 1200,
+            Gesture::Shrug => 1200,
+            Gesture::Wave => 2000,
+            Gesture::Bow => 1800,
+            Gesture::Jump => 900,
+            Gesture::Tilt => 1500,
+        }
+    }
+
+    /// Humanoid bones the gesture moves.
+    fn bones(self) -> impl Iterator<Item = &'static str> {
+        self.sample(0.5, 1.0)
+            .rotations
+            .into_iter()
+            .map(|(bone, _)| bone)
+    }
+
+    /// The gesture at `progress`, from `0` (start) to `1` (end). Every bone is at rest
+    /// at both ends.
+    fn sample(self, progress: f32, intensity: f32) -> GestureFrame {
+        let p = progress.clamp(0.0, 1.0);
+        let env = envelope(p);
+        let swing = |cycles: f32| (TAU * cycles * p).sin();
+        let mut lift = 0.0;
+        let rotations = match self {
+            Gesture::Nod => {
+                let pitch = 18.0 * intensity * (TAU * p).sin().powi(2);
+                vec![
+                    ("head", [pitch, 0.0, 0.0]),
+                    ("neck", [pitch * 0.4, 0.0, 0.0]),
+                ]
+            }
+            Gesture::HeadShake => {
+                let yaw = 20.0 * intensity * swing(3.0) * env;
+                vec![("head", [0.0, yaw, 0.0]), ("neck", [0.0, yaw * 0.4, 0.0])]
+            }
+            Gesture::Shrug => {
+                let up = 14.0 * intensity * env;
+                vec![
+                    ("leftShoulder", [0.0, 0.0, up]),
+                    ("rightShoulder", [0.0, 0.0, -up]),
+                    ("head", [0.0, 0.0, up * 0.4]),
+                ]
+            }
+            Gesture::Wave => {
+                let raise = -110.0 * env;
+                let hand = (-30.0 + 25.0 * intensity * swing(3.0)) * env;
+                vec![
+                    ("rightUpperArm", [0.0, 0.0, raise]),
+                    ("rightLowerArm", [0.0, 0.0, hand]),
+                ]
+            }
+            Gesture::Bow => {
+                let bend = 35.0 * intensity * env;
+                vec![
+                    ("spine", [bend * 0.6, 0.0, 0.0]),
+                    ("chest", [bend * 0.4, 0.0, 0.0]),
+                    ("head", [bend * 0.3, 0.0, 0.0]),
+                ]
+            }
+            Gesture::Jump => {
+                let crouch = bump(p, 0.0, 0.3) + 0.6 * bump(p, 0.75, 1.0);
+                let air = bump(p, 0.25, 0.8);
+                lift = 0.2 * intensity * air;
+                let knee = 60.0 * intensity * crouch;
+                let arms = 35.0 * intensity * air;
+                vec![
+                    ("leftUpperLeg", [-knee * 0.6, 0.0, 0.0]),
+                    ("rightUpperLeg", [-knee * 0.6, 0.0, 0.0]),
+                    ("leftLowerLeg", [knee, 0.0, 0.0]),
+                    ("rightLowerLeg", [knee, 0.0, 0.0]),
+                    ("spine", [knee * 0.25, 0.0, 0.0]),
+                    ("leftUpperArm", [0.0, 0.0, arms]),
+                    ("rightUpperArm", [0.0, 0.0, -arms]),
+                ]
+            }
+            Gesture::Tilt => {
+                let roll = 15.0 * intensity * env;
+                vec![("head", [0.0, 0.0, roll]), ("neck", [0.0, 0.0, roll * 0.3])]
+            }
+        };
+        GestureFrame { rotations, lift }
+    }
+}
+
+/// Eases in over the first part of a gesture, holds, and eases out over the last.
+fn envelope(p: f32) -> f32 {
+    let smooth = |x: f32| {
+        let x = x.clamp(0.0, 1.0);
+        x * x * (3.0 - 2.0 * x)
+    };
+    smooth(p / EASE_SHARE) * smooth((1.0 - p) / EASE_SHARE)
+}
+
+/// A half sine rising from `0` at `start` to `1` and back to `0` at `end`.
+fn bump(p: f32, start: f32, end: f32) -> f32 {
+    if p <= start || end <= p {
+        return 0.0;
+    }
+    (PI * (p - start) / (end - start)).sin()
+}
+
+impl VrmApi {
+    /// Plays a procedural gesture on the specified VRM entity, replacing a gesture in
+    /// progress. Bones return to their previous rotation when it ends.
+    pub async fn perform_gesture(
+        &self,
+        vrm: Entity,
+        gesture: Gesture,
+        args: PerformGesture,
+    ) -> ApiResult {
+        validate_args(&args)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(start_gesture).with((vrm, gesture, args)))
+                    .await?;
+                if args.wait {
+                    task.will(Update, wait::until(gesture_ended).with(vrm))
+                        .await;
+                }
+                Ok(())
+            })
+            .await?
+    }
+}
+
+/// A gesture being performed by a VRM.
+#[derive(Component, Debug)]
+pub(crate) struct Performing {
+    gesture: Gesture,
+    intensity: f32,
+    duration: f32,
+    elapsed: f32,
+    /// Bones the gesture moves on this model, with the rotation set through the API
+    /// before the gesture started.
+    bones: HashMap<&'static str, Option<BoneRotation>>,
+    /// How far the gesture should lift the persona now.
+    lift: f32,
+    /// How far the persona has been lifted.
+    lifted: f32,
+    finished: bool,
+}
+
+pub(super) struct GesturePlugin;
+
+impl Plugin for GesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (perform_gestures, lift_performers).chain())
+            .add_observer(drop_lift);
+    }
+}
+
+fn start_gesture(
+    In((vrm, gesture, args)): In<(Entity, Gesture, PerformGesture)>,
+    mut commands: Commands,
+    mut performers: Query<&mut Performing>,
+    mut poses: Query<&mut Pose>,
+    searcher: ChildSearcher,
+) -> ApiResult {
+    let bones: Vec<&'static str> = gesture
+        .bones()
+        .filter(|bone| {
+            searcher
+                .find_by_bone_name(vrm, &VrmBone(bone.to_string()))
+                .is_some()
+        })
+        .collect();
+    if bones.is_empty() {
+        return Err(ApiError::EntityNotFound);
+    }
+    let targets = poses.get(vrm).map(Pose::targets).unwrap_or_default();
+    let mut previous: HashMap<&'static str, Option<BoneRotation>> = bones
+        .iter()
+        .map(|&bone| (bone, targets.get(bone).copied()))
+        .collect();
+    let duration = args.duration_ms.unwrap_or(gesture.default_duration_ms()) as f32 / 1000.0;
+
+    let Ok(mut performing) = performers.get_mut(vrm) else {
+        commands.entity(vrm).try_insert(Performing {
+            gesture,
+            intensity: args.intensity,
+            duration,
+            elapsed: 0.0,
+            bones: previous,
+            lift: 0.0,
+            lifted: 0.0,
+            finished: false,
+        });
+        return Ok(());
+    };
+    // Keep what the bones held before the interrupted gesture, and return the bones
+    // it moved that the new gesture does not.
+    let mut released = HashMap::new();
+    for (bone, before) in performing.bones.drain() {
+        match previous.get_mut(bone) {
+            Some(slot) => *slot = before,
+            None => {
+                released.insert(bone, before);
+            }
+        }
+    }
+    if let Ok(mut pose) = poses.get_mut(vrm) {
+        restore_bones(&mut pose, vrm, released, &searcher)?;
+    }
+    *performing = Performing {
+        gesture,
+        intensity: args.intensity,
+        duration,
+        elapsed: 0.0,
+        bones: previous,
+        lift: 0.0,
+        lifted: performing.lifted,
+        finished: false,
+    };
+    Ok(())
+}
+
+fn gesture_ended(In(vrm): In<Entity>, performers: Query<(), With<Performing>>) -> bool {
+    !performers.contains(vrm)
+}
+
+/// Advances every gesture and sets the rotations of its bones in the pose.
+fn perform_gestures(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut performers: Query<(Entity, &mut Performing)>,
+    mut poses: Query<&mut Pose>,
+    searcher: ChildSearcher,
+) {
+    for (vrm, mut performing) in performers.iter_mut() {
+        if performing.finished {
+            continue;
+        }
+        performing.elapsed += time.delta_secs();
+        let progress = (performing.elapsed / performing.duration).min(1.0);
+        let mut new_pose = None;
+        let pose = match poses.get_mut(vrm) {
+            Ok(pose) => pose.into_inner(),
+            Err(_) => new_pose.insert(Pose::default()),
+        };
+        let result = if progress < 1.0 {
+            let frame = performing.gesture.sample(progress, performing.intensity);
+            performing.lift = frame.lift;
+            let bones: BonePose = frame
+                .rotations
+                .into_iter()
+                .filter(|(bone, _)| performing.bones.contains_key(bone))
+                .map(|(bone, rotation)| {
+                    let rotation = BoneRotation {
+                        rotation,
+                        weight: 1.0,
+                    };
+                    (bone.to_string(), rotation)
+                })
+                .collect();
+            apply_change(
+                pose,
+                vrm,
+                PoseChange::Bones(bones),
+                PoseTransition::default(),
+                &searcher,
+            )
+            .map(|_| ())
+        } else {
+            performing.lift = 0.0;
+            performing.finished = true;
+            restore_bones(pose, vrm, performing.bones.drain().collect(), &searcher)
+        };
+        if let Err(e) = result {
+            warn!("Failed to perform gesture {:?}: {e}", performing.gesture);
+            performing.finished = true;
+        }
+        if let Some(pose) = new_pose
+            && !pose.is_empty()
+        {
+            commands.entity(vrm).try_insert(pose);
+        }
+    }
+}
+
+/// Returns bones to the rotation they held before the gesture, or to the animation.
+fn restore_bones(
+    pose: &mut Pose,
+    vrm: Entity,
+    bones: HashMap<&'static str, Option<BoneRotation>>,
+    searcher: &ChildSearcher,
+) -> ApiResult {
+    let mut held = BonePose::new();
+    for (bone, before) in bones {
+        match before {
+            Some(rotation) => {
+                held.insert(bone.to_string(), rotation);
+            }
+            None => {
+                apply_change(
+                    pose,
+                    vrm,
+                    PoseChange::Clear(Some(bone.to_string())),
+                    RETURN_TRANSITION,
+                    searcher,
+                )?;
+            }
+        }
+    }
+    apply_change(
+        pose,
+        vrm,
+        PoseChange::Bones(held),
+        RETURN_TRANSITION,
+        searcher,
+    )?;
+    Ok(())
+}
+
+/// Lifts performing personas off their position, and ends finished gestures once the
+/// persona is back down.
+///
+/// The lift is an offset on top of wherever the persona is. Dragging and falling own
+/// the position, so the offset is dropped while they last.
+fn lift_performers(
+    mut commands: Commands,
+    mut performers: Query<(
+        Entity,
+        &mut Performing,
+        &mut Transform,
+        Option<&PersonaState>,
+    )>,
+) {
+    for (vrm, mut performing, mut tf, state) in performers.iter_mut() {
+        if state.is_some_and(|s| matches!(s.0.as_str(), PersonaState::DRAG | FALLING_STATE)) {
+            performing.lifted = 0.0;
+        } else {
+            tf.translation.y += performing.lift - performing.lifted;
+            performing.lifted = performing.lift;
+        }
+        if performing.finished {
+            commands.entity(vrm).try_remove::<Performing>();
+        }
+    }
+}
+
+/// Puts a persona back down when its gesture is cancelled mid-air.
+fn drop_lift(
+    trigger: On<Remove, Performing>,
+    performers: Query<&Performing>,
+    mut transforms: Query<&mut Transform>,
+) {
+    if let Ok(performing) = performers.get(trigger.entity)
+        && performing.lifted != 0.0
+        && let Ok(mut tf) = transforms.get_mut(trigger.entity)
+    {
+        tf.translation.y -= performing.lifted;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotation(frame: &GestureFrame, bone: &str) -> [f32; 3] {
+        frame
+            .rotations
+            .iter()
+            .find(|(name, _)| *name == bone)
+            .map(|(_, rotation)| *rotation)
+            .unwrap()
+    }
+
+    fn assert_near(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn every_gesture_starts_and_ends_at_rest() {
+        for gesture in [
+            Gesture::Nod,
+            Gesture::HeadShake,
+            Gesture::Shrug,
+            Gesture::Wave,
+            Gesture::Bow,
+            Gesture::Jump,
+            Gesture::Tilt,
+        ] {
+            for progress in [0.0, 1.0] {
+                let frame = gesture.sample(progress, 2.0);
+                assert_near(frame.lift, 0.0);
+                for (_, rotation) in frame.rotations {
+                    rotation.iter().for_each(|&v| assert_near(v, 0.0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nod_dips_the_head_twice() {
+        assert_near(rotation(&Gesture::Nod.sample(0.25, 1.0), "head")[0], 18.0);
+        assert_near(rotation(&Gesture::Nod.sample(0.5, 1.0), "head")[0], 0.0);
+        assert_near(rotation(&Gesture::Nod.sample(0.75, 0.5), "head")[0], 9.0);
+        assert_near(rotation(&Gesture::Nod.sample(0.75, 0.5), "neck")[0], 3.6);
+    }
+
+    #[test]
+    fn head_shake_swings_both_ways() {
+        let right = rotation(&Gesture::HeadShake.sample(0.25, 1.0), "head")[1];
+        let left = rotation(&Gesture::HeadShake.sample(0.42, 1.0), "head")[1];
+        assert_near(right, -20.0);
+        assert!(19.0 < left, "{left}");
+    }
+
+    #[test]
+    fn bow_holds_and_scales_with_intensity() {
+        assert_near(rotation(&Gesture::Bow.sample(0.5, 1.0), "spine")[0], 21.0);
+        assert_near(rotation(&Gesture::Bow.sample(0.6, 2.0), "spine")[0], 42.0);
+        assert_near(rotation(&Gesture::Bow.sample(0.1, 1.0), "spine")[0], 10.5);
+    }
+
+    #[test]
+    fn wave_raises_the_right_arm() {
+        let frame = Gesture::Wave.sample(0.5, 1.0);
+        assert_near(rotation(&frame, "rightUpperArm")[2], -110.0);
+        assert_near(rotation(&frame, "rightLowerArm")[2], -30.0);
+        assert!(
+            frame
+                .rotations
+                .iter()
+                .all(|(bone, _)| bone.starts_with("right"))
+        );
+    }
+
+    #[test]
+    fn jump_crouches_then_lifts() {
+        let crouch = Gesture::Jump.sample(0.15, 1.0);
+        assert_near(rotation(&crouch, "leftLowerLeg")[0], 60.0);
+        assert_near(crouch.lift, 0.0);
+        let air = Gesture::Jump.sample(0.525, 1.0);
+        assert_near(air.lift, 0.2);
+        assert_near(rotation(&air, "leftLowerLeg")[0], 0.0);
+    }
+
+    #[test]
+    fn args_are_validated() {
+        assert!(validate_args(&PerformGesture::default()).is_ok());
+        let loud = PerformGesture {
+            intensity: 2.5,
+            ..Default::default()
+        };
+        assert!(validate_args(&loud).is_err());
+        let long = PerformGesture {
+            duration_ms: Some(60_000),
+            ..Default::default()
+        };
+        assert!(validate_args(&long).is_err());
+    }
+
+    #[test]
+    fn gestures_deserialize_from_camel_case() {
+        let gesture: Gesture = serde_json::from_str("\"headShake\"").unwrap();
+        assert_eq!(gesture, Gesture::HeadShake);
+    }
+
+    #[test]
+    fn lift_is_dropped_while_dragged_and_on_cancel() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut app = App::new();
+        app.add_observer(drop_lift);
+        let vrm = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                Performing {
+                    gesture: Gesture::Jump,
+                    intensity: 1.0,
+                    duration: 1.0,
+                    elapsed: 0.5,
+                    bones: HashMap::new(),
+                    lift: 0.2,
+                    lifted: 0.0,
+                    finished: false,
+                },
+            ))
+            .id();
+        let y = |app: &App| app.world().get::<Transform>(vrm).unwrap().translation.y;
+
+        app.world_mut().run_system_once(lift_performers).unwrap();
+        assert_near(y(&app), 1.2);
+
+        // Dragging moves the persona to y = 3; the lift must not be taken off it.
+        app.world_mut().entity_mut(vrm).insert((
+            Transform::from_xyz(0.0, 3.0, 0.0),
+            PersonaState::from(PersonaState::DRAG),
+        ));
+        app.world_mut().run_system_once(lift_performers).unwrap();
+        assert_near(y(&app), 3.0);
+
+        app.world_mut()
+            .entity_mut(vrm)
+            .insert(PersonaState::default());
+        app.world_mut().run_system_once(lift_performers).unwrap();
+        assert_near(y(&app), 3.2);
+
+        app.world_mut().entity_mut(vrm).remove::<Performing>();
+        assert_near(y(&app), 3.0);
+    }
+}
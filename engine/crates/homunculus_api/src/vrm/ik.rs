@@ -0,0 +1,432 @@
+//! Two-bone inverse kinematics for a persona's hands and feet.
+//!
+//! Each target bends the upper and lower bone of an arm or leg so the hand or foot
+//! reaches it, blended by weight over the playing animation.
+
+use crate::error::{ApiError, ApiResult};
+use crate::vrm::VrmApi;
+use crate::vrm::bone_layer::{
+    BoneLayer, BoneLayerSystems, animated_global, apply_layer, base_rotation, release_layers,
+};
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use bevy_vrm1::prelude::ChildSearcher;
+use bevy_vrm1::vrm::VrmBone;
+use homunculus_core::prelude::{Coordinate, GlobalViewport};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Where a hand or foot reaches to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum IkPoint {
+    /// A point in world space.
+    World([f32; 3]),
+    /// A point on the screen, in global viewport coordinates, at the depth of the
+    /// shoulder or hip.
+    Viewport(GlobalViewport),
+}
+
+/// An inverse kinematics target for one hand or foot.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct IkTarget {
+    pub position: IkPoint,
+    /// How much of the solved pose is blended over the animation, from `0` to `1`.
+    #[serde(default = "full_weight")]
+    pub weight: f32,
+    /// World-space point the elbow or knee bends toward. Defaults to the way it
+    /// already bends.
+    #[serde(default)]
+    pub pole: Option<[f32; 3]>,
+}
+
+fn full_weight() -> f32 {
+    1.0
+}
+
+/// Inverse kinematics targets of a persona; limbs without a target follow the animation.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct IkTargets {
+    pub left_hand: Option<IkTarget>,
+    pub right_hand: Option<IkTarget>,
+    pub left_foot: Option<IkTarget>,
+    pub right_foot: Option<IkTarget>,
+}
+
+impl IkTargets {
+    fn limbs(&self) -> [(Limb, Option<&IkTarget>); 4] {
+        [
+            (Limb::LeftHand, self.left_hand.as_ref()),
+            (Limb::RightHand, self.right_hand.as_ref()),
+            (Limb::LeftFoot, self.left_foot.as_ref()),
+            (Limb::RightFoot, self.right_foot.as_ref()),
+        ]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.limbs().iter().all(|(_, target)| target.is_none())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Limb {
+    LeftHand,
+    RightHand,
+    LeftFoot,
+    RightFoot,
+}
+
+impl Limb {
+    /// The upper, lower and end bone of the limb.
+    fn bones(self) -> [&'static str; 3] {
+        match self {
+            Limb::LeftHand => ["leftUpperArm", "leftLowerArm", "leftHand"],
+            Limb::RightHand => ["rightUpperArm", "rightLowerArm", "rightHand"],
+            Limb::LeftFoot => ["leftUpperLeg", "leftLowerLeg", "leftFoot"],
+            Limb::RightFoot => ["rightUpperLeg", "rightLowerLeg", "rightFoot"],
+        }
+    }
+
+    /// Direction the elbow or knee naturally bends, in the persona's space.
+    fn bend(self) -> Vec3 {
+        match self {
+            Limb::LeftHand | Limb::RightHand => Vec3::new(0.0, -0.5, -1.0).normalize(),
+            Limb::LeftFoot | Limb::RightFoot => Vec3::Z,
+        }
+    }
+}
+
+fn validate_targets(targets: &IkTargets) -> ApiResult {
+    for (_, target) in targets.limbs() {
+        let Some(target) = target else {
+            continue;
+        };
+        if !(0.0..=1.0).contains(&target.weight) {
+            return Err(ApiError::InvalidInput(
+                "IK weight must be between 0 and 1".to_string(),
+            ));
+        }
+        let finite = match target.position {
+            IkPoint::World(point) => point.iter().all(|v| v.is_finite()),
+            IkPoint::Viewport(viewport) => viewport.is_finite(),
+        };
+        if !finite || !target.pole.iter().flatten().all(|v| v.is_finite()) {
+            return Err(ApiError::InvalidInput(
+                "IK positions and poles must be finite".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl VrmApi {
+    /// Returns the inverse kinematics targets of the specified VRM entity.
+    pub async fn ik_targets(&self, vrm: Entity) -> ApiResult<IkTargets> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(get_targets).with(vrm)).await
+            })
+            .await
+    }
+
+    /// Replaces the inverse kinematics targets of the specified VRM entity.
+    ///
+    /// Limbs left without a target return to the animation.
+    pub async fn set_ik_targets(&self, vrm: Entity, targets: IkTargets) -> ApiResult<IkTargets> {
+        validate_targets(&targets)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_targets).with((vrm, targets)))
+                    .await
+            })
+            .await
+    }
+}
+
+/// Bones of the limbs of a VRM and where they currently reach.
+#[derive(Component, Debug)]
+pub(crate) struct IkRig {
+    chains: HashMap<Limb, [Entity; 3]>,
+    goals: Vec<IkGoal>,
+}
+
+impl IkRig {
+    fn bones(&self, limb: Limb) -> impl Iterator<Item = Entity> {
+        self.chains.get(&limb).into_iter().flatten().copied()
+    }
+}
+
+/// Where one limb reaches this frame, in world space.
+#[derive(Debug, Clone, Copy)]
+struct IkGoal {
+    bones: [Entity; 3],
+    target: Vec3,
+    pole: Vec3,
+    weight: f32,
+}
+
+pub(super) struct IkPlugin;
+
+impl Plugin for IkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, aim_limbs)
+            .add_systems(PostUpdate, solve_limbs.in_set(BoneLayerSystems::Ik));
+    }
+}
+
+fn get_targets(In(vrm): In<Entity>, targets: Query<&IkTargets>) -> IkTargets {
+    targets.get(vrm).copied().unwrap_or_default()
+}
+
+fn put_targets(
+    In((vrm, targets)): In<(Entity, IkTargets)>,
+    mut commands: Commands,
+    rigs: Query<&IkRig>,
+    mut layered: Query<(&mut Transform, &BoneLayer)>,
+) -> IkTargets {
+    if let Ok(rig) = rigs.get(vrm) {
+        for (limb, target) in targets.limbs() {
+            if target.is_none() {
+                release_layers(&mut commands, rig.bones(limb), &mut layered);
+            }
+        }
+    }
+    if targets.is_empty() {
+        commands.entity(vrm).try_remove::<(IkTargets, IkRig)>();
+    } else {
+        commands.entity(vrm).try_insert(targets);
+    }
+    targets
+}
+
+/// Finds the limb bones and resolves each target to a point in world space.
+fn aim_limbs(
+    mut commands: Commands,
+    mut vrms: Query<(Entity, &IkTargets, &GlobalTransform, Option<&mut IkRig>)>,
+    globals: Query<&GlobalTransform>,
+    searcher: ChildSearcher,
+    coordinate: Coordinate,
+) {
+    for (vrm, targets, root, rig) in vrms.iter_mut() {
+        let Some(mut rig) = rig else {
+            let chains = [
+                Limb::LeftHand,
+                Limb::RightHand,
+                Limb::LeftFoot,
+                Limb::RightFoot,
+            ]
+            .into_iter()
+            .filter_map(|limb| {
+                let [upper, lower, end] = limb
+                    .bones()
+                    .map(|bone| searcher.find_by_bone_name(vrm, &VrmBone(bone.to_string())));
+                Some((limb, [upper?, lower?, end?]))
+            })
+            .collect();
+            commands.entity(vrm).try_insert(IkRig {
+                chains,
+                goals: Vec::new(),
+            });
+            continue;
+        };
+        if rig.chains.is_empty() {
+            // The VRM had not loaded yet; look for its bones again.
+            commands.entity(vrm).try_remove::<IkRig>();
+            continue;
+        }
+        let facing = root.rotation();
+        let goals = targets
+            .limbs()
+            .into_iter()
+            .filter_map(|(limb, target)| {
+                let target = target?;
+                let bones = *rig.chains.get(&limb)?;
+                let [shoulder, elbow] = [bones[0], bones[1]].map(|bone| {
+                    globals
+                        .get(bone)
+                        .map(GlobalTransform::translation)
+                        .unwrap_or_default()
+                });
+                let position = match target.position {
+                    IkPoint::World(point) => Vec3::from_array(point),
+                    IkPoint::Viewport(viewport) => coordinate
+                        .to_world_2d_by_global(viewport)?
+                        .extend(shoulder.z),
+                };
+                let pole = match target.pole {
+                    Some(pole) => Vec3::from_array(pole),
+                    None => elbow + facing * limb.bend() * shoulder.distance(elbow),
+                };
+                Some(IkGoal {
+                    bones,
+                    target: position,
+                    pole,
+                    weight: target.weight,
+                })
+            })
+            .collect();
+        rig.goals = goals;
+    }
+}
+
+/// Bends each targeted limb toward its goal, on top of the animation.
+fn solve_limbs(
+    mut commands: Commands,
+    rigs: Query<&IkRig>,
+    parents: Query<&ChildOf>,
+    mut bones: Query<(&mut Transform, Option<&mut BoneLayer>)>,
+) {
+    for goal in rigs.iter().flat_map(|rig| rig.goals.iter()) {
+        let [upper, lower, end] = goal.bones;
+        let Some(parent) = parents
+            .get(upper)
+            .ok()
+            .and_then(|child_of| animated_global(child_of.parent(), &parents, &bones))
+        else {
+            continue;
+        };
+        let Ok(
+            [
+                (upper_tf, upper_layer),
+                (lower_tf, lower_layer),
+                (end_tf, _),
+            ],
+        ) = bones.get_many([upper, lower, end])
+        else {
+            continue;
+        };
+        let upper_local = Transform {
+            rotation: base_rotation(upper_tf.rotation, upper_layer),
+            ..*upper_tf
+        };
+        let lower_local = Transform {
+            rotation: base_rotation(lower_tf.rotation, lower_layer),
+            ..*lower_tf
+        };
+        let upper_global = parent.mul_transform(upper_local);
+        let lower_global = upper_global.mul_transform(lower_local);
+        let end_global = lower_global.mul_transform(*end_tf);
+
+        let (upper_turn, lower_turn) = solve_two_bone(
+            upper_global.translation(),
+            lower_global.translation(),
+            end_global.translation(),
+            goal.target,
+            goal.pole,
+        );
+        let upper_turn = Quat::IDENTITY.slerp(upper_turn, goal.weight);
+        let lower_turn = Quat::IDENTITY.slerp(lower_turn, goal.weight);
+        let parent_rotation = parent.rotation();
+        let upper_rotation = upper_turn * upper_global.rotation();
+
+        for (bone, local, parent_rotation, turn) in [
+            (upper, upper_local, parent_rotation, upper_turn),
+            (lower, lower_local, upper_rotation, lower_turn),
+        ] {
+            if let Ok((mut tf, layer)) = bones.get_mut(bone) {
+                let delta = parent_rotation.inverse() * turn * parent_rotation;
+                apply_layer(&mut commands, bone, &mut tf, layer, local.rotation, delta);
+            }
+        }
+    }
+}
+
+/// World-space rotations that bend the chain `a` → `b` → `c` so `c` reaches `target`,
+/// with `b` bending toward `pole`.
+///
+/// The first rotation turns the upper bone around `a`; the second turns the lower bone
+/// around its new position. Targets out of reach stretch the chain toward them.
+fn solve_two_bone(a: Vec3, b: Vec3, c: Vec3, target: Vec3, pole: Vec3) -> (Quat, Quat) {
+    let upper = a.distance(b);
+    let lower = b.distance(c);
+    if upper < f32::EPSILON || lower < f32::EPSILON {
+        return (Quat::IDENTITY, Quat::IDENTITY);
+    }
+    let to_target = target - a;
+    let reach = to_target
+        .length()
+        .clamp((upper - lower).abs() + 1e-4, upper + lower - 1e-4);
+    let direction = to_target.normalize_or((c - a).normalize_or(Vec3::Y));
+    let cos =
+        ((upper * upper + reach * reach - lower * lower) / (2.0 * upper * reach)).clamp(-1.0, 1.0);
+    let sin = (1.0 - cos * cos).max(0.0).sqrt();
+    let bend = (pole - a)
+        .reject_from_normalized(direction)
+        .normalize_or(direction.any_orthonormal_vector());
+
+    let joint = a + (direction * cos + bend * sin) * upper;
+    let end = a + direction * reach;
+    let upper_turn = Quat::from_rotation_arc((b - a) / upper, (joint - a) / upper);
+    let lower_turn = Quat::from_rotation_arc(
+        (upper_turn * (c - b)).normalize(),
+        (end - joint).normalize(),
+    );
+    (upper_turn, lower_turn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_reaches_a_target_within_reach() {
+        let (a, b, c) = (
+            Vec3::ZERO,
+            Vec3::new(0.0, -1.0, 0.0),
+            Vec3::new(0.0, -2.0, 0.0),
+        );
+        let target = Vec3::new(1.0, -1.0, 0.0);
+        let pole = Vec3::new(0.0, 0.0, -1.0);
+        let (upper_turn, lower_turn) = solve_two_bone(a, b, c, target, pole);
+
+        let joint = a + upper_turn * (b - a);
+        let end = joint + lower_turn * upper_turn * (c - b);
+        assert!(end.distance(target) < 1e-3, "{end}");
+        assert!((joint.distance(a) - 1.0).abs() < 1e-4);
+        assert!(joint.z < 0.0, "the joint should bend toward the pole");
+    }
+
+    #[test]
+    fn chain_stretches_toward_a_target_out_of_reach() {
+        let (a, b, c) = (
+            Vec3::ZERO,
+            Vec3::new(0.5, -0.5, 0.0),
+            Vec3::new(0.0, -1.0, 0.0),
+        );
+        let target = Vec3::new(5.0, 0.0, 0.0);
+        let (upper_turn, lower_turn) = solve_two_bone(a, b, c, target, Vec3::NEG_Y);
+
+        let joint = a + upper_turn * (b - a);
+        let end = joint + lower_turn * upper_turn * (c - b);
+        assert!(end.normalize().dot(Vec3::X) > 0.999);
+        assert!(end.length() <= a.distance(b) + b.distance(c));
+    }
+
+    #[test]
+    fn weights_must_be_between_zero_and_one() {
+        let target = IkTarget {
+            position: IkPoint::World([0.0, 1.0, 0.0]),
+            weight: 1.5,
+            pole: None,
+        };
+        let targets = IkTargets {
+            left_hand: Some(target),
+            ..default()
+        };
+        assert!(validate_targets(&targets).is_err());
+        let targets = IkTargets {
+            left_hand: Some(IkTarget {
+                weight: 0.5,
+                ..target
+            }),
+            ..default()
+        };
+        assert!(validate_targets(&targets).is_ok());
+        assert!(IkTargets::default().is_empty());
+    }
+}
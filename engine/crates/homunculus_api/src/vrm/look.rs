@@ -1,9 +1,30 @@
+mod gaze;
+
+pub(crate) use gaze::Gaze;
+pub use gaze::LookAtSettings;
+
 use crate::prelude::ApiResult;
 use crate::vrm::VrmApi;
+use crate::vrm::bone_layer::{BoneLayer, BoneLayerSystems};
+use crate::vrm::snapshot::LookAtState;
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
 use bevy_vrm1::prelude::{LeftEyeBoneEntity, LookAt, RightEyeBoneEntity};
 use bevy_vrm1::vrm::RestTransform;
+use gaze::GazeMode;
+use homunculus_core::prelude::GlobalViewport;
+use serde::{Deserialize, Serialize};
+
+/// A point for a persona to look at.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum LookAtPoint {
+    /// A point in world space.
+    World([f32; 3]),
+    /// A point on the screen, in global viewport coordinates.
+    Viewport(GlobalViewport),
+}
 
 impl VrmApi {
     /// Disables look-at control for the specified VRM entity.
@@ -16,32 +37,113 @@ impl VrmApi {
     }
 
     /// Enables look-at control for the specified VRM entity, targeting another entity.
+    ///
+    /// When the target is a VRM, the persona looks at its head.
     pub async fn look_at_target(&self, vrm: Entity, target: Entity) -> ApiResult {
+        self.look(vrm, GazeMode::Target(target)).await
+    }
+
+    /// Enables look-at control for the specified VRM entity, making it look at the cursor.
+    pub async fn look_at_cursor(&self, vrm: Entity) -> ApiResult {
+        self.look(vrm, GazeMode::Cursor).await
+    }
+
+    /// Makes the specified VRM entity look at a fixed point in the world or on the screen.
+    pub async fn look_at_point(&self, vrm: Entity, point: LookAtPoint) -> ApiResult {
+        let mode = match point {
+            LookAtPoint::World(point) => GazeMode::World(Vec3::from_array(point)),
+            LookAtPoint::Viewport(viewport) => GazeMode::Viewport(viewport),
+        };
+        self.look(vrm, mode).await
+    }
+
+    /// Lets the gaze of the specified VRM entity wander idly around.
+    pub async fn look_wander(&self, vrm: Entity) -> ApiResult {
+        self.look(vrm, GazeMode::Wander).await
+    }
+
+    /// Returns how the gaze of the specified VRM entity moves.
+    pub async fn look_settings(&self, vrm: Entity) -> ApiResult<LookAtSettings> {
         self.0
             .schedule(move |task| async move {
-                task.will(Update, once::run(look_at_target).with((vrm, target)))
-                    .await;
+                task.will(Update, once::run(get_settings).with(vrm)).await
             })
             .await
     }
 
-    /// Enables look-at control for the specified VRM entity, making it look at the cursor.
-    pub async fn look_at_cursor(&self, vrm: Entity) -> ApiResult {
+    /// Changes how the gaze of the specified VRM entity moves.
+    ///
+    /// A persona following the cursor with the built-in look-at switches to the gaze
+    /// controller so the settings take effect.
+    pub async fn set_look_settings(
+        &self,
+        vrm: Entity,
+        settings: LookAtSettings,
+    ) -> ApiResult<LookAtSettings> {
+        gaze::validate_settings(&settings)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_settings).with((vrm, settings)))
+                    .await
+            })
+            .await
+    }
+
+    async fn look(&self, vrm: Entity, mode: GazeMode) -> ApiResult {
         self.0
             .schedule(move |task| async move {
-                task.will(Update, once::run(look_at_cursor).with(vrm)).await;
+                task.will(Update, once::run(look).with((vrm, mode))).await;
             })
             .await
     }
 }
 
+/// Makes a newly attached VRM follow the cursor through the gaze controller, so its
+/// [`LookAtSettings`] apply from the start.
+pub(crate) fn look_at_cursor(commands: &mut Commands, vrm: Entity) {
+    gaze::look(commands, vrm, None, GazeMode::Cursor);
+}
+
+/// The look-at mode of a VRM, from its gaze controller or else its built-in look-at.
+pub(crate) fn look_at_state(gaze: Option<&Gaze>, look_at: Option<&LookAt>) -> Option<LookAtState> {
+    if let Some(gaze) = gaze {
+        return Some(match gaze.mode {
+            GazeMode::Cursor => LookAtState::Cursor,
+            GazeMode::Target(entity) => LookAtState::Target { entity },
+            GazeMode::World(point) => LookAtState::World {
+                point: point.to_array(),
+            },
+            GazeMode::Viewport(point) => LookAtState::Viewport { point },
+            GazeMode::Wander => LookAtState::Wander,
+        });
+    }
+    look_at.map(|look_at| match look_at {
+        LookAt::Cursor => LookAtState::Cursor,
+        LookAt::Target(target) => LookAtState::Target { entity: *target },
+    })
+}
+
+pub(super) struct LookPlugin;
+
+impl Plugin for LookPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (gaze::drive_gaze, gaze::despawn_stale_gaze_targets))
+            .add_systems(PostUpdate, gaze::turn_head.in_set(BoneLayerSystems::Gaze));
+    }
+}
+
 fn unlook(
     In(vrm): In<Entity>,
     mut commands: Commands,
     vrms: Query<(&LeftEyeBoneEntity, &RightEyeBoneEntity)>,
+    gazes: Query<&Gaze>,
+    mut bones: Query<(&mut Transform, &BoneLayer)>,
     rests: Query<&RestTransform>,
 ) {
-    commands.entity(vrm).try_remove::<LookAt>();
+    commands.entity(vrm).try_remove::<(LookAt, Gaze)>();
+    if let Ok(gaze) = gazes.get(vrm) {
+        gaze::release_bones(&mut commands, gaze, &mut bones);
+    }
     if let Ok((left_eye, right_eye)) = vrms.get(vrm)
         && let Ok(left_eye_rest) = rests.get(left_eye.0)
         && let Ok(right_eye_rest) = rests.get(right_eye.0)
@@ -51,10 +153,27 @@ fn unlook(
     }
 }
 
-fn look_at_target(In((vrm, target)): In<(Entity, Entity)>, mut commands: Commands) {
-    commands.entity(vrm).try_insert(LookAt::Target(target));
+fn look(
+    In((vrm, mode)): In<(Entity, GazeMode)>,
+    mut commands: Commands,
+    mut gazes: Query<&mut Gaze>,
+) {
+    gaze::look(&mut commands, vrm, gazes.get_mut(vrm).ok(), mode);
 }
 
-fn look_at_cursor(In(vrm): In<Entity>, mut commands: Commands) {
-    commands.entity(vrm).try_insert(LookAt::Cursor);
+fn get_settings(In(vrm): In<Entity>, settings: Query<&LookAtSettings>) -> LookAtSettings {
+    settings.get(vrm).copied().unwrap_or_default()
+}
+
+fn put_settings(
+    In((vrm, settings)): In<(Entity, LookAtSettings)>,
+    mut commands: Commands,
+    gazes: Query<&Gaze>,
+    look_ats: Query<&LookAt>,
+) -> LookAtSettings {
+    commands.entity(vrm).try_insert(settings);
+    if matches!(look_ats.get(vrm), Ok(LookAt::Cursor)) && !gazes.contains(vrm) {
+        gaze::look(&mut commands, vrm, None, GazeMode::Cursor);
+    }
+    settings
 }
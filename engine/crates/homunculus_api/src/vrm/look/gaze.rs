@@ -0,0 +1,416 @@
+//! Gaze control: where a persona looks, within angular limits, with smoothing,
+//! saccades, idle wandering and head/neck participation.
+//!
+//! The VRM's native [`LookAt`] follows a hidden target entity, which is moved each
+//! frame to the point the persona looks at.
+
+use crate::error::{ApiError, ApiResult};
+use crate::vrm::bone_layer::{
+    BoneLayer, animated_global, apply_layer, base_rotation, release_layer, release_layers,
+};
+use bevy::prelude::*;
+use bevy_vrm1::prelude::{ChildSearcher, LookAt};
+use bevy_vrm1::vrm::VrmBone;
+use homunculus_core::prelude::{Coordinate, GlobalViewport};
+use rand::random_range;
+use serde::{Deserialize, Serialize};
+
+/// Distance in front of the head, in world units, of points given on the screen.
+const SCREEN_DEPTH: f32 = 1.0;
+
+/// Distance in front of the head, in world units, of points looked at while wandering.
+const WANDER_DISTANCE: f32 = 1.5;
+
+/// Largest angle of a saccade, in radians.
+const SACCADE_ANGLE: f32 = 0.035;
+
+/// How a persona's gaze moves.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct LookAtSettings {
+    /// Largest horizontal angle between the gaze and the persona's forward, from `0`
+    /// to `180` degrees.
+    pub max_yaw_deg: f32,
+    /// Largest vertical angle between the gaze and the persona's forward, from `0` to
+    /// `90` degrees.
+    pub max_pitch_deg: f32,
+    /// How quickly the gaze follows its target, per second, up to `100`; `0` snaps to it.
+    pub speed: f32,
+    /// Make small, quick eye jumps while looking at something.
+    pub saccades: bool,
+    /// Share of the gaze angle the head turns by, from `0` to `1`.
+    pub head_weight: f32,
+    /// Share of the gaze angle the neck turns by, from `0` to `1`.
+    pub neck_weight: f32,
+}
+
+impl Default for LookAtSettings {
+    fn default() -> Self {
+        Self {
+            max_yaw_deg: 60.0,
+            max_pitch_deg: 35.0,
+            speed: 10.0,
+            saccades: true,
+            head_weight: 0.0,
+            neck_weight: 0.0,
+        }
+    }
+}
+
+pub(crate) fn validate_settings(settings: &LookAtSettings) -> ApiResult {
+    if !(0.0..=180.0).contains(&settings.max_yaw_deg) {
+        return Err(ApiError::InvalidInput(
+            "maxYawDeg must be between 0 and 180".to_string(),
+        ));
+    }
+    if !(0.0..=90.0).contains(&settings.max_pitch_deg) {
+        return Err(ApiError::InvalidInput(
+            "maxPitchDeg must be between 0 and 90".to_string(),
+        ));
+    }
+    if !(0.0..=100.0).contains(&settings.speed) {
+        return Err(ApiError::InvalidInput(
+            "speed must be between 0 and 100".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&settings.head_weight)
+        || !(0.0..=1.0).contains(&settings.neck_weight)
+        || 1.0 < settings.head_weight + settings.neck_weight
+    {
+        return Err(ApiError::InvalidInput(
+            "head and neck weights must be between 0 and 1 and sum to at most 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// What a persona looks at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GazeMode {
+    Cursor,
+    Target(Entity),
+    World(Vec3),
+    Viewport(GlobalViewport),
+    Wander,
+}
+
+/// Drives the gaze of a VRM.
+#[derive(Component, Debug)]
+pub(crate) struct Gaze {
+    pub(crate) mode: GazeMode,
+    /// The entity the VRM's [`LookAt`] targets.
+    pub(crate) target: Entity,
+    /// The point looked at, after limits and smoothing.
+    point: Option<Vec3>,
+    /// Yaw and pitch of the gaze relative to the persona's forward, in radians.
+    angles: Vec2,
+    /// Current saccade offset, as yaw and pitch in radians.
+    saccade: Vec2,
+    next_saccade: f32,
+    /// Current wandering direction, as yaw and pitch in radians.
+    wander: Vec2,
+    next_wander: f32,
+    head: Option<Entity>,
+    neck: Option<Entity>,
+}
+
+impl Gaze {
+    pub(crate) fn new(mode: GazeMode, target: Entity) -> Self {
+        Self {
+            mode,
+            target,
+            point: None,
+            angles: Vec2::ZERO,
+            saccade: Vec2::ZERO,
+            next_saccade: 0.0,
+            wander: Vec2::ZERO,
+            next_wander: 0.0,
+            head: None,
+            neck: None,
+        }
+    }
+
+    /// Switches to `mode`, keeping the current point so the gaze moves there smoothly.
+    pub(crate) fn look(&mut self, mode: GazeMode) {
+        self.mode = mode;
+        self.next_wander = 0.0;
+    }
+
+    /// The head and neck bones turned with the gaze, with their weights.
+    fn turning_bones(&self, settings: &LookAtSettings) -> [(Option<Entity>, f32); 2] {
+        [
+            (self.neck, settings.neck_weight),
+            (self.head, settings.head_weight),
+        ]
+    }
+}
+
+/// Marks the hidden entity a persona's [`Gaze`] moves around.
+#[derive(Component, Debug)]
+pub(crate) struct GazeTarget(pub(crate) Entity);
+
+/// Starts driving the gaze of `vrm` with `mode`.
+pub(crate) fn look(commands: &mut Commands, vrm: Entity, gaze: Option<Mut<Gaze>>, mode: GazeMode) {
+    match gaze {
+        Some(mut gaze) => gaze.look(mode),
+        None => {
+            let target = commands
+                .spawn((
+                    Name::new("GazeTarget"),
+                    GazeTarget(vrm),
+                    Transform::default(),
+                ))
+                .id();
+            commands
+                .entity(vrm)
+                .try_insert((Gaze::new(mode, target), LookAt::Target(target)));
+        }
+    }
+}
+
+/// Takes the gaze turn off the persona's head and neck bones.
+pub(crate) fn release_bones(
+    commands: &mut Commands,
+    gaze: &Gaze,
+    bones: &mut Query<(&mut Transform, &BoneLayer)>,
+) {
+    release_layers(
+        commands,
+        [gaze.neck, gaze.head].into_iter().flatten(),
+        bones,
+    );
+}
+
+/// Yaw and pitch of a direction in the persona's space, where it faces `+Z`.
+fn angles_of(direction: Vec3) -> Vec2 {
+    let horizontal = Vec2::new(direction.x, direction.z).length();
+    Vec2::new(
+        direction.x.atan2(direction.z),
+        direction.y.atan2(horizontal),
+    )
+}
+
+/// Unit direction in the persona's space for a yaw and pitch.
+fn direction_of(angles: Vec2) -> Vec3 {
+    let (yaw_sin, yaw_cos) = angles.x.sin_cos();
+    let (pitch_sin, pitch_cos) = angles.y.sin_cos();
+    Vec3::new(yaw_sin * pitch_cos, pitch_sin, yaw_cos * pitch_cos)
+}
+
+/// Clamps a direction in the persona's space to the angular limits, keeping its length.
+fn limit(direction: Vec3, max_yaw: f32, max_pitch: f32) -> Vec3 {
+    let angles = angles_of(direction);
+    let limited = Vec2::new(
+        angles.x.clamp(-max_yaw, max_yaw),
+        angles.y.clamp(-max_pitch, max_pitch),
+    );
+    if limited == angles {
+        direction
+    } else {
+        direction_of(limited) * direction.length()
+    }
+}
+
+/// Where on the persona's side of the screen a global viewport position lies.
+fn screen_point(coordinate: &Coordinate, viewport: GlobalViewport, head: Vec3) -> Option<Vec3> {
+    coordinate
+        .to_world_2d_by_global(viewport)
+        .map(|point| point.extend(head.z + SCREEN_DEPTH))
+}
+
+/// The head of a VRM target, or the target itself.
+fn target_point(
+    target: Entity,
+    searcher: &ChildSearcher,
+    transforms: &Query<&GlobalTransform>,
+) -> Option<Vec3> {
+    let head = searcher.find_by_bone_name(target, &VrmBone("head".to_string()));
+    transforms
+        .get(head.unwrap_or(target))
+        .ok()
+        .map(GlobalTransform::translation)
+}
+
+/// Moves each persona's gaze target to where it looks.
+pub(crate) fn drive_gaze(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut gazes: Query<(Entity, &mut Gaze, &GlobalTransform, Option<&LookAtSettings>)>,
+    transforms: Query<&GlobalTransform>,
+    searcher: ChildSearcher,
+    coordinate: Coordinate,
+) {
+    let dt = time.delta_secs();
+    let now = time.elapsed_secs();
+    for (vrm, mut gaze, root, settings) in gazes.iter_mut() {
+        let settings = settings.copied().unwrap_or_default();
+        if gaze.head.is_none() {
+            gaze.head = searcher.find_by_bone_name(vrm, &VrmBone("head".to_string()));
+            gaze.neck = searcher.find_by_bone_name(vrm, &VrmBone("neck".to_string()));
+        }
+        let Some(head) = gaze
+            .head
+            .and_then(|head| transforms.get(head).ok())
+            .map(GlobalTransform::translation)
+        else {
+            continue;
+        };
+        let facing = root.rotation();
+
+        let desired = match gaze.mode {
+            GazeMode::Cursor => coordinate
+                .windows
+                .global_cursor_pos()
+                .and_then(|cursor| screen_point(&coordinate, cursor, head)),
+            GazeMode::Viewport(viewport) => screen_point(&coordinate, viewport, head),
+            GazeMode::World(point) => Some(point),
+            GazeMode::Target(target) => target_point(target, &searcher, &transforms),
+            GazeMode::Wander => {
+                if gaze.next_wander <= now {
+                    gaze.wander = if random_range(0.0..1.0) < 0.3 {
+                        Vec2::ZERO
+                    } else {
+                        Vec2::new(random_range(-0.4..0.4), random_range(-0.15..0.1))
+                    };
+                    gaze.next_wander = now + random_range(1.5..4.0);
+                }
+                Some(head + facing * direction_of(gaze.wander) * WANDER_DISTANCE)
+            }
+        };
+        let Some(desired) = desired else {
+            continue;
+        };
+        let desired = head
+            + facing
+                * limit(
+                    facing.inverse() * (desired - head),
+                    settings.max_yaw_deg.to_radians(),
+                    settings.max_pitch_deg.to_radians(),
+                );
+
+        let point = match gaze.point {
+            Some(point) if 0.0 < settings.speed => {
+                point.lerp(desired, 1.0 - (-settings.speed * dt).exp())
+            }
+            _ => desired,
+        };
+        gaze.point = Some(point);
+        let direction = facing.inverse() * (point - head);
+        gaze.angles = angles_of(direction);
+
+        if !settings.saccades {
+            gaze.saccade = Vec2::ZERO;
+        } else if gaze.next_saccade <= now {
+            gaze.saccade = Vec2::new(
+                random_range(-SACCADE_ANGLE..SACCADE_ANGLE),
+                random_range(-SACCADE_ANGLE..SACCADE_ANGLE) * 0.5,
+            );
+            gaze.next_saccade = now + random_range(0.3..2.0);
+        }
+        let looked_at =
+            head + facing * direction_of(gaze.angles + gaze.saccade) * direction.length();
+        commands
+            .entity(gaze.target)
+            .try_insert(Transform::from_translation(looked_at));
+    }
+}
+
+/// Turns head and neck bones by their share of the gaze, on top of the animation.
+pub(crate) fn turn_head(
+    mut commands: Commands,
+    gazes: Query<(&Gaze, &GlobalTransform, Option<&LookAtSettings>)>,
+    parents: Query<&ChildOf>,
+    mut bones: Query<(&mut Transform, Option<&mut BoneLayer>)>,
+) {
+    for (gaze, root, settings) in gazes.iter() {
+        let settings = settings.copied().unwrap_or_default();
+        let facing = root.rotation();
+        for (bone, weight) in gaze.turning_bones(&settings) {
+            let Some(bone) = bone else {
+                continue;
+            };
+            let parent = parents
+                .get(bone)
+                .ok()
+                .and_then(|child_of| animated_global(child_of.parent(), &parents, &bones))
+                .map(|parent| parent.rotation())
+                .unwrap_or(facing);
+            let Ok((mut tf, layer)) = bones.get_mut(bone) else {
+                continue;
+            };
+            if weight <= 0.0 {
+                if let Some(layer) = layer {
+                    release_layer(&mut commands, bone, &mut tf, &layer);
+                }
+                continue;
+            }
+            let base = base_rotation(tf.rotation, layer.as_deref());
+            let turn = Quat::from_euler(
+                EulerRot::YXZ,
+                gaze.angles.x * weight,
+                -gaze.angles.y * weight,
+                0.0,
+            );
+            let delta = parent.inverse() * facing * turn * facing.inverse() * parent;
+            apply_layer(&mut commands, bone, &mut tf, layer, base, delta);
+        }
+    }
+}
+
+/// Despawns gaze targets whose persona no longer drives them.
+pub(crate) fn despawn_stale_gaze_targets(
+    mut commands: Commands,
+    targets: Query<(Entity, &GazeTarget)>,
+    gazes: Query<&Gaze>,
+) {
+    for (entity, owner) in targets.iter() {
+        if gazes.get(owner.0).is_ok_and(|gaze| gaze.target == entity) {
+            continue;
+        }
+        commands.entity(entity).try_despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directions_are_clamped_to_the_angular_limits() {
+        let ahead = Vec3::new(0.1, 0.05, 2.0);
+        assert_eq!(limit(ahead, 1.0, 0.5), ahead);
+
+        let behind = Vec3::new(1.0, 0.0, -1.0);
+        let limited = limit(behind, 60f32.to_radians(), 0.5);
+        let angles = angles_of(limited);
+        assert!((angles.x - 60f32.to_radians()).abs() < 1e-4);
+        assert!((limited.length() - behind.length()).abs() < 1e-4);
+
+        let above = limit(Vec3::new(0.0, 3.0, 1.0), 1.0, 0.3);
+        assert!((angles_of(above).y - 0.3).abs() < 1e-4);
+    }
+
+    #[test]
+    fn angles_round_trip() {
+        let angles = Vec2::new(-0.7, 0.2);
+        assert!((angles_of(direction_of(angles)) - angles).length() < 1e-5);
+        assert_eq!(angles_of(Vec3::Z), Vec2::ZERO);
+    }
+
+    #[test]
+    fn head_and_neck_weights_are_validated() {
+        assert!(validate_settings(&LookAtSettings::default()).is_ok());
+        let both = LookAtSettings {
+            head_weight: 0.7,
+            neck_weight: 0.5,
+            ..default()
+        };
+        assert!(validate_settings(&both).is_err());
+        let wide = LookAtSettings {
+            max_yaw_deg: 200.0,
+            ..default()
+        };
+        assert!(validate_settings(&wide).is_err());
+    }
+}
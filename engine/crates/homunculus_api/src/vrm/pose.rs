@@ -0,0 +1,489 @@
+//! Rotations of named humanoid bones, layered on top of the playing animation.
+//!
+//! Rotations are Euler angles in the persona's space, so "tilt the head" or "raise the
+//! right arm" mean the same on every model regardless of how its bones are oriented.
+
+mod presets;
+
+pub use presets::PosePresets;
+
+use crate::entities::tween::EasingFunction;
+use crate::error::{ApiError, ApiResult};
+use crate::vrm::VrmApi;
+use crate::vrm::bone_layer::{
+    BoneLayer, BoneLayerSystems, animated_global, apply_layer, base_rotation, release_layer,
+};
+use bevy::math::curve::easing::EaseFunction;
+use bevy::prelude::*;
+use bevy_flurx::prelude::*;
+use bevy_vrm1::prelude::ChildSearcher;
+use bevy_vrm1::vrm::VrmBone;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A rotation layered on one humanoid bone.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BoneRotation {
+    /// Degrees around the persona's X (pitch), Y (yaw) and Z (roll) axes, on top of the
+    /// animated pose.
+    pub rotation: [f32; 3],
+    /// How much of the rotation is applied, from `0` to `1`.
+    #[serde(default = "full_weight")]
+    pub weight: f32,
+}
+
+fn full_weight() -> f32 {
+    1.0
+}
+
+impl BoneRotation {
+    /// The weighted rotation, in the persona's space.
+    fn quat(&self) -> Quat {
+        let [x, y, z] = self.rotation.map(f32::to_radians);
+        Quat::IDENTITY.slerp(Quat::from_euler(EulerRot::YXZ, y, x, z), self.weight)
+    }
+}
+
+/// Humanoid bone name to its rotation, e.g. `{"head": {"rotation": [0, 0, 15]}}`.
+pub type BonePose = HashMap<String, BoneRotation>;
+
+/// How bones move to a new rotation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PoseTransition {
+    /// Duration of the transition in milliseconds; `0` moves the bones instantly.
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+}
+
+/// Request arguments for rotating one bone.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SetBoneRotation {
+    /// Degrees around the persona's X (pitch), Y (yaw) and Z (roll) axes.
+    pub rotation: [f32; 3],
+    /// How much of the rotation is applied, from `0` to `1`. Defaults to `1`.
+    #[serde(default = "full_weight")]
+    pub weight: f32,
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+}
+
+impl SetBoneRotation {
+    fn split(self) -> (BoneRotation, PoseTransition) {
+        (
+            BoneRotation {
+                rotation: self.rotation,
+                weight: self.weight,
+            },
+            PoseTransition {
+                duration_ms: self.duration_ms,
+                easing: self.easing,
+            },
+        )
+    }
+}
+
+/// Request arguments for replacing a whole pose.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SetPose {
+    #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, BoneRotation>))]
+    pub bones: BonePose,
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+}
+
+pub(crate) fn validate_pose(pose: &BonePose) -> ApiResult {
+    for (bone, rotation) in pose {
+        if !(0.0..=1.0).contains(&rotation.weight) {
+            return Err(ApiError::InvalidInput(format!(
+                "weight of {bone} must be between 0 and 1"
+            )));
+        }
+        if !rotation.rotation.iter().all(|v| v.is_finite()) {
+            return Err(ApiError::InvalidInput(format!(
+                "rotation of {bone} must be finite"
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl VrmApi {
+    /// Returns the bone rotations set on the specified VRM entity.
+    pub async fn pose(&self, vrm: Entity) -> ApiResult<BonePose> {
+        self.0
+            .schedule(
+                move |task| async move { task.will(Update, once::run(get_pose).with(vrm)).await },
+            )
+            .await
+    }
+
+    /// Rotates one bone of the specified VRM entity, easing from where it is.
+    pub async fn set_bone_rotation(
+        &self,
+        vrm: Entity,
+        bone: String,
+        args: SetBoneRotation,
+    ) -> ApiResult<BonePose> {
+        let (rotation, transition) = args.split();
+        self.change_pose(
+            vrm,
+            PoseChange::Bones(BonePose::from([(bone, rotation)])),
+            transition,
+        )
+        .await
+    }
+
+    /// Returns one bone of the specified VRM entity to the animation.
+    pub async fn clear_bone_rotation(
+        &self,
+        vrm: Entity,
+        bone: String,
+        transition: PoseTransition,
+    ) -> ApiResult<BonePose> {
+        self.change_pose(vrm, PoseChange::Clear(Some(bone)), transition)
+            .await
+    }
+
+    /// Replaces every bone rotation of the specified VRM entity. Bones not in `args`
+    /// return to the animation.
+    pub async fn set_pose(&self, vrm: Entity, args: SetPose) -> ApiResult<BonePose> {
+        let transition = PoseTransition {
+            duration_ms: args.duration_ms,
+            easing: args.easing,
+        };
+        self.change_pose(vrm, PoseChange::Replace(args.bones), transition)
+            .await
+    }
+
+    /// Returns every bone of the specified VRM entity to the animation.
+    pub async fn clear_pose(&self, vrm: Entity, transition: PoseTransition) -> ApiResult<BonePose> {
+        self.change_pose(vrm, PoseChange::Clear(None), transition)
+            .await
+    }
+
+    /// Lists the persona's named poses.
+    pub async fn pose_presets(&self, vrm: Entity) -> ApiResult<PosePresets> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(presets::get_presets).with(vrm))
+                    .await
+            })
+            .await?
+    }
+
+    /// Stores a named pose for the persona.
+    pub async fn save_pose_preset(
+        &self,
+        vrm: Entity,
+        name: String,
+        pose: BonePose,
+    ) -> ApiResult<PosePresets> {
+        presets::validate_preset(&name)?;
+        validate_pose(&pose)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(
+                    Update,
+                    once::run(presets::put_preset).with((vrm, name, pose)),
+                )
+                .await
+            })
+            .await?
+    }
+
+    /// Deletes a named pose of the persona.
+    pub async fn delete_pose_preset(&self, vrm: Entity, name: String) -> ApiResult<PosePresets> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(presets::delete_preset).with((vrm, name)))
+                    .await
+            })
+            .await?
+    }
+
+    /// Replaces the persona's pose with a named pose, easing into it.
+    pub async fn apply_pose_preset(
+        &self,
+        vrm: Entity,
+        name: String,
+        transition: PoseTransition,
+    ) -> ApiResult<BonePose> {
+        let pose = self
+            .0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(presets::get_preset).with((vrm, name)))
+                    .await
+            })
+            .await??;
+        self.change_pose(vrm, PoseChange::Replace(pose), transition)
+            .await
+    }
+
+    async fn change_pose(
+        &self,
+        vrm: Entity,
+        change: PoseChange,
+        transition: PoseTransition,
+    ) -> ApiResult<BonePose> {
+        if let PoseChange::Bones(pose) | PoseChange::Replace(pose) = &change {
+            validate_pose(pose)?;
+        }
+        self.0
+            .schedule(move |task| async move {
+                task.will(
+                    Update,
+                    once::run(change_pose).with((vrm, change, transition)),
+                )
+                .await
+            })
+            .await?
+    }
+}
+
+/// A change to the bone rotations of a VRM.
+#[derive(Debug, Clone)]
+pub(crate) enum PoseChange {
+    /// Rotate these bones, keeping the others.
+    Bones(BonePose),
+    /// Rotate these bones and release the others.
+    Replace(BonePose),
+    /// Release one bone, or every bone.
+    Clear(Option<String>),
+}
+
+/// Bone rotations of a VRM, moving toward their targets.
+#[derive(Component, Debug, Default)]
+pub(crate) struct Pose {
+    bones: HashMap<String, PosedBone>,
+}
+
+impl Pose {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.bones.is_empty()
+    }
+
+    pub(crate) fn targets(&self) -> BonePose {
+        self.bones
+            .iter()
+            .filter_map(|(name, bone)| Some((name.clone(), bone.target?)))
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct PosedBone {
+    entity: Entity,
+    /// The rotation set through the API; `None` while returning to the animation.
+    target: Option<BoneRotation>,
+    from: Quat,
+    to: Quat,
+    current: Quat,
+    elapsed: f32,
+    duration: f32,
+    ease: EaseFunction,
+}
+
+impl PosedBone {
+    fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            target: None,
+            from: Quat::IDENTITY,
+            to: Quat::IDENTITY,
+            current: Quat::IDENTITY,
+            elapsed: 0.0,
+            duration: 0.0,
+            ease: EaseFunction::Linear,
+        }
+    }
+
+    /// Starts moving from the current rotation to `target`, or back to the animation.
+    fn move_to(&mut self, target: Option<BoneRotation>, transition: PoseTransition) {
+        self.target = target;
+        self.from = self.current;
+        self.to = target.as_ref().map_or(Quat::IDENTITY, BoneRotation::quat);
+        self.elapsed = 0.0;
+        self.duration = transition.duration_ms as f32 / 1000.0;
+        self.ease = transition.easing.into();
+    }
+
+    /// Advances the transition and returns whether the bone is back with the animation.
+    fn advance(&mut self, delta: f32) -> bool {
+        self.elapsed += delta;
+        let t = if self.duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.duration).min(1.0)
+        };
+        self.current = self.from.slerp(self.to, self.ease.sample_clamped(t));
+        1.0 <= t && self.target.is_none()
+    }
+}
+
+pub(super) struct PosePlugin;
+
+impl Plugin for PosePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, pose_bones.in_set(BoneLayerSystems::Pose));
+    }
+}
+
+fn get_pose(In(vrm): In<Entity>, poses: Query<&Pose>) -> BonePose {
+    poses.get(vrm).map(Pose::targets).unwrap_or_default()
+}
+
+fn change_pose(
+    In((vrm, change, transition)): In<(Entity, PoseChange, PoseTransition)>,
+    mut commands: Commands,
+    mut poses: Query<&mut Pose>,
+    searcher: ChildSearcher,
+) -> ApiResult<BonePose> {
+    if let Ok(mut pose) = poses.get_mut(vrm) {
+        return apply_change(&mut pose, vrm, change, transition, &searcher);
+    }
+    let mut pose = Pose::default();
+    let targets = apply_change(&mut pose, vrm, change, transition, &searcher)?;
+    if !pose.is_empty() {
+        commands.entity(vrm).try_insert(pose);
+    }
+    Ok(targets)
+}
+
+pub(crate) fn apply_change(
+    pose: &mut Pose,
+    vrm: Entity,
+    change: PoseChange,
+    transition: PoseTransition,
+    searcher: &ChildSearcher,
+) -> ApiResult<BonePose> {
+    let (targets, release_others) = match change {
+        PoseChange::Bones(targets) => (targets, false),
+        PoseChange::Replace(targets) => (targets, true),
+        PoseChange::Clear(Some(bone)) => {
+            if let Some(posed) = pose.bones.get_mut(&bone) {
+                posed.move_to(None, transition);
+            }
+            (BonePose::new(), false)
+        }
+        PoseChange::Clear(None) => (BonePose::new(), true),
+    };
+    // Look every bone up first so an unknown bone changes nothing.
+    let mut entities = HashMap::new();
+    for bone in targets.keys() {
+        let entity = match pose.bones.get(bone) {
+            Some(posed) => posed.entity,
+            None => searcher
+                .find_by_bone_name(vrm, &VrmBone(bone.clone()))
+                .ok_or(ApiError::EntityNotFound)?,
+        };
+        entities.insert(bone.clone(), entity);
+    }
+    if release_others {
+        for (name, posed) in pose.bones.iter_mut() {
+            if !targets.contains_key(name) {
+                posed.move_to(None, transition);
+            }
+        }
+    }
+    for (bone, rotation) in targets {
+        let entity = entities[&bone];
+        pose.bones
+            .entry(bone)
+            .or_insert_with(|| PosedBone::new(entity))
+            .move_to(Some(rotation), transition);
+    }
+    Ok(pose.targets())
+}
+
+/// Rotates every posed bone on top of the animation, and releases bones that are back.
+fn pose_bones(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut poses: Query<(Entity, &mut Pose, &GlobalTransform)>,
+    parents: Query<&ChildOf>,
+    mut bones: Query<(&mut Transform, Option<&mut BoneLayer>)>,
+) {
+    for (vrm, mut pose, root) in poses.iter_mut() {
+        let facing = root.rotation();
+        pose.bones.retain(|_, posed| {
+            let released = posed.advance(time.delta_secs());
+            let parent = parents
+                .get(posed.entity)
+                .ok()
+                .and_then(|child_of| animated_global(child_of.parent(), &parents, &bones))
+                .map(|parent| parent.rotation())
+                .unwrap_or(facing);
+            let Ok((mut tf, layer)) = bones.get_mut(posed.entity) else {
+                return false;
+            };
+            if released {
+                if let Some(layer) = layer {
+                    release_layer(&mut commands, posed.entity, &mut tf, &layer);
+                }
+                return false;
+            }
+            let delta = parent.inverse() * facing * posed.current * facing.inverse() * parent;
+            let base = base_rotation(tf.rotation, layer.as_deref());
+            apply_layer(&mut commands, posed.entity, &mut tf, layer, base, delta);
+            true
+        });
+        if pose.is_empty() {
+            commands.entity(vrm).try_remove::<Pose>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bones_ease_to_their_rotation_and_back() {
+        let mut bone = PosedBone::new(Entity::PLACEHOLDER);
+        let tilt = BoneRotation {
+            rotation: [0.0, 0.0, 20.0],
+            weight: 0.5,
+        };
+        let transition = PoseTransition {
+            duration_ms: 1000,
+            easing: EasingFunction::Linear,
+        };
+        bone.move_to(Some(tilt), transition);
+        assert!(!bone.advance(0.5));
+        let halfway = bone.current.to_euler(EulerRot::YXZ).2.to_degrees();
+        assert!((halfway - 5.0).abs() < 1e-3, "{halfway}");
+        assert!(!bone.advance(0.5));
+        assert!(bone.current.angle_between(tilt.quat()) < 1e-4);
+
+        bone.move_to(None, PoseTransition::default());
+        assert!(bone.advance(0.0));
+        assert!(bone.current.angle_between(Quat::IDENTITY) < 1e-4);
+    }
+
+    #[test]
+    fn weights_must_be_between_zero_and_one() {
+        let pose = BonePose::from([(
+            "head".to_string(),
+            BoneRotation {
+                rotation: [0.0, 0.0, 15.0],
+                weight: 1.2,
+            },
+        )]);
+        assert!(validate_pose(&pose).is_err());
+        assert!(validate_pose(&BonePose::new()).is_ok());
+    }
+}
@@ -0,0 +1,83 @@
+//! Named poses, stored per persona.
+
+use super::BonePose;
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use homunculus_core::prelude::Persona;
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use std::collections::HashMap;
+
+/// Pose name to bone rotations, e.g. `{"wave": {"rightUpperArm": {"rotation": [0, 0, 70]}}}`.
+pub type PosePresets = HashMap<String, BonePose>;
+
+/// Maximum length of a pose name.
+const MAX_NAME_LEN: usize = 64;
+
+pub(super) fn validate_preset(name: &str) -> ApiResult {
+    if name.is_empty() || MAX_NAME_LEN < name.len() {
+        return Err(ApiError::InvalidInput(format!(
+            "pose name must be 1 to {MAX_NAME_LEN} characters"
+        )));
+    }
+    Ok(())
+}
+
+fn load_presets(prefs: &PrefsDatabase, persona: &Persona) -> ApiResult<PosePresets> {
+    prefs
+        .load_as::<PosePresets>(&PrefsKeys::pose_presets(persona.id.as_ref()))
+        .map(Option::unwrap_or_default)
+        .map_err(|e| ApiError::FailedLoad(e.to_string()))
+}
+
+fn save_presets(prefs: &PrefsDatabase, persona: &Persona, presets: &PosePresets) -> ApiResult {
+    prefs
+        .save_as(&PrefsKeys::pose_presets(persona.id.as_ref()), presets)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))
+}
+
+pub(super) fn get_presets(
+    In(vrm): In<Entity>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<PosePresets> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    load_presets(&prefs, persona)
+}
+
+/// Returns the bone rotations of one pose.
+pub(super) fn get_preset(
+    In((vrm, name)): In<(Entity, String)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<BonePose> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    load_presets(&prefs, persona)?
+        .remove(&name)
+        .ok_or(ApiError::PosePresetNotFound(name))
+}
+
+pub(super) fn put_preset(
+    In((vrm, name, pose)): In<(Entity, String, BonePose)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<PosePresets> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mut presets = load_presets(&prefs, persona)?;
+    presets.insert(name, pose);
+    save_presets(&prefs, persona, &presets)?;
+    Ok(presets)
+}
+
+pub(super) fn delete_preset(
+    In((vrm, name)): In<(Entity, String)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<&Persona>,
+) -> ApiResult<PosePresets> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mut presets = load_presets(&prefs, persona)?;
+    if presets.remove(&name).is_none() {
+        return Err(ApiError::PosePresetNotFound(name));
+    }
+    save_presets(&prefs, persona, &presets)?;
+    Ok(presets)
+}
@@ -1,6 +1,7 @@
 use crate::error::ApiResult;
 use crate::vrm::VrmApi;
 use crate::vrm::expressions::{ExpressionInfo, ExpressionsResponse};
+use crate::vrm::look::{Gaze, look_at_state};
 use crate::vrma::VrmaInfo;
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
@@ -41,6 +42,16 @@ pub enum LookAtState {
         #[cfg_attr(feature = "openapi", schema(value_type = String))]
         entity: Entity,
     },
+    /// Looking at a fixed point in world space.
+    World {
+        point: [f32; 3],
+    },
+    /// Looking at a fixed point on the screen.
+    Viewport {
+        point: GlobalViewport,
+    },
+    /// Gazing idly around.
+    Wander,
 }
 
 impl VrmApi {
@@ -62,6 +73,7 @@ fn snapshot_all_vrms(
             Option<&PersonaState>,
             &Transform,
             Option<&LookAt>,
+            Option<&Gaze>,
             Option<&Persona>,
             Option<&ExpressionEntityMap>,
             Option<&AssetIdComponent>,
@@ -82,15 +94,12 @@ fn snapshot_all_vrms(
 ) -> Vec<VrmSnapshot> {
     vrms.iter()
         .map(
-            |(entity, name, state, transform, look_at, persona, expr_map, asset_id_comp)| {
+            |(entity, name, state, transform, look_at, gaze, persona, expr_map, asset_id_comp)| {
                 let expressions = collect_expressions(expr_map, &expr_components);
                 let animations =
                     collect_playing_animations(entity, &children_query, &vrma_query, &players);
                 let global_viewport = coordinate.to_global_by_world(transform.translation);
-                let look_at_state = look_at.map(|la| match la {
-                    LookAt::Cursor => LookAtState::Cursor,
-                    LookAt::Target(target) => LookAtState::Target { entity: *target },
-                });
+                let look_at_state = look_at_state(gaze, look_at);
                 let linked_webviews = persona
                     .map(|p| {
                         linked_personas
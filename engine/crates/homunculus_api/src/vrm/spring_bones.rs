@@ -1,8 +1,22 @@
-use crate::error::ApiResult;
+mod overrides;
+mod presets;
+mod wind;
+
+pub use presets::SPRING_BONE_PRESETS;
+pub use wind::{PersonaWindArgs, SpringBoneSettings, WindSettings};
+
+use crate::error::{ApiError, ApiResult};
 use crate::vrm::VrmApi;
 use bevy::prelude::*;
 use bevy_flurx::prelude::*;
 use bevy_vrm1::prelude::{SpringJointProps, SpringRoot};
+use homunculus_core::prelude::{AssetIdComponent, Persona};
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use overrides::{
+    SpringBoneOverrides, SpringJointBase, load_overrides, overrides_key, record_chain_update,
+    save_overrides, write_joint,
+};
+use presets::SpringBonePreset;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -31,7 +45,7 @@ pub struct SpringBoneChainsResponse {
     pub chains: Vec<SpringBoneChain>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SpringBonePropsUpdate {
@@ -65,6 +79,7 @@ impl VrmApi {
             .await
     }
 
+    /// Updates the properties of a chain and remembers them for the persona's VRM asset.
     pub async fn set_spring_bone_props(
         &self,
         vrm: Entity,
@@ -77,10 +92,66 @@ impl VrmApi {
                     Update,
                     once::run(set_spring_bone_props).with((vrm, chain, update)),
                 )
-                .await;
+                .await
             })
+            .await?
+    }
+
+    /// Applies a built-in preset to every chain and remembers it for the persona's VRM
+    /// asset, replacing the chain changes made before.
+    pub async fn apply_spring_bone_preset(
+        &self,
+        vrm: Entity,
+        preset: String,
+    ) -> ApiResult<SpringBoneChainsResponse> {
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(apply_preset).with((vrm, preset)))
+                    .await?;
+                Ok(task
+                    .will(Update, once::run(list_spring_bones).with(vrm))
+                    .await)
+            })
+            .await?
+    }
+
+    /// Returns the wind and reaction settings.
+    pub async fn spring_bone_settings(&self) -> ApiResult<SpringBoneSettings> {
+        self.0
+            .schedule(move |task| async move { task.will(Update, once::run(get_settings)).await })
             .await
     }
+
+    /// Replaces and persists the wind and reaction settings.
+    pub async fn set_spring_bone_settings(
+        &self,
+        settings: SpringBoneSettings,
+    ) -> ApiResult<SpringBoneSettings> {
+        wind::validate_settings(&settings)?;
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_settings).with(settings))
+                    .await
+            })
+            .await?
+    }
+
+    /// Overrides the wind blowing on one persona.
+    pub async fn set_persona_wind(
+        &self,
+        vrm: Entity,
+        args: PersonaWindArgs,
+    ) -> ApiResult<SpringBoneSettings> {
+        if let Some(wind) = &args.wind {
+            wind::validate_wind(wind)?;
+        }
+        self.0
+            .schedule(move |task| async move {
+                task.will(Update, once::run(put_persona_wind).with((vrm, args.wind)))
+                    .await
+            })
+            .await?
+    }
 }
 
 fn props_from(p: &SpringJointProps) -> SpringBoneProps {
@@ -103,11 +174,19 @@ fn default_props() -> SpringBoneProps {
     }
 }
 
+/// Properties of a joint as configured, without wind and reactions.
+fn configured_props(
+    (props, base): (&SpringJointProps, Option<&SpringJointBase>),
+) -> SpringBoneProps {
+    base.map(|base| base.configured.clone())
+        .unwrap_or_else(|| props_from(props))
+}
+
 fn list_spring_bones(
     In(vrm): In<Entity>,
     spring_roots: Query<&SpringRoot>,
     names: Query<&Name>,
-    joint_props: Query<&SpringJointProps>,
+    joint_props: Query<(&SpringJointProps, Option<&SpringJointBase>)>,
     children: Query<&Children>,
 ) -> SpringBoneChainsResponse {
     SpringBoneChainsResponse {
@@ -119,7 +198,7 @@ fn get_spring_bone(
     In((_vrm, chain)): In<(Entity, Entity)>,
     spring_roots: Query<&SpringRoot>,
     names: Query<&Name>,
-    joint_props: Query<&SpringJointProps>,
+    joint_props: Query<(&SpringJointProps, Option<&SpringJointBase>)>,
 ) -> Option<SpringBoneChain> {
     let root = spring_roots.get(chain).ok()?;
     let joint_names: Vec<String> = root
@@ -136,7 +215,7 @@ fn get_spring_bone(
         .joints
         .first()
         .and_then(|&e| joint_props.get(e).ok())
-        .map(props_from)
+        .map(configured_props)
         .unwrap_or_else(default_props);
     Some(SpringBoneChain {
         entity: chain,
@@ -149,7 +228,7 @@ fn find_all_spring_bones(
     entity: Entity,
     spring_roots: &Query<&SpringRoot>,
     names: &Query<&Name>,
-    joint_props: &Query<&SpringJointProps>,
+    joint_props: &Query<(&SpringJointProps, Option<&SpringJointBase>)>,
     children: &Query<&Children>,
 ) -> Vec<SpringBoneChain> {
     let mut chains = Vec::new();
@@ -164,7 +243,7 @@ fn find_all_spring_bones(
                 .joints
                 .first()
                 .and_then(|&e| joint_props.get(e).ok())
-                .map(props_from)
+                .map(configured_props)
                 .unwrap_or_else(default_props);
             chains.push(SpringBoneChain {
                 entity: child,
@@ -189,30 +268,122 @@ fn find_all_spring_bones(
 }
 
 fn set_spring_bone_props(
-    In((_vrm, chain, update)): In<(Entity, Entity, SpringBonePropsUpdate)>,
+    In((vrm, chain, update)): In<(Entity, Entity, SpringBonePropsUpdate)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<(&Persona, Option<&AssetIdComponent>)>,
     spring_roots: Query<&SpringRoot>,
-    mut joint_props: Query<&mut SpringJointProps>,
-) {
-    let Ok(root) = spring_roots.get(chain) else {
-        return;
-    };
-    for &joint_entity in root.joints.iter() {
-        if let Ok(mut props) = joint_props.get_mut(joint_entity) {
-            if let Some(stiffness) = update.stiffness {
-                props.stiffness = stiffness;
-            }
-            if let Some(drag_force) = update.drag_force {
-                props.drag_force = drag_force;
-            }
-            if let Some(gravity_power) = update.gravity_power {
-                props.gravity_power = gravity_power;
-            }
-            if let Some(gravity_dir) = update.gravity_dir {
-                props.gravity_dir = Vec3::from_array(gravity_dir);
-            }
-            if let Some(hit_radius) = update.hit_radius {
-                props.hit_radius = hit_radius;
+    names: Query<&Name>,
+    mut joints: Query<(&mut SpringJointProps, Option<&mut SpringJointBase>)>,
+) -> ApiResult {
+    let (persona, asset_id) = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let root = spring_roots
+        .get(chain)
+        .map_err(|_| ApiError::EntityNotFound)?;
+    for &joint in root.joints.iter() {
+        if let Ok((mut props, mut base)) = joints.get_mut(joint) {
+            let mut configured = match base.as_deref() {
+                Some(base) => base.configured.clone(),
+                None => props_from(&props),
+            };
+            update.apply_to(&mut configured);
+            write_joint(&mut props, base.as_deref_mut(), configured);
+        }
+    }
+    if let (Some(key), Ok(name)) = (overrides_key(persona, asset_id), names.get(chain)) {
+        let mut overrides = load_overrides(&prefs, &key)?;
+        record_chain_update(&mut overrides, name.to_string(), &update);
+        save_overrides(&prefs, &key, &overrides)?;
+    }
+    Ok(())
+}
+
+fn apply_preset(
+    In((vrm, name)): In<(Entity, String)>,
+    prefs: NonSend<PrefsDatabase>,
+    personas: Query<(&Persona, Option<&AssetIdComponent>)>,
+    spring_roots: Query<&SpringRoot>,
+    children: Query<&Children>,
+    mut joints: Query<(&mut SpringJointProps, &mut SpringJointBase)>,
+) -> ApiResult {
+    let preset = SpringBonePreset::find(&name)
+        .ok_or_else(|| ApiError::SpringBonePresetNotFound(name.clone()))?;
+    let (persona, asset_id) = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    for root in children
+        .iter_descendants(vrm)
+        .filter_map(|e| spring_roots.get(e).ok())
+    {
+        for &joint in root.joints.iter() {
+            if let Ok((mut props, mut base)) = joints.get_mut(joint) {
+                let configured = preset.apply(&base.model);
+                write_joint(&mut props, Some(&mut *base), configured);
             }
         }
     }
+    let Some(key) = overrides_key(persona, asset_id) else {
+        return Ok(());
+    };
+    if name == "default" {
+        prefs
+            .delete(&key)
+            .map_err(|e| ApiError::FailedSave(e.to_string()))
+    } else {
+        let overrides = SpringBoneOverrides {
+            preset: Some(name),
+            ..default()
+        };
+        save_overrides(&prefs, &key, &overrides)
+    }
+}
+
+fn get_settings(settings: Res<SpringBoneSettings>) -> SpringBoneSettings {
+    settings.clone()
+}
+
+fn put_settings(
+    In(settings): In<SpringBoneSettings>,
+    mut current: ResMut<SpringBoneSettings>,
+    prefs: NonSend<PrefsDatabase>,
+) -> ApiResult<SpringBoneSettings> {
+    prefs
+        .save_as(PrefsKeys::SPRING_BONE_SETTINGS, &settings)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))?;
+    *current = settings.clone();
+    Ok(settings)
+}
+
+fn put_persona_wind(
+    In((vrm, wind)): In<(Entity, Option<WindSettings>)>,
+    personas: Query<&Persona>,
+    mut current: ResMut<SpringBoneSettings>,
+    prefs: NonSend<PrefsDatabase>,
+) -> ApiResult<SpringBoneSettings> {
+    let persona = personas.get(vrm).map_err(|_| ApiError::EntityNotFound)?;
+    let mut settings = current.clone();
+    match wind {
+        Some(wind) => settings.personas.insert(persona.id.clone(), wind),
+        None => settings.personas.remove(&persona.id),
+    };
+    prefs
+        .save_as(PrefsKeys::SPRING_BONE_SETTINGS, &settings)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))?;
+    *current = settings.clone();
+    Ok(settings)
+}
+
+pub(super) struct SpringBonesPlugin;
+
+impl Plugin for SpringBonesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpringBoneSettings>()
+            .add_systems(Startup, wind::load_settings)
+            .add_systems(
+                Update,
+                (
+                    overrides::capture_joint_bases,
+                    overrides::load_spring_bone_overrides,
+                    wind::blow_spring_bones,
+                )
+                    .chain(),
+            );
+    }
 }
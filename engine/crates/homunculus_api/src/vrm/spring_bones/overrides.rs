@@ -0,0 +1,231 @@
+//! Spring bone properties as configured, persisted per persona and VRM asset.
+
+use super::presets::SpringBonePreset;
+use super::{SpringBoneProps, SpringBonePropsUpdate, props_from};
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use bevy_vrm1::prelude::{Initialized, SpringJointProps, SpringRoot};
+use homunculus_core::prelude::{AssetIdComponent, Persona};
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Properties of a spring joint before wind and reactions are added.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub(crate) struct SpringJointBase {
+    /// The properties the VRM model was authored with.
+    pub(crate) model: SpringBoneProps,
+    /// The properties after presets and API changes.
+    pub(crate) configured: SpringBoneProps,
+}
+
+/// Spring bone changes of one persona on one VRM asset.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct SpringBoneOverrides {
+    /// Preset applied to every chain before `chains`.
+    pub(crate) preset: Option<String>,
+    /// Chain name to the properties changed on that chain.
+    pub(crate) chains: HashMap<String, SpringBonePropsUpdate>,
+}
+
+impl SpringBoneOverrides {
+    /// Properties of a chain with the model's `model` properties.
+    pub(crate) fn configure(
+        &self,
+        chain: Option<&str>,
+        model: &SpringBoneProps,
+    ) -> SpringBoneProps {
+        let mut props = match self.preset.as_deref().and_then(SpringBonePreset::find) {
+            Some(preset) => preset.apply(model),
+            None => model.clone(),
+        };
+        if let Some(update) = chain.and_then(|chain| self.chains.get(chain)) {
+            update.apply_to(&mut props);
+        }
+        props
+    }
+}
+
+impl SpringBonePropsUpdate {
+    pub(crate) fn apply_to(&self, props: &mut SpringBoneProps) {
+        if let Some(stiffness) = self.stiffness {
+            props.stiffness = stiffness;
+        }
+        if let Some(drag_force) = self.drag_force {
+            props.drag_force = drag_force;
+        }
+        if let Some(gravity_power) = self.gravity_power {
+            props.gravity_power = gravity_power;
+        }
+        if let Some(gravity_dir) = self.gravity_dir {
+            props.gravity_dir = gravity_dir;
+        }
+        if let Some(hit_radius) = self.hit_radius {
+            props.hit_radius = hit_radius;
+        }
+    }
+
+    /// Adds the fields set in `other`, replacing those set in both.
+    fn merge(&mut self, other: &Self) {
+        self.stiffness = other.stiffness.or(self.stiffness);
+        self.drag_force = other.drag_force.or(self.drag_force);
+        self.gravity_power = other.gravity_power.or(self.gravity_power);
+        self.gravity_dir = other.gravity_dir.or(self.gravity_dir);
+        self.hit_radius = other.hit_radius.or(self.hit_radius);
+    }
+}
+
+/// Prefs key of the persona's overrides for its VRM asset, or `None` without a VRM.
+pub(crate) fn overrides_key(
+    persona: &Persona,
+    asset_id: Option<&AssetIdComponent>,
+) -> Option<String> {
+    asset_id.map(|asset_id| PrefsKeys::spring_bones(persona.id.as_ref(), &asset_id.0.to_string()))
+}
+
+pub(crate) fn load_overrides(prefs: &PrefsDatabase, key: &str) -> ApiResult<SpringBoneOverrides> {
+    prefs
+        .load_as::<SpringBoneOverrides>(key)
+        .map(Option::unwrap_or_default)
+        .map_err(|e| ApiError::FailedLoad(e.to_string()))
+}
+
+pub(crate) fn save_overrides(
+    prefs: &PrefsDatabase,
+    key: &str,
+    overrides: &SpringBoneOverrides,
+) -> ApiResult {
+    prefs
+        .save_as(key, overrides)
+        .map_err(|e| ApiError::FailedSave(e.to_string()))
+}
+
+/// Records a change to one chain in `overrides`.
+pub(crate) fn record_chain_update(
+    overrides: &mut SpringBoneOverrides,
+    chain: String,
+    update: &SpringBonePropsUpdate,
+) {
+    overrides.chains.entry(chain).or_default().merge(update);
+}
+
+/// Writes configured properties to a joint, keeping its model properties.
+pub(crate) fn write_joint(
+    props: &mut SpringJointProps,
+    base: Option<&mut SpringJointBase>,
+    configured: SpringBoneProps,
+) {
+    props.stiffness = configured.stiffness;
+    props.drag_force = configured.drag_force;
+    props.gravity_power = configured.gravity_power;
+    props.gravity_dir = Vec3::from_array(configured.gravity_dir);
+    props.hit_radius = configured.hit_radius;
+    if let Some(base) = base {
+        base.configured = configured;
+    }
+}
+
+/// Remembers the authored properties of every new spring joint.
+pub(crate) fn capture_joint_bases(
+    mut commands: Commands,
+    joints: Query<(Entity, &SpringJointProps), Without<SpringJointBase>>,
+) {
+    for (joint, props) in joints.iter() {
+        let model = props_from(props);
+        commands.entity(joint).try_insert(SpringJointBase {
+            configured: model.clone(),
+            model,
+        });
+    }
+}
+
+/// Applies the stored overrides once a persona's VRM has loaded.
+pub(crate) fn load_spring_bone_overrides(
+    vrms: Query<(Entity, &Persona, Option<&AssetIdComponent>), Added<Initialized>>,
+    prefs: NonSend<PrefsDatabase>,
+    spring_roots: Query<(Entity, &SpringRoot)>,
+    names: Query<&Name>,
+    children: Query<&Children>,
+    mut joints: Query<(&mut SpringJointProps, &mut SpringJointBase)>,
+) {
+    for (vrm, persona, asset_id) in vrms.iter() {
+        let Some(key) = overrides_key(persona, asset_id) else {
+            continue;
+        };
+        let overrides = match load_overrides(&prefs, &key) {
+            Ok(overrides) if overrides != SpringBoneOverrides::default() => overrides,
+            Ok(_) => continue,
+            Err(e) => {
+                warn!(
+                    "Failed to load spring bone overrides of {}: {e}",
+                    persona.id
+                );
+                continue;
+            }
+        };
+        for (chain, root) in children
+            .iter_descendants(vrm)
+            .filter_map(|e| spring_roots.get(e).ok())
+        {
+            let name = names.get(chain).ok().map(Name::as_str);
+            for &joint in root.joints.iter() {
+                if let Ok((mut props, mut base)) = joints.get_mut(joint) {
+                    let configured = overrides.configure(name, &base.model);
+                    write_joint(&mut props, Some(&mut *base), configured);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> SpringBoneProps {
+        SpringBoneProps {
+            stiffness: 1.0,
+            drag_force: 0.4,
+            gravity_power: 0.5,
+            gravity_dir: [0.0, -1.0, 0.0],
+            hit_radius: 0.02,
+        }
+    }
+
+    #[test]
+    fn chain_updates_apply_on_top_of_the_preset() {
+        let mut overrides = SpringBoneOverrides {
+            preset: Some("stiff".to_string()),
+            ..default()
+        };
+        record_chain_update(
+            &mut overrides,
+            "hair".to_string(),
+            &SpringBonePropsUpdate {
+                drag_force: Some(0.1),
+                ..default()
+            },
+        );
+        record_chain_update(
+            &mut overrides,
+            "hair".to_string(),
+            &SpringBonePropsUpdate {
+                hit_radius: Some(0.05),
+                ..default()
+            },
+        );
+
+        let hair = overrides.configure(Some("hair"), &model());
+        let skirt = overrides.configure(Some("skirt"), &model());
+        assert_eq!(hair.stiffness, skirt.stiffness);
+        assert!(model().stiffness < skirt.stiffness);
+        assert_eq!(hair.drag_force, 0.1);
+        assert_eq!(hair.hit_radius, 0.05);
+        assert_eq!(skirt.hit_radius, model().hit_radius);
+        assert_eq!(
+            SpringBoneOverrides::default().configure(None, &model()),
+            model()
+        );
+    }
+}
@@ -0,0 +1,69 @@
+//! Built-in spring bone presets, applied to every chain relative to the model's own values.
+
+use super::SpringBoneProps;
+
+/// Names of the built-in spring bone presets.
+pub const SPRING_BONE_PRESETS: [&str; 4] = ["default", "stiff", "soft", "floaty"];
+
+/// Factors a preset scales the model's spring bone properties by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SpringBonePreset {
+    stiffness: f32,
+    drag_force: f32,
+    gravity_power: f32,
+}
+
+impl SpringBonePreset {
+    /// Returns the built-in preset called `name`.
+    pub(crate) fn find(name: &str) -> Option<Self> {
+        let (stiffness, drag_force, gravity_power) = match name {
+            "default" => (1.0, 1.0, 1.0),
+            "stiff" => (3.0, 1.5, 1.0),
+            "soft" => (0.5, 0.7, 1.0),
+            "floaty" => (0.3, 0.3, 0.2),
+            _ => return None,
+        };
+        Some(Self {
+            stiffness,
+            drag_force,
+            gravity_power,
+        })
+    }
+
+    pub(crate) fn apply(&self, model: &SpringBoneProps) -> SpringBoneProps {
+        SpringBoneProps {
+            stiffness: model.stiffness * self.stiffness,
+            drag_force: (model.drag_force * self.drag_force).clamp(0.0, 1.0),
+            gravity_power: model.gravity_power * self.gravity_power,
+            ..model.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_preset_exists() {
+        for name in SPRING_BONE_PRESETS {
+            assert!(SpringBonePreset::find(name).is_some(), "{name}");
+        }
+        assert!(SpringBonePreset::find("wobbly").is_none());
+
+        let model = SpringBoneProps {
+            stiffness: 1.0,
+            drag_force: 0.8,
+            gravity_power: 0.5,
+            gravity_dir: [0.0, -1.0, 0.0],
+            hit_radius: 0.02,
+        };
+        let stiff = SpringBonePreset::find("stiff").unwrap().apply(&model);
+        assert_eq!(stiff.stiffness, 3.0);
+        assert_eq!(stiff.drag_force, 1.0);
+        assert_eq!(
+            SpringBonePreset::find("default").unwrap().apply(&model),
+            model
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Wind and motion reactions, added to the gravity of spring joints.
+//!
+//! Both are forces in world space, in the same units as `gravityPower`. Each frame
+//! they are added to the configured gravity of every joint of the persona, so hair and
+//! clothing sway in the wind and swing when the persona is dragged or lands.
+
+use super::overrides::SpringJointBase;
+use crate::error::{ApiError, ApiResult};
+use bevy::prelude::*;
+use bevy_vrm1::prelude::{Initialized, SpringJointProps, SpringRoot};
+use homunculus_core::prelude::{Persona, PersonaId, PersonaState};
+use homunculus_drag::physics::LANDING_STATE;
+use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f32::consts::TAU;
+
+/// Upper bound of the reaction force.
+const MAX_REACTION: f32 = 1.0;
+
+/// How quickly the reaction force follows the persona's acceleration, per second.
+const REACTION_RESPONSE: f32 = 12.0;
+
+/// Wind blowing on a persona's spring bones.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct WindSettings {
+    pub enabled: bool,
+    /// Direction the wind blows toward, in world space.
+    pub direction: [f32; 3],
+    /// Average force of the wind, from `0` to `5`.
+    pub strength: f32,
+    /// How far gusts push the force above and below `strength`, from `0` to `1`.
+    pub gustiness: f32,
+    /// Rate of gusts per second, from `0` to `10`.
+    pub gust_frequency: f32,
+}
+
+impl Default for WindSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            direction: [1.0, 0.0, 0.0],
+            strength: 0.3,
+            gustiness: 0.5,
+            gust_frequency: 0.4,
+        }
+    }
+}
+
+impl WindSettings {
+    /// Wind force at `secs`. `phase` keeps the gusts of different personas apart.
+    fn force(&self, secs: f32, phase: f32) -> Vec3 {
+        if !self.enabled {
+            return Vec3::ZERO;
+        }
+        let gust = gust(secs * self.gust_frequency, phase);
+        Vec3::from_array(self.direction).normalize_or_zero()
+            * self.strength
+            * (1.0 + self.gustiness * gust)
+    }
+}
+
+/// Smooth noise from `-1` to `1`, built from sines of unrelated frequencies.
+fn gust(cycles: f32, phase: f32) -> f32 {
+    let x = TAU * cycles + phase;
+    0.5 * x.sin() + 0.3 * (2.3 * x + 1.7 * phase).sin() + 0.2 * (5.1 * x + 0.6 * phase).sin()
+}
+
+/// Wind and reaction settings for spring bones, persisted under
+/// [`PrefsKeys::SPRING_BONE_SETTINGS`].
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct SpringBoneSettings {
+    /// Wind for personas without their own.
+    pub wind: WindSettings,
+    /// Per-persona wind, replacing `wind`.
+    #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, WindSettings>))]
+    pub personas: HashMap<PersonaId, WindSettings>,
+    /// How strongly spring bones swing when a persona is dragged or lands, from `0`
+    /// (off) to `1`.
+    pub reaction: f32,
+}
+
+impl Default for SpringBoneSettings {
+    fn default() -> Self {
+        Self {
+            wind: WindSettings::default(),
+            personas: HashMap::new(),
+            reaction: 0.03,
+        }
+    }
+}
+
+impl SpringBoneSettings {
+    /// The wind blowing on the persona.
+    pub fn wind_for(&self, persona: &PersonaId) -> &WindSettings {
+        self.personas.get(persona).unwrap_or(&self.wind)
+    }
+}
+
+/// Request body for overriding the wind of one persona.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaWindArgs {
+    /// The persona's own wind; `null` follows the global wind.
+    pub wind: Option<WindSettings>,
+}
+
+pub(crate) fn validate_wind(wind: &WindSettings) -> ApiResult {
+    if !wind.direction.iter().all(|v| v.is_finite())
+        || (wind.enabled && Vec3::from_array(wind.direction) == Vec3::ZERO)
+    {
+        return Err(ApiError::InvalidInput(
+            "wind direction must be a finite, non-zero vector".to_string(),
+        ));
+    }
+    if !(0.0..=5.0).contains(&wind.strength) {
+        return Err(ApiError::InvalidInput(
+            "wind strength must be between 0 and 5".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&wind.gustiness) {
+        return Err(ApiError::InvalidInput(
+            "gustiness must be between 0 and 1".to_string(),
+        ));
+    }
+    if !(0.0..=10.0).contains(&wind.gust_frequency) {
+        return Err(ApiError::InvalidInput(
+            "gustFrequency must be between 0 and 10".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_settings(settings: &SpringBoneSettings) -> ApiResult {
+    validate_wind(&settings.wind)?;
+    settings.personas.values().try_for_each(validate_wind)?;
+    if !(0.0..=1.0).contains(&settings.reaction) {
+        return Err(ApiError::InvalidInput(
+            "reaction must be between 0 and 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// A persona's recent motion and the force it currently adds to its spring bones.
+#[derive(Component, Debug, Default)]
+pub(crate) struct SpringMotion {
+    position: Option<Vec3>,
+    velocity: Vec3,
+    reaction: Vec3,
+    /// The force written to the joints last frame.
+    applied: Vec3,
+}
+
+impl SpringMotion {
+    /// Tracks the persona's motion and returns the reaction force.
+    ///
+    /// While `reacting`, the force pushes against the persona's acceleration, as
+    /// inertia would; otherwise it dies down.
+    fn react(&mut self, position: Vec3, dt: f32, reacting: bool, strength: f32) -> Vec3 {
+        if dt <= 0.0 {
+            return self.reaction;
+        }
+        let velocity = self
+            .position
+            .map(|last| (position - last) / dt)
+            .unwrap_or_default();
+        let acceleration = (velocity - self.velocity) / dt;
+        self.position = Some(position);
+        self.velocity = velocity;
+        let target = if reacting {
+            (-acceleration * strength).clamp_length_max(MAX_REACTION)
+        } else {
+            Vec3::ZERO
+        };
+        self.reaction = self
+            .reaction
+            .lerp(target, 1.0 - (-REACTION_RESPONSE * dt).exp());
+        self.reaction
+    }
+}
+
+pub(crate) fn load_settings(mut commands: Commands, prefs: NonSend<PrefsDatabase>) {
+    if let Some(settings) = prefs
+        .load_as::<SpringBoneSettings>(PrefsKeys::SPRING_BONE_SETTINGS)
+        .ok()
+        .flatten()
+    {
+        commands.insert_resource(settings);
+    }
+}
+
+/// Adds wind and reactions to the configured gravity of every persona's spring joints.
+pub(crate) fn blow_spring_bones(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<SpringBoneSettings>,
+    mut vrms: Query<
+        (
+            Entity,
+            &Persona,
+            &Transform,
+            Option<&PersonaState>,
+            Option<&mut SpringMotion>,
+        ),
+        With<Initialized>,
+    >,
+    children: Query<&Children>,
+    spring_roots: Query<&SpringRoot>,
+    mut joints: Query<(&mut SpringJointProps, &SpringJointBase)>,
+) {
+    for (vrm, persona, tf, state, motion) in vrms.iter_mut() {
+        let Some(mut motion) = motion else {
+            commands.entity(vrm).try_insert(SpringMotion::default());
+            continue;
+        };
+        let reacting = state.is_some_and(|s| s.0 == PersonaState::DRAG || s.0 == LANDING_STATE);
+        let phase = (vrm.to_bits() % 1024) as f32 * 0.618;
+        let force = settings
+            .wind_for(&persona.id)
+            .force(time.elapsed_secs(), phase)
+            + motion.react(
+                tf.translation,
+                time.delta_secs(),
+                reacting,
+                settings.reaction,
+            );
+        if force.length_squared() < 1e-8 && motion.applied == Vec3::ZERO {
+            continue;
+        }
+        motion.applied = if force.length_squared() < 1e-8 {
+            Vec3::ZERO
+        } else {
+            force
+        };
+
+        for root in children
+            .iter_descendants(vrm)
+            .filter_map(|e| spring_roots.get(e).ok())
+        {
+            for &joint in root.joints.iter() {
+                let Ok((mut props, base)) = joints.get_mut(joint) else {
+                    continue;
+                };
+                let dir = Vec3::from_array(base.configured.gravity_dir);
+                let gravity =
+                    dir.normalize_or_zero() * base.configured.gravity_power + motion.applied;
+                props.gravity_power = gravity.length();
+                props.gravity_dir = gravity.normalize_or(dir);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gusts_vary_around_the_wind_strength() {
+        let wind = WindSettings {
+            enabled: true,
+            direction: [2.0, 0.0, 0.0],
+            strength: 0.4,
+            gustiness: 0.5,
+            gust_frequency: 1.0,
+        };
+        let forces: Vec<f32> = (0..200)
+            .map(|i| wind.force(i as f32 * 0.05, 1.0).x)
+            .collect();
+        assert!(forces.iter().all(|f| (0.2..=0.6).contains(f)));
+        assert!(forces.iter().any(|f| *f < 0.35) && forces.iter().any(|f| 0.45 < *f));
+
+        let calm = WindSettings {
+            enabled: false,
+            ..wind
+        };
+        assert_eq!(calm.force(1.0, 0.0), Vec3::ZERO);
+    }
+
+    #[test]
+    fn landing_swings_spring_bones_down_and_settles() {
+        let mut motion = SpringMotion::default();
+        let dt = 1.0 / 60.0;
+        motion.react(Vec3::new(0.0, 1.0, 0.0), dt, false, 0.03);
+        motion.react(Vec3::new(0.0, 0.9, 0.0), dt, false, 0.03);
+        let landed = motion.react(Vec3::new(0.0, 0.9, 0.0), dt, true, 0.03);
+        assert!(landed.y < 0.0);
+        assert!(landed.length() <= MAX_REACTION);
+
+        let mut settled = landed;
+        for _ in 0..120 {
+            settled = motion.react(Vec3::new(0.0, 0.9, 0.0), dt, false, 0.03);
+        }
+        assert!(settled.length() < 1e-3);
+    }
+}
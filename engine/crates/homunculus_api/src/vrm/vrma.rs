@@ -22,7 +22,7 @@ impl VrmApi {
     }
 }
 
-fn fetch_vrma(
+pub(crate) fn fetch_vrma(
     In((vrm_entity, asset_id)): In<(Entity, AssetId)>,
     mut commands: Commands,
     asset_resolver: AssetResolver,
@@ -20,6 +20,32 @@ pub struct VrmaState {
     pub elapsed_secs: f32,
 }
 
+/// Repetition behavior of a VRMA animation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Repeat {
+    /// Loop forever.
+    Forever,
+    /// Play once and stop.
+    Never,
+    /// Repeat a fixed number of times.
+    Count {
+        /// Number of repetitions.
+        count: u32,
+    },
+}
+
+impl From<Repeat> for RepeatAnimation {
+    fn from(repeat: Repeat) -> Self {
+        match repeat {
+            Repeat::Forever => RepeatAnimation::Forever,
+            Repeat::Never => RepeatAnimation::Never,
+            Repeat::Count { count } => RepeatAnimation::Count(count),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VrmaInfo {
@@ -87,12 +113,12 @@ impl VrmAnimationApi {
     }
 }
 
-fn play(In(event): In<PlayVrma>, mut commands: Commands) {
+pub(crate) fn play(In(event): In<PlayVrma>, mut commands: Commands) {
     info!("[vrma] play trigger: vrma={}", event.vrma);
     commands.trigger(event);
 }
 
-fn wait_animation_finished(
+pub(crate) fn wait_animation_finished(
     In((vrma, wait_finish)): In<(Entity, bool)>,
     vrmas: Query<&VrmaDuration>,
 ) -> Option<Duration> {
@@ -102,7 +128,7 @@ fn wait_animation_finished(
     vrmas.get(vrma).ok().map(|duration| duration.0)
 }
 
-fn stop(In(vrma): In<Entity>, mut commands: Commands) {
+pub(crate) fn stop(In(vrma): In<Entity>, mut commands: Commands) {
     commands.trigger(StopVrma { entity: vrma });
 }
 
@@ -148,7 +174,7 @@ fn get_vrma_state(
     }
 }
 
-fn set_vrma_speed(
+pub(crate) fn set_vrma_speed(
     In((vrma, speed)): In<(Entity, f32)>,
     vrma_players: Query<&VrmaAnimationPlayers>,
     mut players: Query<&mut AnimationPlayer>,
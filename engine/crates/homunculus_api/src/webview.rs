@@ -1,12 +1,21 @@
-mod close;
+pub(super) mod close;
 pub(crate) mod constraint;
+#[cfg(feature = "gui")]
 mod get;
+#[cfg(not(feature = "gui"))]
+pub(super) mod headless;
 mod is_closed;
+#[cfg(feature = "gui")]
 mod linked_persona;
+#[cfg(feature = "gui")]
 mod list;
+#[cfg(feature = "gui")]
 mod navigate;
+#[cfg(feature = "gui")]
 pub(super) mod open;
+#[cfg(feature = "gui")]
 mod reload;
+#[cfg(feature = "gui")]
 mod update;
 
 use crate::api;
@@ -18,6 +27,8 @@ pub struct WebviewApiPlugin;
 
 impl Plugin for WebviewApiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((open::WebviewOpenPlugin, constraint::ConstraintPlugin));
+        app.add_plugins(constraint::ConstraintPlugin);
+        #[cfg(feature = "gui")]
+        app.add_plugins(open::WebviewOpenPlugin);
     }
 }
@@ -14,6 +14,6 @@ impl WebviewApi {
     }
 }
 
-fn close(In(entity): In<Entity>, mut commands: Commands) {
+pub(crate) fn close(In(entity): In<Entity>, mut commands: Commands) {
     commands.entity(entity).try_despawn();
 }
@@ -0,0 +1,81 @@
+//! [`WebviewApi`] for builds without the `gui` feature.
+//!
+//! No webview can exist without CEF, so opening one fails with
+//! [`ApiError::Unsupported`] and every lookup fails with [`ApiError::WebviewNotFound`].
+
+use crate::error::{ApiError, ApiResult};
+use crate::prelude::WebviewApi;
+use bevy::prelude::*;
+use homunculus_core::prelude::{
+    NavigationState, PersonaId, TransformArgs, WebviewInfo, WebviewOpenOptions,
+    WebviewPatchRequest, WebviewSource,
+};
+
+/// Error message returned when a webview is opened in a build without the `gui` feature.
+pub(crate) const WEBVIEWS_UNSUPPORTED: &str = "webviews require the `gui` feature";
+
+impl WebviewApi {
+    pub async fn open(&self, _options: WebviewOpenOptions) -> ApiResult<Entity> {
+        Err(ApiError::Unsupported(WEBVIEWS_UNSUPPORTED.to_string()))
+    }
+
+    pub async fn list(&self) -> ApiResult<Vec<WebviewInfo>> {
+        Ok(Vec::new())
+    }
+
+    pub async fn get(&self, webview: Entity) -> ApiResult<WebviewInfo> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn linked_persona(&self, webview: Entity) -> ApiResult<Option<PersonaId>> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn set_linked_persona(
+        &self,
+        webview: Entity,
+        _persona_id: PersonaId,
+    ) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn unlink_persona(&self, webview: Entity) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn go_back(&self, webview: Entity) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn go_forward(&self, webview: Entity) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn navigation_state(&self, webview: Entity) -> ApiResult<NavigationState> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn navigate(&self, webview: Entity, _source: WebviewSource) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn reload(&self, webview: Entity) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn set_transform(&self, webview: Entity, _transform: TransformArgs) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn set_size(&self, webview: Entity, _size: Vec2) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn set_viewport_size(&self, webview: Entity, _size: Vec2) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+
+    pub async fn patch(&self, webview: Entity, _request: WebviewPatchRequest) -> ApiResult<()> {
+        Err(ApiError::WebviewNotFound(webview))
+    }
+}
@@ -55,7 +55,7 @@ fn visible(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn create_global_webview(
+pub(crate) fn create_global_webview(
     In(options): In<WebviewOpenOptions>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -0,0 +1,91 @@
+//! Helpers for subcommands that talk to a running engine.
+
+use clap::Args;
+use homunculus_client::{ClientError, HomunculusClient};
+use homunculus_utils::config::HomunculusConfig;
+use homunculus_utils::error::{UtilError, UtilResult};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Connection and output options shared by the runtime subcommands.
+#[derive(Args)]
+pub struct EngineArgs {
+    /// Engine port (defaults to the port in config.toml)
+    #[arg(long, global = true)]
+    pub port: Option<u16>,
+    /// Print machine-readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+}
+
+impl EngineArgs {
+    pub fn client(&self) -> UtilResult<HomunculusClient> {
+        client(self.port)
+    }
+
+    /// Prints `value` as JSON in `--json` mode, or `text()` otherwise.
+    pub fn output<T: Serialize>(&self, value: &T, text: impl FnOnce() -> String) -> UtilResult {
+        if self.json {
+            print_json(value)
+        } else {
+            println!("{}", text());
+            Ok(())
+        }
+    }
+}
+
+/// Returns a client for the engine on `port`, or on the port from `config.toml`.
+pub fn client(port: Option<u16>) -> UtilResult<HomunculusClient> {
+    match port {
+        Some(port) => Ok(HomunculusClient::local(port)),
+        None => Ok(HomunculusClient::from_config(&HomunculusConfig::load()?)),
+    }
+}
+
+/// Runs `future` to completion on a single-threaded runtime.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build the tokio runtime")
+        .block_on(future)
+}
+
+/// Runs a client call to completion, converting its error into the CLI's error type.
+pub fn call<T>(future: impl Future<Output = Result<T, ClientError>>) -> UtilResult<T> {
+    block_on(future).map_err(client_error)
+}
+
+/// Converts a client error into the CLI's error type.
+pub fn client_error(e: ClientError) -> UtilError {
+    UtilError::Other(anyhow::anyhow!(e))
+}
+
+/// Prints `value` as pretty JSON.
+pub fn print_json<T: Serialize>(value: &T) -> UtilResult {
+    let json = serde_json::to_string_pretty(value).map_err(|e| anyhow::anyhow!(e))?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Parses a payload argument as JSON, falling back to a plain string.
+///
+/// `'{"x":1}'`, `42` and `true` are sent as JSON; `hello` is sent as `"hello"`.
+pub fn parse_payload(input: &str) -> Value {
+    serde_json::from_str(input).unwrap_or_else(|_| Value::String(input.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_payload() {
+        assert_eq!(parse_payload(r#"{"x":1}"#), json!({"x": 1}));
+        assert_eq!(parse_payload("42"), json!(42));
+        assert_eq!(parse_payload("null"), Value::Null);
+        assert_eq!(parse_payload("hello world"), json!("hello world"));
+        assert_eq!(parse_payload("{broken"), json!("{broken"));
+    }
+}
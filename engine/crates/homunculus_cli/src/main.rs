@@ -3,8 +3,17 @@
 use clap::{Parser, Subcommand};
 
 mod config;
+mod engine;
 mod mods;
+mod persona;
 mod prefs;
+mod process;
+mod replay;
+mod rpc;
+mod signal;
+mod status;
+mod vrma;
+mod webview;
 
 /// Top-level CLI structure for the `hmcs` command.
 #[derive(Parser)]
@@ -23,17 +32,64 @@ enum Commands {
     Config(config::ConfigArgs),
     /// Manage mods.
     Mod(mods::ModsArgs),
+    /// Re-issue a recorded API session against the engine and diff the responses
+    Replay(replay::ReplayArgs),
+    /// Show whether the engine is running
+    Status(status::StatusArgs),
+    /// List, spawn and despawn personas, or make them speak
+    Persona(persona::PersonaArgs),
+    /// Play VRMA animations
+    Vrma(vrma::VrmaArgs),
+    /// Send and watch signals
+    Signal(signal::SignalArgs),
+    /// Open and close webviews
+    Webview(webview::WebviewArgs),
+    /// List and stop MOD processes
+    Process(process::ProcessArgs),
+    /// Call MOD RPC methods
+    Rpc(rpc::RpcArgs),
+}
+
+impl Commands {
+    /// Whether a failure sets a non-zero exit status.
+    ///
+    /// Limited to the runtime commands, which scripts and hooks use as guards;
+    /// the other subcommands keep their existing exit behavior.
+    fn exits_on_error(&self) -> bool {
+        matches!(
+            self,
+            Commands::Status(_)
+                | Commands::Persona(_)
+                | Commands::Vrma(_)
+                | Commands::Signal(_)
+                | Commands::Webview(_)
+                | Commands::Process(_)
+                | Commands::Rpc(_)
+        )
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
+    let exits_on_error = cli.command.exits_on_error();
     let result = match cli.command {
         Commands::Prefs(args) => args.execute(),
         Commands::Config(args) => args.execute(),
         Commands::Mod(args) => args.execute(),
+        Commands::Replay(args) => args.execute(),
+        Commands::Status(args) => args.execute(),
+        Commands::Persona(args) => args.execute(),
+        Commands::Vrma(args) => args.execute(),
+        Commands::Signal(args) => args.execute(),
+        Commands::Webview(args) => args.execute(),
+        Commands::Process(args) => args.execute(),
+        Commands::Rpc(args) => args.execute(),
     };
 
     if let Err(e) = result {
         eprintln!("{e}");
+        if exits_on_error {
+            std::process::exit(1);
+        }
     }
 }
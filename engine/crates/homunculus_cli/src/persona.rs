@@ -0,0 +1,148 @@
+//! `hmcs persona` — list, spawn and despawn personas, and make them speak.
+
+use crate::engine::{EngineArgs, call};
+use clap::{Args, Subcommand};
+use comfy_table::Table;
+use comfy_table::presets::NOTHING;
+use homunculus_client::{HomunculusClient, PersonaSnapshot};
+use homunculus_utils::error::{UtilError, UtilResult};
+use serde_json::json;
+
+/// CLI arguments for the `hmcs persona` subcommand.
+#[derive(Args)]
+pub struct PersonaArgs {
+    #[command(subcommand)]
+    pub command: PersonaSubcommand,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// Operations on the personas of a running engine.
+#[derive(Subcommand)]
+pub enum PersonaSubcommand {
+    /// List personas and their state
+    List,
+    /// Spawn a stored persona into the scene
+    Spawn {
+        /// Persona ID
+        id: String,
+    },
+    /// Remove a persona from the scene, keeping its record
+    Despawn {
+        /// Persona ID
+        id: String,
+    },
+    /// Speak text through a TTS MOD (e.g. @hmcs/voicevox)
+    Say {
+        /// Persona ID
+        id: String,
+        /// Text to speak; each argument is spoken as one sentence
+        #[arg(required = true)]
+        text: Vec<String>,
+        /// TTS method as `mod-name:method` (defaults to the first method in the `tts` category)
+        #[arg(long, value_name = "MOD:METHOD")]
+        tts: Option<String>,
+    },
+}
+
+impl PersonaArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        match self.command {
+            PersonaSubcommand::List => {
+                let personas = call(client.list_personas())?;
+                self.engine.output(&personas, || format_table(&personas))
+            }
+            PersonaSubcommand::Spawn { id } => {
+                let persona = call(client.spawn_persona(&id))?;
+                self.engine
+                    .output(&persona, || format!("Spawned {}", persona.id))
+            }
+            PersonaSubcommand::Despawn { id } => {
+                call(client.despawn_persona(&id))?;
+                self.engine
+                    .output(&json!({ "id": id, "spawned": false }), || {
+                        format!("Despawned {id}")
+                    })
+            }
+            PersonaSubcommand::Say { id, text, tts } => {
+                let (mod_name, method) = match tts {
+                    Some(tts) => parse_method(&tts)?,
+                    None => find_tts(&client)?,
+                };
+                let body = json!({ "personaId": id, "text": text });
+                let result = call(client.rpc_call(&mod_name, &method, Some(&body)))?;
+                self.engine
+                    .output(&result, || format!("{id} spoke via {mod_name}:{method}"))
+            }
+        }
+    }
+}
+
+/// Splits `mod-name:method`, using the last `:` so scoped names like `@hmcs/voicevox` work.
+fn parse_method(input: &str) -> UtilResult<(String, String)> {
+    match input.rsplit_once(':') {
+        Some((mod_name, method)) if !mod_name.is_empty() && !method.is_empty() => {
+            Ok((mod_name.to_owned(), method.to_owned()))
+        }
+        _ => Err(UtilError::Other(anyhow::anyhow!(
+            "invalid TTS method '{input}', expected mod-name:method"
+        ))),
+    }
+}
+
+/// Picks the first registered RPC method whose `meta.category` is `tts`.
+fn find_tts(client: &HomunculusClient) -> UtilResult<(String, String)> {
+    let registrations = call(client.rpc_registrations())?;
+    let mut candidates: Vec<(String, String)> = registrations
+        .into_iter()
+        .flat_map(|(mod_name, registration)| {
+            registration
+                .methods
+                .into_iter()
+                .filter(|(_, info)| info.category() == Some("tts"))
+                .map(move |(method, _)| (mod_name.clone(), method))
+        })
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next().ok_or_else(|| {
+        UtilError::Other(anyhow::anyhow!(
+            "no TTS MOD is running; install one (e.g. @hmcs/voicevox) or pass --tts"
+        ))
+    })
+}
+
+fn format_table(personas: &[PersonaSnapshot]) -> String {
+    if personas.is_empty() {
+        return "No personas found.".to_string();
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(["ID", "NAME", "STATE", "SPAWNED", "VRM"]);
+    for persona in personas {
+        table.add_row([
+            persona.id.as_str(),
+            persona.name.as_deref().unwrap_or(""),
+            persona.state.as_str(),
+            if persona.spawned { "yes" } else { "no" },
+            persona.vrm_asset_id.as_deref().unwrap_or(""),
+        ]);
+    }
+    table.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_method_splits_on_last_colon() {
+        assert_eq!(
+            parse_method("@hmcs/voicevox:speak").unwrap(),
+            ("@hmcs/voicevox".to_string(), "speak".to_string())
+        );
+        assert!(parse_method("speak").is_err());
+        assert!(parse_method("voicevox:").is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! `hmcs process` — inspect and stop processes managed by the engine.
+
+use crate::engine::{EngineArgs, call};
+use clap::{Args, Subcommand};
+use comfy_table::Table;
+use comfy_table::presets::NOTHING;
+use homunculus_client::ProcessInfo;
+use homunculus_utils::error::UtilResult;
+use serde_json::json;
+
+/// CLI arguments for the `hmcs process` subcommand.
+#[derive(Args)]
+pub struct ProcessArgs {
+    #[command(subcommand)]
+    pub command: ProcessSubcommand,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// Managed process operations.
+#[derive(Subcommand)]
+pub enum ProcessSubcommand {
+    /// List running MOD command processes
+    Ls,
+    /// Stop a running process
+    Kill {
+        /// Handle ID, as printed by `ls`
+        handle_id: String,
+    },
+}
+
+impl ProcessArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        match self.command {
+            ProcessSubcommand::Ls => {
+                let processes = call(client.list_processes())?;
+                self.engine.output(&processes, || format_table(&processes))
+            }
+            ProcessSubcommand::Kill { handle_id } => {
+                call(client.stop_process(&handle_id))?;
+                self.engine.output(&json!({ "handleId": handle_id }), || {
+                    format!("Stopped {handle_id}")
+                })
+            }
+        }
+    }
+}
+
+fn format_table(processes: &[ProcessInfo]) -> String {
+    if processes.is_empty() {
+        return "No processes running.".to_string();
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(["HANDLE", "PID", "COMMAND", "STARTED"]);
+    for process in processes {
+        let command = std::iter::once(process.command.as_str())
+            .chain(process.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        table.add_row([
+            process.handle_id.clone(),
+            process.pid.to_string(),
+            command,
+            process.started_at.clone(),
+        ]);
+    }
+    table.to_string()
+}
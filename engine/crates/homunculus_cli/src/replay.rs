@@ -0,0 +1,284 @@
+//! `hmcs replay` — re-issues a recorded API session and diffs the responses.
+
+use crate::engine::{call, client};
+use clap::Args;
+use homunculus_client::{HomunculusClient, RawResponse};
+use homunculus_utils::error::{UtilError, UtilResult};
+use homunculus_utils::schema::session::{RecordedRequest, RecordedResponse, SessionEntry};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// CLI arguments for the `hmcs replay` subcommand.
+#[derive(Args)]
+pub struct ReplayArgs {
+    /// Session trace recorded by the engine (JSONL)
+    pub file: PathBuf,
+    /// Engine port (defaults to the configured port)
+    #[arg(long)]
+    pub port: Option<u16>,
+    /// Ignore this JSON field wherever it appears when diffing (repeatable)
+    #[arg(long = "ignore", value_name = "FIELD")]
+    pub ignore: Vec<String>,
+    /// Wait between requests as long as the recording did
+    #[arg(long)]
+    pub realtime: bool,
+}
+
+impl ReplayArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = client(self.port)?;
+        let exchanges = load_exchanges(&self.file)?;
+
+        let mut mismatched = 0;
+        let mut skipped = 0;
+        let mut previous: Option<i64> = None;
+        for (request, expected) in &exchanges {
+            let label = format!("#{} {} {}", request.id, request.method, request.uri);
+            if request.body_omitted {
+                println!("{label} ... skipped (body not recorded)");
+                skipped += 1;
+                continue;
+            }
+            if self.realtime
+                && let Some(previous) = previous
+            {
+                let gap = request.timestamp.saturating_sub(previous).max(0);
+                std::thread::sleep(Duration::from_millis(gap as u64));
+            }
+            previous = Some(request.timestamp);
+
+            let actual = send(&client, request)?;
+            let diffs = diff_responses(expected, &actual, &self.ignore);
+            if diffs.is_empty() {
+                println!("{label} ... ok");
+            } else {
+                mismatched += 1;
+                println!("{label} ... mismatch");
+                for diff in diffs {
+                    println!("    {diff}");
+                }
+            }
+        }
+
+        println!(
+            "\n{} requests replayed, {mismatched} mismatched, {skipped} skipped",
+            exchanges.len() - skipped
+        );
+        if 0 < mismatched {
+            return Err(UtilError::Other(anyhow::anyhow!(
+                "{mismatched} responses differ from the recording"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Reads a trace and pairs each request with its recorded response, in request order.
+///
+/// Requests without a response (e.g. the engine exited mid-request) are dropped.
+fn load_exchanges(path: &Path) -> UtilResult<Vec<(RecordedRequest, RecordedResponse)>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", path.display()))?;
+    parse_exchanges(&content)
+}
+
+fn parse_exchanges(content: &str) -> UtilResult<Vec<(RecordedRequest, RecordedResponse)>> {
+    let mut requests = Vec::new();
+    let mut responses = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: SessionEntry = serde_json::from_str(line)
+            .map_err(|e| anyhow::anyhow!("invalid trace entry on line {}: {e}", i + 1))?;
+        match entry {
+            SessionEntry::Request(request) => requests.push(request),
+            SessionEntry::Response(response) => {
+                responses.insert(response.id, response);
+            }
+            SessionEntry::Event { .. } => {}
+        }
+    }
+    Ok(requests
+        .into_iter()
+        .filter_map(|request| {
+            let response = responses.remove(&request.id)?;
+            Some((request, response))
+        })
+        .collect())
+}
+
+fn send(client: &HomunculusClient, request: &RecordedRequest) -> UtilResult<RawResponse> {
+    call(client.send_raw(&request.method, &request.uri, request.body.as_ref()))
+}
+
+fn diff_responses(
+    expected: &RecordedResponse,
+    actual: &RawResponse,
+    ignore: &[String],
+) -> Vec<String> {
+    let mut diffs = Vec::new();
+    if expected.status != actual.status {
+        diffs.push(format!(
+            "status: expected {}, got {}",
+            expected.status, actual.status
+        ));
+    }
+    if expected.body_omitted {
+        return diffs;
+    }
+    match (&expected.body, &actual.body) {
+        (Some(expected), Some(actual)) => diff_json("$", expected, actual, ignore, &mut diffs),
+        (None, None) => {}
+        (expected, actual) => diffs.push(format!(
+            "$: expected {}, got {}",
+            display(expected.as_ref()),
+            display(actual.as_ref())
+        )),
+    }
+    diffs
+}
+
+/// Collects the differences between two JSON values as `path: expected X, got Y` lines.
+fn diff_json(
+    path: &str,
+    expected: &Value,
+    actual: &Value,
+    ignore: &[String],
+    out: &mut Vec<String>,
+) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            let keys = expected
+                .keys()
+                .chain(actual.keys().filter(|k| !expected.contains_key(*k)));
+            for key in keys {
+                if ignore.iter().any(|i| i == key) {
+                    continue;
+                }
+                let child = format!("{path}.{key}");
+                match (expected.get(key), actual.get(key)) {
+                    (Some(e), Some(a)) => diff_json(&child, e, a, ignore, out),
+                    (e, a) => out.push(format!(
+                        "{child}: expected {}, got {}",
+                        display(e),
+                        display(a)
+                    )),
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            if expected.len() != actual.len() {
+                out.push(format!(
+                    "{path}: expected {} items, got {}",
+                    expected.len(),
+                    actual.len()
+                ));
+            }
+            for (i, (e, a)) in expected.iter().zip(actual).enumerate() {
+                diff_json(&format!("{path}[{i}]"), e, a, ignore, out);
+            }
+        }
+        (expected, actual) if expected != actual => {
+            out.push(format!("{path}: expected {expected}, got {actual}"));
+        }
+        _ => {}
+    }
+}
+
+fn display(value: Option<&Value>) -> String {
+    value.map_or_else(|| "nothing".to_string(), Value::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn diff(expected: Value, actual: Value, ignore: &[&str]) -> Vec<String> {
+        let ignore: Vec<String> = ignore.iter().map(|s| s.to_string()).collect();
+        let mut out = Vec::new();
+        diff_json("$", &expected, &actual, &ignore, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_identical_values_have_no_diff() {
+        let value = json!({"a": [1, {"b": "c"}], "d": null});
+        assert!(diff(value.clone(), value, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_paths() {
+        let diffs = diff(
+            json!({"a": [1, {"b": "c"}], "gone": true}),
+            json!({"a": [1, {"b": "x"}], "new": 1}),
+            &[],
+        );
+        assert_eq!(
+            diffs,
+            vec![
+                r#"$.a[1].b: expected "c", got "x""#,
+                "$.gone: expected true, got nothing",
+                "$.new: expected nothing, got 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_ignores_fields_at_any_depth() {
+        let diffs = diff(
+            json!({"entity": 1, "items": [{"entity": 2, "name": "a"}]}),
+            json!({"entity": 5, "items": [{"entity": 6, "name": "a"}]}),
+            &["entity"],
+        );
+        assert!(diffs.is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_length_change() {
+        let diffs = diff(json!([1, 2]), json!([1]), &[]);
+        assert_eq!(diffs, vec!["$: expected 2 items, got 1"]);
+    }
+
+    #[test]
+    fn test_parse_exchanges_pairs_by_id() {
+        let trace = [
+            r#"{"type":"request","id":1,"timestamp":0,"method":"GET","uri":"/signals"}"#,
+            r#"{"type":"request","id":2,"timestamp":1,"method":"POST","uri":"/signals/a","body":1}"#,
+            r#"{"type":"event","timestamp":2,"event":{"event":"drag"}}"#,
+            r#"{"type":"response","id":2,"timestamp":3,"status":200}"#,
+            r#"{"type":"response","id":1,"timestamp":4,"status":200,"body":[]}"#,
+            r#"{"type":"request","id":3,"timestamp":5,"method":"GET","uri":"/unanswered"}"#,
+        ]
+        .join("\n");
+        let exchanges = parse_exchanges(&trace).unwrap();
+        let ids: Vec<_> = exchanges
+            .iter()
+            .map(|(req, res)| (req.id, res.id))
+            .collect();
+        assert_eq!(ids, vec![(1, 1), (2, 2)]);
+        assert_eq!(exchanges[0].1.body, Some(json!([])));
+    }
+
+    #[test]
+    fn test_status_mismatch_is_reported() {
+        let expected = RecordedResponse {
+            id: 1,
+            timestamp: 0,
+            status: 200,
+            body: None,
+            body_omitted: false,
+        };
+        let actual = RawResponse {
+            status: 404,
+            body: None,
+        };
+        assert_eq!(
+            diff_responses(&expected, &actual, &[]),
+            vec!["status: expected 200, got 404"]
+        );
+    }
+}
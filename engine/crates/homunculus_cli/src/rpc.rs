@@ -0,0 +1,79 @@
+//! `hmcs rpc` — call methods registered by MOD services.
+
+use crate::engine::{EngineArgs, call, parse_payload, print_json};
+use clap::{Args, Subcommand};
+use comfy_table::Table;
+use comfy_table::presets::NOTHING;
+use homunculus_utils::error::UtilResult;
+
+/// CLI arguments for the `hmcs rpc` subcommand.
+#[derive(Args)]
+pub struct RpcArgs {
+    #[command(subcommand)]
+    pub command: RpcSubcommand,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// MOD RPC operations.
+#[derive(Subcommand)]
+pub enum RpcSubcommand {
+    /// List registered RPC methods
+    List,
+    /// Call a method and print its result
+    Call {
+        /// MOD name (e.g. @hmcs/voicevox)
+        mod_name: String,
+        /// Method name
+        method: String,
+        /// Request body; parsed as JSON, otherwise sent as a string
+        body: Option<String>,
+    },
+}
+
+impl RpcArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        match self.command {
+            RpcSubcommand::List => {
+                let registrations = call(client.rpc_registrations())?;
+                self.engine.output(&registrations, || {
+                    let mut rows: Vec<[String; 3]> = registrations
+                        .iter()
+                        .flat_map(|(mod_name, registration)| {
+                            registration.methods.iter().map(move |(method, info)| {
+                                [
+                                    mod_name.clone(),
+                                    method.clone(),
+                                    info.description.clone().unwrap_or_default(),
+                                ]
+                            })
+                        })
+                        .collect();
+                    if rows.is_empty() {
+                        return "No RPC methods registered.".to_string();
+                    }
+                    rows.sort();
+                    let mut table = Table::new();
+                    table
+                        .load_preset(NOTHING)
+                        .set_header(["MOD", "METHOD", "DESCRIPTION"]);
+                    for row in rows {
+                        table.add_row(row);
+                    }
+                    table.to_string()
+                })
+            }
+            RpcSubcommand::Call {
+                mod_name,
+                method,
+                body,
+            } => {
+                let body = body.as_deref().map(parse_payload);
+                let result = call(client.rpc_call(&mod_name, &method, body.as_ref()))?;
+                // The result is already JSON, so it is printed the same way in both modes.
+                print_json(&result)
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! `hmcs signal` — send and watch signals.
+
+use crate::engine::{EngineArgs, block_on, call, client_error, parse_payload};
+use clap::{Args, Subcommand};
+use futures_util::StreamExt;
+use homunculus_utils::error::UtilResult;
+use serde_json::json;
+
+/// CLI arguments for the `hmcs signal` subcommand.
+#[derive(Args)]
+pub struct SignalArgs {
+    #[command(subcommand)]
+    pub command: SignalSubcommand,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// Signal operations.
+#[derive(Subcommand)]
+pub enum SignalSubcommand {
+    /// Broadcast a payload to a signal's subscribers
+    Send {
+        /// Signal name
+        signal: String,
+        /// Payload; parsed as JSON, otherwise sent as a string (defaults to null)
+        payload: Option<String>,
+    },
+    /// Print messages as they arrive until interrupted
+    Watch {
+        /// Signal names or wildcard patterns (e.g. persona.*, stt/#)
+        #[arg(required = true)]
+        channels: Vec<String>,
+    },
+}
+
+impl SignalArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        match self.command {
+            SignalSubcommand::Send { signal, payload } => {
+                let payload = payload.as_deref().map(parse_payload).unwrap_or_default();
+                call(client.send_signal(&signal, &payload))?;
+                self.engine
+                    .output(&json!({ "signal": signal, "payload": payload }), || {
+                        format!("Sent {signal}")
+                    })
+            }
+            SignalSubcommand::Watch { channels } => block_on(async {
+                let channels: Vec<&str> = channels.iter().map(String::as_str).collect();
+                let messages = client
+                    .subscribe_signals(&channels)
+                    .await
+                    .map_err(client_error)?;
+                let mut messages = std::pin::pin!(messages);
+                while let Some(message) = messages.next().await {
+                    let message = message.map_err(client_error)?;
+                    if self.engine.json {
+                        // One object per line, so the output can be piped into `jq`.
+                        println!("{}", json!(message));
+                    } else {
+                        println!("[{}] #{} {}", message.channel, message.seq, message.data);
+                    }
+                }
+                Ok(())
+            }),
+        }
+    }
+}
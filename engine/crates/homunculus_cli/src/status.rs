@@ -0,0 +1,95 @@
+//! `hmcs status` — reports whether the engine is running and what it has loaded.
+
+use crate::engine::{EngineArgs, block_on};
+use clap::Args;
+use homunculus_client::{AppInfo, HomunculusClient};
+use homunculus_utils::error::{UtilError, UtilResult};
+use serde::Serialize;
+
+/// CLI arguments for the `hmcs status` subcommand.
+#[derive(Args)]
+pub struct StatusArgs {
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// The summary printed by `hmcs status`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Status {
+    running: bool,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<AppInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    personas: Option<PersonaCount>,
+}
+
+#[derive(Serialize)]
+struct PersonaCount {
+    total: usize,
+    spawned: usize,
+}
+
+impl StatusArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        let status = block_on(fetch_status(&client));
+        let running = status.running;
+        self.engine.output(&status, || format_status(&status))?;
+        if running {
+            Ok(())
+        } else {
+            Err(UtilError::Other(anyhow::anyhow!(
+                "engine is not running at {}",
+                client.base()
+            )))
+        }
+    }
+}
+
+/// Collects the status; any connection failure reports the engine as not running.
+async fn fetch_status(client: &HomunculusClient) -> Status {
+    let mut status = Status {
+        running: false,
+        url: client.base().to_string(),
+        info: None,
+        personas: None,
+    };
+    if !client.health().await.unwrap_or(false) {
+        return status;
+    }
+    status.running = true;
+    status.info = client.app_info().await.ok();
+    status.personas = client
+        .list_personas()
+        .await
+        .ok()
+        .map(|personas| PersonaCount {
+            total: personas.len(),
+            spawned: personas.iter().filter(|p| p.spawned).count(),
+        });
+    status
+}
+
+fn format_status(status: &Status) -> String {
+    if !status.running {
+        return format!("Engine: not running ({})", status.url);
+    }
+    let mut lines = vec![format!("Engine:   running ({})", status.url)];
+    if let Some(info) = &status.info {
+        lines.push(format!("Version:  {}", info.version));
+        lines.push(format!(
+            "Platform: {} {}",
+            info.platform.os, info.platform.arch
+        ));
+        lines.push(format!("MODs:     {}", info.mods.len()));
+    }
+    if let Some(personas) = &status.personas {
+        lines.push(format!(
+            "Personas: {} ({} spawned)",
+            personas.total, personas.spawned
+        ));
+    }
+    lines.join("\n")
+}
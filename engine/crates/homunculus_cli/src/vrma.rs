@@ -0,0 +1,104 @@
+//! `hmcs vrma` — play VRMA animations on a persona.
+
+use crate::engine::{EngineArgs, call};
+use clap::{Args, Subcommand};
+use homunculus_client::{PlayVrma, Repeat};
+use homunculus_utils::error::{UtilError, UtilResult};
+use serde_json::json;
+
+/// CLI arguments for the `hmcs vrma` subcommand.
+#[derive(Args)]
+pub struct VrmaArgs {
+    #[command(subcommand)]
+    pub command: VrmaSubcommand,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// VRMA animation operations.
+#[derive(Subcommand)]
+pub enum VrmaSubcommand {
+    /// Play a VRMA animation on a persona
+    Play {
+        /// Persona ID
+        id: String,
+        /// VRMA asset ID (e.g. vrma:idle-maid)
+        asset: String,
+        /// How often to play: `never` (once), `forever`, or a count
+        #[arg(long, default_value = "never")]
+        repeat: String,
+        /// Cross-fade duration in seconds
+        #[arg(long)]
+        transition: Option<f64>,
+        /// Return only after the animation has finished
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Stop a VRMA animation on a persona
+    Stop {
+        /// Persona ID
+        id: String,
+        /// VRMA asset ID
+        asset: String,
+    },
+}
+
+impl VrmaArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        match self.command {
+            VrmaSubcommand::Play {
+                id,
+                asset,
+                repeat,
+                transition,
+                wait,
+            } => {
+                let mut play = PlayVrma::new(&asset);
+                play.repeat = Some(parse_repeat(&repeat)?);
+                play.transition_secs = transition;
+                play.wait_for_completion = Some(wait);
+                call(client.play_vrma(&id, &play))?;
+                self.engine
+                    .output(&json!({ "id": id, "asset": asset }), || {
+                        format!("Playing {asset} on {id}")
+                    })
+            }
+            VrmaSubcommand::Stop { id, asset } => {
+                call(client.stop_vrma(&id, &asset))?;
+                self.engine
+                    .output(&json!({ "id": id, "asset": asset }), || {
+                        format!("Stopped {asset} on {id}")
+                    })
+            }
+        }
+    }
+}
+
+fn parse_repeat(input: &str) -> UtilResult<Repeat> {
+    match input {
+        "never" => Ok(Repeat::Never),
+        "forever" => Ok(Repeat::Forever),
+        _ => input
+            .parse()
+            .map(|count| Repeat::Count { count })
+            .map_err(|_| {
+                UtilError::Other(anyhow::anyhow!(
+                    "invalid repeat '{input}', expected never, forever or a count"
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repeat() {
+        assert_eq!(parse_repeat("never").unwrap(), Repeat::Never);
+        assert_eq!(parse_repeat("forever").unwrap(), Repeat::Forever);
+        assert_eq!(parse_repeat("3").unwrap(), Repeat::Count { count: 3 });
+        assert!(parse_repeat("often").is_err());
+    }
+}
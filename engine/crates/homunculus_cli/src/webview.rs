@@ -0,0 +1,98 @@
+//! `hmcs webview` — open and close webviews.
+
+use crate::engine::{EngineArgs, call};
+use clap::{Args, Subcommand};
+use comfy_table::Table;
+use comfy_table::presets::NOTHING;
+use homunculus_client::{OpenWebview, WebviewInfo, WebviewSource};
+use homunculus_utils::error::UtilResult;
+use serde_json::json;
+
+/// CLI arguments for the `hmcs webview` subcommand.
+#[derive(Args)]
+pub struct WebviewArgs {
+    #[command(subcommand)]
+    pub command: WebviewSubcommand,
+    #[command(flatten)]
+    pub engine: EngineArgs,
+}
+
+/// Webview operations.
+#[derive(Subcommand)]
+pub enum WebviewSubcommand {
+    /// List open webviews
+    List,
+    /// Open a webview and print its entity
+    Open {
+        /// URL or module path (e.g. https://example.com, mods://my-mod/ui/index.html)
+        url: String,
+        /// Persona the webview follows
+        #[arg(long)]
+        persona: Option<String>,
+        /// Mesh size in world units
+        #[arg(long, num_args = 2, value_names = ["WIDTH", "HEIGHT"])]
+        size: Option<Vec<f32>>,
+        /// Viewport resolution in pixels
+        #[arg(long, num_args = 2, value_names = ["WIDTH", "HEIGHT"])]
+        viewport: Option<Vec<f32>>,
+    },
+    /// Close a webview
+    Close {
+        /// Webview entity, as printed by `open` or `list`
+        entity: u64,
+    },
+}
+
+impl WebviewArgs {
+    pub fn execute(self) -> UtilResult {
+        let client = self.engine.client()?;
+        match self.command {
+            WebviewSubcommand::List => {
+                let webviews = call(client.list_webviews())?;
+                self.engine.output(&webviews, || format_table(&webviews))
+            }
+            WebviewSubcommand::Open {
+                url,
+                persona,
+                size,
+                viewport,
+            } => {
+                let mut webview = OpenWebview::url(url);
+                webview.linked_persona = persona;
+                webview.size = size.map(|s| [s[0], s[1]]);
+                webview.viewport_size = viewport.map(|s| [s[0], s[1]]);
+                let entity = call(client.open_webview(&webview))?;
+                self.engine
+                    .output(&json!({ "entity": entity }), || entity.to_string())
+            }
+            WebviewSubcommand::Close { entity } => {
+                call(client.close_webview(entity))?;
+                self.engine
+                    .output(&json!({ "entity": entity }), || format!("Closed {entity}"))
+            }
+        }
+    }
+}
+
+fn format_table(webviews: &[WebviewInfo]) -> String {
+    if webviews.is_empty() {
+        return "No webviews open.".to_string();
+    }
+    let mut table = Table::new();
+    table
+        .load_preset(NOTHING)
+        .set_header(["ENTITY", "SOURCE", "PERSONA"]);
+    for webview in webviews {
+        let source = match &webview.source {
+            WebviewSource::Url { url } => url.clone(),
+            WebviewSource::Html { .. } => "(inline html)".to_string(),
+            WebviewSource::Local { id } => id.to_string(),
+        };
+        table.add_row([
+            webview.entity.to_string(),
+            source,
+            webview.linked_persona.clone().unwrap_or_default(),
+        ]);
+    }
+    table.to_string()
+}
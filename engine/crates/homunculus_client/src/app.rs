@@ -0,0 +1,33 @@
+use crate::{ClientResult, HomunculusClient};
+use serde::{Deserialize, Serialize};
+
+/// Response of `GET /app/info`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    pub version: String,
+    pub platform: PlatformInfo,
+    /// Compile-time features enabled in the engine.
+    pub features: Vec<String>,
+    /// Loaded MODs.
+    pub mods: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlatformInfo {
+    pub os: String,
+    pub arch: String,
+}
+
+impl HomunculusClient {
+    /// `GET /app/health` — returns `true` if the engine answered.
+    pub async fn health(&self) -> ClientResult<bool> {
+        let response: serde_json::Value = self.get_json("/app/health").await?;
+        Ok(response["status"] == "ok")
+    }
+
+    /// `GET /app/info`
+    pub async fn app_info(&self) -> ClientResult<AppInfo> {
+        self.get_json("/app/info").await
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{AssetId, ClientResult, HomunculusClient};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Body of `POST /audio/se`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaySe {
+    pub asset: AssetId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub panning: Option<f64>,
+}
+
+impl PlaySe {
+    pub fn new(asset: impl Into<String>) -> Self {
+        Self {
+            asset: AssetId::new(asset),
+            volume: None,
+            speed: None,
+            panning: None,
+        }
+    }
+}
+
+/// Body of `POST /audio/bgm`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayBgm {
+    pub asset: AssetId,
+    #[serde(rename = "loop", skip_serializing_if = "Option::is_none")]
+    pub is_loop: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    /// Fade-in tween, e.g. `{"durationSecs": 1.0}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fade_in: Option<Value>,
+}
+
+impl PlayBgm {
+    pub fn new(asset: impl Into<String>) -> Self {
+        Self {
+            asset: AssetId::new(asset),
+            is_loop: None,
+            volume: None,
+            speed: None,
+            fade_in: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BgmPlaybackState {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+/// Response of `GET /audio/bgm`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BgmStatus {
+    #[serde(default)]
+    pub asset: Option<AssetId>,
+    pub state: BgmPlaybackState,
+    #[serde(rename = "loop")]
+    pub is_loop: bool,
+    pub volume: f64,
+    pub speed: f64,
+}
+
+impl HomunculusClient {
+    /// `POST /audio/se`
+    pub async fn play_se(&self, se: &PlaySe) -> ClientResult {
+        self.post("/audio/se", se).await
+    }
+
+    /// `POST /audio/bgm`
+    pub async fn play_bgm(&self, bgm: &PlayBgm) -> ClientResult {
+        self.post("/audio/bgm", bgm).await
+    }
+
+    /// `POST /audio/bgm/stop`, optionally fading out.
+    pub async fn stop_bgm(&self, fade_out: Option<Value>) -> ClientResult {
+        self.post(
+            "/audio/bgm/stop",
+            &serde_json::json!({ "fadeOut": fade_out }),
+        )
+        .await
+    }
+
+    /// `GET /audio/bgm`
+    pub async fn bgm_status(&self) -> ClientResult<BgmStatus> {
+        self.get_json("/audio/bgm").await
+    }
+}
@@ -0,0 +1,35 @@
+pub type ClientResult<T = ()> = Result<T, ClientError>;
+
+/// Errors returned by [`HomunculusClient`](crate::HomunculusClient).
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    /// The engine could not be reached, or the connection failed mid-request.
+    #[error("failed to reach the engine: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The engine answered with a non-success status.
+    #[error("engine returned {status}: {message}")]
+    Status { status: u16, message: String },
+    /// The response body did not match the expected type.
+    #[error("invalid response from the engine: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// The signals WebSocket failed.
+    #[error("websocket error: {0}")]
+    WebSocket(#[from] Box<tokio_tungstenite::tungstenite::Error>),
+    /// The request could not be built, e.g. an invalid HTTP method.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    /// The engine rejected a WebSocket subscription.
+    #[error("subscription to '{channel}' rejected: {message}")]
+    Subscription { channel: String, message: String },
+}
+
+impl ClientError {
+    /// Returns the HTTP status if the engine answered with an error status.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            ClientError::Status { status, .. } => Some(*status),
+            ClientError::Http(e) => e.status().map(|s| s.as_u16()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! # Homunculus Client
+//!
+//! Typed async client for the Desktop Homunculus HTTP API, for Rust tools that talk
+//! to a running engine (the `hmcs` CLI, external daemons, integration tests).
+//!
+//! The client is hand-maintained against the engine's OpenAPI document (`ApiDoc` in
+//! `homunculus_http_server`) and deliberately does not depend on Bevy: request and
+//! response types are lightweight mirrors of the engine schemas.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use homunculus_client::{HomunculusClient, PlayVrma};
+//!
+//! # async fn run() -> homunculus_client::ClientResult {
+//! let client = HomunculusClient::local(3100);
+//! for persona in client.list_personas().await? {
+//!     println!("{} ({})", persona.id, persona.state);
+//! }
+//! client
+//!     .play_vrma("elmer", &PlayVrma::new("vrma:idle-maid"))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ## Coverage
+//!
+//! - App: health, info
+//! - Personas: CRUD, spawn/despawn, SSE event streams
+//! - VRM: attach/detach, VRMA play/stop
+//! - Webviews: list, open, close
+//! - Audio: sound effects and BGM
+//! - Signals: list, send, request/reply, history, WebSocket subscriptions
+//! - Processes and MOD RPC (registrations and calls)
+//!
+//! Endpoints without a typed method can be reached with [`HomunculusClient::send_raw`].
+
+mod app;
+mod audio;
+mod error;
+mod personas;
+mod processes;
+mod raw;
+mod rpc;
+mod signals;
+mod sse;
+mod vrm;
+mod webviews;
+
+pub use app::*;
+pub use audio::*;
+pub use error::*;
+pub use personas::*;
+pub use processes::*;
+pub use raw::RawResponse;
+pub use signals::*;
+pub use sse::SseEvent;
+pub use vrm::*;
+pub use webviews::*;
+
+pub use homunculus_utils::schema::asset::AssetId;
+
+use homunculus_utils::config::HomunculusConfig;
+use reqwest::{RequestBuilder, Response};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// A client for one engine instance.
+///
+/// Cheap to clone; clones share the underlying connection pool.
+#[derive(Debug, Clone)]
+pub struct HomunculusClient {
+    http: reqwest::Client,
+    base: String,
+}
+
+impl HomunculusClient {
+    /// Creates a client for the engine at `base`, e.g. `http://127.0.0.1:3100`.
+    pub fn new(base: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base: base.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Creates a client for an engine listening on `127.0.0.1:<port>`.
+    pub fn local(port: u16) -> Self {
+        Self::new(format!("http://127.0.0.1:{port}"))
+    }
+
+    /// Creates a client for the engine configured in `config.toml`.
+    pub fn from_config(config: &HomunculusConfig) -> Self {
+        Self::new(format!("http://{}", config.host()))
+    }
+
+    /// The base URL requests are sent to.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.base)
+    }
+
+    async fn send(&self, request: RequestBuilder) -> ClientResult<Response> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let text = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .and_then(|v| v.get("error")?.as_str().map(str::to_string))
+            .unwrap_or(text);
+        Err(ClientError::Status {
+            status: status.as_u16(),
+            message,
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> ClientResult<T> {
+        json(self.send(self.http.get(self.url(path))).await?).await
+    }
+
+    async fn post_json<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> ClientResult<T> {
+        json(self.send(self.http.post(self.url(path)).json(body)).await?).await
+    }
+
+    async fn post<B: Serialize + ?Sized>(&self, path: &str, body: &B) -> ClientResult {
+        self.send(self.http.post(self.url(path)).json(body)).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> ClientResult {
+        self.send(self.http.delete(self.url(path))).await?;
+        Ok(())
+    }
+}
+
+async fn json<T: DeserializeOwned>(response: Response) -> ClientResult<T> {
+    Ok(serde_json::from_slice(&response.bytes().await?)?)
+}
+
+/// Percent-encodes one path segment (persona IDs, signal names, ...).
+fn segment(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' | b'@' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_escapes_reserved_characters() {
+        assert_eq!(segment("elmer"), "elmer");
+        assert_eq!(segment("my-mod:chat"), "my-mod:chat");
+        assert_eq!(segment("stt/result"), "stt%2Fresult");
+        assert_eq!(segment("a b?"), "a%20b%3F");
+    }
+
+    #[test]
+    fn base_url_is_normalized() {
+        assert_eq!(
+            HomunculusClient::new("http://localhost:3100/").base(),
+            "http://localhost:3100"
+        );
+        assert_eq!(
+            HomunculusClient::local(3200).base(),
+            "http://127.0.0.1:3200"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use crate::sse::{self, SseEvent};
+use crate::{ClientResult, HomunculusClient, json, segment};
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Gender identity of a persona.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Gender {
+    Male,
+    Female,
+    Other,
+    #[default]
+    Unknown,
+}
+
+/// A persona with its ephemeral state, as returned by the persona CRUD endpoints.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonaSnapshot {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub age: Option<u32>,
+    #[serde(default)]
+    pub gender: Gender,
+    #[serde(default)]
+    pub first_person_pronoun: Option<String>,
+    #[serde(default)]
+    pub profile: String,
+    #[serde(default)]
+    pub personality: Option<String>,
+    #[serde(default)]
+    pub metadata: Map<String, Value>,
+    #[serde(default)]
+    pub thumbnail: Option<String>,
+    #[serde(default)]
+    pub vrm_asset_id: Option<String>,
+    /// Current state, e.g. `idle`, `sitting` or `drag`.
+    pub state: String,
+    /// Whether the persona has a live entity in the scene.
+    pub spawned: bool,
+}
+
+/// Body of `POST /personas`. Unset fields take the engine defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePersona {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Gender>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_person_pronoun: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub personality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Map<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vrm_asset_id: Option<String>,
+}
+
+impl CreatePersona {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl HomunculusClient {
+    /// `GET /personas`
+    pub async fn list_personas(&self) -> ClientResult<Vec<PersonaSnapshot>> {
+        self.get_json("/personas").await
+    }
+
+    /// `GET /personas/{id}`
+    pub async fn get_persona(&self, id: &str) -> ClientResult<PersonaSnapshot> {
+        self.get_json(&format!("/personas/{}", segment(id))).await
+    }
+
+    /// `POST /personas`
+    pub async fn create_persona(&self, persona: &CreatePersona) -> ClientResult<PersonaSnapshot> {
+        self.post_json("/personas", persona).await
+    }
+
+    /// `DELETE /personas/{id}`
+    pub async fn delete_persona(&self, id: &str) -> ClientResult {
+        self.delete(&format!("/personas/{}", segment(id))).await
+    }
+
+    /// `POST /personas/{id}/spawn` — spawns a stored persona into the scene.
+    pub async fn spawn_persona(&self, id: &str) -> ClientResult<PersonaSnapshot> {
+        let path = format!("/personas/{}/spawn", segment(id));
+        json(self.send(self.http.post(self.url(&path))).await?).await
+    }
+
+    /// `POST /personas/{id}/despawn` — removes the persona from the scene, keeping its record.
+    pub async fn despawn_persona(&self, id: &str) -> ClientResult {
+        let path = format!("/personas/{}/despawn", segment(id));
+        self.send(self.http.post(self.url(&path))).await?;
+        Ok(())
+    }
+
+    /// `GET /personas/{id}/events` — the persona's SSE event stream.
+    pub async fn persona_events(
+        &self,
+        id: &str,
+    ) -> ClientResult<impl Stream<Item = ClientResult<SseEvent>>> {
+        let path = format!("/personas/{}/events", segment(id));
+        let response = self.send(self.http.get(self.url(&path))).await?;
+        Ok(sse::events(response))
+    }
+
+    /// `GET /personas/stream` — the combined SSE stream of all personas.
+    pub async fn all_persona_events(
+        &self,
+    ) -> ClientResult<impl Stream<Item = ClientResult<SseEvent>>> {
+        let response = self
+            .send(self.http.get(self.url("/personas/stream")))
+            .await?;
+        Ok(sse::events(response))
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{ClientResult, HomunculusClient, segment};
+use serde::{Deserialize, Serialize};
+
+/// A running managed process, as returned by `GET /processes`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessInfo {
+    pub handle_id: String,
+    /// Full MOD command reference (`mod-name:bin-name`).
+    pub command: String,
+    pub args: Vec<String>,
+    pub pid: u32,
+    /// ISO 8601 start time.
+    pub started_at: String,
+}
+
+#[derive(Serialize)]
+struct StartProcess<'a> {
+    command: &'a str,
+    args: &'a [String],
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StartProcessResponse {
+    handle_id: String,
+}
+
+impl HomunculusClient {
+    /// `GET /processes`
+    pub async fn list_processes(&self) -> ClientResult<Vec<ProcessInfo>> {
+        self.get_json("/processes").await
+    }
+
+    /// `POST /processes/start` — returns the handle ID of the started process.
+    pub async fn start_process(&self, command: &str, args: &[String]) -> ClientResult<String> {
+        let response: StartProcessResponse = self
+            .post_json("/processes/start", &StartProcess { command, args })
+            .await?;
+        Ok(response.handle_id)
+    }
+
+    /// `DELETE /processes/{handle_id}`
+    pub async fn stop_process(&self, handle_id: &str) -> ClientResult {
+        self.delete(&format!("/processes/{}", segment(handle_id)))
+            .await
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{ClientError, ClientResult, HomunculusClient};
+use serde_json::Value;
+
+/// A response to [`HomunculusClient::send_raw`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawResponse {
+    pub status: u16,
+    /// The body, if the engine answered with JSON.
+    pub body: Option<Value>,
+}
+
+impl HomunculusClient {
+    /// Sends an arbitrary request and returns the response whatever its status.
+    ///
+    /// `path` includes the query string, e.g. `/signals/chat/history?since=2`.
+    pub async fn send_raw(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&Value>,
+    ) -> ClientResult<RawResponse> {
+        let method = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|_| ClientError::InvalidRequest(format!("invalid method {method}")))?;
+        let mut request = self.http.request(method, self.url(path));
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let is_json = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/json"));
+        let body = if is_json {
+            response.json::<Value>().await.ok()
+        } else {
+            None
+        };
+        Ok(RawResponse { status, body })
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{ClientResult, HomunculusClient};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// A MOD service's registered RPC methods, as returned by `GET /rpc/registrations`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcRegistration {
+    pub port: u16,
+    pub methods: HashMap<String, RpcMethodInfo>,
+}
+
+/// Metadata published for one RPC method.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMethodInfo {
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Timeout in milliseconds.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    pub input_schema: Option<Map<String, Value>>,
+    #[serde(default)]
+    pub output_schema: Option<Map<String, Value>>,
+    /// Arbitrary metadata attached by the MOD, e.g. `{ "category": "tts" }`.
+    #[serde(default)]
+    pub meta: Option<Map<String, Value>>,
+}
+
+impl RpcMethodInfo {
+    /// The `meta.category` the MOD attached to the method, if any.
+    pub fn category(&self) -> Option<&str> {
+        self.meta.as_ref()?.get("category")?.as_str()
+    }
+}
+
+#[derive(Deserialize)]
+struct RegistrationsResponse {
+    registrations: HashMap<String, RpcRegistration>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallRequest<'a> {
+    mod_name: &'a str,
+    method: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<&'a Value>,
+}
+
+impl HomunculusClient {
+    /// `GET /rpc/registrations` — registered methods keyed by MOD name.
+    pub async fn rpc_registrations(&self) -> ClientResult<HashMap<String, RpcRegistration>> {
+        let response: RegistrationsResponse = self.get_json("/rpc/registrations").await?;
+        Ok(response.registrations)
+    }
+
+    /// `POST /rpc/call` — calls a method registered by a MOD service.
+    ///
+    /// Returns `null` if the method answered with an empty body.
+    pub async fn rpc_call(
+        &self,
+        mod_name: &str,
+        method: &str,
+        body: Option<&Value>,
+    ) -> ClientResult<Value> {
+        let request = CallRequest {
+            mod_name,
+            method,
+            body,
+        };
+        let response = self
+            .send(self.http.post(self.url("/rpc/call")).json(&request))
+            .await?;
+        let bytes = response.bytes().await?;
+        if bytes.is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{ClientError, ClientResult, HomunculusClient, segment};
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_tungstenite::tungstenite::Message;
+
+/// An active signal channel, as returned by `GET /signals`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalInfo {
+    pub signal: String,
+    pub subscribers: usize,
+    /// Subscribers to wildcard patterns matching this channel.
+    #[serde(default)]
+    pub pattern_subscribers: usize,
+    /// The retention policy, if history is kept for this channel.
+    #[serde(default)]
+    pub retention: Option<Value>,
+}
+
+/// A retained signal message, as returned by `GET /signals/{signal}/history`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalRecord {
+    pub seq: u64,
+    /// Unix time in milliseconds.
+    pub timestamp: i64,
+    pub payload: Value,
+}
+
+/// A message received on a [`HomunculusClient::subscribe_signals`] stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalMessage {
+    /// The channel the message was sent on.
+    pub channel: String,
+    /// The wildcard pattern that matched, for pattern subscriptions.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    pub seq: u64,
+    pub data: Value,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignalRequest<'a> {
+    payload: &'a Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout_ms: Option<u64>,
+}
+
+impl HomunculusClient {
+    /// `GET /signals`
+    pub async fn list_signals(&self) -> ClientResult<Vec<SignalInfo>> {
+        self.get_json("/signals").await
+    }
+
+    /// `POST /signals/{signal}` — broadcasts `payload` to the channel's subscribers.
+    pub async fn send_signal(&self, signal: &str, payload: &Value) -> ClientResult {
+        self.post(&format!("/signals/{}", segment(signal)), payload)
+            .await
+    }
+
+    /// `POST /signals/{signal}/request` — sends a request and waits for the first reply.
+    pub async fn request_signal(
+        &self,
+        signal: &str,
+        payload: &Value,
+        timeout_ms: Option<u64>,
+    ) -> ClientResult<Value> {
+        self.post_json(
+            &format!("/signals/{}/request", segment(signal)),
+            &SignalRequest {
+                payload,
+                timeout_ms,
+            },
+        )
+        .await
+    }
+
+    /// `GET /signals/{signal}/history` — retained messages with `seq` greater than `since`.
+    pub async fn signal_history(
+        &self,
+        signal: &str,
+        since: Option<u64>,
+    ) -> ClientResult<Vec<SignalRecord>> {
+        let mut path = format!("/signals/{}/history", segment(signal));
+        if let Some(since) = since {
+            path.push_str(&format!("?since={since}"));
+        }
+        self.get_json(&path).await
+    }
+
+    /// Subscribes to `channels` over `/signals/ws`.
+    ///
+    /// Channels may be wildcard patterns such as `persona.*` or `stt/#`. The stream
+    /// yields an error item if the engine rejects a subscription and ends when the
+    /// connection closes.
+    pub async fn subscribe_signals(
+        &self,
+        channels: &[&str],
+    ) -> ClientResult<impl Stream<Item = ClientResult<SignalMessage>> + use<>> {
+        let url = format!("{}/signals/ws", self.base.replacen("http", "ws", 1));
+        let (mut ws, _) = tokio_tungstenite::connect_async(url)
+            .await
+            .map_err(Box::new)?;
+        for channel in channels {
+            let frame = serde_json::json!({ "type": "subscribe", "channel": channel });
+            ws.send(Message::Text(frame.to_string().into()))
+                .await
+                .map_err(Box::new)?;
+        }
+        Ok(ws.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => parse_ws_message(&text),
+                Ok(_) => None,
+                Err(e) => Some(Err(ClientError::from(Box::new(e)))),
+            }
+        }))
+    }
+}
+
+/// Decodes one server frame; acknowledgements yield `None`.
+fn parse_ws_message(text: &str) -> Option<ClientResult<SignalMessage>> {
+    let value: Value = match serde_json::from_str(text) {
+        Ok(value) => value,
+        Err(e) => return Some(Err(e.into())),
+    };
+    match value.get("type").and_then(Value::as_str) {
+        Some("subscribed") => None,
+        Some("error") => Some(Err(ClientError::Subscription {
+            channel: value["channel"].as_str().unwrap_or_default().to_string(),
+            message: value["message"].as_str().unwrap_or_default().to_string(),
+        })),
+        _ => Some(serde_json::from_value(value).map_err(ClientError::from)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn acknowledgements_are_skipped() {
+        assert!(parse_ws_message(r#"{"type":"subscribed","channel":"chat"}"#).is_none());
+    }
+
+    #[test]
+    fn events_are_decoded() {
+        let message = parse_ws_message(
+            r#"{"channel":"persona.spawned","pattern":"persona.*","seq":3,"data":"elmer"}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            message,
+            SignalMessage {
+                channel: "persona.spawned".to_string(),
+                pattern: Some("persona.*".to_string()),
+                seq: 3,
+                data: serde_json::json!("elmer"),
+            }
+        );
+    }
+
+    #[test]
+    fn rejections_are_errors() {
+        let error = parse_ws_message(
+            r#"{"type":"error","channel":"stt/#/x","message":"'#' must be the last segment"}"#,
+        )
+        .unwrap()
+        .unwrap_err();
+        assert!(matches!(error, ClientError::Subscription { channel, .. } if channel == "stt/#/x"));
+    }
+}
@@ -0,0 +1,113 @@
+//! Minimal Server-Sent Events decoding for the engine's event streams.
+
+use crate::error::{ClientError, ClientResult};
+use futures_util::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+/// One event received on an SSE stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The event name (`event:` field), or `message` if the server sent none.
+    pub event: String,
+    /// The event payload (`data:` lines joined with `\n`).
+    pub data: String,
+}
+
+impl SseEvent {
+    /// Parses the payload as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> ClientResult<T> {
+        Ok(serde_json::from_str(&self.data)?)
+    }
+}
+
+/// Incremental SSE parser; feed it chunks as they arrive.
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        // Split on complete lines only, so multi-byte characters split across chunks survive.
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line = String::from_utf8_lossy(&self.buffer[..end])
+                .trim_end_matches('\r')
+                .to_string();
+            self.buffer.drain(..=end);
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(SseEvent {
+                        event: self.event.take().unwrap_or_else(|| "message".to_string()),
+                        data: self.data.join("\n"),
+                    });
+                }
+                self.event = None;
+                self.data.clear();
+                continue;
+            }
+            let (field, value) = line.split_once(':').unwrap_or((&line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                // Comments (keep-alives), `id` and `retry` are not used by the engine.
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+/// Turns an SSE response into a stream of events, ending when the connection closes.
+pub(crate) fn events(response: reqwest::Response) -> impl Stream<Item = ClientResult<SseEvent>> {
+    let mut parser = SseParser::default();
+    response
+        .bytes_stream()
+        .map(move |chunk| match chunk {
+            Ok(bytes) => Ok(parser.push(&bytes)),
+            Err(e) => Err(ClientError::from(e)),
+        })
+        .flat_map(|result| {
+            let items: Vec<ClientResult<SseEvent>> = match result {
+                Ok(events) => events.into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            futures_util::stream::iter(items)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_named_events_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: state-change\nda").is_empty());
+        let events = parser.push(b"ta: {\"state\":\"idle\"}\n\n: keep-alive\n\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: "state-change".to_string(),
+                data: r#"{"state":"idle"}"#.to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn joins_multiline_data_and_defaults_event_name() {
+        let mut parser = SseParser::default();
+        let events = parser.push(b"data: a\r\ndata: b\r\n\r\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: "message".to_string(),
+                data: "a\nb".to_string(),
+            }]
+        );
+    }
+}
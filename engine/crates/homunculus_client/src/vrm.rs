@@ -0,0 +1,81 @@
+use crate::{AssetId, ClientResult, HomunculusClient, PersonaSnapshot, segment};
+use serde::{Deserialize, Serialize};
+
+/// Repetition of a VRMA animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Repeat {
+    Forever,
+    Never,
+    Count { count: u32 },
+}
+
+/// Body of `POST /personas/{id}/vrm/vrma/play`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayVrma {
+    pub asset: AssetId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<Repeat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transition_secs: Option<f64>,
+    /// Wait until the animation finishes before the request returns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_for_completion: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_spring_bones: Option<bool>,
+}
+
+impl PlayVrma {
+    pub fn new(asset: impl Into<String>) -> Self {
+        Self {
+            asset: AssetId::new(asset),
+            repeat: None,
+            transition_secs: None,
+            wait_for_completion: None,
+            reset_spring_bones: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttachVrm<'a> {
+    asset_id: &'a str,
+}
+
+#[derive(Serialize)]
+struct AssetRequest<'a> {
+    asset: &'a str,
+}
+
+impl HomunculusClient {
+    /// `POST /personas/{id}/vrm` — attaches a VRM model, replacing any attached one.
+    pub async fn attach_vrm(&self, id: &str, asset_id: &str) -> ClientResult<PersonaSnapshot> {
+        self.post_json(
+            &format!("/personas/{}/vrm", segment(id)),
+            &AttachVrm { asset_id },
+        )
+        .await
+    }
+
+    /// `DELETE /personas/{id}/vrm`
+    pub async fn detach_vrm(&self, id: &str) -> ClientResult {
+        self.delete(&format!("/personas/{}/vrm", segment(id))).await
+    }
+
+    /// `POST /personas/{id}/vrm/vrma/play`
+    pub async fn play_vrma(&self, id: &str, play: &PlayVrma) -> ClientResult {
+        self.post(&format!("/personas/{}/vrm/vrma/play", segment(id)), play)
+            .await
+    }
+
+    /// `POST /personas/{id}/vrm/vrma/stop`
+    pub async fn stop_vrma(&self, id: &str, asset: &str) -> ClientResult {
+        self.post(
+            &format!("/personas/{}/vrm/vrma/stop", segment(id)),
+            &AssetRequest { asset },
+        )
+        .await
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{AssetId, ClientResult, HomunculusClient, json};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// What a webview displays.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WebviewSource {
+    /// A URL or module path (`mods://`, `asset://`, `https://`, ...).
+    Url { url: String },
+    /// Inline HTML. Omitted (`None`) in list responses.
+    Html {
+        #[serde(default)]
+        content: Option<String>,
+    },
+    /// A local HTML asset.
+    Local { id: AssetId },
+}
+
+/// Body of `POST /webviews`. Unset fields take the engine defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenWebview {
+    pub source: WebviewSource,
+    /// Persona the webview follows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linked_persona: Option<String>,
+    /// Mesh size in world units.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<[f32; 2]>,
+    /// Viewport resolution in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewport_size: Option<[f32; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resizable: Option<Value>,
+}
+
+impl OpenWebview {
+    pub fn new(source: WebviewSource) -> Self {
+        Self {
+            source,
+            linked_persona: None,
+            size: None,
+            viewport_size: None,
+            transform: None,
+            constraints: None,
+            resizable: None,
+        }
+    }
+
+    /// Opens `url`, which may be a URL or a module path.
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::new(WebviewSource::Url { url: url.into() })
+    }
+}
+
+/// An open webview, as returned by `GET /webviews`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewInfo {
+    /// The webview entity, used to address it in other calls.
+    pub entity: u64,
+    pub source: WebviewSource,
+    pub size: [f32; 2],
+    pub viewport_size: [f32; 2],
+    pub transform: Value,
+    pub constraints: Value,
+    #[serde(default)]
+    pub linked_persona: Option<String>,
+}
+
+impl HomunculusClient {
+    /// `GET /webviews`
+    pub async fn list_webviews(&self) -> ClientResult<Vec<WebviewInfo>> {
+        self.get_json("/webviews").await
+    }
+
+    /// `POST /webviews` — returns the new webview's entity.
+    pub async fn open_webview(&self, webview: &OpenWebview) -> ClientResult<u64> {
+        json(
+            self.send(self.http.post(self.url("/webviews")).json(webview))
+                .await?,
+        )
+        .await
+    }
+
+    /// `DELETE /webviews/{entity}`
+    pub async fn close_webview(&self, entity: u64) -> ClientResult {
+        self.delete(&format!("/webviews/{entity}")).await
+    }
+}
@@ -80,6 +80,7 @@ pub struct PersonaState(pub String);
 
 impl PersonaState {
     pub const SITTING: &'static str = "sitting";
+    pub const DRAG: &'static str = "drag";
 }
 
 impl Default for PersonaState {
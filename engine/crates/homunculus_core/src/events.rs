@@ -5,15 +5,17 @@ use bevy::prelude::*;
 use bevy_vrm1::prelude::{Initialized, ParentSearcher};
 use bevy_vrm1::vrm::{Vrm, VrmBone};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 mod vrm;
 
 pub mod prelude {
     pub use crate::events::{
-        PersonaChangeEvent, PersonaDeletedEvent, PersonaDespawnedEvent, PersonaEvent,
-        PersonaSpawnedEvent, PersonaStateChangeEvent, VrmAttachedEvent, VrmDetachedEvent, VrmEvent,
-        VrmEventReceiver, VrmEventSender, VrmMetadata, vrm::*,
+        EmotionChangeEvent, PersonaChangeEvent, PersonaDeletedEvent, PersonaDespawnedEvent,
+        PersonaEvent, PersonaSpawnedEvent, PersonaStateChangeEvent, ProximityEnterEvent,
+        ProximityLeaveEvent, VrmAttachedEvent, VrmDetachedEvent, VrmEvent, VrmEventReceiver,
+        VrmEventSender, VrmMetadata, WalkArriveEvent, WalkCancelEvent, vrm::*,
     };
 }
 
@@ -82,6 +84,54 @@ pub struct PersonaDespawnedEvent {
     pub persona_id: PersonaId,
 }
 
+/// Fired on both personas when another persona comes within the proximity radius.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProximityEnterEvent {
+    /// The persona that came close.
+    pub other: PersonaId,
+    /// Distance between the two personas in world units.
+    pub distance: f32,
+}
+
+/// Fired on both personas when a nearby persona moves out of the proximity radius.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProximityLeaveEvent {
+    /// The persona that moved away.
+    pub other: PersonaId,
+}
+
+/// Fired when a walking persona reaches its target.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkArriveEvent {
+    /// Final world position.
+    pub position: Vec3,
+}
+
+/// Fired when a walk ends before reaching its target.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkCancelEvent {
+    /// `stopped`, `replaced` or `interrupted` (the persona state changed, e.g. by a drag).
+    pub reason: String,
+    /// World position where the persona stopped.
+    pub position: Vec3,
+}
+
+/// Fired when a persona's emotion is set, and when it has faded back to neutral.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmotionChangeEvent {
+    /// Intensity of each named emotion (0.0–1.0). Empty when neutral.
+    pub emotions: HashMap<String, f32>,
+    /// Pleasantness, from -1.0 (unpleasant) to 1.0 (pleasant).
+    pub valence: f32,
+    /// Activation, from -1.0 (calm) to 1.0 (excited).
+    pub arousal: f32,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub struct VrmMetadata {
     pub name: String,
@@ -113,6 +163,11 @@ impl Plugin for HomunculusEventsPlugin {
         setup_channel::<PersonaChangeEvent>(app);
         setup_channel::<PersonaSpawnedEvent>(app);
         setup_channel::<PersonaDespawnedEvent>(app);
+        setup_channel::<ProximityEnterEvent>(app);
+        setup_channel::<ProximityLeaveEvent>(app);
+        setup_channel::<WalkArriveEvent>(app);
+        setup_channel::<WalkCancelEvent>(app);
+        setup_channel::<EmotionChangeEvent>(app);
 
         app.add_systems(Update, (start_observe_vrm, state_change, vrm_metadata));
     }
@@ -9,9 +9,29 @@ use std::sync::{Arc, RwLock};
 ///
 /// Separate from `ModInfo` / `ModManifest` (statically resolved from `package.json`).
 /// RPC registrations are dynamic — they appear when a MOD service starts.
-#[derive(Default, Debug, Clone)]
+///
+/// Every registration change is broadcast to receivers obtained via
+/// [`RpcRegistry::subscribe`], so consumers such as the MCP server can react
+/// when the set of available methods changes.
+#[derive(Debug, Clone)]
 pub struct RpcRegistry {
     entries: HashMap<String, RpcRegistration>,
+    changes: async_broadcast::Sender<()>,
+    /// Keeps the change channel open while nobody is subscribed.
+    _changes_keepalive: async_broadcast::InactiveReceiver<()>,
+}
+
+impl Default for RpcRegistry {
+    fn default() -> Self {
+        let (mut changes, receiver) = async_broadcast::broadcast(1);
+        // Only "something changed" matters, so pending notifications are coalesced.
+        changes.set_overflow(true);
+        Self {
+            entries: HashMap::default(),
+            changes,
+            _changes_keepalive: receiver.deactivate(),
+        }
+    }
 }
 
 /// A single MOD's RPC registration.
@@ -21,6 +41,44 @@ pub struct RpcRegistry {
 pub struct RpcRegistration {
     pub port: u16,
     pub methods: HashMap<String, RpcMethodMeta>,
+    /// Validators compiled from each method's schemas when it was registered.
+    #[serde(skip)]
+    validators: HashMap<String, MethodValidators>,
+}
+
+impl RpcRegistration {
+    /// Returns the compiled schema validators of `method`.
+    pub fn validators(&self, method: &str) -> Option<&MethodValidators> {
+        self.validators.get(method)
+    }
+}
+
+/// JSON Schema validators compiled from a method's `inputSchema` and `outputSchema`.
+///
+/// Schemas are compiled once at registration so calls do not pay for it.
+#[derive(Debug, Clone, Default)]
+pub struct MethodValidators {
+    pub input: Option<Arc<jsonschema::Validator>>,
+    pub output: Option<Arc<jsonschema::Validator>>,
+}
+
+impl MethodValidators {
+    /// Compiles the schemas of `method`, failing on the first that is not a valid
+    /// JSON Schema.
+    fn compile(method: &str, meta: &RpcMethodMeta) -> Result<Self, String> {
+        let compile = |field: &str, schema: &Option<serde_json::Map<String, serde_json::Value>>| {
+            let Some(schema) = schema else {
+                return Ok(None);
+            };
+            jsonschema::validator_for(&serde_json::Value::Object(schema.clone()))
+                .map(|validator| Some(Arc::new(validator)))
+                .map_err(|e| format!("Method '{method}' has an invalid {field}: {e}"))
+        };
+        Ok(Self {
+            input: compile("inputSchema", &meta.input_schema)?,
+            output: compile("outputSchema", &meta.output_schema)?,
+        })
+    }
 }
 
 /// Metadata for a single RPC method.
@@ -35,6 +93,13 @@ pub struct RpcMethodMeta {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub input_schema: Option<serde_json::Map<String, serde_json::Value>>,
+    /// JSON Schema describing the method's response body.
+    ///
+    /// Published via `GET /rpc/registrations` for client code generation and
+    /// enforced on responses when `[rpc] validate_output` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub output_schema: Option<serde_json::Map<String, serde_json::Value>>,
     /// Arbitrary metadata the MOD attaches to the method (e.g. `{ category: "tts" }`).
     /// Surfaced verbatim via `GET /rpc/registrations` so clients can filter by
     /// fields like `meta.category`.
@@ -65,19 +130,42 @@ impl std::ops::Deref for SharedRpcRegistry {
 
 impl RpcRegistry {
     /// Register (or re-register) a MOD service's RPC endpoint.
+    ///
+    /// Fails without changing the registry when a method declares a schema that is
+    /// not a valid JSON Schema.
     pub fn register(
         &mut self,
         mod_name: String,
         port: u16,
         methods: HashMap<String, RpcMethodMeta>,
-    ) {
-        self.entries
-            .insert(mod_name, RpcRegistration { port, methods });
+    ) -> Result<(), String> {
+        let validators = methods
+            .iter()
+            .map(|(method, meta)| Ok((method.clone(), MethodValidators::compile(method, meta)?)))
+            .collect::<Result<_, String>>()?;
+        self.entries.insert(
+            mod_name,
+            RpcRegistration {
+                port,
+                methods,
+                validators,
+            },
+        );
+        self.notify_changed();
+        Ok(())
     }
 
     /// Remove a MOD service's registration.
     pub fn deregister(&mut self, mod_name: &str) {
-        self.entries.remove(mod_name);
+        if self.entries.remove(mod_name).is_some() {
+            self.notify_changed();
+        }
+    }
+
+    /// Returns a receiver that yields `()` whenever a registration is added,
+    /// replaced, or removed.
+    pub fn subscribe(&self) -> async_broadcast::Receiver<()> {
+        self.changes.new_receiver()
     }
 
     /// Look up a MOD's RPC registration.
@@ -89,6 +177,11 @@ impl RpcRegistry {
     pub fn all(&self) -> &HashMap<String, RpcRegistration> {
         &self.entries
     }
+
+    fn notify_changed(&self) {
+        // `Inactive` just means nobody is listening right now.
+        let _ = self.changes.try_broadcast(());
+    }
 }
 
 #[cfg(test)]
@@ -107,7 +200,7 @@ mod tests {
                 ..Default::default()
             },
         );
-        reg.register("visual".to_string(), 54321, methods);
+        reg.register("visual".to_string(), 54321, methods).unwrap();
 
         let entry = reg.get("visual").unwrap();
         assert_eq!(entry.port, 54321);
@@ -115,6 +208,35 @@ mod tests {
         assert_eq!(entry.methods["show"].timeout, Some(15000));
     }
 
+    #[test]
+    fn register_compiles_method_schemas() {
+        let schema = serde_json::json!({ "type": "object" }).as_object().cloned();
+        let method = |output_schema| {
+            HashMap::from([(
+                "show".to_string(),
+                RpcMethodMeta {
+                    input_schema: schema.clone(),
+                    output_schema,
+                    ..Default::default()
+                },
+            )])
+        };
+        let mut reg = RpcRegistry::default();
+        reg.register("visual".to_string(), 54321, method(None))
+            .unwrap();
+
+        let validators = reg.get("visual").unwrap().validators("show").unwrap();
+        assert!(validators.input.is_some());
+        assert!(validators.output.is_none());
+
+        let invalid = serde_json::json!({ "type": 5 }).as_object().cloned();
+        let error = reg
+            .register("visual".to_string(), 1, method(invalid))
+            .unwrap_err();
+        assert!(error.contains("outputSchema"), "{error}");
+        assert_eq!(reg.get("visual").unwrap().port, 54321);
+    }
+
     #[test]
     fn lookup_missing_returns_none() {
         let reg = RpcRegistry::default();
@@ -124,7 +246,8 @@ mod tests {
     #[test]
     fn deregister_removes_entry() {
         let mut reg = RpcRegistry::default();
-        reg.register("visual".to_string(), 54321, HashMap::new());
+        reg.register("visual".to_string(), 54321, HashMap::new())
+            .unwrap();
         assert!(reg.get("visual").is_some());
         reg.deregister("visual");
         assert!(reg.get("visual").is_none());
@@ -135,11 +258,11 @@ mod tests {
         let mut reg = RpcRegistry::default();
         let mut m1 = HashMap::new();
         m1.insert("a".to_string(), RpcMethodMeta::default());
-        reg.register("mod1".to_string(), 1000, m1);
+        reg.register("mod1".to_string(), 1000, m1).unwrap();
 
         let mut m2 = HashMap::new();
         m2.insert("b".to_string(), RpcMethodMeta::default());
-        reg.register("mod1".to_string(), 2000, m2);
+        reg.register("mod1".to_string(), 2000, m2).unwrap();
 
         let entry = reg.get("mod1").unwrap();
         assert_eq!(entry.port, 2000);
@@ -150,11 +273,30 @@ mod tests {
     #[test]
     fn all_returns_all_entries() {
         let mut reg = RpcRegistry::default();
-        reg.register("a".to_string(), 1000, HashMap::new());
-        reg.register("b".to_string(), 2000, HashMap::new());
+        reg.register("a".to_string(), 1000, HashMap::new()).unwrap();
+        reg.register("b".to_string(), 2000, HashMap::new()).unwrap();
         assert_eq!(reg.all().len(), 2);
     }
 
+    #[test]
+    fn register_and_deregister_notify_subscribers() {
+        let mut reg = RpcRegistry::default();
+        let mut rx = reg.subscribe();
+        reg.register("visual".to_string(), 54321, HashMap::new())
+            .unwrap();
+        assert!(rx.try_recv().is_ok());
+        reg.deregister("visual");
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn deregister_missing_does_not_notify() {
+        let mut reg = RpcRegistry::default();
+        let mut rx = reg.subscribe();
+        reg.deregister("nonexistent");
+        assert!(rx.try_recv().is_err());
+    }
+
     #[test]
     fn method_meta_default_has_none_fields() {
         let meta = RpcMethodMeta::default();
@@ -177,13 +319,15 @@ mod tests {
         let meta = RpcMethodMeta {
             description: Some("test".to_string()),
             timeout: Some(5000),
-            input_schema: Some(input_schema),
+            input_schema: Some(input_schema.clone()),
+            output_schema: Some(input_schema),
             meta: Some(tag),
         };
         let json = serde_json::to_value(&meta).unwrap();
         assert_eq!(json["description"], "test");
         assert_eq!(json["timeout"], 5000);
         assert_eq!(json["inputSchema"]["type"], "object");
+        assert_eq!(json["outputSchema"]["type"], "object");
         assert_eq!(json["meta"]["category"], "tts");
     }
 
@@ -197,6 +341,7 @@ mod tests {
         assert_eq!(meta.description.as_deref(), Some("hello"));
         assert_eq!(meta.timeout, Some(3000));
         assert!(meta.input_schema.is_none());
+        assert!(meta.output_schema.is_none());
         assert!(meta.meta.is_none());
     }
 
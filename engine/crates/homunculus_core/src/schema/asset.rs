@@ -1,9 +1,24 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use std::path::Path;
 
 // Re-export types that moved to homunculus_utils for backward compatibility.
 #[allow(unused_imports)]
-pub use homunculus_utils::schema::asset::{AssetDeclaration, AssetEntry, AssetId, AssetType};
+pub use homunculus_utils::schema::asset::{
+    AssetDeclaration, AssetEntry, AssetId, AssetType, VrmaMetadata,
+};
+
+/// Reads the animation facts of a VRMA asset file; other asset types have none.
+///
+/// Files that cannot be read are logged and registered without metadata.
+pub fn read_vrma_metadata(asset_type: &AssetType, path: &Path) -> Option<VrmaMetadata> {
+    if asset_type != &AssetType::Vrma {
+        return None;
+    }
+    homunculus_utils::vrma::read_vrma_metadata(path)
+        .inspect_err(|e| warn!("Failed to read VRMA metadata of {}: {e}", path.display()))
+        .ok()
+}
 
 /// Component to track the asset ID loaded on an entity.
 #[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
@@ -43,6 +58,11 @@ impl AssetRegistry {
         self.entries.get(id)
     }
 
+    /// Look up an asset entry by ID for updating facts read after registration.
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut AssetEntry> {
+        self.entries.get_mut(id)
+    }
+
     /// Returns all registered assets.
     pub fn all(&self) -> impl Iterator<Item = &AssetEntry> {
         self.entries.values()
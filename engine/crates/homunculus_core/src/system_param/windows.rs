@@ -21,6 +21,14 @@ impl AppWindows<'_, '_> {
             })
     }
 
+    /// The area of each app window in global viewport coordinates.
+    pub fn rects(&self) -> Vec<Rect> {
+        self.windows
+            .iter()
+            .map(|(_, window, _)| window_to_rect(window))
+            .collect()
+    }
+
     pub fn global_cursor_pos(&self) -> Option<GlobalViewport> {
         self.windows.iter().find_map(|(_, window, _)| {
             let cursor = window.cursor_position()?;
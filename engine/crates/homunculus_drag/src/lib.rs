@@ -34,7 +34,8 @@
 //! 3. **Drag End**: User releases the mouse button
 //!    - System checks if mascot was dropped on a window
 //!    - If on window: enters sitting mode on that window
-//!    - If not on window: returns to default idle state
+//!    - If physics is enabled: falls with the throw velocity (see [`physics`])
+//!    - Otherwise: returns to default idle state
 //!
 //! ## Sitting Integration
 //!
@@ -50,21 +51,28 @@
 //! when dragging begins. This animation loops continuously until the drag
 //! operation ends, providing visual feedback to the user.
 
+pub mod physics;
+
 use bevy::camera::NormalizedRenderTarget;
+#[cfg(feature = "gui")]
 use bevy::image::Image;
+#[cfg(feature = "gui")]
 use bevy::pbr::MeshMaterial3d;
 use bevy::picking::mesh_picking::ray_cast::RayMeshHit;
 use bevy::prelude::*;
+#[cfg(feature = "gui")]
 use bevy_cef::prelude::WebviewExtendStandardMaterial;
 use bevy_vrm1::prelude::Initialized;
 use bevy_vrm1::vrm::Vrm;
 use homunculus_core::prelude::{
-    AppWindows, BoneOffsets, Coordinate, MascotTracker, PersonaState, VrmMeshRayCast,
-    global_cursor_pos,
+    AppWindows, BoneOffsets, Coordinate, GlobalViewport, MascotTracker, Persona, PersonaState,
+    VrmMeshRayCast, global_cursor_pos,
 };
+#[cfg(feature = "gui")]
 use homunculus_core::texture::{TRANSPARENT_ALPHA_THRESHOLD, sample_texture_alpha};
-use homunculus_screen::prelude::GlobalWindows;
+use homunculus_screen::prelude::{GlobalWindow, GlobalWindows};
 use homunculus_sitting::SittingWindow;
+use physics::{DragTrail, PhysicsSettings};
 
 /// Plugin that provides drag and drop functionality for VRM mascot models.
 ///
@@ -81,6 +89,8 @@ use homunculus_sitting::SittingWindow;
 /// - **Drag Move Observer**: Updates model position during dragging
 /// - **Drag End Observer**: Manages drop behavior and state transitions
 /// - **Animation Integration**: Loads and plays drag animations
+/// - **Physics**: Gravity and throwing for released mascots, configured by
+///   [`PhysicsSettings`]
 ///
 /// # Requirements
 ///
@@ -99,7 +109,12 @@ pub struct HomunculusDragPlugin;
 
 impl Plugin for HomunculusDragPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, observe_vrm);
+        app.init_resource::<PhysicsSettings>()
+            .add_systems(Startup, physics::load_settings)
+            .add_systems(
+                Update,
+                (observe_vrm, physics::fall, physics::finish_landing),
+            );
     }
 }
 
@@ -135,16 +150,22 @@ fn on_drag_start(
     mut commands: Commands,
     mut vrm_ray_cast: VrmMeshRayCast,
     bone_offsets: BoneOffsets,
-    webview_materials: Query<&MeshMaterial3d<WebviewExtendStandardMaterial>>,
-    webview_assets: Res<Assets<WebviewExtendStandardMaterial>>,
-    images: Res<Assets<Image>>,
+    #[cfg(feature = "gui")] webview_materials: Query<
+        &MeshMaterial3d<WebviewExtendStandardMaterial>,
+    >,
+    #[cfg(feature = "gui")] webview_assets: Res<Assets<WebviewExtendStandardMaterial>>,
+    #[cfg(feature = "gui")] images: Res<Assets<Image>>,
 ) {
     if !matches!(trigger.event.button, PointerButton::Primary) {
         return;
     }
+    #[cfg(feature = "gui")]
     let should_skip = |entity: Entity, hit: &RayMeshHit| -> bool {
         is_webview_transparent(entity, hit, &webview_materials, &webview_assets, &images)
     };
+    // Without webviews there is nothing transparent to pick through.
+    #[cfg(not(feature = "gui"))]
+    let should_skip = |_: Entity, _: &RayMeshHit| -> bool { false };
     if !vrm_ray_cast.is_frontmost_hit(&trigger.pointer_location, should_skip) {
         return;
     }
@@ -155,11 +176,12 @@ fn on_drag_start(
         .unwrap_or_default();
     commands
         .entity(vrm_entity)
-        .try_insert(DragHipsOffset(initial_offset))
-        .try_insert(PersonaState::from("drag"));
+        .try_insert((DragHipsOffset(initial_offset), DragTrail::default()))
+        .try_insert(PersonaState::from(PersonaState::DRAG));
 }
 
 /// Returns `true` if the hit is on a transparent WebView pixel that should be skipped.
+#[cfg(feature = "gui")]
 fn is_webview_transparent(
     entity: Entity,
     hit: &RayMeshHit,
@@ -190,6 +212,7 @@ fn on_drag_move(
     mut commands: Commands,
     coordinate: Coordinate,
     drag_offsets: Query<(&Transform, &DragHipsOffset)>,
+    mut trails: Query<&mut DragTrail>,
     bone_offsets: BoneOffsets,
     time: Res<Time>,
 ) {
@@ -218,10 +241,14 @@ fn on_drag_move(
     }
 
     let hips_offset = smoothed.0.extend(0.0);
+    let translation = current - hips_offset;
+    if let Ok(mut trail) = trails.get_mut(vrm_entity) {
+        trail.push(time.elapsed_secs(), translation);
+    }
     commands.entity(vrm_entity).try_insert((
         smoothed,
         Transform {
-            translation: current - hips_offset,
+            translation,
             ..*transform
         },
     ));
@@ -233,37 +260,78 @@ fn on_drag_end(
     windows: AppWindows,
     tracker: MascotTracker,
     bone_offsets: BoneOffsets,
+    coordinate: Coordinate,
+    physics: Res<PhysicsSettings>,
+    personas: Query<(&Persona, Option<&DragTrail>)>,
+    time: Res<Time>,
 ) {
     if !matches!(trigger.event.button, PointerButton::Primary) {
         return;
     }
     let vrm = trigger.entity;
-    commands.entity(vrm).remove::<DragHipsOffset>();
+    commands.entity(vrm).remove::<(DragHipsOffset, DragTrail)>();
     let Some(global_cursor_pos) = global_cursor_pos(&trigger, &windows) else {
         return;
     };
-    match GlobalWindows::find_all().and_then(|gw| gw.find_sitting_window(global_cursor_pos)) {
-        Some(global_window) => {
-            let hips_offset_y = bone_offsets.hips_offset(vrm).map(|h| h.y).unwrap_or(0.0);
-            let sitting_pos = global_window.sitting_pos(global_cursor_pos);
-            let sitting_window = SittingWindow::new(global_window, sitting_pos, hips_offset_y);
-            let Some(transform) = tracker.tracking_with_fixed_offset(
-                vrm,
-                sitting_window.sitting_pos(),
-                hips_offset_y,
-                homunculus_sitting::SITTING_ADJUST,
-            ) else {
-                return;
-            };
-            info!("Sitting on {:?}", sitting_window.window.title);
-            commands.entity(vrm).try_insert((
-                sitting_window,
-                transform,
-                PersonaState::from(PersonaState::SITTING),
-            ));
-        }
-        None => {
-            commands.entity(vrm).try_insert(PersonaState::default());
-        }
+    let global_windows = GlobalWindows::find_all();
+    if let Some(global_window) = global_windows
+        .as_ref()
+        .and_then(|gw| gw.find_sitting_window(global_cursor_pos))
+        && sit_on_window(
+            &mut commands,
+            vrm,
+            global_window,
+            global_cursor_pos,
+            &tracker,
+            &bone_offsets,
+        )
+    {
+        return;
+    }
+    if let Ok((persona, trail)) = personas.get(vrm)
+        && physics.enabled_for(&persona.id)
+        && physics::start_fall(
+            &mut commands,
+            vrm,
+            trail,
+            time.elapsed_secs(),
+            &physics,
+            &coordinate,
+            global_windows,
+        )
+    {
+        return;
     }
+    commands.entity(vrm).try_insert(PersonaState::default());
+}
+
+/// Sits the mascot on the top edge of `global_window` at `drop_pos`.
+///
+/// Returns `false` when the sitting position is not on any app window.
+pub(crate) fn sit_on_window(
+    commands: &mut Commands,
+    vrm: Entity,
+    global_window: GlobalWindow,
+    drop_pos: GlobalViewport,
+    tracker: &MascotTracker,
+    bone_offsets: &BoneOffsets,
+) -> bool {
+    let hips_offset_y = bone_offsets.hips_offset(vrm).map(|h| h.y).unwrap_or(0.0);
+    let sitting_pos = global_window.sitting_pos(drop_pos);
+    let sitting_window = SittingWindow::new(global_window, sitting_pos, hips_offset_y);
+    let Some(transform) = tracker.tracking_with_fixed_offset(
+        vrm,
+        sitting_window.sitting_pos(),
+        hips_offset_y,
+        homunculus_sitting::SITTING_ADJUST,
+    ) else {
+        return false;
+    };
+    info!("Sitting on {:?}", sitting_window.window.title);
+    commands.entity(vrm).try_insert((
+        sitting_window,
+        transform,
+        PersonaState::from(PersonaState::SITTING),
+    ));
+    true
 }
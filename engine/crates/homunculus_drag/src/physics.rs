@@ -0,0 +1,462 @@
+//! Optional gravity for released personas.
+//!
+//! With physics enabled, a persona released away from a window keeps the velocity it
+//! was thrown with, falls under gravity and bounces on the bottom of the monitor work
+//! area or the top edge of a window until it settles. Settling on a window sits the
+//! persona on it; settling anywhere else passes through the `landing` state to `idle`.
+
+use crate::sit_on_window;
+use bevy::prelude::*;
+use homunculus_core::prelude::{
+    BoneOffsets, Coordinate, GlobalViewport, MascotTracker, PersonaId, PersonaState,
+};
+use homunculus_prefs::PrefsDatabase;
+use homunculus_screen::prelude::{GlobalDisplays, GlobalWindow, GlobalWindows};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Preferences key of [`PhysicsSettings`].
+pub const PHYSICS_KEY: &str = "physics";
+
+/// The persona state while falling.
+pub const FALLING_STATE: &str = "falling";
+
+/// The persona state right after settling, before it returns to `idle`.
+pub const LANDING_STATE: &str = "landing";
+
+/// How long the `landing` state lasts.
+const LANDING_SECS: f32 = 0.5;
+
+/// Drag samples older than this at release do not count toward the throw.
+const THROW_WINDOW_SECS: f32 = 0.1;
+
+/// A bounce slower than this settles the persona, in world units per second.
+const SETTLE_SPEED: f32 = 0.5;
+
+/// Gravity settings, persisted under [`PHYSICS_KEY`].
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase", default)]
+pub struct PhysicsSettings {
+    /// Whether released personas fall. Entries in `personas` take precedence.
+    pub enabled: bool,
+    /// Downward acceleration in world units per second squared.
+    pub gravity: f32,
+    /// Fraction of the speed kept on each bounce, from `0` to `1`.
+    pub bounciness: f32,
+    /// Upper bound of the throw speed in world units per second.
+    pub max_throw_speed: f32,
+    /// Per-persona overrides of `enabled`.
+    #[cfg_attr(feature = "openapi", schema(value_type = HashMap<String, bool>))]
+    pub personas: HashMap<PersonaId, bool>,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            gravity: 9.8,
+            bounciness: 0.3,
+            max_throw_speed: 10.0,
+            personas: HashMap::new(),
+        }
+    }
+}
+
+impl PhysicsSettings {
+    /// Whether the persona falls when released.
+    pub fn enabled_for(&self, persona: &PersonaId) -> bool {
+        self.personas.get(persona).copied().unwrap_or(self.enabled)
+    }
+}
+
+/// Recent positions of a dragged persona, used to measure the throw.
+#[derive(Component, Debug, Default)]
+pub(crate) struct DragTrail(VecDeque<(f32, Vec3)>);
+
+impl DragTrail {
+    pub(crate) fn push(&mut self, secs: f32, position: Vec3) {
+        self.0.push_back((secs, position));
+        while self
+            .0
+            .front()
+            .is_some_and(|(t, _)| *t < secs - THROW_WINDOW_SECS)
+        {
+            self.0.pop_front();
+        }
+    }
+
+    /// Average velocity over the samples of the last [`THROW_WINDOW_SECS`] before `now`.
+    ///
+    /// A persona held still before release has no recent samples and is not thrown.
+    fn velocity(&self, now: f32) -> Vec3 {
+        let mut recent = self.0.iter().filter(|(t, _)| now - *t <= THROW_WINDOW_SECS);
+        let (Some(first), Some(last)) = (recent.next(), recent.last()) else {
+            return Vec3::ZERO;
+        };
+        let elapsed = last.0 - first.0;
+        if elapsed <= f32::EPSILON {
+            return Vec3::ZERO;
+        }
+        ((last.1 - first.1) / elapsed).with_z(0.0)
+    }
+}
+
+/// A horizontal edge a falling persona can land on, in world coordinates.
+#[derive(Debug, Clone)]
+struct Surface {
+    left: f32,
+    right: f32,
+    y: f32,
+    /// The window whose top edge this is; `None` for the bottom of a work area.
+    window: Option<GlobalWindow>,
+}
+
+/// A persona falling under gravity.
+#[derive(Component, Debug)]
+pub(crate) struct Falling {
+    velocity: Vec3,
+    surfaces: Vec<Surface>,
+    /// Left and right edges of the work areas; the persona bounces off them.
+    walls: Option<(f32, f32)>,
+}
+
+/// A persona in the `landing` state.
+#[derive(Component, Debug)]
+pub(crate) struct Landing(Timer);
+
+/// Starts a fall from the drag release.
+///
+/// Returns `false` when there is nothing to land on (e.g. no display information),
+/// in which case the persona stays where it was released.
+pub(crate) fn start_fall(
+    commands: &mut Commands,
+    vrm: Entity,
+    trail: Option<&DragTrail>,
+    now: f32,
+    settings: &PhysicsSettings,
+    coordinate: &Coordinate,
+    windows: Option<GlobalWindows>,
+) -> bool {
+    let (surfaces, walls) = collect_surfaces(coordinate, windows);
+    if surfaces.is_empty() {
+        return false;
+    }
+    let velocity = trail
+        .map(|trail| trail.velocity(now))
+        .unwrap_or_default()
+        .clamp_length_max(settings.max_throw_speed);
+    commands.entity(vrm).try_insert((
+        Falling {
+            velocity,
+            surfaces,
+            walls,
+        },
+        PersonaState::from(FALLING_STATE),
+    ));
+    true
+}
+
+/// Converts work area bottoms and window tops to world-space surfaces.
+///
+/// Edges that no app window covers cannot be converted and are skipped.
+fn collect_surfaces(
+    coordinate: &Coordinate,
+    windows: Option<GlobalWindows>,
+) -> (Vec<Surface>, Option<(f32, f32)>) {
+    let to_world =
+        |x: f32, y: f32| coordinate.to_world_2d_by_global(GlobalViewport(Vec2::new(x, y)));
+    let edge =
+        |rect: &Rect, y: f32| Some((to_world(rect.min.x, y)?, to_world(rect.max.x - 1.0, y)?));
+
+    let mut surfaces = Vec::new();
+    let mut walls: Option<(f32, f32)> = None;
+    for display in GlobalDisplays::find_all().iter() {
+        let work_area = display.work_area;
+        let Some((left, right)) = edge(&work_area, work_area.max.y - 1.0) else {
+            continue;
+        };
+        walls = Some(match walls {
+            Some((min, max)) => (min.min(left.x), max.max(right.x)),
+            None => (left.x, right.x),
+        });
+        surfaces.push(Surface {
+            left: left.x,
+            right: right.x,
+            y: left.y,
+            window: None,
+        });
+    }
+    for window in windows.into_iter().flat_map(|windows| windows.0) {
+        let Some((left, right)) = edge(&window.frame, window.frame.min.y) else {
+            continue;
+        };
+        surfaces.push(Surface {
+            left: left.x,
+            right: right.x,
+            y: left.y,
+            window: Some(window),
+        });
+    }
+    (surfaces, walls)
+}
+
+/// The outcome of one simulation step.
+#[derive(Debug, PartialEq)]
+struct FallStep {
+    position: Vec3,
+    velocity: Vec3,
+    /// Index of the surface the persona settled on.
+    settled_on: Option<usize>,
+}
+
+/// Advances a falling persona by `dt` seconds.
+///
+/// The persona lands on the highest surface it passes through this step. Falling
+/// below every surface (e.g. released over the taskbar) lands it on the lowest one.
+fn step_fall(
+    position: Vec3,
+    mut velocity: Vec3,
+    dt: f32,
+    settings: &PhysicsSettings,
+    surfaces: &[Surface],
+    walls: Option<(f32, f32)>,
+) -> FallStep {
+    velocity.y -= settings.gravity * dt;
+    let mut next = position + velocity * dt;
+
+    if let Some((min, max)) = walls
+        && (next.x < min || max < next.x)
+    {
+        next.x = next.x.clamp(min, max);
+        velocity.x = -velocity.x * settings.bounciness;
+    }
+
+    let crossed = surfaces
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| {
+            s.left <= next.x && next.x <= s.right && next.y <= s.y && s.y <= position.y
+        })
+        .max_by(|(_, a), (_, b)| a.y.total_cmp(&b.y))
+        .or_else(|| {
+            surfaces
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y))
+                .filter(|(_, lowest)| next.y < lowest.y)
+        });
+    let Some((index, surface)) = crossed else {
+        return FallStep {
+            position: next,
+            velocity,
+            settled_on: None,
+        };
+    };
+
+    next.y = surface.y;
+    let rebound = -velocity.y * settings.bounciness;
+    if rebound < SETTLE_SPEED {
+        return FallStep {
+            position: next,
+            velocity: Vec3::ZERO,
+            settled_on: Some(index),
+        };
+    }
+    velocity.y = rebound;
+    velocity.x *= settings.bounciness;
+    FallStep {
+        position: next,
+        velocity,
+        settled_on: None,
+    }
+}
+
+pub(crate) fn load_settings(mut commands: Commands, db: NonSend<PrefsDatabase>) {
+    if let Some(settings) = db.load_as::<PhysicsSettings>(PHYSICS_KEY).ok().flatten() {
+        commands.insert_resource(settings);
+    }
+}
+
+/// Moves falling personas and settles them on the surface they land on.
+///
+/// The fall ends without landing when something else changes the persona state,
+/// such as a new drag.
+pub(crate) fn fall(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<PhysicsSettings>,
+    mut fallers: Query<(Entity, &mut Falling, &PersonaState)>,
+    transforms: Query<&Transform>,
+    coordinate: Coordinate,
+    tracker: MascotTracker,
+    bone_offsets: BoneOffsets,
+) {
+    for (vrm, mut falling, state) in fallers.iter_mut() {
+        if state.0 != FALLING_STATE {
+            commands.entity(vrm).try_remove::<Falling>();
+            continue;
+        }
+        let Ok(tf) = transforms.get(vrm) else {
+            continue;
+        };
+        let step = step_fall(
+            tf.translation,
+            falling.velocity,
+            time.delta_secs(),
+            &settings,
+            &falling.surfaces,
+            falling.walls,
+        );
+        falling.velocity = step.velocity;
+        let moved = Transform {
+            translation: step.position,
+            ..*tf
+        };
+        let Some(index) = step.settled_on else {
+            commands.entity(vrm).try_insert(moved);
+            continue;
+        };
+
+        commands.entity(vrm).try_remove::<Falling>();
+        if let Some(window) = &falling.surfaces[index].window
+            && let Some(global) = coordinate.to_global_by_world(step.position)
+            && sit_on_window(
+                &mut commands,
+                vrm,
+                window.clone(),
+                GlobalViewport(Vec2::new(global.x, window.frame.min.y)),
+                &tracker,
+                &bone_offsets,
+            )
+        {
+            continue;
+        }
+        commands.entity(vrm).try_insert((
+            moved,
+            Landing(Timer::from_seconds(LANDING_SECS, TimerMode::Once)),
+            PersonaState::from(LANDING_STATE),
+        ));
+    }
+}
+
+/// Returns landed personas to `idle` once the `landing` state has played out.
+pub(crate) fn finish_landing(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut landings: Query<(Entity, &mut Landing, &PersonaState)>,
+) {
+    for (vrm, mut landing, state) in landings.iter_mut() {
+        if state.0 != LANDING_STATE {
+            commands.entity(vrm).try_remove::<Landing>();
+        } else if landing.0.tick(time.delta()).is_finished() {
+            commands
+                .entity(vrm)
+                .try_remove::<Landing>()
+                .try_insert(PersonaState::default());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floor(y: f32) -> Surface {
+        Surface {
+            left: -10.0,
+            right: 10.0,
+            y,
+            window: None,
+        }
+    }
+
+    #[test]
+    fn throw_velocity_uses_recent_samples_only() {
+        let mut trail = DragTrail::default();
+        trail.push(0.0, Vec3::new(-5.0, 0.0, 0.0));
+        trail.push(1.0, Vec3::new(0.0, 0.0, 2.0));
+        trail.push(1.05, Vec3::new(0.1, 0.05, 2.0));
+        trail.push(1.1, Vec3::new(0.2, 0.1, 2.0));
+        let velocity = trail.velocity(1.1);
+        assert!((velocity - Vec3::new(2.0, 1.0, 0.0)).length() < 1e-3);
+
+        assert_eq!(trail.velocity(2.0), Vec3::ZERO);
+    }
+
+    #[test]
+    fn falling_lands_on_the_highest_surface_below() {
+        let settings = PhysicsSettings::default();
+        let window = Surface {
+            left: -1.0,
+            right: 1.0,
+            y: 0.5,
+            window: Some(GlobalWindow::default()),
+        };
+        let surfaces = [floor(-2.0), window];
+        let step = step_fall(
+            Vec3::new(0.0, 0.52, 0.0),
+            Vec3::ZERO,
+            0.05,
+            &settings,
+            &surfaces,
+            None,
+        );
+        assert_eq!(step.settled_on, Some(1));
+        assert_eq!(step.position, Vec3::new(0.0, 0.5, 0.0));
+
+        let step = step_fall(
+            Vec3::new(2.0, 0.6, 0.0),
+            Vec3::ZERO,
+            0.01,
+            &settings,
+            &surfaces,
+            None,
+        );
+        assert_eq!(step.settled_on, None);
+        assert!(step.position.y < 0.6);
+    }
+
+    #[test]
+    fn fast_landings_bounce_with_damping() {
+        let settings = PhysicsSettings::default();
+        let step = step_fall(
+            Vec3::new(0.0, 0.1, 0.0),
+            Vec3::new(4.0, -10.0, 0.0),
+            0.02,
+            &settings,
+            &[floor(0.0)],
+            None,
+        );
+        assert_eq!(step.settled_on, None);
+        assert_eq!(step.position.y, 0.0);
+        assert!(step.velocity.y > 0.0 && step.velocity.y < 10.0 * settings.bounciness + 0.1);
+        assert!((step.velocity.x - 4.0 * settings.bounciness).abs() < 1e-5);
+    }
+
+    #[test]
+    fn walls_reflect_and_lowest_surface_catches_everything() {
+        let settings = PhysicsSettings::default();
+        let step = step_fall(
+            Vec3::new(4.9, 1.0, 0.0),
+            Vec3::new(10.0, 0.0, 0.0),
+            0.1,
+            &settings,
+            &[floor(-1.0)],
+            Some((-5.0, 5.0)),
+        );
+        assert_eq!(step.position.x, 5.0);
+        assert!(step.velocity.x < 0.0);
+
+        let step = step_fall(
+            Vec3::new(20.0, -3.0, 0.0),
+            Vec3::ZERO,
+            0.01,
+            &settings,
+            &[floor(-1.0)],
+            None,
+        );
+        assert_eq!(step.settled_on, Some(0));
+        assert_eq!(step.position.y, -1.0);
+    }
+}
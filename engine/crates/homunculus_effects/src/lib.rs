@@ -38,6 +38,7 @@ use bevy::camera::RenderTarget;
 use bevy::camera::visibility::RenderLayers;
 pub use bevy::prelude::*;
 use bevy::window::WindowRef;
+#[cfg(feature = "gui")]
 use bevy::winit::WINIT_WINDOWS;
 use homunculus_core::prelude::CameraOrders;
 use serde::{Deserialize, Serialize};
@@ -87,8 +88,9 @@ pub struct HomunculusEffectsPlugin;
 impl Plugin for HomunculusEffectsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(StampEffectsPlugin)
-            .add_systems(Startup, spawn_camera2d)
-            .add_systems(Update, initialize_camera_position);
+            .add_systems(Startup, spawn_camera2d);
+        #[cfg(feature = "gui")]
+        app.add_systems(Update, initialize_camera_position);
     }
 }
 
@@ -112,6 +114,7 @@ fn spawn_camera2d(mut commands: Commands, windows: Query<(Entity, &RenderLayers)
 #[reflect(Component, Serialize, Deserialize)]
 struct UninitializedEffectCamera;
 
+#[cfg(feature = "gui")]
 fn initialize_camera_position(
     mut commands: Commands,
     cameras: Query<
@@ -41,8 +41,45 @@
 //! ### VRM Operations (via persona)
 //! - `POST /personas/{id}/vrm` - Attach VRM model
 //! - `DELETE /personas/{id}/vrm` - Detach VRM model
+//! - `GET/PATCH/DELETE /personas/{id}/vrm/expressions` - Expression weights, optionally eased
+//! - `GET /personas/{id}/vrm/expressions/presets` - List expression presets
+//! - `PUT/DELETE /personas/{id}/vrm/expressions/presets/{name}` - Save or delete a preset
+//! - `POST /personas/{id}/vrm/expressions/presets/{name}/apply` - Apply a preset
+//! - `GET/PUT /personas/{id}/vrm/expressions/auto` - Auto-blink and micro-expression settings
+//! - `PATCH /personas/{id}/vrm/spring-bones/{chain_id}` - Change and remember a spring bone chain
+//! - `POST /personas/{id}/vrm/spring-bones/presets/{name}` - Apply a spring bone preset
+//! - `PUT /personas/{id}/vrm/spring-bones/wind` - Override the wind for this persona
+//! - `GET/PUT/DELETE /personas/{id}/vrm/ik` - Hand and foot inverse kinematics targets
+//! - `PUT/DELETE /personas/{id}/vrm/bone/{bone_name}` - Rotate a bone on top of the animation
+//! - `GET/PUT/DELETE /personas/{id}/vrm/pose` - All bone rotations at once
+//! - `GET /personas/{id}/vrm/pose/presets` - List named poses
+//! - `PUT/DELETE /personas/{id}/vrm/pose/presets/{name}` - Save or delete a named pose
+//! - `POST /personas/{id}/vrm/pose/presets/{name}/apply` - Take a named pose
+//! - `PUT /personas/{id}/vrm/look/point` - Look at a world or screen point
+//! - `PUT /personas/{id}/vrm/look/wander` - Let the gaze wander idly
+//! - `GET/PUT /personas/{id}/vrm/look/settings` - Gaze limits, smoothing, saccades and head turns
 //! - `POST /personas/{id}/vrm/vrma/play` - Play VRMA animation
 //! - `POST /personas/{id}/vrm/vrma/stop` - Stop VRMA animation
+//! - `POST /personas/{id}/approach` - Move next to another persona
+//! - `POST /personas/{id}/face` - Turn toward another persona
+//! - `POST /personas/{id}/walk` - Walk to a position
+//! - `POST /personas/{id}/walk/stop` - Stop walking
+//! - `POST /personas/{id}/gestures/{name}` - Nod, shake the head, shrug, wave, bow, jump or tilt
+//! - `PUT /personas/{id}/physics` - Override gravity for this persona
+//! - `GET/POST /personas/{id}/emotion` - Get or set the fading emotion
+//! - `GET/PUT /personas/{id}/emotion/mapping` - Emotion-to-expression mapping for the VRM model
+//!
+//! ### Interaction
+//! - `POST /interaction/formation` - Line personas up along the bottom of the screen
+//! - `GET/PUT /interaction/settings` - Separation and proximity settings
+//!
+//! ### Timelines (via persona)
+//! - `GET /personas/{id}/timelines` - Timeline playback state
+//! - `POST /personas/{id}/timelines/play` - Play a timeline script
+//! - `POST /personas/{id}/timelines/{stop,pause,resume,seek}` - Control playback
+//!
+//! ### Batch
+//! - `POST /batch` - Run several operations in one reactor task
 //!
 //! ### Effects
 //! - `POST /effects/stamps` - Display visual stamp effect
@@ -68,6 +105,7 @@
 //! - Development tools and debuggers
 
 mod extract;
+mod recorder;
 mod route;
 mod state;
 
@@ -75,15 +113,17 @@ pub mod prelude {
     pub use crate::HomunculusHttpServerPlugin;
 }
 
+use crate::recorder::SessionRecorder;
 use crate::route::{
-    assets, audio, coordinates, displays, info, persona, preferences, settings, shadow_panel, stt,
-    webviews,
+    assets, audio, batch, coordinates, displays, info, persona, preferences, settings,
+    shadow_panel, stt, webviews,
 };
 use crate::state::HttpState;
 use axum::Router;
 use bevy::prelude::*;
 use bevy_flurx::action::side_effect;
 use bevy_flurx::prelude::Reactor;
+use homunculus_api::events::EventsApi;
 use homunculus_api::prelude::ApiReactor;
 use homunculus_core::rpc_registry::{RpcRegistry, SharedRpcRegistry};
 use homunculus_utils::config::HomunculusConfig;
@@ -105,6 +145,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
     tags(
         (name = "app", description = "Application lifecycle"),
         (name = "audio", description = "Sound effects and background music"),
+        (name = "batch", description = "Multi-step operations in one request"),
         (name = "personas", description = "Persona management"),
         (name = "entities", description = "Entity transform and tween control"),
         (name = "webviews", description = "WebView management"),
@@ -112,6 +153,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
         (name = "effects", description = "Visual effects"),
         (name = "preferences", description = "User preferences"),
         (name = "signals", description = "Pub/sub signal system"),
+        (name = "events", description = "Unified engine event stream"),
         (name = "settings", description = "Application settings"),
         (name = "shadow-panel", description = "Shadow panel transparency"),
         (name = "displays", description = "Display information"),
@@ -217,11 +259,25 @@ async fn start_http_server(
 ) -> std::io::Result<()> {
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     info!("HTTP server listening on {addr}");
-    axum::serve(
-        listener,
-        create_router(reactor, config, runtime, rpc_registry),
-    )
-    .await?;
+    let mut router = create_router(reactor.clone(), config.clone(), runtime, rpc_registry);
+    if let Some(path) = &config.session.record_path {
+        match SessionRecorder::create(path) {
+            Ok(recorder) => {
+                info!("Recording API session to {}", path.display());
+                let recorder = Arc::new(recorder);
+                tokio::spawn(recorder::record_events(
+                    recorder.clone(),
+                    EventsApi::from(reactor),
+                ));
+                router = router.layer(axum::middleware::from_fn_with_state(
+                    recorder,
+                    recorder::record_session,
+                ));
+            }
+            Err(e) => error!("Failed to open session trace {}: {e}", path.display()),
+        }
+    }
+    axum::serve(listener, router).await?;
     Ok(())
 }
 
@@ -234,11 +290,13 @@ fn build_openapi_router() -> OpenApiRouter<HttpState> {
         .nest("/shadow-panel", shadow_panel_router())
         .nest("/entities", entities_router())
         .nest("/personas", persona_router())
+        .nest("/interaction", interaction_router())
         .nest("/coordinates", coordinates_router())
         .nest("/preferences", preferences_router())
         .nest("/webviews", webviews_router())
         .nest("/displays", display_router())
         .nest("/signals", signals_router())
+        .nest("/events", events_router())
         .nest("/audio", audio_router())
         .nest("/effects", effects_router())
         .nest("/mods", mods_router())
@@ -249,6 +307,8 @@ fn build_openapi_router() -> OpenApiRouter<HttpState> {
         .routes(routes!(assets::list_assets))
         .routes(routes!(assets::import))
         .routes(routes!(assets::get_asset_file))
+        .routes(routes!(assets::get_diagnostics))
+        .routes(routes!(batch::batch))
         .nest("/rpc", rpc_openapi_router())
 }
 
@@ -268,7 +328,7 @@ fn create_router(
         ))
         .nest_service(
             "/mcp",
-            homunculus_mcp::create_mcp_service(reactor, config, runtime),
+            homunculus_mcp::create_mcp_service(reactor, config, runtime, rpc_registry),
         )
         .layer(
             CorsLayer::new()
@@ -365,6 +425,7 @@ fn entities_id_router() -> OpenApiRouter<HttpState> {
         .routes(routes!(entities::tween::tween_position))
         .routes(routes!(entities::tween::tween_rotation))
         .routes(routes!(entities::tween::tween_scale))
+        .routes(routes!(entities::tween::tween_path))
 }
 
 fn persona_router() -> OpenApiRouter<HttpState> {
@@ -378,6 +439,15 @@ fn persona_router() -> OpenApiRouter<HttpState> {
         .nest("/{id}", persona_id_router())
 }
 
+fn interaction_router() -> OpenApiRouter<HttpState> {
+    OpenApiRouter::new()
+        .routes(routes!(persona::interaction::formation))
+        .routes(routes!(
+            persona::interaction::get_settings,
+            persona::interaction::put_settings
+        ))
+}
+
 fn persona_id_router() -> OpenApiRouter<HttpState> {
     OpenApiRouter::new()
         .routes(routes!(
@@ -431,19 +501,77 @@ fn persona_id_router() -> OpenApiRouter<HttpState> {
             persona::vrm::expressions::modify_expressions,
             persona::vrm::expressions::clear_expressions
         ))
+        .routes(routes!(persona::vrm::expressions::list_presets))
+        .routes(routes!(
+            persona::vrm::expressions::put_preset,
+            persona::vrm::expressions::delete_preset
+        ))
+        .routes(routes!(persona::vrm::expressions::apply_preset))
+        .routes(routes!(
+            persona::vrm::expressions::get_auto,
+            persona::vrm::expressions::put_auto
+        ))
         .routes(routes!(persona::vrm::vrma::play_vrma))
         .routes(routes!(persona::vrm::vrma::stop_vrma))
         .routes(routes!(persona::vrm::vrma::get_vrma))
         .routes(routes!(persona::vrm::position::get_position))
-        .routes(routes!(persona::vrm::bone::get_bone))
+        .routes(routes!(
+            persona::vrm::bone::get_bone,
+            persona::vrm::pose::put_bone,
+            persona::vrm::pose::delete_bone
+        ))
         .routes(routes!(persona::vrm::look::look_cursor))
         .routes(routes!(persona::vrm::look::look_target))
         .routes(routes!(persona::vrm::look::unlook))
+        .routes(routes!(
+            persona::vrm::ik::get_ik,
+            persona::vrm::ik::put_ik,
+            persona::vrm::ik::delete_ik
+        ))
+        .routes(routes!(
+            persona::vrm::pose::get_pose,
+            persona::vrm::pose::put_pose,
+            persona::vrm::pose::delete_pose
+        ))
+        .routes(routes!(persona::vrm::pose::list_presets))
+        .routes(routes!(
+            persona::vrm::pose::put_preset,
+            persona::vrm::pose::delete_preset
+        ))
+        .routes(routes!(persona::vrm::pose::apply_preset))
+        .routes(routes!(persona::vrm::look::look_point))
+        .routes(routes!(persona::vrm::look::look_wander))
+        .routes(routes!(
+            persona::vrm::look::get_settings,
+            persona::vrm::look::put_settings
+        ))
+        .routes(routes!(persona::interaction::approach))
+        .routes(routes!(persona::interaction::face))
+        .routes(routes!(persona::locomotion::walk))
+        .routes(routes!(persona::gestures::perform))
+        .routes(routes!(persona::locomotion::stop_walk))
+        .routes(routes!(persona::physics::put_physics))
+        .routes(routes!(
+            persona::emotion::get_emotion,
+            persona::emotion::set_emotion
+        ))
+        .routes(routes!(
+            persona::emotion::get_mapping,
+            persona::emotion::put_mapping
+        ))
         .routes(routes!(
             persona::vrm::spring_bones::list_spring_bones,
             persona::vrm::spring_bones::patch_spring_bones
         ))
+        .routes(routes!(persona::vrm::spring_bones::apply_preset))
+        .routes(routes!(persona::vrm::spring_bones::put_wind))
         .routes(routes!(persona::vrm::speech::speech_timeline))
+        .routes(routes!(persona::timelines::get_timeline))
+        .routes(routes!(persona::timelines::play_timeline))
+        .routes(routes!(persona::timelines::stop_timeline))
+        .routes(routes!(persona::timelines::pause_timeline))
+        .routes(routes!(persona::timelines::resume_timeline))
+        .routes(routes!(persona::timelines::seek_timeline))
         .layer(axum::extract::DefaultBodyLimit::max(20 * 1024 * 1024))
 }
 
@@ -454,7 +582,13 @@ fn coordinates_router() -> OpenApiRouter<HttpState> {
 }
 
 fn settings_router() -> OpenApiRouter<HttpState> {
-    OpenApiRouter::new().routes(routes!(settings::get_fps, settings::set_fps))
+    OpenApiRouter::new()
+        .routes(routes!(settings::get_fps, settings::set_fps))
+        .routes(routes!(settings::get_physics, settings::put_physics))
+        .routes(routes!(
+            settings::get_spring_bones,
+            settings::put_spring_bones
+        ))
 }
 
 fn shadow_panel_router() -> OpenApiRouter<HttpState> {
@@ -499,9 +633,19 @@ fn signals_router() -> OpenApiRouter<HttpState> {
     OpenApiRouter::new()
         .routes(routes!(route::signals::list_signals))
         .routes(routes!(route::signals::send))
+        .routes(routes!(route::signals::history))
+        .routes(routes!(route::signals::request))
+        .routes(routes!(
+            route::signals::set_retention,
+            route::signals::clear_retention
+        ))
         .route("/ws", axum::routing::get(route::signals::ws_handler))
 }
 
+fn events_router() -> OpenApiRouter<HttpState> {
+    OpenApiRouter::new().route("/ws", axum::routing::get(route::events::ws_handler))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::create_router;
@@ -514,7 +658,8 @@ mod tests {
     use homunculus_api::HomunculusApiPlugin;
     use homunculus_api::prelude::{ApiReactor, ShadowPanelApiPlugin, WebviewApiPlugin};
     use homunculus_core::prelude::{
-        AssetRegistry, ModInfo, ModMenuMetadata, ModMenuMetadataList, ModRegistry,
+        AssetRegistry, ModInfo, ModMenuMetadata, ModMenuMetadataList, ModRegistry, Persona,
+        PersonaId, PersonaIndex,
     };
     use homunculus_core::rpc_registry::RpcRegistry;
     use homunculus_prefs::PrefsDatabase;
@@ -550,7 +695,8 @@ mod tests {
         app.init_resource::<AssetRegistry>();
         app.init_resource::<ModRegistry>();
         app.init_resource::<ModMenuMetadataList>();
-        app.init_resource::<homunculus_core::prelude::PersonaIndex>();
+        app.init_resource::<PersonaIndex>();
+        app.init_resource::<homunculus_drag::physics::PhysicsSettings>();
         let config = HomunculusConfig::default();
         let runtime = RuntimeResolver::detect();
         app.insert_resource(config.clone());
@@ -624,6 +770,7 @@ mod tests {
                         path: "test.vrm".to_string(),
                         asset_type: AssetType::Vrm,
                         description: None,
+                        tags: vec![],
                     },
                 )]),
                 menus: vec![],
@@ -649,6 +796,7 @@ mod tests {
                         path: "test.vrm".to_string(),
                         asset_type: AssetType::Vrm,
                         description: None,
+                        tags: vec![],
                     },
                 )]),
                 menus: vec![],
@@ -693,6 +841,34 @@ mod tests {
         }
     }
 
+    /// Spawns a persona registered under `id` in the [`PersonaIndex`].
+    pub fn spawn_test_persona(app: &mut App, id: &str) -> Entity {
+        let entity = app
+            .world_mut()
+            .spawn((
+                Persona {
+                    id: PersonaId::new(id),
+                    ..Default::default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        app.world_mut()
+            .resource_mut::<PersonaIndex>()
+            .insert(PersonaId::new(id), entity);
+        entity
+    }
+
+    /// Builds a request with a JSON body.
+    pub fn json_request(method: &str, uri: &str, body: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
     #[test]
     fn test_get_mod_by_name() {
         let (mut app, router) = test_app();
@@ -712,6 +888,7 @@ mod tests {
                         path: "test.vrm".to_string(),
                         asset_type: AssetType::Vrm,
                         description: None,
+                        tags: vec![],
                     },
                 )]),
                 menus: vec![],
@@ -737,6 +914,7 @@ mod tests {
                         path: "test.vrm".to_string(),
                         asset_type: AssetType::Vrm,
                         description: None,
+                        tags: vec![],
                     },
                 )]),
                 menus: vec![],
@@ -815,6 +993,7 @@ mod tests {
                         path: "test.vrm".to_string(),
                         asset_type: AssetType::Vrm,
                         description: None,
+                        tags: vec![],
                     },
                 )]),
                 menus: vec![],
@@ -850,6 +1029,7 @@ mod tests {
                             path: "test.vrm".to_string(),
                             asset_type: AssetType::Vrm,
                             description: None,
+                            tags: vec![],
                         },
                     )]),
                     menus: vec![],
@@ -897,6 +1077,87 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_list_assets_by_tag() {
+        use homunculus_api::assets::AssetInfo;
+        use homunculus_core::prelude::{AssetEntry, AssetId, VrmaMetadata};
+
+        let (mut app, router) = test_app();
+        let wave = VrmaMetadata {
+            duration_secs: 1.5,
+            loopable: false,
+            bones: vec!["rightUpperArm".to_string()],
+            root_motion: false,
+        };
+        let mut registry = app.world_mut().resource_mut::<AssetRegistry>();
+        for (id, tags, vrma) in [
+            (
+                "test-mod:wave",
+                vec!["greeting".to_string()],
+                Some(wave.clone()),
+            ),
+            ("test-mod:idle", vec!["idle".to_string()], None),
+        ] {
+            registry.register(AssetEntry {
+                id: AssetId::new(id),
+                path: PathBuf::from(format!("{id}.vrma")),
+                absolute_path: PathBuf::from(format!("/tmp/{id}.vrma")),
+                asset_type: AssetType::Vrma,
+                description: None,
+                tags,
+                vrma,
+                license: None,
+                mod_name: "test-mod".to_string(),
+            });
+        }
+
+        let request = Request::get("/assets?type=vrma&tag=greeting")
+            .body(Body::empty())
+            .unwrap();
+        let response = block_on(call(&mut app, router, request));
+        let body = block_on(response.into_body().collect()).unwrap().to_bytes();
+        let assets: Vec<AssetInfo> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].id, AssetId::new("test-mod:wave"));
+        assert_eq!(assets[0].tags, vec!["greeting".to_string()]);
+        assert_eq!(assets[0].vrma, Some(wave));
+    }
+
+    #[test]
+    fn test_list_assets_includes_vrm_license() {
+        use homunculus_api::assets::AssetInfo;
+        use homunculus_core::prelude::{AssetEntry, AssetId, VrmLicense};
+
+        let (mut app, router) = test_app();
+        let license = VrmLicense {
+            name: Some("Elmer".to_string()),
+            allow_redistribution: Some(false),
+            ..Default::default()
+        };
+        app.world_mut()
+            .resource_mut::<AssetRegistry>()
+            .register(AssetEntry {
+                id: AssetId::new("test-mod:elmer"),
+                path: PathBuf::from("elmer.vrm"),
+                absolute_path: PathBuf::from("/tmp/elmer.vrm"),
+                asset_type: AssetType::Vrm,
+                description: None,
+                tags: vec![],
+                vrma: None,
+                license: Some(license.clone()),
+                mod_name: "test-mod".to_string(),
+            });
+
+        let request = Request::get("/assets?type=vrm")
+            .body(Body::empty())
+            .unwrap();
+        let response = block_on(call(&mut app, router, request));
+        let body = block_on(response.into_body().collect()).unwrap().to_bytes();
+        let assets: Vec<AssetInfo> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].license, Some(license));
+    }
+
     #[test]
     fn test_get_asset_file_returns_file_content() {
         let (mut app, router) = test_app();
@@ -913,6 +1174,9 @@ mod tests {
                 absolute_path: tmp.clone(),
                 asset_type: AssetType::Image,
                 description: None,
+                tags: vec![],
+                vrma: None,
+                license: None,
                 mod_name: "test-mod".to_string(),
             },
         );
@@ -977,6 +1241,9 @@ mod tests {
                 absolute_path: tmp.clone(),
                 asset_type: AssetType::Image,
                 description: None,
+                tags: vec![],
+                vrma: None,
+                license: None,
                 mod_name: "local".to_string(),
             });
 
@@ -1010,6 +1277,9 @@ mod tests {
                 absolute_path: PathBuf::from("/tmp/does_not_exist_12345.png"),
                 asset_type: AssetType::Image,
                 description: None,
+                tags: vec![],
+                vrma: None,
+                license: None,
                 mod_name: "test-mod".to_string(),
             },
         );
@@ -1021,6 +1291,56 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_asset_diagnostics() {
+        let (mut app, router) = test_app();
+
+        let tmp = std::env::temp_dir().join("test_diagnostics.vrm");
+        std::fs::write(&tmp, b"not-a-glb").unwrap();
+        {
+            let mut registry = app.world_mut().resource_mut::<AssetRegistry>();
+            for (id, asset_type) in [
+                ("vrm:local:broken", AssetType::Vrm),
+                ("img", AssetType::Image),
+            ] {
+                registry.register_imported(homunculus_core::prelude::AssetEntry {
+                    id: homunculus_utils::prelude::AssetId::new(id),
+                    path: PathBuf::from("test_diagnostics.vrm"),
+                    absolute_path: tmp.clone(),
+                    asset_type,
+                    description: None,
+                    tags: vec![],
+                    vrma: None,
+                    license: None,
+                    mod_name: "local".to_string(),
+                });
+            }
+        }
+
+        let request = Request::get("/assets/vrm%3Alocal%3Abroken/diagnostics")
+            .body(Body::empty())
+            .unwrap();
+        let response = block_on(call(&mut app, router.clone(), request));
+        let body = block_on(response.into_body().collect()).unwrap().to_bytes();
+        let report: homunculus_core::prelude::VrmDiagnostics =
+            serde_json::from_slice(&body).unwrap();
+        assert_eq!(report.spec_version, None);
+        assert!(!report.errors.is_empty());
+
+        for (id, status) in [
+            ("img", StatusCode::BAD_REQUEST),
+            ("missing", StatusCode::NOT_FOUND),
+        ] {
+            let request = Request::get(format!("/assets/{id}/diagnostics"))
+                .body(Body::empty())
+                .unwrap();
+            let response = block_on(call_any_status(&mut app, router.clone(), request));
+            assert_eq!(response.status(), status);
+        }
+
+        std::fs::remove_file(&tmp).ok();
+    }
+
     #[test]
     fn test_list_signals_with_channels() {
         let (mut app, router) = test_app();
@@ -1049,4 +1369,309 @@ mod tests {
             assert_eq!(signals[0].subscribers, 0);
         });
     }
+
+    #[test]
+    fn test_signal_history_with_retention() {
+        let (mut app, router) = test_app();
+
+        let request = Request::put("/signals/chat/retention")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"maxMessages":2}"#))
+            .unwrap();
+        block_on(async {
+            let response = call(&mut app, router.clone(), request).await;
+            assert_eq!(response.status(), StatusCode::OK);
+        });
+
+        for n in 1..=3 {
+            let request = Request::post("/signals/chat")
+                .header("content-type", "application/json")
+                .body(Body::from(format!(r#"{{"n":{n}}}"#)))
+                .unwrap();
+            block_on(call(&mut app, router.clone(), request));
+        }
+
+        // Only the last two messages are retained
+        let request = Request::get("/signals/chat/history")
+            .body(Body::empty())
+            .unwrap();
+        block_on(async {
+            let response = call(&mut app, router.clone(), request).await;
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let records: Vec<homunculus_api::prelude::SignalRecord> =
+                serde_json::from_slice(&body).unwrap();
+            assert_eq!(
+                records.iter().map(|r| r.seq).collect::<Vec<_>>(),
+                vec![2, 3]
+            );
+            assert_eq!(records[1].payload, serde_json::json!({"n": 3}));
+        });
+
+        // `since` skips messages the caller has already seen
+        let request = Request::get("/signals/chat/history?since=2")
+            .body(Body::empty())
+            .unwrap();
+        block_on(async {
+            let response = call(&mut app, router, request).await;
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let records: Vec<homunculus_api::prelude::SignalRecord> =
+                serde_json::from_slice(&body).unwrap();
+            assert_eq!(records.len(), 1);
+            assert_eq!(records[0].seq, 3);
+        });
+    }
+
+    #[test]
+    fn test_signal_history_without_retention_is_empty() {
+        let (mut app, router) = test_app();
+        let request = Request::post("/signals/chat")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"n":1}"#))
+            .unwrap();
+        block_on(call(&mut app, router.clone(), request));
+
+        let request = Request::get("/signals/chat/history")
+            .body(Body::empty())
+            .unwrap();
+        block_on(
+            assert_response::<Vec<homunculus_api::prelude::SignalRecord>>(
+                &mut app,
+                router,
+                request,
+                vec![],
+            ),
+        );
+    }
+
+    #[test]
+    fn test_signal_retention_requires_a_limit() {
+        let (mut app, router) = test_app();
+        let request = Request::put("/signals/chat/retention")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"persist":true}"#))
+            .unwrap();
+        let response = block_on(call_any_status(&mut app, router, request));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_persistent_signal_history_is_stored() {
+        let (mut app, router) = test_app();
+        let request = Request::put("/signals/chat/retention")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"maxAgeSecs":60,"persist":true}"#))
+            .unwrap();
+        block_on(call(&mut app, router.clone(), request));
+
+        let request = Request::post("/signals/chat")
+            .header("content-type", "application/json")
+            .body(Body::from(r#""hello""#))
+            .unwrap();
+        block_on(call(&mut app, router, request));
+        app.update();
+
+        let prefs = app.world().non_send_resource::<PrefsDatabase>();
+        let history = prefs.load_signal_history("chat").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].payload, serde_json::json!("hello"));
+        assert_eq!(prefs.list_signal_retentions().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_pattern_subscription_receives_matching_signals() {
+        use bevy::tasks::futures_lite::StreamExt;
+        use homunculus_api::prelude::SignalsApi;
+
+        let (mut app, router) = test_app();
+        let api = SignalsApi::from(app.world().resource::<ApiReactor>().clone());
+        let subscribe = api.stream_pattern("persona.*");
+        let mut subscribe = std::pin::pin!(subscribe);
+        let stream = loop {
+            app.update();
+            if let Some(result) = block_on(poll_once(&mut subscribe)) {
+                break result.unwrap();
+            }
+        };
+        let mut stream = std::pin::pin!(stream);
+
+        for signal in ["persona.spawned", "persona/spawned"] {
+            let request = Request::post(format!("/signals/{signal}"))
+                .header("content-type", "application/json")
+                .body(Body::from(r#""elmer""#))
+                .unwrap();
+            block_on(call(&mut app, router.clone(), request));
+        }
+
+        let (signal, record) = block_on(stream.next()).unwrap();
+        assert_eq!(signal, "persona.spawned");
+        assert_eq!(record.payload, serde_json::json!("elmer"));
+        assert!(block_on(poll_once(stream.next())).is_none());
+
+        let request = Request::get("/signals").body(Body::empty()).unwrap();
+        block_on(async {
+            let response = call(&mut app, router, request).await;
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            let signals: Vec<homunculus_api::prelude::SignalInfo> =
+                serde_json::from_slice(&body).unwrap();
+            let count = |name: &str| {
+                signals
+                    .iter()
+                    .find(|s| s.signal == name)
+                    .unwrap()
+                    .pattern_subscribers
+            };
+            assert_eq!(count("persona.spawned"), 1);
+            assert_eq!(count("persona/spawned"), 0);
+        });
+    }
+
+    #[test]
+    fn test_event_bus_carries_signals_with_sequence_numbers() {
+        use bevy::tasks::futures_lite::StreamExt;
+        use homunculus_api::events::{EventBusItem, EventsApi};
+
+        let (mut app, router) = test_app();
+        let api = EventsApi::from(app.world().resource::<ApiReactor>().clone());
+        let subscribe = api.subscribe();
+        let mut subscribe = std::pin::pin!(subscribe);
+        let stream = loop {
+            app.update();
+            if let Some(result) = block_on(poll_once(&mut subscribe)) {
+                break result.unwrap();
+            }
+        };
+        let mut stream = std::pin::pin!(stream);
+
+        for signal in ["chat", "persona.spawned"] {
+            let request = Request::post(format!("/signals/{signal}"))
+                .header("content-type", "application/json")
+                .body(Body::from(r#""elmer""#))
+                .unwrap();
+            block_on(call(&mut app, router.clone(), request));
+        }
+        app.update();
+
+        let mut events = Vec::new();
+        for _ in 0..2 {
+            match block_on(stream.next()).unwrap() {
+                EventBusItem::Event(event) => events.push(event),
+                EventBusItem::Lagged(missed) => panic!("unexpected lag of {missed}"),
+            }
+        }
+        assert!(events.iter().all(|e| e.event == "signal"));
+        assert_eq!(events[0].signal.as_deref(), Some("chat"));
+        assert_eq!(events[1].signal.as_deref(), Some("persona.spawned"));
+        assert_eq!(events[0].data, serde_json::json!("elmer"));
+        assert_eq!(events[1].seq, events[0].seq + 1);
+    }
+
+    #[test]
+    fn test_session_recorder_logs_requests_and_responses() {
+        use crate::recorder::{SessionRecorder, record_session};
+        use homunculus_utils::schema::session::SessionEntry;
+
+        let (mut app, router) = test_app();
+        let path =
+            std::env::temp_dir().join(format!("hmcs-session-{}.jsonl", uuid::Uuid::new_v4()));
+        let recorder = Arc::new(SessionRecorder::create(&path).unwrap());
+        let router = router.layer(axum::middleware::from_fn_with_state(
+            recorder.clone(),
+            record_session,
+        ));
+
+        let request = Request::post("/signals/chat")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"text":"hi"}"#))
+            .unwrap();
+        block_on(call(&mut app, router.clone(), request));
+        let request = Request::get("/signals").body(Body::empty()).unwrap();
+        block_on(call(&mut app, router, request));
+        recorder.flush();
+
+        let trace = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let entries: Vec<SessionEntry> = trace
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 4);
+        let SessionEntry::Request(send) = &entries[0] else {
+            panic!("expected a request, got {:?}", entries[0]);
+        };
+        assert_eq!(send.id, 1);
+        assert_eq!(send.method, "POST");
+        assert_eq!(send.uri, "/signals/chat");
+        assert_eq!(send.body, Some(serde_json::json!({"text": "hi"})));
+        let SessionEntry::Response(sent) = &entries[1] else {
+            panic!("expected a response, got {:?}", entries[1]);
+        };
+        assert_eq!((sent.id, sent.status), (1, 200));
+        let SessionEntry::Response(list) = &entries[3] else {
+            panic!("expected a response, got {:?}", entries[3]);
+        };
+        assert_eq!(list.id, 2);
+        assert_eq!(list.body.as_ref().unwrap()[0]["signal"], "chat");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_signal_request_returns_first_matching_reply() {
+        use bevy::tasks::futures_lite::StreamExt;
+        use homunculus_api::prelude::SignalsApi;
+
+        let (mut app, router) = test_app();
+        let api = SignalsApi::from(app.world().resource::<ApiReactor>().clone());
+        let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+        tokio::spawn(async move {
+            let requests = api.clone().stream("greet").await.unwrap();
+            ready_tx.send(()).unwrap();
+            let mut requests = std::pin::pin!(requests);
+            let request = requests.next().await.unwrap();
+            let reply_to = request["replyTo"].as_str().unwrap().to_string();
+            // A reply for another request must be ignored.
+            api.clone()
+                .send(
+                    reply_to.clone(),
+                    serde_json::json!({"correlationId": "other", "payload": "wrong"}),
+                )
+                .await
+                .unwrap();
+            api.send(
+                reply_to,
+                serde_json::json!({
+                    "correlationId": request["correlationId"],
+                    "payload": {"greeting": format!("hello {}", request["payload"]["name"].as_str().unwrap())},
+                }),
+            )
+            .await
+            .unwrap();
+        });
+        while ready_rx.try_recv().is_err() {
+            app.update();
+        }
+
+        let request = Request::post("/signals/greet/request")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                r#"{"payload":{"name":"elmer"},"timeoutMs":5000}"#,
+            ))
+            .unwrap();
+        assert_response(
+            &mut app,
+            router,
+            request,
+            serde_json::json!({"greeting": "hello elmer"}),
+        )
+        .await;
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_signal_request_times_out_without_reply() {
+        let (mut app, router) = test_app();
+        let request = Request::post("/signals/greet/request")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"timeoutMs":50}"#))
+            .unwrap();
+        let response = call_any_status(&mut app, router, request).await;
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    }
 }
@@ -0,0 +1,218 @@
+//! Session recorder that logs every HTTP exchange and engine event to a JSONL file.
+//!
+//! Enabled by `[session] record_path` in `config.toml` (or `HMCS_RECORD_SESSION`).
+//! Each line is a [`SessionEntry`]; the trace can be re-issued against an engine
+//! with `hmcs replay <file>`.
+//!
+//! Entries are written by a dedicated thread, so recording never blocks the
+//! async middleware on file I/O.
+//!
+//! Only JSON bodies are recorded. Streaming responses (SSE), binary bodies and
+//! WebSocket upgrades are logged with `bodyOmitted`, and `/mcp` traffic is skipped
+//! since it cannot be replayed outside its MCP session.
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use bevy::log::error;
+use homunculus_api::events::{EventBusItem, EventsApi, SIGNAL_EVENT};
+use homunculus_utils::schema::session::{
+    RecordedRequest, RecordedResponse, SessionEntry, now_millis,
+};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+/// Appends [`SessionEntry`] lines to a trace file.
+pub(crate) struct SessionRecorder {
+    writer: Sender<WriterCommand>,
+    next_id: AtomicU64,
+}
+
+enum WriterCommand {
+    Line(Vec<u8>),
+    /// Acknowledges once every line queued before it has been written.
+    #[cfg(test)]
+    Flush(Sender<()>),
+}
+
+impl SessionRecorder {
+    /// Creates (or truncates) the trace file at `path` and starts its writer thread.
+    ///
+    /// The thread exits once the recorder is dropped.
+    pub(crate) fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(path)?;
+        let (writer, commands) = channel();
+        std::thread::Builder::new()
+            .name("session-recorder".to_string())
+            .spawn(move || write_lines(file, commands))?;
+        Ok(Self {
+            writer,
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    fn write(&self, entry: &SessionEntry) {
+        let Ok(mut line) = serde_json::to_vec(entry) else {
+            return;
+        };
+        line.push(b'\n');
+        let _ = self.writer.send(WriterCommand::Line(line));
+    }
+
+    /// Blocks until every entry recorded so far is on disk.
+    #[cfg(test)]
+    pub(crate) fn flush(&self) {
+        let (ack, done) = channel();
+        if self.writer.send(WriterCommand::Flush(ack)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+}
+
+/// Writes queued lines, flushing whenever the queue runs dry.
+fn write_lines(file: File, commands: Receiver<WriterCommand>) {
+    let mut file = BufWriter::new(file);
+    while let Ok(command) = commands.recv() {
+        let mut pending = Some(command);
+        while let Some(command) = pending.take() {
+            match command {
+                WriterCommand::Line(line) => {
+                    if let Err(e) = file.write_all(&line) {
+                        error!("Failed to write session trace: {e}");
+                    }
+                }
+                #[cfg(test)]
+                WriterCommand::Flush(ack) => {
+                    let _ = file.flush();
+                    let _ = ack.send(());
+                }
+            }
+            pending = commands.try_recv().ok();
+        }
+        if let Err(e) = file.flush() {
+            error!("Failed to write session trace: {e}");
+        }
+    }
+}
+
+/// Middleware that records each request and its response.
+pub(crate) async fn record_session(
+    State(recorder): State<Arc<SessionRecorder>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.uri().path().starts_with("/mcp") || request.headers().contains_key(header::UPGRADE) {
+        return next.run(request).await;
+    }
+
+    let id = recorder.next_id.fetch_add(1, Ordering::Relaxed);
+    let (parts, body) = request.into_parts();
+    let uri = parts
+        .uri
+        .path_and_query()
+        .map(|pq| pq.to_string())
+        .unwrap_or_else(|| parts.uri.path().to_string());
+    let (body, recorded, body_omitted) = if is_json(&parts.headers) {
+        let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+            return StatusCode::BAD_REQUEST.into_response();
+        };
+        let recorded = parse_json(&bytes);
+        (Body::from(bytes), recorded, false)
+    } else {
+        let omitted = parts
+            .headers
+            .get(header::CONTENT_LENGTH)
+            .is_some_and(|len| len != "0");
+        (body, None, omitted)
+    };
+    recorder.write(&SessionEntry::Request(RecordedRequest {
+        id,
+        timestamp: now_millis(),
+        method: parts.method.to_string(),
+        uri,
+        body: recorded,
+        body_omitted,
+    }));
+
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    let status = response.status().as_u16();
+    let (response, body, body_omitted) = if is_json(response.headers()) {
+        let (parts, body) = response.into_parts();
+        let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+        let recorded = parse_json(&bytes);
+        (
+            Response::from_parts(parts, Body::from(bytes)),
+            recorded,
+            false,
+        )
+    } else {
+        let omitted = response.headers().contains_key(header::CONTENT_TYPE);
+        (response, None, omitted)
+    };
+    recorder.write(&SessionEntry::Response(RecordedResponse {
+        id,
+        timestamp: now_millis(),
+        status,
+        body,
+        body_omitted,
+    }));
+    response
+}
+
+/// Records engine events until the event bus closes.
+///
+/// Signal events are skipped, since signals sent over HTTP are already
+/// recorded as requests.
+pub(crate) async fn record_events(recorder: Arc<SessionRecorder>, api: EventsApi) {
+    use futures::StreamExt;
+
+    let events = match api.subscribe().await {
+        Ok(events) => events,
+        Err(e) => {
+            error!("Failed to record engine events: {e}");
+            return;
+        }
+    };
+    let mut events = std::pin::pin!(events);
+    while let Some(item) = events.next().await {
+        let EventBusItem::Event(event) = item else {
+            continue;
+        };
+        if event.event == SIGNAL_EVENT {
+            continue;
+        }
+        let Ok(event) = serde_json::to_value(event) else {
+            continue;
+        };
+        recorder.write(&SessionEntry::Event {
+            timestamp: now_millis(),
+            event,
+        });
+    }
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"))
+}
+
+fn parse_json(bytes: &Bytes) -> Option<serde_json::Value> {
+    if bytes.is_empty() {
+        return None;
+    }
+    serde_json::from_slice(bytes).ok()
+}
@@ -6,11 +6,13 @@ use utoipa::ToSchema;
 pub(crate) mod app;
 pub(crate) mod assets;
 pub(crate) mod audio;
+pub(crate) mod batch;
 pub(crate) mod coordinates;
 pub(crate) mod dialog;
 pub(crate) mod displays;
 pub(crate) mod effects;
 pub(crate) mod entities;
+pub(crate) mod events;
 pub(crate) mod info;
 pub(crate) mod mods;
 pub(crate) mod persona;
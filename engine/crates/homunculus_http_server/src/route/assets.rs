@@ -1,12 +1,16 @@
 use axum::Json;
-use axum::extract::{Query, State};
+use axum::extract::{Path, Query, State};
 use axum::response::{IntoResponse, Response};
 use homunculus_api::assets::{AssetFilter, AssetInfo, AssetsApi, ImportAsset, ImportAssetResponse};
 use homunculus_api::prelude::ApiError;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_core::prelude::VrmDiagnostics;
 use serde::Deserialize;
 
-/// List available assets, optionally filtered by type or mod name.
+/// List available assets, optionally filtered by type, mod name or tag.
+///
+/// VRMA assets include their duration, loopability, the bones they move and whether
+/// they carry root motion.
 #[utoipa::path(
     get,
     path = "/assets",
@@ -14,6 +18,7 @@ use serde::Deserialize;
     params(
         ("type" = Option<String>, Query, description = "Filter by asset type"),
         ("mod" = Option<String>, Query, description = "Filter by mod name"),
+        ("tag" = Option<String>, Query, description = "Filter by tag, e.g. greeting"),
     ),
     responses(
         (status = 200, description = "List of assets", body = Vec<AssetInfo>),
@@ -47,6 +52,31 @@ pub async fn import(
     api.import(body).await.into_http_result()
 }
 
+/// Validate a VRM asset without rendering it.
+///
+/// Reports the VRM spec version, humanoid bone coverage, expressions, vertex and texture
+/// counts with a memory estimate, license meta, and any errors or warnings.
+/// Asset IDs containing `/` or `:` must be URL-encoded.
+#[utoipa::path(
+    get,
+    path = "/assets/{id}/diagnostics",
+    tag = "assets",
+    params(
+        ("id" = String, Path, description = "VRM asset ID (e.g. 'vrm:local:my-persona')"),
+    ),
+    responses(
+        (status = 200, description = "Validation report", body = VrmDiagnostics),
+        (status = 400, description = "The asset is not a VRM"),
+        (status = 404, description = "Asset not found or file missing"),
+    ),
+)]
+pub async fn get_diagnostics(
+    State(api): State<AssetsApi>,
+    Path(id): Path<String>,
+) -> HttpResult<VrmDiagnostics> {
+    api.diagnostics(id).await.into_http_result()
+}
+
 /// Query parameters for the asset file endpoint.
 #[derive(Deserialize)]
 pub struct AssetFileQuery {
@@ -0,0 +1,92 @@
+//! `/batch` runs an ordered list of operations in one reactor task.
+
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::batch::{BatchApi, BatchRequest, BatchResponse};
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+
+/// Run several operations in one request.
+///
+/// Steps run in order in a single reactor task. In `sameFrame` mode every step
+/// is applied in the same frame; in `sequential` mode (the default) steps run one
+/// after another and `wait` steps pause the batch. `{"$ref": "<step id>"}` objects
+/// in `args` are replaced with the result of an earlier step.
+///
+/// Step failures do not fail the request: each step reports `ok`, `error` or
+/// `skipped`. With `onError: "stop"` (the default) the steps after a failure are
+/// skipped.
+#[utoipa::path(
+    post,
+    path = "/batch",
+    tag = "batch",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "Per-step results", body = BatchResponse),
+        (status = 400, description = "No steps or duplicate step IDs"),
+    ),
+)]
+pub async fn batch(
+    State(api): State<BatchApi>,
+    Json(request): Json<BatchRequest>,
+) -> HttpResult<BatchResponse> {
+    api.execute(request).await.into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{call, call_any_status, json_request, test_app};
+    use axum::http::StatusCode;
+    use bevy::tasks::block_on;
+    use homunculus_api::batch::{BatchResponse, BatchStepStatus};
+    use http_body_util::BodyExt;
+
+    #[test]
+    fn test_batch_reports_per_step_results() {
+        let (mut app, router) = test_app();
+        let batch = |on_error: &str| {
+            serde_json::json!({
+                "mode": "sameFrame",
+                "onError": on_error,
+                "steps": [
+                    {"id": "hello", "op": "signal.send", "args": {"signal": "chat", "payload": 1}},
+                    {"op": "expressions.modify", "args": {"persona": "nobody", "weights": {"happy": 1.0}}},
+                    {"op": "signal.send", "args": {"signal": "chat", "payload": {"$ref": "hello"}}},
+                ],
+            })
+            .to_string()
+        };
+
+        for (on_error, last) in [
+            ("continue", BatchStepStatus::Ok),
+            ("stop", BatchStepStatus::Skipped),
+        ] {
+            let request = json_request("POST", "/batch", &batch(on_error));
+            let response = block_on(call(&mut app, router.clone(), request));
+            let body = block_on(response.into_body().collect()).unwrap().to_bytes();
+            let response: BatchResponse = serde_json::from_slice(&body).unwrap();
+            assert!(!response.ok);
+            let statuses: Vec<_> = response.results.iter().map(|r| r.status).collect();
+            assert_eq!(
+                statuses,
+                [BatchStepStatus::Ok, BatchStepStatus::Error, last],
+                "onError={on_error}"
+            );
+            assert_eq!(
+                response.results[1].error.as_deref(),
+                Some("Not found entity")
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_rejects_duplicate_step_ids() {
+        let (mut app, router) = test_app();
+        let request = json_request(
+            "POST",
+            "/batch",
+            r#"{"steps":[{"id":"a","op":"wait","args":{"ms":0}},{"id":"a","op":"wait","args":{"ms":0}}]}"#,
+        );
+        let response = block_on(call_any_status(&mut app, router, request));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -2,7 +2,8 @@ use crate::extract::EntityId;
 use axum::{Json, extract::State};
 use bevy::prelude::*;
 use homunculus_api::entities::{
-    EasingFunction, EntitiesApi, TweenPositionArgs, TweenRotationArgs, TweenScaleArgs,
+    EasingFunction, EntitiesApi, TweenPathArgs, TweenPositionArgs, TweenRotationArgs,
+    TweenScaleArgs,
 };
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
 use serde::{Deserialize, Serialize};
@@ -125,6 +126,30 @@ pub async fn tween_scale(
     api.tween_scale(entity, args).await.into_http_result()
 }
 
+/// Move an entity along a polyline, Catmull-Rom or cubic Bézier path.
+///
+/// The entity moves at a constant speed along the path before easing is applied.
+/// Progress is published on the event bus as `path-tween-loop` and `path-tween-finish`.
+#[utoipa::path(
+    post,
+    path = "/tween/path",
+    tag = "entities",
+    params(("entity" = String, Path, description = "Entity ID")),
+    request_body = TweenPathArgs,
+    responses(
+        (status = 200, description = "Path tween started"),
+        (status = 400, description = "Invalid path, duration or wait"),
+        (status = 404, description = "Entity not found"),
+    ),
+)]
+pub async fn tween_path(
+    State(api): State<EntitiesApi>,
+    EntityId(entity): EntityId,
+    Json(body): Json<TweenPathArgs>,
+) -> HttpResult {
+    api.tween_path(entity, body).await.into_http_result()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,4 +321,59 @@ mod tests {
         let response = call(&mut app, router, request).await;
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn tween_path_moves_along_the_path() {
+        let (mut app, router) = test_app();
+        let entity = app.world_mut().spawn(Transform::default()).id();
+        app.update();
+
+        let request = Request::post(format!("/entities/{}/tween/path", entity.to_bits()))
+            .header("content-type", "application/json")
+            .body(
+                serde_json::to_string(&json!({
+                    "points": [[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]],
+                    "curve": "bezier",
+                    "durationMs": 50,
+                    "wait": true,
+                }))
+                .unwrap()
+                .into(),
+            )
+            .unwrap();
+
+        let response = call(&mut app, router, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let tf = app.world().get::<Transform>(entity).unwrap();
+        assert!(tf.translation.distance(Vec3::new(1.0, 0.0, 0.0)) < 1e-4);
+    }
+
+    #[tokio::test]
+    async fn tween_path_rejects_invalid_paths() {
+        let (mut app, router) = test_app();
+        let entity = app.world_mut().spawn(Transform::default()).id();
+        app.update();
+
+        for body in [
+            json!({ "points": [[0.0, 0.0]], "durationMs": 100 }),
+            json!({
+                "points": [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]],
+                "curve": "bezier",
+                "durationMs": 100,
+            }),
+            json!({
+                "points": [[0.0, 0.0], [1.0, 0.0]],
+                "durationMs": 100,
+                "repeat": { "type": "forever" },
+                "wait": true,
+            }),
+        ] {
+            let request = Request::post(format!("/entities/{}/tween/path", entity.to_bits()))
+                .header("content-type", "application/json")
+                .body(serde_json::to_string(&body).unwrap().into())
+                .unwrap();
+            let response = call_any_status(&mut app, router.clone(), request).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
 }
@@ -0,0 +1,171 @@
+//! `/events` multiplexes every engine event and user signal over one WebSocket.
+//!
+//! Clients send `{"type":"subscribe", "events"?, "personas"?, "signals"?}` to start
+//! (or re-filter) delivery and `{"type":"unsubscribe"}` to stop it. Each event arrives
+//! as `{"type":"event", "seq", "timestamp", "event", "personaId"?, "signal"?, "data"}`.
+
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use futures::{SinkExt as _, StreamExt as _};
+use homunculus_api::events::{
+    CompiledEventFilter, EngineEvent, EventBusItem, EventFilter, EventsApi,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum WsClientMessage {
+    Subscribe(EventFilter),
+    Unsubscribe,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum WsServerMessage {
+    Subscribed,
+    Unsubscribed,
+    Event(EngineEvent),
+    /// Events were dropped because the client did not keep up.
+    Lagged {
+        missed: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Upgrade to WebSocket for the unified engine event stream.
+pub async fn ws_handler(
+    State(api): State<EventsApi>,
+    ws: WebSocketUpgrade,
+) -> impl axum::response::IntoResponse {
+    ws.on_upgrade(move |socket| handle_ws_connection(socket, api))
+}
+
+async fn handle_ws_connection(socket: WebSocket, api: EventsApi) {
+    let (mut ws_sink, mut ws_stream) = socket.split();
+    let mut filter: Option<CompiledEventFilter> = None;
+    let mut events = match api.subscribe().await {
+        Ok(events) => Box::pin(events),
+        Err(e) => {
+            let _ = send(
+                &mut ws_sink,
+                &WsServerMessage::Error {
+                    message: e.to_string(),
+                },
+            )
+            .await;
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            item = events.next() => {
+                let msg = match item {
+                    Some(EventBusItem::Event(event)) => match &filter {
+                        Some(filter) if filter.matches(&event) => WsServerMessage::Event(event),
+                        _ => continue,
+                    },
+                    Some(EventBusItem::Lagged(missed)) if filter.is_some() => {
+                        WsServerMessage::Lagged { missed }
+                    }
+                    Some(EventBusItem::Lagged(_)) => continue,
+                    None => break,
+                };
+                if send(&mut ws_sink, &msg).await.is_err() {
+                    break;
+                }
+            }
+            frame = ws_stream.next() => {
+                let reply = match frame {
+                    Some(Ok(Message::Text(text))) => handle_client_message(&text, &mut filter),
+                    Some(Ok(Message::Close(_))) | None => break,
+                    _ => continue,
+                };
+                if send(&mut ws_sink, &reply).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn handle_client_message(text: &str, filter: &mut Option<CompiledEventFilter>) -> WsServerMessage {
+    match serde_json::from_str::<WsClientMessage>(text) {
+        Ok(WsClientMessage::Subscribe(requested)) => match requested.compile() {
+            Ok(compiled) => {
+                *filter = Some(compiled);
+                WsServerMessage::Subscribed
+            }
+            Err(message) => WsServerMessage::Error { message },
+        },
+        Ok(WsClientMessage::Unsubscribe) => {
+            *filter = None;
+            WsServerMessage::Unsubscribed
+        }
+        Err(e) => WsServerMessage::Error {
+            message: format!("invalid message: {e}"),
+        },
+    }
+}
+
+async fn send(
+    ws_sink: &mut futures::stream::SplitSink<WebSocket, Message>,
+    msg: &WsServerMessage,
+) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(msg).unwrap();
+    ws_sink.send(Message::Text(text.into())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscribe_replaces_filter() {
+        let mut filter = None;
+        let reply = handle_client_message(
+            r#"{"type":"subscribe","events":["drag"],"personas":["elmer"]}"#,
+            &mut filter,
+        );
+        assert!(matches!(reply, WsServerMessage::Subscribed));
+        assert!(filter.is_some());
+
+        let reply = handle_client_message(r#"{"type":"unsubscribe"}"#, &mut filter);
+        assert!(matches!(reply, WsServerMessage::Unsubscribed));
+        assert!(filter.is_none());
+    }
+
+    #[test]
+    fn invalid_filter_is_reported() {
+        let mut filter = None;
+        let reply =
+            handle_client_message(r#"{"type":"subscribe","events":["dragging"]}"#, &mut filter);
+        assert!(matches!(reply, WsServerMessage::Error { .. }));
+        assert!(filter.is_none());
+    }
+
+    #[test]
+    fn event_envelope_is_tagged() {
+        let msg = WsServerMessage::Event(EngineEvent {
+            seq: 7,
+            timestamp: 0,
+            event: "signal".to_string(),
+            persona_id: None,
+            signal: Some("chat".to_string()),
+            data: serde_json::json!("hi"),
+        });
+        assert_eq!(
+            serde_json::to_value(&msg).unwrap(),
+            serde_json::json!({
+                "type": "event",
+                "seq": 7,
+                "timestamp": 0,
+                "event": "signal",
+                "signal": "chat",
+                "data": "hi",
+            })
+        );
+    }
+}
@@ -2,13 +2,19 @@
 
 pub(crate) mod create;
 pub(crate) mod delete;
+pub(crate) mod emotion;
 pub(crate) mod events;
 pub(crate) mod fields;
+pub(crate) mod gestures;
 pub(crate) mod get;
+pub(crate) mod interaction;
+pub(crate) mod locomotion;
+pub(crate) mod physics;
 pub(crate) mod snapshot;
 pub(crate) mod spawn;
 pub(crate) mod state;
 pub(crate) mod stream;
+pub(crate) mod timelines;
 pub(crate) mod update;
 pub(crate) mod vrm;
 
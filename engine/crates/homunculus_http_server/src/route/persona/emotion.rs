@@ -0,0 +1,166 @@
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::emotion::{EmotionApi, EmotionArgs, EmotionMapping, EmotionState};
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Set the persona's emotion.
+///
+/// The emotion holds for `holdSecs`, then fades to neutral over `decaySecs`, driving
+/// the mapped expressions as it goes. Setting `neutral` clears it immediately.
+#[utoipa::path(
+    post,
+    path = "/emotion",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = EmotionArgs,
+    responses(
+        (status = 200, description = "Emotion set", body = EmotionState),
+        (status = 400, description = "Invalid emotion, intensity or timing"),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn set_emotion(
+    State(api): State<EmotionApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<EmotionArgs>,
+) -> HttpResult<EmotionState> {
+    api.set(path.entity, body).await.into_http_result()
+}
+
+/// Get the persona's current emotion.
+#[utoipa::path(
+    get,
+    path = "/emotion",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Current emotion", body = EmotionState),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn get_emotion(
+    State(api): State<EmotionApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<EmotionState> {
+    api.get(path.entity).await.into_http_result()
+}
+
+/// Get the emotion-to-expression mapping of the persona's VRM model.
+#[utoipa::path(
+    get,
+    path = "/emotion/mapping",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Emotion mapping, including defaults", body = Object),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn get_mapping(
+    State(api): State<EmotionApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<EmotionMapping> {
+    api.mapping(path.entity).await.into_http_result()
+}
+
+/// Store an emotion-to-expression mapping for the persona's VRM model.
+///
+/// Emotions in the body replace their default entries and can add new emotions.
+#[utoipa::path(
+    put,
+    path = "/emotion/mapping",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = Object,
+    responses(
+        (status = 200, description = "Updated emotion mapping", body = Object),
+        (status = 400, description = "Weight out of range"),
+        (status = 404, description = "Persona not found"),
+        (status = 409, description = "No VRM attached"),
+    ),
+)]
+pub async fn put_mapping(
+    State(api): State<EmotionApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<EmotionMapping>,
+) -> HttpResult<EmotionMapping> {
+    api.set_mapping(path.entity, body).await.into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use bevy::tasks::block_on;
+    use homunculus_api::emotion::EmotionState;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_emotion_set_get_and_mapping() {
+        let (mut app, router) = test_app();
+        spawn_test_persona(&mut app, "elmer");
+        let happy = EmotionState {
+            emotions: HashMap::from([("happy".to_string(), 1.0)]),
+            valence: 0.8,
+            arousal: 0.5,
+        };
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/emotion",
+                r#"{"emotion":"happy","decaySecs":null}"#,
+            ),
+            happy.clone(),
+        ));
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            Request::get("/personas/elmer/emotion")
+                .body(Body::empty())
+                .unwrap(),
+            happy,
+        ));
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/emotion",
+                r#"{"emotion":"neutral"}"#,
+            ),
+            EmotionState::default(),
+        ));
+
+        for body in [
+            r#"{"emotion":"happy","valence":0.5}"#,
+            r#"{"emotion":"bored"}"#,
+            r#"{"valence":2.0}"#,
+        ] {
+            let response = block_on(call_any_status(
+                &mut app,
+                router.clone(),
+                json_request("POST", "/personas/elmer/emotion", body),
+            ));
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{body}");
+        }
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router,
+            json_request(
+                "PUT",
+                "/personas/elmer/emotion/mapping",
+                r#"{"happy":{"happy":0.5}}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
@@ -7,10 +7,12 @@ use bevy_flurx::action::once;
 use futures::stream::select_all;
 use homunculus_api::prelude::{ApiError, ApiReactor};
 use homunculus_core::prelude::{
-    ExpressionChangeEvent, OnClickEvent, OnDragEndEvent, OnDragEvent, OnDragStartEvent,
-    OnPointerCancelEvent, OnPointerMoveEvent, OnPointerOutEvent, OnPointerOverEvent,
-    OnPointerPressedEvent, OnPointerReleasedEvent, PersonaChangeEvent, PersonaStateChangeEvent,
-    VrmAttachedEvent, VrmDetachedEvent, VrmEventReceiver, VrmaFinishEvent, VrmaPlayEvent,
+    EmotionChangeEvent, ExpressionChangeEvent, OnClickEvent, OnDragEndEvent, OnDragEvent,
+    OnDragStartEvent, OnPointerCancelEvent, OnPointerMoveEvent, OnPointerOutEvent,
+    OnPointerOverEvent, OnPointerPressedEvent, OnPointerReleasedEvent, PersonaChangeEvent,
+    PersonaStateChangeEvent, ProximityEnterEvent, ProximityLeaveEvent, VrmAttachedEvent,
+    VrmDetachedEvent, VrmEventReceiver, VrmaFinishEvent, VrmaPlayEvent, WalkArriveEvent,
+    WalkCancelEvent,
 };
 use serde::Serialize;
 use std::convert::Infallible;
@@ -24,7 +26,8 @@ use super::SpawnedPersonaPath;
 /// Always delivered: persona-change, state-change, vrm-attached, vrm-detached.
 /// Delivered only when VRM is attached: drag-start, drag, drag-end, pointer-press,
 /// pointer-click, pointer-move, pointer-release, pointer-over, pointer-out,
-/// pointer-cancel, expression-change, vrma-play, vrma-finish.
+/// pointer-cancel, expression-change, vrma-play, vrma-finish, proximity-enter,
+/// proximity-leave, walk-arrive, walk-cancel, emotion-change.
 #[utoipa::path(
     get,
     path = "/events",
@@ -150,6 +153,39 @@ pub async fn events(
                     once::run(observe_stream::<VrmaFinishEvent>).with(("vrma-finish", entity)),
                 )
                 .await;
+            let proximity_enter = task
+                .will(
+                    Update,
+                    once::run(observe_stream::<ProximityEnterEvent>)
+                        .with(("proximity-enter", entity)),
+                )
+                .await;
+            let proximity_leave = task
+                .will(
+                    Update,
+                    once::run(observe_stream::<ProximityLeaveEvent>)
+                        .with(("proximity-leave", entity)),
+                )
+                .await;
+            let walk_arrive = task
+                .will(
+                    Update,
+                    once::run(observe_stream::<WalkArriveEvent>).with(("walk-arrive", entity)),
+                )
+                .await;
+            let walk_cancel = task
+                .will(
+                    Update,
+                    once::run(observe_stream::<WalkCancelEvent>).with(("walk-cancel", entity)),
+                )
+                .await;
+            let emotion_change = task
+                .will(
+                    Update,
+                    once::run(observe_stream::<EmotionChangeEvent>)
+                        .with(("emotion-change", entity)),
+                )
+                .await;
             select_all([
                 persona_change,
                 vrm_attached,
@@ -168,6 +204,11 @@ pub async fn events(
                 expression_change,
                 vrma_play,
                 vrma_finish,
+                proximity_enter,
+                proximity_leave,
+                walk_arrive,
+                walk_cancel,
+                emotion_change,
             ])
         })
         .await?;
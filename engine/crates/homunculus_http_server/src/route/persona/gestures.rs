@@ -0,0 +1,82 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_api::vrm::{Gesture, PerformGesture, VrmApi};
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Perform a procedural gesture.
+///
+/// Gestures are generated on the fly and layered on top of the playing animation, so
+/// they work without a matching VRMA. Bones return to their previous rotation when the
+/// gesture ends.
+#[utoipa::path(
+    post,
+    path = "/gestures/{name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = Gesture, Path, description = "Gesture to perform"),
+    ),
+    request_body = PerformGesture,
+    responses(
+        (status = 200, description = "Gesture started, or finished when `wait` is set"),
+        (status = 400, description = "Unknown gesture, or invalid intensity or duration"),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn perform(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, gesture)): Path<(String, Gesture)>,
+    Json(body): Json<PerformGesture>,
+) -> HttpResult {
+    api.perform_gesture(path.entity, gesture, body)
+        .await
+        .into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{call_any_status, json_request, spawn_test_persona, test_app};
+    use axum::http::StatusCode;
+    use bevy::tasks::block_on;
+
+    #[test]
+    fn test_perform_gesture_validates_requests() {
+        let (mut app, router) = test_app();
+        spawn_test_persona(&mut app, "elmer");
+
+        for (uri, body, status) in [
+            (
+                "/personas/elmer/gestures/dance",
+                "{}",
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/personas/elmer/gestures/nod",
+                r#"{"intensity":3}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "/personas/elmer/gestures/wave",
+                r#"{"durationMs":20}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            // A persona without a VRM has no bones to move.
+            (
+                "/personas/elmer/gestures/headShake",
+                "{}",
+                StatusCode::NOT_FOUND,
+            ),
+            ("/personas/ghost/gestures/nod", "{}", StatusCode::NOT_FOUND),
+        ] {
+            let response = block_on(call_any_status(
+                &mut app,
+                router.clone(),
+                json_request("POST", uri, body),
+            ));
+            assert_eq!(response.status(), status, "{uri} {body}");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::interaction::{
+    ApproachArgs, FaceArgs, FormationArgs, InteractionApi, InteractionSettings,
+};
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Move the persona next to another persona.
+///
+/// The persona stops `distance` world units beside the target, on the side it is
+/// already on, and turns toward it unless `face` is `false`.
+#[utoipa::path(
+    post,
+    path = "/approach",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = ApproachArgs,
+    responses(
+        (status = 200, description = "Persona is moving"),
+        (status = 400, description = "Invalid distance or target is the persona itself"),
+        (status = 404, description = "Persona or target not found"),
+    ),
+)]
+pub async fn approach(
+    State(api): State<InteractionApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<ApproachArgs>,
+) -> HttpResult {
+    api.approach(path.entity, body).await.into_http_result()
+}
+
+/// Turn the persona toward another persona.
+#[utoipa::path(
+    post,
+    path = "/face",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = FaceArgs,
+    responses(
+        (status = 200, description = "Persona is turning"),
+        (status = 400, description = "Target is the persona itself"),
+        (status = 404, description = "Persona or target not found"),
+    ),
+)]
+pub async fn face(
+    State(api): State<InteractionApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<FaceArgs>,
+) -> HttpResult {
+    api.face(path.entity, body).await.into_http_result()
+}
+
+/// Line personas up along the bottom of the primary monitor.
+#[utoipa::path(
+    post,
+    path = "/formation",
+    tag = "personas",
+    request_body = FormationArgs,
+    responses(
+        (status = 200, description = "Personas are moving"),
+        (status = 404, description = "A listed persona is not spawned or has no VRM"),
+    ),
+)]
+pub async fn formation(
+    State(api): State<InteractionApi>,
+    Json(body): Json<FormationArgs>,
+) -> HttpResult {
+    api.formation(body).await.into_http_result()
+}
+
+/// Get the separation and proximity settings.
+#[utoipa::path(
+    get,
+    path = "/settings",
+    tag = "personas",
+    responses(
+        (status = 200, description = "Current settings", body = InteractionSettings),
+    ),
+)]
+pub async fn get_settings(State(api): State<InteractionApi>) -> HttpResult<InteractionSettings> {
+    api.settings().await.into_http_result()
+}
+
+/// Replace the separation and proximity settings.
+#[utoipa::path(
+    put,
+    path = "/settings",
+    tag = "personas",
+    request_body = InteractionSettings,
+    responses(
+        (status = 200, description = "Updated settings", body = InteractionSettings),
+        (status = 400, description = "Invalid settings"),
+    ),
+)]
+pub async fn put_settings(
+    State(api): State<InteractionApi>,
+    Json(body): Json<InteractionSettings>,
+) -> HttpResult<InteractionSettings> {
+    api.set_settings(body).await.into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{call, call_any_status, json_request, spawn_test_persona, test_app};
+    use axum::http::StatusCode;
+    use bevy::prelude::*;
+    use bevy::tasks::block_on;
+
+    #[test]
+    fn test_approach_and_face_other_persona() {
+        let (mut app, router) = test_app();
+        let elmer = spawn_test_persona(&mut app, "elmer");
+        let luna = spawn_test_persona(&mut app, "luna");
+        app.world_mut()
+            .entity_mut(luna)
+            .insert(Transform::from_xyz(3.0, 1.0, 0.0));
+
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/approach",
+                r#"{"target":"luna","distance":0.5,"durationMs":0}"#,
+            ),
+        ));
+        let tf = *app.world().get::<Transform>(elmer).unwrap();
+        assert_eq!(tf.translation, Vec3::new(2.5, 1.0, 0.0));
+        assert_ne!(tf.rotation, Quat::IDENTITY);
+
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/luna/face",
+                r#"{"target":"elmer","durationMs":0}"#,
+            ),
+        ));
+        let luna_rotation = app.world().get::<Transform>(luna).unwrap().rotation;
+        assert_eq!(luna_rotation, tf.rotation.inverse());
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router,
+            json_request("POST", "/personas/elmer/face", r#"{"target":"elmer"}"#),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
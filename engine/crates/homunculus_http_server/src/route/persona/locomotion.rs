@@ -0,0 +1,97 @@
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::locomotion::{LocomotionApi, WalkArgs};
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Walk the persona to a position.
+///
+/// The persona loops `walkVrma` while moving at `speed`, turns toward the direction of
+/// travel and is in the `walking` state. On arrival it blends to `idleVrma` and returns
+/// to `idle`. The walk ends with a `walk-arrive` or `walk-cancel` event.
+#[utoipa::path(
+    post,
+    path = "/walk",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = WalkArgs,
+    responses(
+        (status = 200, description = "Persona is walking, or has arrived when `wait` is set"),
+        (status = 400, description = "Invalid speed or transition"),
+        (status = 404, description = "Persona or VRMA not found"),
+    ),
+)]
+pub async fn walk(
+    State(api): State<LocomotionApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<WalkArgs>,
+) -> HttpResult {
+    api.walk(path.entity, body).await.into_http_result()
+}
+
+/// Stop the persona where it is.
+#[utoipa::path(
+    post,
+    path = "/walk/stop",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Walk stopped"),
+        (status = 404, description = "Persona not found"),
+        (status = 409, description = "Persona is not walking"),
+    ),
+)]
+pub async fn stop_walk(State(api): State<LocomotionApi>, path: SpawnedPersonaPath) -> HttpResult {
+    api.stop(path.entity).await.into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{call, call_any_status, json_request, spawn_test_persona, test_app};
+    use axum::http::StatusCode;
+    use bevy::prelude::*;
+    use bevy::tasks::block_on;
+    use homunculus_core::prelude::PersonaState;
+
+    #[test]
+    fn test_walk_arrives_and_returns_to_idle() {
+        let (mut app, router) = test_app();
+        let elmer = spawn_test_persona(&mut app, "elmer");
+        app.world_mut()
+            .entity_mut(elmer)
+            .insert(PersonaState::default());
+
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/walk",
+                r#"{"target":{"type":"world","position":[1.0,0.0]},"speed":100.0,"wait":true}"#,
+            ),
+        ));
+        let tf = app.world().get::<Transform>(elmer).unwrap();
+        assert_eq!(tf.translation, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(tf.rotation, Quat::IDENTITY);
+        assert_eq!(app.world().get::<PersonaState>(elmer).unwrap().0, "idle");
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request("POST", "/personas/elmer/walk/stop", ""),
+        ));
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router,
+            json_request(
+                "POST",
+                "/personas/elmer/walk",
+                r#"{"target":{"type":"world","position":[1.0,0.0]},"speed":0.0}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
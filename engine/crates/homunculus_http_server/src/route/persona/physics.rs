@@ -0,0 +1,88 @@
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::physics::{PersonaPhysicsArgs, PhysicsApi};
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_drag::physics::PhysicsSettings;
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Override whether the persona falls when released.
+///
+/// `enabled: null` removes the override so the global setting applies again.
+#[utoipa::path(
+    put,
+    path = "/physics",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = PersonaPhysicsArgs,
+    responses(
+        (status = 200, description = "Updated physics settings", body = PhysicsSettings),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn put_physics(
+    State(api): State<PhysicsApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<PersonaPhysicsArgs>,
+) -> HttpResult<PhysicsSettings> {
+    api.set_persona(path.entity, body).await.into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use bevy::tasks::block_on;
+    use homunculus_core::prelude::PersonaId;
+    use homunculus_drag::physics::{PHYSICS_KEY, PhysicsSettings};
+    use homunculus_prefs::PrefsDatabase;
+
+    #[test]
+    fn test_physics_settings_and_persona_override() {
+        let (mut app, router) = test_app();
+        spawn_test_persona(&mut app, "elmer");
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            Request::get("/settings/physics")
+                .body(Body::empty())
+                .unwrap(),
+            PhysicsSettings::default(),
+        ));
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/settings/physics",
+                r#"{"enabled":true,"gravity":12.0}"#,
+            ),
+        ));
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request("PUT", "/settings/physics", r#"{"bounciness":1.5}"#),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        block_on(call(
+            &mut app,
+            router,
+            json_request("PUT", "/personas/elmer/physics", r#"{"enabled":false}"#),
+        ));
+        let settings = app.world().resource::<PhysicsSettings>().clone();
+        assert!(settings.enabled);
+        assert_eq!(settings.gravity, 12.0);
+        assert!(!settings.enabled_for(&PersonaId::new("elmer")));
+        let saved = app
+            .world()
+            .non_send_resource::<PrefsDatabase>()
+            .load_as::<PhysicsSettings>(PHYSICS_KEY)
+            .unwrap();
+        assert_eq!(saved, Some(settings));
+    }
+}
@@ -8,8 +8,9 @@ use futures::stream::select_all;
 use homunculus_api::prelude::ApiReactor;
 use homunculus_core::prelude::{
     PersonaChangeEvent, PersonaDeletedEvent, PersonaDespawnedEvent, PersonaEvent, PersonaId,
-    PersonaIndex, PersonaSpawnedEvent, PersonaStateChangeEvent, VrmAttachedEvent, VrmDetachedEvent,
-    VrmEventReceiver,
+    PersonaIndex, PersonaSpawnedEvent, PersonaStateChangeEvent, ProximityEnterEvent,
+    ProximityLeaveEvent, VrmAttachedEvent, VrmDetachedEvent, VrmEventReceiver, WalkArriveEvent,
+    WalkCancelEvent,
 };
 use std::collections::HashMap;
 use std::convert::Infallible;
@@ -57,6 +58,10 @@ fn build_combined_stream(
     rx_deleted: Res<VrmEventReceiver<PersonaDeletedEvent>>,
     rx_spawned: Res<VrmEventReceiver<PersonaSpawnedEvent>>,
     rx_despawned: Res<VrmEventReceiver<PersonaDespawnedEvent>>,
+    rx_proximity_enter: Res<VrmEventReceiver<ProximityEnterEvent>>,
+    rx_proximity_leave: Res<VrmEventReceiver<ProximityLeaveEvent>>,
+    rx_walk_arrive: Res<VrmEventReceiver<WalkArriveEvent>>,
+    rx_walk_cancel: Res<VrmEventReceiver<WalkCancelEvent>>,
 ) -> Pin<Box<dyn Stream<Item = Result<Event, Infallible>> + Send + Sync + 'static>> {
     let reverse: ReverseIndex = Arc::new(RwLock::new(build_reverse_index(&index)));
 
@@ -68,6 +73,18 @@ fn build_combined_stream(
         persona_deleted_stream(rx_deleted.clone(), Arc::clone(&reverse)),
         persona_spawned_stream(rx_spawned.clone(), Arc::clone(&reverse)),
         persona_despawned_stream(rx_despawned.clone(), Arc::clone(&reverse)),
+        entity_event_stream(
+            "proximity-enter",
+            rx_proximity_enter.clone(),
+            Arc::clone(&reverse),
+        ),
+        entity_event_stream(
+            "proximity-leave",
+            rx_proximity_leave.clone(),
+            Arc::clone(&reverse),
+        ),
+        entity_event_stream("walk-arrive", rx_walk_arrive.clone(), Arc::clone(&reverse)),
+        entity_event_stream("walk-cancel", rx_walk_cancel.clone(), Arc::clone(&reverse)),
     ];
 
     Box::pin(select_all(streams))
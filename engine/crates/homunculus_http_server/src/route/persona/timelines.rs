@@ -0,0 +1,188 @@
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_api::timeline::{PlayTimeline, TimelineApi, TimelineStatus};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Request body for seeking a timeline.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SeekBody {
+    /// Position in seconds from the start of the timeline.
+    pub at: f32,
+}
+
+/// Get the persona's timeline playback state (`null` if none is playing).
+#[utoipa::path(
+    get,
+    path = "/timelines",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Playback state", body = Option<TimelineStatus>),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn get_timeline(
+    State(api): State<TimelineApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<Option<TimelineStatus>> {
+    api.status(path.entity).await.into_http_result()
+}
+
+/// Play a timeline script on a persona, replacing the one it is playing.
+///
+/// Progress is reported on the event bus as `timeline-play`, `timeline-cue`
+/// and `timeline-finish` events.
+#[utoipa::path(
+    post,
+    path = "/timelines/play",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = PlayTimeline,
+    responses(
+        (status = 200, description = "Timeline started", body = TimelineStatus),
+        (status = 400, description = "Invalid timeline or asset type"),
+        (status = 404, description = "Persona or asset not found"),
+    ),
+)]
+pub async fn play_timeline(
+    State(api): State<TimelineApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<PlayTimeline>,
+) -> HttpResult<TimelineStatus> {
+    api.play(path.entity, body).await.into_http_result()
+}
+
+/// Stop the persona's timeline. Actions already started run to completion.
+#[utoipa::path(
+    post,
+    path = "/timelines/stop",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Timeline stopped"),
+        (status = 404, description = "Persona not found"),
+        (status = 409, description = "No timeline is playing"),
+    ),
+)]
+pub async fn stop_timeline(State(api): State<TimelineApi>, path: SpawnedPersonaPath) -> HttpResult {
+    api.stop(path.entity).await.into_http_result()
+}
+
+/// Pause the persona's timeline and the VRMA animations it started.
+#[utoipa::path(
+    post,
+    path = "/timelines/pause",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Timeline paused", body = TimelineStatus),
+        (status = 404, description = "Persona not found"),
+        (status = 409, description = "No timeline is playing"),
+    ),
+)]
+pub async fn pause_timeline(
+    State(api): State<TimelineApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<TimelineStatus> {
+    api.pause(path.entity).await.into_http_result()
+}
+
+/// Resume the persona's paused timeline.
+#[utoipa::path(
+    post,
+    path = "/timelines/resume",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Timeline resumed", body = TimelineStatus),
+        (status = 404, description = "Persona not found"),
+        (status = 409, description = "No timeline is playing"),
+    ),
+)]
+pub async fn resume_timeline(
+    State(api): State<TimelineApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<TimelineStatus> {
+    api.resume(path.entity).await.into_http_result()
+}
+
+/// Move the persona's timeline to a position. Cues from that position on fire again.
+#[utoipa::path(
+    post,
+    path = "/timelines/seek",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = SeekBody,
+    responses(
+        (status = 200, description = "Timeline moved", body = TimelineStatus),
+        (status = 400, description = "Position outside the timeline"),
+        (status = 404, description = "Persona not found"),
+        (status = 409, description = "No timeline is playing"),
+    ),
+)]
+pub async fn seek_timeline(
+    State(api): State<TimelineApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<SeekBody>,
+) -> HttpResult<TimelineStatus> {
+    api.seek(path.entity, body.at).await.into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{call, call_any_status, json_request, spawn_test_persona, test_app};
+    use axum::body::Body;
+    use axum::http::{Response, StatusCode};
+    use bevy::tasks::block_on;
+    use homunculus_api::timeline::TimelineStatus;
+    use http_body_util::BodyExt;
+
+    #[test]
+    fn test_timeline_playback_controls() {
+        let (mut app, router) = test_app();
+        spawn_test_persona(&mut app, "elmer");
+        let status = |response: Response<Body>| -> TimelineStatus {
+            let body = block_on(response.into_body().collect()).unwrap().to_bytes();
+            serde_json::from_slice(&body).unwrap()
+        };
+
+        let play = json_request(
+            "POST",
+            "/personas/elmer/timelines/play",
+            r#"{"timeline":{"duration":10,"tracks":[{"name":"ui","cues":[{"at":0,"type":"signal","signal":"chat"}]}]}}"#,
+        );
+        let started = status(block_on(call(&mut app, router.clone(), play)));
+        assert_eq!(started.timeline, "inline");
+        assert_eq!(started.duration, 10.0);
+
+        let paused = block_on(call(
+            &mut app,
+            router.clone(),
+            json_request("POST", "/personas/elmer/timelines/pause", ""),
+        ));
+        assert!(status(paused).paused);
+
+        let seeked = block_on(call(
+            &mut app,
+            router.clone(),
+            json_request("POST", "/personas/elmer/timelines/seek", r#"{"at":4.5}"#),
+        ));
+        assert_eq!(status(seeked).elapsed, 4.5);
+
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request("POST", "/personas/elmer/timelines/stop", ""),
+        ));
+        let response = block_on(call_any_status(
+            &mut app,
+            router,
+            json_request("POST", "/personas/elmer/timelines/stop", ""),
+        ));
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+}
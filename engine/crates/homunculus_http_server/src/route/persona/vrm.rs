@@ -1,6 +1,8 @@
 pub(crate) mod bone;
 pub(crate) mod expressions;
+pub(crate) mod ik;
 pub(crate) mod look;
+pub(crate) mod pose;
 pub(crate) mod position;
 pub(crate) mod speech;
 pub(crate) mod spring_bones;
@@ -1,15 +1,31 @@
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Path, Query, State};
+use homunculus_api::prelude::EasingFunction;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
-use homunculus_api::vrm::{ExpressionsResponse, VrmApi};
+use homunculus_api::vrm::{
+    ApplyExpressionPreset, AutoExpressionSettings, ExpressionPresets, ExpressionTransition,
+    ExpressionsResponse, VrmApi,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use utoipa::ToSchema;
 
 use crate::route::persona::SpawnedPersonaPath;
 
 #[derive(Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct WeightsBody {
-    pub weights: std::collections::HashMap<String, f32>,
+    pub weights: HashMap<String, f32>,
+    /// Duration of the transition in milliseconds; `0` changes the weights instantly.
+    #[serde(default)]
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub easing: EasingFunction,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PresetBody {
+    pub weights: HashMap<String, f32>,
 }
 
 /// List all expressions and their current weights for a persona's VRM.
@@ -31,6 +47,8 @@ pub async fn list_expressions(
 }
 
 /// Modify specific expression weights (merges with current weights).
+///
+/// With `durationMs`, the weights ease from their current values instead of snapping.
 #[utoipa::path(
     patch,
     path = "/vrm/expressions",
@@ -47,22 +65,283 @@ pub async fn modify_expressions(
     path: SpawnedPersonaPath,
     Json(body): Json<WeightsBody>,
 ) -> HttpResult {
-    api.modify_expressions(path.entity, body.weights)
+    let transition = ExpressionTransition {
+        duration_ms: body.duration_ms,
+        easing: body.easing,
+    };
+    api.modify_expressions_with(path.entity, body.weights, transition)
         .await
         .into_http_result()
 }
 
 /// Clear all expression weights.
+///
+/// With `durationMs`, the weights fade to zero before control returns to VRMA animation.
 #[utoipa::path(
     delete,
     path = "/vrm/expressions",
     tag = "personas",
-    params(("id" = String, Path, description = "Persona ID")),
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("durationMs" = Option<u64>, Query, description = "Fade duration in milliseconds"),
+        ("easing" = Option<EasingFunction>, Query, description = "Easing of the fade"),
+    ),
     responses(
         (status = 200, description = "Expressions cleared"),
         (status = 404, description = "Persona or VRM not found"),
     ),
 )]
-pub async fn clear_expressions(State(api): State<VrmApi>, path: SpawnedPersonaPath) -> HttpResult {
-    api.clear_expressions(path.entity).await.into_http_result()
+pub async fn clear_expressions(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Query(transition): Query<ExpressionTransition>,
+) -> HttpResult {
+    api.clear_expressions_with(path.entity, transition)
+        .await
+        .into_http_result()
+}
+
+/// List the persona's expression presets.
+#[utoipa::path(
+    get,
+    path = "/vrm/expressions/presets",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Preset name to expression weights", body = Object),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn list_presets(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<ExpressionPresets> {
+    api.expression_presets(path.entity).await.into_http_result()
+}
+
+/// Save a named combination of expression weights for the persona.
+#[utoipa::path(
+    put,
+    path = "/vrm/expressions/presets/{name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Preset name"),
+    ),
+    request_body = PresetBody,
+    responses(
+        (status = 200, description = "All presets after saving", body = Object),
+        (status = 400, description = "Invalid name or weight"),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn put_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+    Json(body): Json<PresetBody>,
+) -> HttpResult<ExpressionPresets> {
+    api.save_expression_preset(path.entity, name, body.weights)
+        .await
+        .into_http_result()
+}
+
+/// Delete one of the persona's expression presets.
+#[utoipa::path(
+    delete,
+    path = "/vrm/expressions/presets/{name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Preset name"),
+    ),
+    responses(
+        (status = 200, description = "Remaining presets", body = Object),
+        (status = 404, description = "Persona or preset not found"),
+    ),
+)]
+pub async fn delete_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+) -> HttpResult<ExpressionPresets> {
+    api.delete_expression_preset(path.entity, name)
+        .await
+        .into_http_result()
+}
+
+/// Apply one of the persona's expression presets, optionally easing into it.
+#[utoipa::path(
+    post,
+    path = "/vrm/expressions/presets/{name}/apply",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Preset name"),
+    ),
+    request_body = ApplyExpressionPreset,
+    responses(
+        (status = 200, description = "Preset applied"),
+        (status = 404, description = "Persona or preset not found"),
+    ),
+)]
+pub async fn apply_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+    Json(body): Json<ApplyExpressionPreset>,
+) -> HttpResult {
+    api.apply_expression_preset(path.entity, name, body)
+        .await
+        .into_http_result()
+}
+
+/// Get the persona's auto-blink and micro-expression settings.
+#[utoipa::path(
+    get,
+    path = "/vrm/expressions/auto",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Auto expression settings", body = AutoExpressionSettings),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn get_auto(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<AutoExpressionSettings> {
+    api.auto_expressions(path.entity).await.into_http_result()
+}
+
+/// Replace and persist the persona's auto-blink and micro-expression settings.
+#[utoipa::path(
+    put,
+    path = "/vrm/expressions/auto",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = AutoExpressionSettings,
+    responses(
+        (status = 200, description = "Updated settings", body = AutoExpressionSettings),
+        (status = 400, description = "Rate, duration or intensity out of range"),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn put_auto(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<AutoExpressionSettings>,
+) -> HttpResult<AutoExpressionSettings> {
+    api.set_auto_expressions(path.entity, body)
+        .await
+        .into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use bevy::tasks::block_on;
+    use homunculus_api::vrm::{AutoExpressionSettings, ExpressionPresets};
+    use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_expression_presets_and_auto_settings() {
+        let (mut app, router) = test_app();
+        let elmer = spawn_test_persona(&mut app, "elmer");
+        let smug = HashMap::from([("happy".to_string(), 0.4), ("blinkLeft".to_string(), 1.0)]);
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/expressions/presets/smug",
+                r#"{"weights":{"happy":0.4,"blinkLeft":1.0}}"#,
+            ),
+            ExpressionPresets::from([("smug".to_string(), smug.clone())]),
+        ));
+        let saved = app
+            .world()
+            .non_send_resource::<PrefsDatabase>()
+            .load_as::<ExpressionPresets>(&PrefsKeys::expression_presets("elmer"))
+            .unwrap();
+        assert_eq!(saved.unwrap()["smug"], smug);
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/vrm/expressions/presets/smug/apply",
+                r#"{"durationMs":200,"easing":"quadraticOut"}"#,
+            ),
+        ));
+
+        for (method, uri, body, status) in [
+            (
+                "POST",
+                "/personas/elmer/vrm/expressions/presets/grumpy/apply",
+                "{}",
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                "PUT",
+                "/personas/elmer/vrm/expressions/presets/smug",
+                r#"{"weights":{"happy":2.0}}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                "PUT",
+                "/personas/elmer/vrm/expressions/auto",
+                r#"{"blinksPerMinute":500}"#,
+                StatusCode::BAD_REQUEST,
+            ),
+        ] {
+            let response = block_on(call_any_status(
+                &mut app,
+                router.clone(),
+                json_request(method, uri, body),
+            ));
+            assert_eq!(response.status(), status, "{method} {uri}");
+        }
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("DELETE", "/personas/elmer/vrm/expressions/presets/smug", ""),
+            ExpressionPresets::new(),
+        ));
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            Request::get("/personas/elmer/vrm/expressions/auto")
+                .body(Body::empty())
+                .unwrap(),
+            AutoExpressionSettings::default(),
+        ));
+        let settings = AutoExpressionSettings {
+            blinks_per_minute: 20.0,
+            micro_expressions: true,
+            ..Default::default()
+        };
+        block_on(assert_response(
+            &mut app,
+            router,
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/expressions/auto",
+                r#"{"blinksPerMinute":20,"microExpressions":true}"#,
+            ),
+            settings.clone(),
+        ));
+        assert_eq!(
+            app.world().get::<AutoExpressionSettings>(elmer),
+            Some(&settings)
+        );
+    }
 }
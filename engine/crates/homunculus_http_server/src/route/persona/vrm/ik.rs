@@ -0,0 +1,137 @@
+use axum::Json;
+use axum::extract::State;
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_api::vrm::{IkTargets, VrmApi};
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Get the inverse kinematics targets of a persona's hands and feet.
+#[utoipa::path(
+    get,
+    path = "/vrm/ik",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "IK targets", body = IkTargets),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn get_ik(State(api): State<VrmApi>, path: SpawnedPersonaPath) -> HttpResult<IkTargets> {
+    api.ik_targets(path.entity).await.into_http_result()
+}
+
+/// Replace the inverse kinematics targets of a persona's hands and feet.
+///
+/// Each target is a world or viewport position with a blend weight, layered on top of
+/// the playing VRMA. Limbs left out or `null` return to the animation.
+#[utoipa::path(
+    put,
+    path = "/vrm/ik",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = IkTargets,
+    responses(
+        (status = 200, description = "Updated IK targets", body = IkTargets),
+        (status = 400, description = "Invalid weight or position"),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn put_ik(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Json(targets): Json<IkTargets>,
+) -> HttpResult<IkTargets> {
+    api.set_ik_targets(path.entity, targets)
+        .await
+        .into_http_result()
+}
+
+/// Release every inverse kinematics target, returning the limbs to the animation.
+#[utoipa::path(
+    delete,
+    path = "/vrm/ik",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "IK targets released", body = IkTargets),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn delete_ik(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<IkTargets> {
+    api.set_ik_targets(path.entity, IkTargets::default())
+        .await
+        .into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::http::StatusCode;
+    use bevy::prelude::*;
+    use bevy::tasks::block_on;
+    use homunculus_api::vrm::{IkPoint, IkTarget, IkTargets};
+    use homunculus_core::prelude::GlobalViewport;
+
+    #[test]
+    fn test_ik_targets() {
+        let (mut app, router) = test_app();
+        let elmer = spawn_test_persona(&mut app, "elmer");
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/ik",
+                r#"{"leftHand":{"position":{"world":[0,1,0]},"weight":2}}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let targets = IkTargets {
+            left_hand: Some(IkTarget {
+                position: IkPoint::Viewport(GlobalViewport(Vec2::new(400.0, 300.0))),
+                weight: 1.0,
+                pole: None,
+            }),
+            right_foot: Some(IkTarget {
+                position: IkPoint::World([0.1, 0.0, 0.2]),
+                weight: 0.5,
+                pole: Some([0.1, 0.5, 1.0]),
+            }),
+            ..Default::default()
+        };
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/ik",
+                r#"{
+                    "leftHand":{"position":{"viewport":[400,300]}},
+                    "rightFoot":{"position":{"world":[0.1,0,0.2]},"weight":0.5,"pole":[0.1,0.5,1]}
+                }"#,
+            ),
+            targets,
+        ));
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("GET", "/personas/elmer/vrm/ik", ""),
+            targets,
+        ));
+
+        block_on(assert_response(
+            &mut app,
+            router,
+            json_request("DELETE", "/personas/elmer/vrm/ik", ""),
+            IkTargets::default(),
+        ));
+        assert!(app.world().get::<IkTargets>(elmer).is_none());
+    }
+}
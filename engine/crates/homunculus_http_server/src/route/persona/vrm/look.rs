@@ -1,7 +1,8 @@
+use axum::Json;
 use axum::extract::{Path, State};
 use bevy::prelude::Entity;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
-use homunculus_api::vrm::VrmApi;
+use homunculus_api::vrm::{LookAtPoint, LookAtSettings, VrmApi};
 
 use crate::route::persona::SpawnedPersonaPath;
 
@@ -58,3 +59,165 @@ pub async fn look_target(
 pub async fn unlook(State(api): State<VrmApi>, path: SpawnedPersonaPath) -> HttpResult {
     api.unlook(path.entity).await.into_http_result()
 }
+
+/// Look at a fixed point in the world or on the screen.
+///
+/// The body is `{"world": [x, y, z]}` or `{"viewport": [x, y]}` in global viewport
+/// coordinates, e.g. where a notification webview appears.
+#[utoipa::path(
+    put,
+    path = "/vrm/look/point",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = LookAtPoint,
+    responses(
+        (status = 200, description = "Look-at point set"),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn look_point(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Json(point): Json<LookAtPoint>,
+) -> HttpResult {
+    api.look_at_point(path.entity, point)
+        .await
+        .into_http_result()
+}
+
+/// Let the gaze wander idly around.
+#[utoipa::path(
+    put,
+    path = "/vrm/look/wander",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Idle gaze wandering enabled"),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn look_wander(State(api): State<VrmApi>, path: SpawnedPersonaPath) -> HttpResult {
+    api.look_wander(path.entity).await.into_http_result()
+}
+
+/// Get the angular limits, smoothing, saccades and head/neck weights of the gaze.
+#[utoipa::path(
+    get,
+    path = "/vrm/look/settings",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Look-at settings", body = LookAtSettings),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn get_settings(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<LookAtSettings> {
+    api.look_settings(path.entity).await.into_http_result()
+}
+
+/// Change the angular limits, smoothing, saccades and head/neck weights of the gaze.
+#[utoipa::path(
+    put,
+    path = "/vrm/look/settings",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = LookAtSettings,
+    responses(
+        (status = 200, description = "Updated look-at settings", body = LookAtSettings),
+        (status = 400, description = "Invalid settings"),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn put_settings(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Json(settings): Json<LookAtSettings>,
+) -> HttpResult<LookAtSettings> {
+    api.set_look_settings(path.entity, settings)
+        .await
+        .into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::http::StatusCode;
+    use bevy::tasks::block_on;
+    use bevy_vrm1::prelude::LookAt;
+    use homunculus_api::vrm::LookAtSettings;
+
+    #[test]
+    fn test_look_at_point_wander_and_settings() {
+        let (mut app, router) = test_app();
+        let elmer = spawn_test_persona(&mut app, "elmer");
+
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/look/point",
+                r#"{"world":[1.0,1.5,2.0]}"#,
+            ),
+        ));
+        let Some(&LookAt::Target(gaze_target)) = app.world().get::<LookAt>(elmer) else {
+            panic!("the persona should follow its gaze target");
+        };
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request("PUT", "/personas/elmer/vrm/look/wander", ""),
+        ));
+        assert!(matches!(
+            app.world().get::<LookAt>(elmer),
+            Some(LookAt::Target(target)) if *target == gaze_target
+        ));
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/look/settings",
+                r#"{"headWeight":0.8,"neckWeight":0.4}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let settings = LookAtSettings {
+            max_yaw_deg: 45.0,
+            head_weight: 0.3,
+            neck_weight: 0.2,
+            ..Default::default()
+        };
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/look/settings",
+                r#"{"maxYawDeg":45,"headWeight":0.3,"neckWeight":0.2}"#,
+            ),
+            settings,
+        ));
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("GET", "/personas/elmer/vrm/look/settings", ""),
+            settings,
+        ));
+
+        block_on(call(
+            &mut app,
+            router,
+            json_request("DELETE", "/personas/elmer/vrm/look", ""),
+        ));
+        app.update();
+        assert!(app.world().get::<LookAt>(elmer).is_none());
+        assert!(app.world().get_entity(gaze_target).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use homunculus_api::prelude::EasingFunction;
+use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_api::vrm::{
+    BonePose, BoneRotation, PosePresets, PoseTransition, SetBoneRotation, SetPose, VrmApi,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+use crate::route::persona::SpawnedPersonaPath;
+
+/// Request body for saving a named pose.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PosePresetBody {
+    #[schema(value_type = HashMap<String, BoneRotation>)]
+    pub bones: BonePose,
+}
+
+/// Rotate one humanoid bone on top of the playing animation.
+///
+/// `rotation` is in degrees around the persona's X (pitch), Y (yaw) and Z (roll) axes,
+/// e.g. `{"rotation": [0, 0, 15], "durationMs": 300}` tilts the head.
+#[utoipa::path(
+    put,
+    path = "/vrm/bone/{bone_name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("bone_name" = String, Path, description = "Humanoid bone name, e.g. head or rightUpperArm"),
+    ),
+    request_body = SetBoneRotation,
+    responses(
+        (status = 200, description = "Bone rotations after the change", body = Object),
+        (status = 400, description = "Invalid rotation or weight"),
+        (status = 404, description = "Persona, VRM or bone not found"),
+    ),
+)]
+pub async fn put_bone(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, bone_name)): Path<(String, String)>,
+    Json(body): Json<SetBoneRotation>,
+) -> HttpResult<BonePose> {
+    api.set_bone_rotation(path.entity, bone_name, body)
+        .await
+        .into_http_result()
+}
+
+/// Return one bone to the animation, optionally easing back.
+#[utoipa::path(
+    delete,
+    path = "/vrm/bone/{bone_name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("bone_name" = String, Path, description = "Humanoid bone name"),
+        ("durationMs" = Option<u64>, Query, description = "Transition duration in milliseconds"),
+        ("easing" = Option<EasingFunction>, Query, description = "Easing of the transition"),
+    ),
+    responses(
+        (status = 200, description = "Bone rotations after the change", body = Object),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn delete_bone(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, bone_name)): Path<(String, String)>,
+    Query(transition): Query<PoseTransition>,
+) -> HttpResult<BonePose> {
+    api.clear_bone_rotation(path.entity, bone_name, transition)
+        .await
+        .into_http_result()
+}
+
+/// Get the bone rotations set on a persona.
+#[utoipa::path(
+    get,
+    path = "/vrm/pose",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Bone name to rotation", body = Object),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn get_pose(State(api): State<VrmApi>, path: SpawnedPersonaPath) -> HttpResult<BonePose> {
+    api.pose(path.entity).await.into_http_result()
+}
+
+/// Replace every bone rotation of a persona; bones left out return to the animation.
+#[utoipa::path(
+    put,
+    path = "/vrm/pose",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = SetPose,
+    responses(
+        (status = 200, description = "Bone rotations after the change", body = Object),
+        (status = 400, description = "Invalid rotation or weight"),
+        (status = 404, description = "Persona, VRM or bone not found"),
+    ),
+)]
+pub async fn put_pose(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<SetPose>,
+) -> HttpResult<BonePose> {
+    api.set_pose(path.entity, body).await.into_http_result()
+}
+
+/// Return every bone to the animation, optionally easing back.
+#[utoipa::path(
+    delete,
+    path = "/vrm/pose",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("durationMs" = Option<u64>, Query, description = "Transition duration in milliseconds"),
+        ("easing" = Option<EasingFunction>, Query, description = "Easing of the transition"),
+    ),
+    responses(
+        (status = 200, description = "Bone rotations after the change", body = Object),
+        (status = 404, description = "Persona or VRM not found"),
+    ),
+)]
+pub async fn delete_pose(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Query(transition): Query<PoseTransition>,
+) -> HttpResult<BonePose> {
+    api.clear_pose(path.entity, transition)
+        .await
+        .into_http_result()
+}
+
+/// List the persona's named poses.
+#[utoipa::path(
+    get,
+    path = "/vrm/pose/presets",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    responses(
+        (status = 200, description = "Pose name to bone rotations", body = Object),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn list_presets(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+) -> HttpResult<PosePresets> {
+    api.pose_presets(path.entity).await.into_http_result()
+}
+
+/// Save a named pose for the persona.
+#[utoipa::path(
+    put,
+    path = "/vrm/pose/presets/{name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Pose name"),
+    ),
+    request_body = PosePresetBody,
+    responses(
+        (status = 200, description = "All poses after saving", body = Object),
+        (status = 400, description = "Invalid name, rotation or weight"),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn put_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+    Json(body): Json<PosePresetBody>,
+) -> HttpResult<PosePresets> {
+    api.save_pose_preset(path.entity, name, body.bones)
+        .await
+        .into_http_result()
+}
+
+/// Delete one of the persona's named poses.
+#[utoipa::path(
+    delete,
+    path = "/vrm/pose/presets/{name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Pose name"),
+    ),
+    responses(
+        (status = 200, description = "Remaining poses", body = Object),
+        (status = 404, description = "Persona or pose not found"),
+    ),
+)]
+pub async fn delete_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+) -> HttpResult<PosePresets> {
+    api.delete_pose_preset(path.entity, name)
+        .await
+        .into_http_result()
+}
+
+/// Take one of the persona's named poses, optionally easing into it.
+#[utoipa::path(
+    post,
+    path = "/vrm/pose/presets/{name}/apply",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Pose name"),
+    ),
+    request_body = PoseTransition,
+    responses(
+        (status = 200, description = "Bone rotations after the change", body = Object),
+        (status = 404, description = "Persona, VRM, pose or bone not found"),
+    ),
+)]
+pub async fn apply_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+    Json(transition): Json<PoseTransition>,
+) -> HttpResult<BonePose> {
+    api.apply_pose_preset(path.entity, name, transition)
+        .await
+        .into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::http::StatusCode;
+    use bevy::tasks::block_on;
+    use homunculus_api::vrm::{BonePose, BoneRotation, PosePresets};
+    use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+
+    #[test]
+    fn test_bone_pose_and_presets() {
+        let (mut app, router) = test_app();
+        spawn_test_persona(&mut app, "elmer");
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("GET", "/personas/elmer/vrm/pose", ""),
+            BonePose::new(),
+        ));
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/bone/head",
+                r#"{"rotation":[0,0,15],"weight":1.5}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/bone/head",
+                r#"{"rotation":[0,0,15],"durationMs":200}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let tilt = BonePose::from([(
+            "head".to_string(),
+            BoneRotation {
+                rotation: [0.0, 0.0, 15.0],
+                weight: 0.5,
+            },
+        )]);
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/pose/presets/tilt",
+                r#"{"bones":{"head":{"rotation":[0,0,15],"weight":0.5}}}"#,
+            ),
+            PosePresets::from([("tilt".to_string(), tilt.clone())]),
+        ));
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("GET", "/personas/elmer/vrm/pose/presets", ""),
+            PosePresets::from([("tilt".to_string(), tilt.clone())]),
+        ));
+        let saved = app
+            .world()
+            .non_send_resource::<PrefsDatabase>()
+            .load_as::<PosePresets>(&PrefsKeys::pose_presets("elmer"))
+            .unwrap();
+        assert_eq!(saved, Some(PosePresets::from([("tilt".to_string(), tilt)])));
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/vrm/pose/presets/shrug/apply",
+                r#"{"durationMs":300}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("DELETE", "/personas/elmer/vrm/pose/presets/tilt", ""),
+            PosePresets::new(),
+        ));
+        let response = block_on(call_any_status(
+            &mut app,
+            router,
+            json_request("DELETE", "/personas/elmer/vrm/pose/presets/tilt", ""),
+        ));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -2,7 +2,9 @@ use axum::Json;
 use axum::extract::{Path, State};
 use bevy::prelude::Entity;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
-use homunculus_api::vrm::{SpringBoneChainsResponse, SpringBonePropsUpdate, VrmApi};
+use homunculus_api::vrm::{
+    PersonaWindArgs, SpringBoneChainsResponse, SpringBonePropsUpdate, SpringBoneSettings, VrmApi,
+};
 
 use crate::route::persona::SpawnedPersonaPath;
 
@@ -25,6 +27,8 @@ pub async fn list_spring_bones(
 }
 
 /// Update properties of a spring bone chain.
+///
+/// The change is remembered for the persona's VRM asset and applied again when it loads.
 #[utoipa::path(
     patch,
     path = "/vrm/spring-bones/{chain_id}",
@@ -49,3 +53,151 @@ pub async fn patch_spring_bones(
         .await
         .into_http_result()
 }
+
+/// Apply a built-in spring bone preset to every chain.
+///
+/// Presets scale the model's own stiffness, drag and gravity: `default`, `stiff`,
+/// `soft` or `floaty`. The preset is remembered for the persona's VRM asset and replaces
+/// earlier chain changes; `default` forgets them.
+#[utoipa::path(
+    post,
+    path = "/vrm/spring-bones/presets/{name}",
+    tag = "personas",
+    params(
+        ("id" = String, Path, description = "Persona ID"),
+        ("name" = String, Path, description = "Preset name"),
+    ),
+    responses(
+        (status = 200, description = "Chains after applying", body = SpringBoneChainsResponse),
+        (status = 404, description = "Persona or preset not found"),
+    ),
+)]
+pub async fn apply_preset(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Path((_, name)): Path<(String, String)>,
+) -> HttpResult<SpringBoneChainsResponse> {
+    api.apply_spring_bone_preset(path.entity, name)
+        .await
+        .into_http_result()
+}
+
+/// Override the wind blowing on the persona's spring bones.
+///
+/// `wind: null` removes the override so the global wind applies again.
+#[utoipa::path(
+    put,
+    path = "/vrm/spring-bones/wind",
+    tag = "personas",
+    params(("id" = String, Path, description = "Persona ID")),
+    request_body = PersonaWindArgs,
+    responses(
+        (status = 200, description = "Updated spring bone settings", body = SpringBoneSettings),
+        (status = 400, description = "Invalid wind"),
+        (status = 404, description = "Persona not found"),
+    ),
+)]
+pub async fn put_wind(
+    State(api): State<VrmApi>,
+    path: SpawnedPersonaPath,
+    Json(body): Json<PersonaWindArgs>,
+) -> HttpResult<SpringBoneSettings> {
+    api.set_persona_wind(path.entity, body)
+        .await
+        .into_http_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{
+        assert_response, call, call_any_status, json_request, spawn_test_persona, test_app,
+    };
+    use axum::http::StatusCode;
+    use bevy::tasks::block_on;
+    use homunculus_api::vrm::SpringBoneSettings;
+    use homunculus_core::prelude::{AssetId, AssetIdComponent, PersonaId};
+    use homunculus_prefs::{PrefsDatabase, PrefsKeys};
+
+    #[test]
+    fn test_spring_bone_settings_wind_and_presets() {
+        let (mut app, router) = test_app();
+        let elmer = spawn_test_persona(&mut app, "elmer");
+        app.world_mut()
+            .entity_mut(elmer)
+            .insert(AssetIdComponent(AssetId::new("vrm:elmer")));
+
+        block_on(assert_response(
+            &mut app,
+            router.clone(),
+            json_request("GET", "/settings/spring-bones", ""),
+            SpringBoneSettings::default(),
+        ));
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/settings/spring-bones",
+                r#"{"wind":{"enabled":true,"strength":0.5},"reaction":0.1}"#,
+            ),
+        ));
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/settings/spring-bones",
+                r#"{"wind":{"gustiness":2.0}}"#,
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        block_on(call(
+            &mut app,
+            router.clone(),
+            json_request(
+                "PUT",
+                "/personas/elmer/vrm/spring-bones/wind",
+                r#"{"wind":{"enabled":false}}"#,
+            ),
+        ));
+        let settings = app.world().resource::<SpringBoneSettings>().clone();
+        assert!(settings.wind.enabled);
+        assert_eq!(settings.wind.strength, 0.5);
+        assert_eq!(settings.reaction, 0.1);
+        assert!(!settings.wind_for(&PersonaId::new("elmer")).enabled);
+        let saved = app
+            .world()
+            .non_send_resource::<PrefsDatabase>()
+            .load_as::<SpringBoneSettings>(PrefsKeys::SPRING_BONE_SETTINGS)
+            .unwrap();
+        assert_eq!(saved, Some(settings));
+
+        let response = block_on(call_any_status(
+            &mut app,
+            router.clone(),
+            json_request(
+                "POST",
+                "/personas/elmer/vrm/spring-bones/presets/wobbly",
+                "",
+            ),
+        ));
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        block_on(call(
+            &mut app,
+            router,
+            json_request(
+                "POST",
+                "/personas/elmer/vrm/spring-bones/presets/floaty",
+                "",
+            ),
+        ));
+        let saved = app
+            .world()
+            .non_send_resource::<PrefsDatabase>()
+            .load_json(&PrefsKeys::spring_bones("elmer", "vrm:elmer"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved["preset"], "floaty");
+    }
+}
@@ -3,7 +3,7 @@ use axum::extract::State;
 use bevy::animation::RepeatAnimation;
 use bevy_vrm1::prelude::PlayVrma;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
-use homunculus_api::prelude::{VrmAnimationApi, VrmaInfo};
+use homunculus_api::prelude::{Repeat, VrmAnimationApi, VrmaInfo};
 use homunculus_api::vrm::VrmApi;
 use homunculus_core::prelude::AssetId;
 use serde::{Deserialize, Serialize};
@@ -31,21 +31,6 @@ pub struct PlayBody {
     pub reset_spring_bones: Option<bool>,
 }
 
-/// Repetition behavior of a VRMA animation.
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
-#[serde(tag = "type", rename_all = "camelCase")]
-pub enum Repeat {
-    /// Loop forever.
-    Forever,
-    /// Play once and stop.
-    Never,
-    /// Repeat a fixed number of times.
-    Count {
-        /// Number of repetitions.
-        count: u32,
-    },
-}
-
 /// List all VRMA animations under a persona's VRM.
 #[utoipa::path(
     get,
@@ -94,11 +79,7 @@ pub async fn play_vrma(
         args.transition_duration = Duration::from_secs_f64(transition_secs);
     }
     if let Some(repeat) = body.repeat {
-        args.repeat = match repeat {
-            Repeat::Forever => RepeatAnimation::Forever,
-            Repeat::Never => RepeatAnimation::Never,
-            Repeat::Count { count } => RepeatAnimation::Count(count),
-        };
+        args.repeat = repeat.into();
     }
     vrma_api
         .play(args, body.wait_for_completion.unwrap_or_default())
@@ -8,15 +8,14 @@ use axum::Json;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
+use homunculus_api::rpc::{self, SchemaViolation};
 use homunculus_core::rpc_registry::{RpcMethodMeta, RpcRegistration, RpcRegistry};
+use homunculus_utils::config::HomunculusConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use utoipa::ToSchema;
 
-/// Default per-method proxy timeout (30 s).
-const DEFAULT_TIMEOUT_MS: u64 = 30_000;
-
 /// Body for `POST /rpc/register`.
 #[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -39,6 +38,14 @@ pub struct RegistrationsResponse {
     pub registrations: HashMap<String, RpcRegistration>,
 }
 
+/// Error body returned when a request fails JSON Schema validation.
+#[derive(Serialize, Debug, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaViolationsResponse {
+    pub error: String,
+    pub violations: Vec<SchemaViolation>,
+}
+
 /// Body for `POST /rpc/call`.
 #[derive(Deserialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -62,6 +69,7 @@ pub struct CallRequest {
     responses(
         (status = 200, description = "Methods registered"),
         (status = 404, description = "MOD has no pre-allocated port"),
+        (status = 422, description = "A method declares an invalid JSON Schema"),
         (status = 500, description = "Registry lock poisoned"),
     ),
 )]
@@ -74,10 +82,14 @@ pub async fn register(
         Err(e) => return e,
     };
     match reg.get(&body.mod_name).map(|e| e.port) {
-        Some(port) => {
-            reg.register(body.mod_name, port, body.methods);
-            StatusCode::OK.into_response()
-        }
+        Some(port) => match reg.register(body.mod_name, port, body.methods) {
+            Ok(()) => StatusCode::OK.into_response(),
+            Err(e) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({ "error": e })),
+            )
+                .into_response(),
+        },
         None => (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!({
@@ -140,8 +152,10 @@ pub async fn list_registrations(State(registry): State<Arc<RwLock<RpcRegistry>>>
 /// Error codes:
 /// - `503` — MOD not registered (not yet started or crashed)
 /// - `404` — method unknown for this MOD
+/// - `422` — `body` violates the method's `inputSchema`
 /// - `504` — MOD service timed out
-/// - `502` — MOD service refused the connection
+/// - `502` — MOD service refused the connection, or (with `[rpc] validate_output`)
+///   its response violates the method's `outputSchema`
 #[utoipa::path(
     post,
     path = "/call",
@@ -150,26 +164,30 @@ pub async fn list_registrations(State(registry): State<Arc<RwLock<RpcRegistry>>>
     responses(
         (status = 200, description = "RPC method response (JSON)"),
         (status = 404, description = "Method not found"),
-        (status = 502, description = "MOD service unreachable"),
+        (status = 422, description = "Body does not match the input schema", body = SchemaViolationsResponse),
+        (status = 502, description = "MOD service unreachable or invalid output"),
         (status = 503, description = "MOD not registered"),
         (status = 504, description = "Timeout exceeded"),
     ),
 )]
 pub async fn call(
     State(registry): State<Arc<RwLock<RpcRegistry>>>,
+    State(config): State<HomunculusConfig>,
     Json(req): Json<CallRequest>,
 ) -> Response {
-    let (port, timeout_ms) = {
-        let reg = match read_registry(&registry) {
-            Ok(r) => r,
-            Err(e) => return e,
-        };
-        match resolve_proxy_target(&reg, &req.mod_name, &req.method) {
-            Ok(t) => t,
-            Err(e) => return e,
-        }
-    };
-    forward_to_mod(port, &req.method, &req.mod_name, timeout_ms, req.body).await
+    let validate_output = config.rpc.validate_output.unwrap_or(false);
+    match rpc::call(
+        &registry,
+        &req.mod_name,
+        &req.method,
+        req.body,
+        validate_output,
+    )
+    .await
+    {
+        Ok(response) => response.into_response(),
+        Err(e) => e.into_response(),
+    }
 }
 
 /// Returns a 500 registry-lock-poisoned error response.
@@ -193,98 +211,124 @@ fn read_registry(
     registry.read().map_err(|_| lock_poisoned_error())
 }
 
-/// Validates mod+method in the registry and returns `(port, timeout_ms)`.
-fn resolve_proxy_target(
-    reg: &RpcRegistry,
-    mod_name: &str,
-    method: &str,
-) -> Result<(u16, u64), Response> {
-    let entry = reg.get(mod_name).ok_or_else(|| {
-        (
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(serde_json::json!({
-                "error": format!("Mod '{mod_name}' is not registered")
-            })),
-        )
-            .into_response()
-    })?;
-    if !entry.methods.is_empty() && !entry.methods.contains_key(method) {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "error": format!("Method '{method}' not found in mod '{mod_name}'")
-            })),
-        )
-            .into_response());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    fn speak_schema() -> serde_json::Map<String, serde_json::Value> {
+        serde_json::json!({
+            "type": "object",
+            "required": ["text"],
+            "properties": { "text": { "type": "string" } }
+        })
+        .as_object()
+        .cloned()
+        .unwrap()
     }
-    let timeout_ms = entry
-        .methods
-        .get(method)
-        .and_then(|m| m.timeout)
-        .unwrap_or(DEFAULT_TIMEOUT_MS);
-    Ok((entry.port, timeout_ms))
-}
 
-/// Forwards the request body to the MOD service and relays the response.
-async fn forward_to_mod(
-    port: u16,
-    method: &str,
-    mod_name: &str,
-    timeout_ms: u64,
-    body: Option<serde_json::Value>,
-) -> Response {
-    let url = format!("http://127.0.0.1:{port}/{method}");
-    let timeout = std::time::Duration::from_millis(timeout_ms);
-    let mut request = reqwest::Client::new().post(&url);
-    request = match body {
-        Some(value) => request.json(&value),
-        None => request
-            .header("content-type", "application/json")
-            .header("content-length", "0"),
-    };
-    let result = tokio::time::timeout(timeout, request.send()).await;
-    match result {
-        Err(_) => (
-            StatusCode::GATEWAY_TIMEOUT,
-            Json(serde_json::json!({
-                "error": format!("Mod '{mod_name}' method '{method}' timed out after {timeout_ms}ms")
-            })),
-        )
-            .into_response(),
-        Ok(Err(e)) if e.is_connect() => (
-            StatusCode::BAD_GATEWAY,
-            Json(serde_json::json!({
-                "error": format!("Mod '{mod_name}' refused connection: {e}")
-            })),
-        )
-            .into_response(),
-        Ok(Err(e)) => (
-            StatusCode::BAD_GATEWAY,
-            Json(serde_json::json!({
-                "error": format!("Mod '{mod_name}' proxy error: {e}")
-            })),
+    fn registry_with_speak() -> Arc<RwLock<RpcRegistry>> {
+        let mut reg = RpcRegistry::default();
+        reg.register(
+            "voicevox".to_string(),
+            // Nothing listens here; validation must fail before proxying.
+            1,
+            HashMap::from([(
+                "speak".to_string(),
+                RpcMethodMeta {
+                    input_schema: Some(speak_schema()),
+                    ..Default::default()
+                },
+            )]),
         )
-            .into_response(),
-        Ok(Ok(resp)) => relay_mod_response(resp).await,
+        .unwrap();
+        Arc::new(RwLock::new(reg))
+    }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
     }
-}
 
-async fn relay_mod_response(resp: reqwest::Response) -> Response {
-    let status =
-        StatusCode::from_u16(resp.status().as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-    match resp.bytes().await {
-        Ok(bytes) => (
-            status,
-            axum::response::AppendHeaders([(axum::http::header::CONTENT_TYPE, "application/json")]),
-            bytes,
+    #[tokio::test]
+    async fn call_rejects_body_violating_input_schema() {
+        let request = CallRequest {
+            mod_name: "voicevox".to_string(),
+            method: "speak".to_string(),
+            body: Some(serde_json::json!({ "text": 42 })),
+        };
+        let response = call(
+            State(registry_with_speak()),
+            State(HomunculusConfig::default()),
+            Json(request),
         )
-            .into_response(),
-        Err(e) => (
-            StatusCode::BAD_GATEWAY,
-            Json(serde_json::json!({
-                "error": format!("Failed to read mod response: {e}")
-            })),
+        .await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let json = json_body(response).await;
+        assert_eq!(json["violations"][0]["pointer"], "/text");
+    }
+
+    #[tokio::test]
+    async fn call_validates_missing_body_as_empty_object() {
+        let request = CallRequest {
+            mod_name: "voicevox".to_string(),
+            method: "speak".to_string(),
+            body: None,
+        };
+        let response = call(
+            State(registry_with_speak()),
+            State(HomunculusConfig::default()),
+            Json(request),
         )
-            .into_response(),
+        .await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let json = json_body(response).await;
+        assert_eq!(json["violations"][0]["pointer"], "");
+    }
+
+    #[tokio::test]
+    async fn register_rejects_invalid_schema() {
+        let registry = registry_with_speak();
+        let request = RegisterRequest {
+            mod_name: "voicevox".to_string(),
+            methods: HashMap::from([(
+                "speak".to_string(),
+                RpcMethodMeta {
+                    output_schema: serde_json::json!({ "type": 5 }).as_object().cloned(),
+                    ..Default::default()
+                },
+            )]),
+        };
+        let response = register(State(registry.clone()), Json(request)).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let reg = registry.read().unwrap();
+        assert!(
+            reg.get("voicevox").unwrap().methods["speak"]
+                .input_schema
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn registrations_publish_output_schema() {
+        let registry = registry_with_speak();
+        let request = RegisterRequest {
+            mod_name: "voicevox".to_string(),
+            methods: HashMap::from([(
+                "speak".to_string(),
+                RpcMethodMeta {
+                    input_schema: Some(speak_schema()),
+                    output_schema: Some(speak_schema()),
+                    ..Default::default()
+                },
+            )]),
+        };
+        let response = register(State(registry.clone()), Json(request)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = json_body(list_registrations(State(registry)).await).await;
+        assert_eq!(
+            json["registrations"]["voicevox"]["methods"]["speak"]["outputSchema"]["type"],
+            "object"
+        );
     }
 }
@@ -1,7 +1,10 @@
 use axum::Json;
 use axum::extract::State;
+use homunculus_api::physics::PhysicsApi;
 use homunculus_api::prelude::SettingsApi;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
+use homunculus_api::vrm::{SpringBoneSettings, VrmApi};
+use homunculus_drag::physics::PhysicsSettings;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -32,6 +35,69 @@ pub async fn set_fps(State(api): State<SettingsApi>, Json(body): Json<SetFpsBody
     api.set_fps(body.fps).await.into_http_result()
 }
 
+/// Get the gravity and throwing settings for released personas.
+#[utoipa::path(
+    get,
+    path = "/physics",
+    tag = "settings",
+    responses(
+        (status = 200, description = "Current physics settings", body = PhysicsSettings),
+    ),
+)]
+pub async fn get_physics(State(api): State<PhysicsApi>) -> HttpResult<PhysicsSettings> {
+    api.settings().await.into_http_result()
+}
+
+/// Replace the gravity and throwing settings. Persists and applies to the next release.
+#[utoipa::path(
+    put,
+    path = "/physics",
+    tag = "settings",
+    request_body = PhysicsSettings,
+    responses(
+        (status = 200, description = "Updated physics settings", body = PhysicsSettings),
+        (status = 400, description = "Invalid settings"),
+    ),
+)]
+pub async fn put_physics(
+    State(api): State<PhysicsApi>,
+    Json(body): Json<PhysicsSettings>,
+) -> HttpResult<PhysicsSettings> {
+    api.set_settings(body).await.into_http_result()
+}
+
+/// Get the wind and reaction settings for spring bones.
+#[utoipa::path(
+    get,
+    path = "/spring-bones",
+    tag = "settings",
+    responses(
+        (status = 200, description = "Current spring bone settings", body = SpringBoneSettings),
+    ),
+)]
+pub async fn get_spring_bones(State(api): State<VrmApi>) -> HttpResult<SpringBoneSettings> {
+    api.spring_bone_settings().await.into_http_result()
+}
+
+/// Replace the wind and reaction settings for spring bones. Persists and applies
+/// immediately.
+#[utoipa::path(
+    put,
+    path = "/spring-bones",
+    tag = "settings",
+    request_body = SpringBoneSettings,
+    responses(
+        (status = 200, description = "Updated spring bone settings", body = SpringBoneSettings),
+        (status = 400, description = "Invalid settings"),
+    ),
+)]
+pub async fn put_spring_bones(
+    State(api): State<VrmApi>,
+    Json(body): Json<SpringBoneSettings>,
+) -> HttpResult<SpringBoneSettings> {
+    api.set_spring_bone_settings(body).await.into_http_result()
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SetFpsBody {
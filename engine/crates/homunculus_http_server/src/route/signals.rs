@@ -3,11 +3,11 @@
 
 use axum::Json;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use bevy::platform::collections::HashMap;
 use futures::SinkExt as _;
 use homunculus_api::prelude::axum::{HttpResult, IntoHttpResult};
-use homunculus_api::prelude::{ApiError, SignalInfo, SignalsApi};
+use homunculus_api::prelude::{ApiError, SignalInfo, SignalRecord, SignalRetention, SignalsApi};
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
@@ -49,11 +49,119 @@ pub async fn send(
     Ok(())
 }
 
+/// Send a request on a signal and wait for the first reply.
+///
+/// Subscribers receive `{ correlationId, replyTo, payload }` and answer by sending
+/// `{ correlationId, payload }` to the `replyTo` signal (`<signal>:reply`).
+/// The response body is the `payload` of the first matching reply.
+#[utoipa::path(
+    post,
+    path = "/{signal}/request",
+    tag = "signals",
+    params(
+        ("signal" = String, Path, description = "Signal channel name"),
+    ),
+    request_body = SignalRequest,
+    responses(
+        (status = 200, description = "Reply payload", body = Object),
+        (status = 400, description = "Invalid timeout"),
+        (status = 504, description = "No reply within the timeout"),
+    ),
+)]
+pub async fn request(
+    State(api): State<SignalsApi>,
+    Path(signal): Path<String>,
+    Json(body): Json<SignalRequest>,
+) -> HttpResult<serde_json::Value> {
+    api.request(signal, body).await.into_http_result()
+}
+
+/// Query parameters for the signal history endpoint.
+#[derive(Deserialize, Debug, Default)]
+pub struct HistoryQuery {
+    /// Only return messages with a sequence number greater than this.
+    pub since: Option<u64>,
+}
+
+/// Get the retained messages of a signal, oldest first.
+///
+/// Only signals with a retention policy keep history; for any other signal the list is empty.
+#[utoipa::path(
+    get,
+    path = "/{signal}/history",
+    tag = "signals",
+    params(
+        ("signal" = String, Path, description = "Signal channel name"),
+        ("since" = Option<u64>, Query, description = "Only return messages with a greater sequence number"),
+    ),
+    responses(
+        (status = 200, description = "Retained messages", body = Vec<SignalRecord>),
+    ),
+)]
+pub async fn history(
+    State(api): State<SignalsApi>,
+    Path(signal): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> HttpResult<Vec<SignalRecord>> {
+    api.history(signal, query.since).await.into_http_result()
+}
+
+/// Enable history retention for a signal.
+///
+/// Keeps the last `maxMessages` messages and/or the messages of the last `maxAgeSecs` seconds.
+/// With `persist`, the history survives application restarts.
+#[utoipa::path(
+    put,
+    path = "/{signal}/retention",
+    tag = "signals",
+    params(
+        ("signal" = String, Path, description = "Signal channel name"),
+    ),
+    request_body = SignalRetention,
+    responses(
+        (status = 200, description = "Retention policy applied"),
+        (status = 400, description = "Invalid retention policy"),
+    ),
+)]
+pub async fn set_retention(
+    State(api): State<SignalsApi>,
+    Path(signal): Path<String>,
+    Json(body): Json<SignalRetention>,
+) -> HttpResult {
+    api.set_retention(signal, body).await.into_http_result()
+}
+
+/// Disable history retention for a signal and discard its history.
+#[utoipa::path(
+    delete,
+    path = "/{signal}/retention",
+    tag = "signals",
+    params(
+        ("signal" = String, Path, description = "Signal channel name"),
+    ),
+    responses(
+        (status = 200, description = "Retention disabled"),
+    ),
+)]
+pub async fn clear_retention(
+    State(api): State<SignalsApi>,
+    Path(signal): Path<String>,
+) -> HttpResult {
+    api.clear_retention(signal).await.into_http_result()
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(tag = "type", rename_all = "camelCase")]
 enum WsClientMessage {
-    Subscribe { channel: String },
-    Unsubscribe { channel: String },
+    Subscribe {
+        channel: String,
+        /// Replay retained messages with a greater sequence number before live ones.
+        #[serde(default)]
+        since: Option<u64>,
+    },
+    Unsubscribe {
+        channel: String,
+    },
 }
 
 #[derive(Serialize, Debug)]
@@ -72,6 +180,10 @@ enum WsServerMessage {
     },
     Event {
         channel: String,
+        /// The subscribed wildcard pattern, for events delivered through a pattern subscription.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pattern: Option<String>,
+        seq: u64,
         data: serde_json::Value,
     },
 }
@@ -92,8 +204,13 @@ impl WsServerMessage {
         }
     }
 
-    fn event(channel: String, data: serde_json::Value) -> Self {
-        Self::Event { channel, data }
+    fn event(channel: String, pattern: Option<String>, record: SignalRecord) -> Self {
+        Self::Event {
+            channel,
+            pattern,
+            seq: record.seq,
+            data: record.payload,
+        }
     }
 }
 
@@ -153,8 +270,8 @@ async fn handle_client_message(
     };
 
     match msg {
-        WsClientMessage::Subscribe { channel } => {
-            handle_subscribe(channel, api, merge_tx, forwarding_tasks).await;
+        WsClientMessage::Subscribe { channel, since } => {
+            handle_subscribe(channel, since, api, merge_tx, forwarding_tasks).await;
         }
         WsClientMessage::Unsubscribe { channel } => {
             handle_unsubscribe(channel, forwarding_tasks);
@@ -164,6 +281,7 @@ async fn handle_client_message(
 
 async fn handle_subscribe(
     channel: String,
+    since: Option<u64>,
     api: &SignalsApi,
     merge_tx: &mpsc::Sender<WsServerMessage>,
     forwarding_tasks: &mut HashMap<String, JoinHandle<()>>,
@@ -183,37 +301,58 @@ async fn handle_subscribe(
         return;
     }
 
-    let rx = match api.clone().stream(channel.clone()).await {
-        Ok(stream) => stream,
+    use bevy::tasks::futures_lite::StreamExt;
+    let subscribed = if SignalPattern::is_pattern(&channel) {
+        let pattern = channel.clone();
+        api.clone().stream_pattern(channel.clone()).await.map(|rx| {
+            spawn_forwarding(
+                rx.map(move |(signal, record)| {
+                    WsServerMessage::event(signal, Some(pattern.clone()), record)
+                }),
+                merge_tx.clone(),
+            )
+        })
+    } else {
+        let ch = channel.clone();
+        api.clone()
+            .stream_since(channel.clone(), since)
+            .await
+            .map(|rx| {
+                spawn_forwarding(
+                    rx.map(move |record| WsServerMessage::event(ch.clone(), None, record)),
+                    merge_tx.clone(),
+                )
+            })
+    };
+
+    match subscribed {
+        Ok(handle) => {
+            let _ = merge_tx
+                .send(WsServerMessage::subscribed(channel.clone()))
+                .await;
+            forwarding_tasks.insert(channel, handle);
+        }
         Err(e) => {
             let _ = merge_tx
                 .send(WsServerMessage::error(channel, e.to_string()))
                 .await;
-            return;
         }
-    };
+    }
+}
 
-    let _ = merge_tx
-        .send(WsServerMessage::subscribed(channel.clone()))
-        .await;
-
-    let tx = merge_tx.clone();
-    let ch = channel.clone();
-    let handle = tokio::spawn(async move {
-        use bevy::tasks::futures_lite::StreamExt;
-        let mut stream = std::pin::pin!(rx);
-        while let Some(value) = stream.next().await {
-            if tx
-                .send(WsServerMessage::event(ch.clone(), value))
-                .await
-                .is_err()
-            {
+fn spawn_forwarding(
+    stream: impl Stream<Item = WsServerMessage> + Send + 'static,
+    tx: mpsc::Sender<WsServerMessage>,
+) -> JoinHandle<()> {
+    use bevy::tasks::futures_lite::StreamExt;
+    tokio::spawn(async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(msg) = stream.next().await {
+            if tx.send(msg).await.is_err() {
                 break;
             }
         }
-    });
-
-    forwarding_tasks.insert(channel, handle);
+    })
 }
 
 fn handle_unsubscribe(channel: String, forwarding_tasks: &mut HashMap<String, JoinHandle<()>>) {
@@ -1,7 +1,13 @@
 use axum::extract::FromRef;
 use homunculus_api::assets::AssetsApi;
+use homunculus_api::batch::BatchApi;
+use homunculus_api::emotion::EmotionApi;
+use homunculus_api::events::EventsApi;
+use homunculus_api::interaction::InteractionApi;
+use homunculus_api::locomotion::LocomotionApi;
 use homunculus_api::mods::ModsApi;
 use homunculus_api::persona::PersonaApi;
+use homunculus_api::physics::PhysicsApi;
 use homunculus_api::preferences::PrefsApi;
 use homunculus_api::prelude::{
     ApiReactor, AppApi, AudioBgmApi, AudioSeApi, CameraApi, EffectsApi, EntitiesApi, SettingsApi,
@@ -9,6 +15,7 @@ use homunculus_api::prelude::{
 };
 use homunculus_api::processes::ProcessesApi;
 use homunculus_api::stt::SttApi;
+use homunculus_api::timeline::TimelineApi;
 use homunculus_api::vrm::VrmApi;
 use homunculus_core::rpc_registry::RpcRegistry;
 use homunculus_utils::config::HomunculusConfig;
@@ -21,6 +28,7 @@ pub struct HttpState {
     pub app: AppApi,
     pub audio_se: AudioSeApi,
     pub audio_bgm: AudioBgmApi,
+    pub batch: BatchApi,
     pub persona: PersonaApi,
     pub vrm: VrmApi,
     pub vrma: VrmAnimationApi,
@@ -32,10 +40,16 @@ pub struct HttpState {
     pub effects: EffectsApi,
     pub speak: SpeechApi,
     pub signals: SignalsApi,
+    pub events: EventsApi,
     pub entities: EntitiesApi,
     pub assets: AssetsApi,
     pub mods: ModsApi,
     pub processes: ProcessesApi,
+    pub timeline: TimelineApi,
+    pub interaction: InteractionApi,
+    pub locomotion: LocomotionApi,
+    pub physics: PhysicsApi,
+    pub emotion: EmotionApi,
     /// STT API — stateless speech recognition and model downloads.
     /// Bypasses ApiReactor; audio pipelines are managed internally.
     pub stt: SttApi,
@@ -55,6 +69,7 @@ impl HttpState {
             app: AppApi::from(reactor.clone()),
             audio_se: AudioSeApi::from(reactor.clone()),
             audio_bgm: AudioBgmApi::from(reactor.clone()),
+            batch: BatchApi::from(reactor.clone()),
             persona: PersonaApi::from(reactor.clone()),
             vrm: VrmApi::from(reactor.clone()),
             vrma: VrmAnimationApi::from(reactor.clone()),
@@ -66,10 +81,16 @@ impl HttpState {
             effects: EffectsApi::from(reactor.clone()),
             speak: SpeechApi::from(reactor.clone()),
             signals: SignalsApi::from(reactor.clone()),
+            events: EventsApi::from(reactor.clone()),
             entities: EntitiesApi::from(reactor.clone()),
             assets: AssetsApi::from(reactor.clone()),
             mods: ModsApi::from(reactor.clone()),
             processes: ProcessesApi::from(reactor.clone()),
+            timeline: TimelineApi::from(reactor.clone()),
+            interaction: InteractionApi::from(reactor.clone()),
+            locomotion: LocomotionApi::from(reactor.clone()),
+            physics: PhysicsApi::from(reactor.clone()),
+            emotion: EmotionApi::from(reactor.clone()),
             stt: SttApi::new(reactor.clone()),
             config,
             runtime,
@@ -6,16 +6,19 @@
 
 mod prompts;
 mod resources;
+mod rpc_tools;
 mod tools;
 
 use bevy::prelude::Entity;
 use homunculus_api::assets::AssetsApi;
+use homunculus_api::emotion::EmotionApi;
 use homunculus_api::mods::ModsApi;
 use homunculus_api::prelude::{
     ApiReactor, AudioBgmApi, AudioSeApi, EntitiesApi, PersonaApi, VrmAnimationApi, VrmApi,
     WebviewApi,
 };
 use homunculus_core::prelude::{Persona, PersonaId};
+use homunculus_core::rpc_registry::RpcRegistry;
 use homunculus_utils::config::HomunculusConfig;
 use homunculus_utils::runtime::RuntimeResolver;
 use rmcp::handler::server::router::tool::ToolRouter;
@@ -25,9 +28,9 @@ use rmcp::model::{
     ListPromptsResult, ListResourcesResult, ListToolsResult, PaginatedRequestParams,
     ReadResourceRequestParams, ReadResourceResult, ServerCapabilities, ServerInfo, Tool,
 };
-use rmcp::service::RequestContext;
+use rmcp::service::{NotificationContext, RequestContext};
 use rmcp::{RoleServer, ServerHandler};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 
 const SERVER_NAME: &str = "homunculus";
 
@@ -60,18 +63,26 @@ pub struct HomunculusMcpHandler {
     pub(crate) entities_api: EntitiesApi,
     pub(crate) vrma_api: VrmAnimationApi,
     pub(crate) persona_api: PersonaApi,
+    pub(crate) emotion_api: EmotionApi,
     /// Stores the active persona's [`PersonaId`] for character resolution.
     pub(crate) active_character: Arc<Mutex<Option<PersonaId>>>,
     pub(crate) config: HomunculusConfig,
     pub(crate) runtime: RuntimeResolver,
     /// Tracks open webview IDs so they can be cleaned up when the MCP session ends.
     pub(crate) open_webviews: Arc<Mutex<Vec<u64>>>,
+    /// MOD RPC registrations, exposed as dynamic `<mod>__<method>` tools.
+    pub(crate) rpc_registry: Arc<RwLock<RpcRegistry>>,
     tool_router: ToolRouter<Self>,
 }
 
 impl HomunculusMcpHandler {
     /// Creates a new handler, constructing all domain APIs from the given reactor.
-    pub fn new(reactor: ApiReactor, config: HomunculusConfig, runtime: RuntimeResolver) -> Self {
+    pub fn new(
+        reactor: ApiReactor,
+        config: HomunculusConfig,
+        runtime: RuntimeResolver,
+        rpc_registry: Arc<RwLock<RpcRegistry>>,
+    ) -> Self {
         Self {
             webview_api: WebviewApi::from(reactor.clone()),
             vrm_api: VrmApi::from(reactor.clone()),
@@ -81,11 +92,13 @@ impl HomunculusMcpHandler {
             entities_api: EntitiesApi::from(reactor.clone()),
             vrma_api: VrmAnimationApi::from(reactor.clone()),
             persona_api: PersonaApi::from(reactor.clone()),
+            emotion_api: EmotionApi::from(reactor.clone()),
             assets_api: AssetsApi::from(reactor),
             active_character: Arc::new(Mutex::new(None)),
             config,
             runtime,
             open_webviews: Arc::new(Mutex::new(Vec::new())),
+            rpc_registry,
             tool_router: tools::tool_router(),
         }
     }
@@ -155,12 +168,22 @@ impl HomunculusMcpHandler {
             e.into_inner()
         }) = persona_id;
     }
+
+    /// Runs `f` against the RPC registry, recovering from a poisoned lock.
+    fn with_rpc_registry<T>(&self, f: impl FnOnce(&RpcRegistry) -> T) -> T {
+        let reg = self.rpc_registry.read().unwrap_or_else(|e| {
+            bevy::log::warn!("RwLock poisoned: {e}");
+            e.into_inner()
+        });
+        f(&reg)
+    }
 }
 
 impl ServerHandler for HomunculusMcpHandler {
     fn get_info(&self) -> ServerInfo {
         let capabilities = ServerCapabilities::builder()
             .enable_tools()
+            .enable_tool_list_changed()
             .enable_resources()
             .enable_prompts()
             .build();
@@ -179,10 +202,12 @@ impl ServerHandler for HomunculusMcpHandler {
         _context: RequestContext<RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListToolsResult, rmcp::ErrorData>> + Send + '_
     {
+        let mut tools = self.tool_router.list_all();
+        tools.extend(self.with_rpc_registry(rpc_tools::list));
         std::future::ready(Ok(ListToolsResult {
             meta: None,
             next_cursor: None,
-            tools: self.tool_router.list_all(),
+            tools,
         }))
     }
 
@@ -191,12 +216,29 @@ impl ServerHandler for HomunculusMcpHandler {
         request: CallToolRequestParams,
         context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
+        if self.tool_router.get(&request.name).is_none()
+            && let Some((mod_name, method)) =
+                self.with_rpc_registry(|reg| rpc_tools::resolve(reg, &request.name))
+        {
+            return self.call_rpc_tool(&mod_name, &method, request).await;
+        }
         let tcc = ToolCallContext::new(self, request, context);
         self.tool_router.call(tcc).await
     }
 
     fn get_tool(&self, name: &str) -> Option<Tool> {
-        self.tool_router.get(name).cloned()
+        self.tool_router
+            .get(name)
+            .cloned()
+            .or_else(|| self.with_rpc_registry(|reg| rpc_tools::get(reg, name)))
+    }
+
+    fn on_initialized(
+        &self,
+        context: NotificationContext<RoleServer>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.spawn_tool_list_watcher(context);
+        std::future::ready(())
     }
 
     fn list_resources(
@@ -259,7 +301,7 @@ mod tests {
             ..Default::default()
         };
         let runtime = RuntimeResolver::detect();
-        HomunculusMcpHandler::new(reactor, config, runtime)
+        HomunculusMcpHandler::new(reactor, config, runtime, Default::default())
     }
 
     #[test]
@@ -330,12 +372,12 @@ mod tests {
     }
 
     #[test]
-    fn resource_definitions_lists_four_resources() {
+    fn resource_definitions_lists_five_resources() {
         let resources = resources::resource_definitions();
         assert_eq!(
             resources.len(),
-            4,
-            "expected 4 resources, got {}",
+            5,
+            "expected 5 resources, got {}",
             resources.len()
         );
     }
@@ -355,6 +397,10 @@ mod tests {
             uris.contains(&"homunculus://assets"),
             "missing assets resource"
         );
+        assert!(
+            uris.contains(&"homunculus://animations"),
+            "missing animations resource"
+        );
     }
 
     #[test]
@@ -455,6 +501,46 @@ mod tests {
         assert!(handler.get_tool("totally_unknown").is_none());
     }
 
+    #[test]
+    fn get_tool_finds_registered_rpc_methods() {
+        let handler = test_handler();
+        let mut input_schema = serde_json::Map::new();
+        input_schema.insert("type".to_string(), serde_json::json!("object"));
+        handler
+            .rpc_registry
+            .write()
+            .unwrap()
+            .register(
+                "visual".to_string(),
+                4000,
+                std::collections::HashMap::from([(
+                    "show".to_string(),
+                    homunculus_core::rpc_registry::RpcMethodMeta {
+                        description: Some("Display visual".to_string()),
+                        input_schema: Some(input_schema),
+                        ..Default::default()
+                    },
+                )]),
+            )
+            .unwrap();
+
+        let tool = handler
+            .get_tool("visual__show")
+            .expect("registered RPC method should be exposed as a tool");
+        assert_eq!(tool.description.as_deref(), Some("Display visual"));
+
+        handler.rpc_registry.write().unwrap().deregister("visual");
+        assert!(handler.get_tool("visual__show").is_none());
+    }
+
+    #[test]
+    fn get_info_enables_tool_list_changed() {
+        let handler = test_handler();
+        let info = ServerHandler::get_info(&handler);
+        let tools = info.capabilities.tools.expect("tools capability");
+        assert_eq!(tools.list_changed, Some(true));
+    }
+
     #[test]
     fn get_character_snapshot_has_read_only_annotation() {
         let handler = test_handler();
@@ -1,6 +1,7 @@
 //! MCP resource definitions and read logic.
 
 use homunculus_api::assets::AssetFilter;
+use homunculus_core::prelude::AssetType;
 use rmcp::model::{
     AnnotateAble, RawResource, ReadResourceRequestParams, ReadResourceResult, Resource,
     ResourceContents,
@@ -27,6 +28,10 @@ pub(super) fn resource_definitions() -> Vec<Resource> {
             .with_description("List of available assets across all mods")
             .with_mime_type("application/json")
             .no_annotation(),
+        RawResource::new("homunculus://animations", "homunculus-animations")
+            .with_description("VRMA animations with their description, tags (e.g. greeting, idle), duration, whether they loop, the bones they move and whether they move the character")
+            .with_mime_type("application/json")
+            .no_annotation(),
     ]
 }
 
@@ -67,6 +72,17 @@ pub(super) async fn read_resource(
                 .map_err(api_err)?;
             to_json_string(&assets)?
         }
+        "homunculus://animations" => {
+            let animations = handler
+                .assets_api
+                .list(AssetFilter {
+                    asset_type: Some(AssetType::Vrma),
+                    ..Default::default()
+                })
+                .await
+                .map_err(api_err)?;
+            to_json_string(&animations)?
+        }
         _ => {
             return Err(rmcp::ErrorData::resource_not_found(
                 format!("Unknown resource: {uri}"),
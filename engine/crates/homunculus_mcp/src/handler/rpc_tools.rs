@@ -0,0 +1,290 @@
+//! Dynamic MCP tools backed by MOD service RPC methods.
+//!
+//! Every method a MOD publishes via `POST /rpc/register` with an `inputSchema`
+//! is exposed as a tool named `<mod>__<method>`. Calls are proxied through the
+//! same path as `POST /rpc/call`, and connected clients receive
+//! `notifications/tools/list_changed` whenever the registry changes.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use homunculus_api::rpc;
+use homunculus_core::rpc_registry::{RpcMethodMeta, RpcRegistry};
+use rmcp::model::{CallToolRequestParams, CallToolResult, Content, Tool};
+use rmcp::service::{NotificationContext, RoleServer};
+
+use super::HomunculusMcpHandler;
+
+/// Separates the MOD name from the method name in a dynamic tool name.
+const SEPARATOR: &str = "__";
+
+/// Builds the MCP tool name for a MOD RPC method.
+///
+/// Characters outside `[A-Za-z0-9_-]` (e.g. the `@` and `/` of scoped package
+/// names) are replaced with `_` so the name stays valid for MCP clients.
+pub(super) fn tool_name(mod_name: &str, method: &str) -> String {
+    let sanitized_mod = sanitize(mod_name);
+    let sanitized_method = sanitize(method);
+    format!(
+        "{}{SEPARATOR}{sanitized_method}",
+        sanitized_mod.trim_start_matches('_')
+    )
+}
+
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Assigns a unique tool name to every registered method that declares an input schema.
+///
+/// Sanitization can map different methods to the same name (e.g. `a.b` and `a_b`).
+/// Methods are named in `(mod, method)` order and a colliding name gets a `_2`, `_3`, …
+/// suffix, so no method silently shadows another.
+fn tool_names(registry: &RpcRegistry) -> BTreeMap<String, (String, String)> {
+    let mut methods: Vec<(&String, &String)> = registry
+        .all()
+        .iter()
+        .flat_map(|(mod_name, registration)| {
+            registration
+                .methods
+                .iter()
+                .filter(|(_, meta)| meta.input_schema.is_some())
+                .map(move |(method, _)| (mod_name, method))
+        })
+        .collect();
+    methods.sort();
+
+    let mut names = BTreeMap::new();
+    for (mod_name, method) in methods {
+        let base = tool_name(mod_name, method);
+        let mut name = base.clone();
+        let mut suffix = 2;
+        while names.contains_key(&name) {
+            name = format!("{base}_{suffix}");
+            suffix += 1;
+        }
+        names.insert(name, (mod_name.clone(), method.clone()));
+    }
+    names
+}
+
+/// Returns a tool definition for every registered method that declares an input schema.
+pub(super) fn list(registry: &RpcRegistry) -> Vec<Tool> {
+    tool_names(registry)
+        .into_iter()
+        .filter_map(|(name, (mod_name, method))| {
+            let meta = registry.get(&mod_name)?.methods.get(&method)?;
+            to_tool(name, &mod_name, &method, meta)
+        })
+        .collect()
+}
+
+/// Looks up the dynamic tool called `name`.
+pub(super) fn get(registry: &RpcRegistry, name: &str) -> Option<Tool> {
+    let (mod_name, method) = resolve(registry, name)?;
+    let meta = registry.get(&mod_name)?.methods.get(&method)?;
+    to_tool(name.to_string(), &mod_name, &method, meta)
+}
+
+/// Maps a dynamic tool name back to its `(mod_name, method)` pair.
+///
+/// Names are matched against the registry rather than split on the separator,
+/// because sanitization is lossy for scoped package names.
+pub(super) fn resolve(registry: &RpcRegistry, name: &str) -> Option<(String, String)> {
+    if !name.contains(SEPARATOR) {
+        return None;
+    }
+    tool_names(registry).remove(name)
+}
+
+/// Formats an RPC error for the agent, listing schema violations one per line.
+fn format_rpc_error(e: &rpc::RpcError) -> String {
+    let mut text = format!("Error: {e}");
+    for violation in e.violations().unwrap_or_default() {
+        let pointer = if violation.pointer.is_empty() {
+            "/"
+        } else {
+            violation.pointer.as_str()
+        };
+        text.push_str(&format!("\n- {pointer}: {}", violation.message));
+    }
+    text
+}
+
+fn to_tool(name: String, mod_name: &str, method: &str, meta: &RpcMethodMeta) -> Option<Tool> {
+    let input_schema = meta.input_schema.clone()?;
+    let description = meta
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Call the '{method}' RPC method of the '{mod_name}' MOD."));
+    Some(Tool::new(
+        Cow::Owned(name),
+        Cow::Owned(description),
+        Arc::new(input_schema),
+    ))
+}
+
+impl HomunculusMcpHandler {
+    /// Proxies a dynamic RPC tool call to the owning MOD service.
+    pub(super) async fn call_rpc_tool(
+        &self,
+        mod_name: &str,
+        method: &str,
+        request: CallToolRequestParams,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let body = request.arguments.map(serde_json::Value::Object);
+        let validate_output = self.config.rpc.validate_output.unwrap_or(false);
+        match rpc::call(&self.rpc_registry, mod_name, method, body, validate_output).await {
+            Ok(response) if response.is_success() => {
+                Ok(CallToolResult::success(vec![Content::text(
+                    response.text(),
+                )]))
+            }
+            Ok(response) => Ok(CallToolResult::error(vec![Content::text(format!(
+                "Error: Mod '{mod_name}' method '{method}' returned status {}: {}",
+                response.status,
+                response.text()
+            ))])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(
+                format_rpc_error(&e),
+            )])),
+        }
+    }
+
+    /// Forwards RPC registry changes to the client as `tools/list_changed`.
+    ///
+    /// Runs until the client disconnects.
+    pub(super) fn spawn_tool_list_watcher(&self, context: NotificationContext<RoleServer>) {
+        let Ok(mut changes) = self.rpc_registry.read().map(|reg| reg.subscribe()) else {
+            bevy::log::warn!("RPC registry lock poisoned; tool list changes will not be sent");
+            return;
+        };
+        let peer = context.peer;
+        tokio::spawn(async move {
+            while changes.recv().await.is_ok() {
+                if peer.notify_tool_list_changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn schema() -> serde_json::Map<String, serde_json::Value> {
+        serde_json::json!({
+            "type": "object",
+            "properties": { "text": { "type": "string" } }
+        })
+        .as_object()
+        .cloned()
+        .unwrap()
+    }
+
+    fn registry() -> RpcRegistry {
+        let mut reg = RpcRegistry::default();
+        reg.register(
+            "@hmcs/voicevox".to_string(),
+            4000,
+            HashMap::from([
+                (
+                    "speak".to_string(),
+                    RpcMethodMeta {
+                        description: Some("Speak text".to_string()),
+                        input_schema: Some(schema()),
+                        ..Default::default()
+                    },
+                ),
+                ("internal".to_string(), RpcMethodMeta::default()),
+            ]),
+        )
+        .unwrap();
+        reg
+    }
+
+    #[test]
+    fn tool_name_sanitizes_scoped_packages() {
+        assert_eq!(tool_name("@hmcs/voicevox", "speak"), "hmcs_voicevox__speak");
+        assert_eq!(tool_name("visual", "show"), "visual__show");
+    }
+
+    #[test]
+    fn list_only_includes_methods_with_input_schema() {
+        let tools = list(&registry());
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "hmcs_voicevox__speak");
+        assert_eq!(tools[0].description.as_deref(), Some("Speak text"));
+        assert_eq!(tools[0].input_schema.get("type"), schema().get("type"));
+    }
+
+    #[test]
+    fn resolve_maps_tool_name_back_to_mod_and_method() {
+        let reg = registry();
+        assert_eq!(
+            resolve(&reg, "hmcs_voicevox__speak"),
+            Some(("@hmcs/voicevox".to_string(), "speak".to_string()))
+        );
+        assert_eq!(resolve(&reg, "hmcs_voicevox__internal"), None);
+        assert_eq!(resolve(&reg, "play_animation"), None);
+    }
+
+    #[test]
+    fn colliding_tool_names_get_a_suffix() {
+        let method = || RpcMethodMeta {
+            input_schema: Some(schema()),
+            ..Default::default()
+        };
+        let mut reg = RpcRegistry::default();
+        reg.register(
+            "visual".to_string(),
+            4000,
+            HashMap::from([("a.b".to_string(), method()), ("a_b".to_string(), method())]),
+        )
+        .unwrap();
+        let names: Vec<_> = list(&reg).into_iter().map(|tool| tool.name).collect();
+        assert_eq!(names, vec!["visual__a_b", "visual__a_b_2"]);
+        assert_eq!(
+            resolve(&reg, "visual__a_b"),
+            Some(("visual".to_string(), "a.b".to_string()))
+        );
+        assert_eq!(
+            resolve(&reg, "visual__a_b_2"),
+            Some(("visual".to_string(), "a_b".to_string()))
+        );
+    }
+
+    #[test]
+    fn get_falls_back_to_generated_description() {
+        let mut reg = RpcRegistry::default();
+        reg.register(
+            "visual".to_string(),
+            4000,
+            HashMap::from([(
+                "show".to_string(),
+                RpcMethodMeta {
+                    input_schema: Some(schema()),
+                    ..Default::default()
+                },
+            )]),
+        )
+        .unwrap();
+        let tool = get(&reg, "visual__show").expect("tool should exist");
+        assert_eq!(
+            tool.description.as_deref(),
+            Some("Call the 'show' RPC method of the 'visual' MOD.")
+        );
+    }
+}
@@ -3,6 +3,7 @@
 use super::super::HomunculusMcpHandler;
 use bevy::animation::RepeatAnimation;
 use bevy_vrm1::prelude::PlayVrma;
+use homunculus_api::vrm::{Gesture, PerformGesture};
 use homunculus_utils::schema::asset::AssetId;
 use rmcp::handler::server::wrapper::Parameters;
 use rmcp::schemars;
@@ -27,12 +28,26 @@ pub struct PlayAnimationParams {
     pub reset_spring_bones: Option<bool>,
 }
 
+/// Parameters for the `perform_gesture` tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformGestureParams {
+    /// Gesture: "nod", "headShake", "shrug", "wave", "bow", "jump" or "tilt".
+    pub gesture: String,
+    /// How pronounced the gesture is, 0.0-2.0 (default: 1.0).
+    pub intensity: Option<f32>,
+    /// Length of the gesture in milliseconds, 100-10000 (default: depends on the gesture).
+    pub duration_ms: Option<u64>,
+    /// Whether to wait for the gesture to finish before returning (default: false).
+    pub wait: Option<bool>,
+}
+
 #[rmcp::tool_router(router = animation_tool_router, vis = "pub(super)")]
 impl HomunculusMcpHandler {
     /// Play a VRMA animation on the active character.
     #[tool(
         name = "play_animation",
-        description = "Play a VRMA animation on the active character. Use the homunculus://assets resource to discover available VRMA animations.",
+        description = "Play a VRMA animation on the active character. Use the homunculus://animations resource to discover available VRMA animations, their tags, duration and whether they loop.",
         annotations(destructive_hint = false, open_world_hint = false)
     )]
     async fn play_animation(&self, params: Parameters<PlayAnimationParams>) -> String {
@@ -82,4 +97,41 @@ impl HomunculusMcpHandler {
             Err(e) => format!("Error playing animation: {e}"),
         }
     }
+
+    /// Perform a procedural gesture on the active character.
+    #[tool(
+        name = "perform_gesture",
+        description = "Perform a short gesture on the active character without a VRMA asset: nod (agree), headShake (disagree), shrug (unsure), wave (greet), bow (thank or apologize), jump (excitement) or tilt (curious). Layered over the playing animation.",
+        annotations(destructive_hint = false, open_world_hint = false)
+    )]
+    async fn perform_gesture(&self, params: Parameters<PerformGestureParams>) -> String {
+        let args = params.0;
+
+        let character = match self.resolve_character().await {
+            Ok(e) => e,
+            Err(e) => return format!("Error: {e}"),
+        };
+
+        let name = serde_json::Value::String(args.gesture.clone());
+        let Ok(gesture) = serde_json::from_value::<Gesture>(name) else {
+            return format!(
+                "Unknown gesture '{}'. Use nod, headShake, shrug, wave, bow, jump or tilt.",
+                args.gesture
+            );
+        };
+        let perform = PerformGesture {
+            intensity: args.intensity.unwrap_or(1.0),
+            duration_ms: args.duration_ms,
+            wait: args.wait.unwrap_or(false),
+        };
+
+        match self
+            .vrm_api
+            .perform_gesture(character, gesture, perform)
+            .await
+        {
+            Ok(()) => format!("Performing gesture '{}'", args.gesture),
+            Err(e) => format!("Error performing gesture: {e}"),
+        }
+    }
 }
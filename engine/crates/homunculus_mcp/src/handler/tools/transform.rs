@@ -2,10 +2,12 @@
 
 use bevy::math::{Quat, Vec2, Vec3};
 use homunculus_api::entities::MoveTarget;
+use homunculus_api::entities::path_tween::{PathCurve, PathSpace, TweenPathArgs};
 use homunculus_api::entities::tween::{
     EasingFunction, TweenPositionViewportArgs, TweenRotationArgs, TweenRotationAxisArgs,
     TweenScaleArgs,
 };
+use homunculus_api::prelude::Repeat;
 use rmcp::handler::server::wrapper::Parameters;
 use rmcp::schemars;
 use rmcp::schemars::JsonSchema;
@@ -94,6 +96,31 @@ pub struct SpinCharacterParams {
     pub wait: Option<bool>,
 }
 
+/// Parameters for the `tween_path` tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TweenPathParams {
+    /// Path points as [x, y] viewport pixels (0,0 = top-left of primary monitor).
+    pub points: Vec<[f32; 2]>,
+    /// Path shape: "polyline" (default), "catmullRom" (smooth curve through the points) or
+    /// "bezier" (cubic segments: anchor, control, control, anchor, ...; needs 3n+1 points).
+    pub curve: Option<String>,
+    /// Start from the character's current position instead of the first point (default: false).
+    pub from_current: Option<bool>,
+    /// Duration of one pass along the path in milliseconds.
+    pub duration_ms: u64,
+    /// Easing function name (default: "linear"). See EasingFunction for available options.
+    pub easing: Option<String>,
+    /// Turn the character toward the direction of travel (default: false).
+    pub orient_to_path: Option<bool>,
+    /// Number of passes along the path; 0 repeats forever (default: 1).
+    pub passes: Option<u32>,
+    /// Travel back along the path on every other pass (default: false).
+    pub ping_pong: Option<bool>,
+    /// Whether to wait for the last pass to finish before returning (default: false).
+    pub wait: Option<bool>,
+}
+
 /// Parses an easing function name string into an [`EasingFunction`].
 ///
 /// Uses serde JSON deserialization to reuse the rename mappings defined on
@@ -107,6 +134,15 @@ fn parse_easing(easing: &Option<String>) -> EasingFunction {
     }
 }
 
+/// Parses a path curve name, falling back to [`PathCurve::Polyline`].
+fn parse_curve(curve: &Option<String>) -> PathCurve {
+    match curve {
+        Some(s) => serde_json::from_value::<PathCurve>(serde_json::Value::String(s.clone()))
+            .unwrap_or_default(),
+        None => PathCurve::default(),
+    }
+}
+
 #[rmcp::tool_router(router = transform_tool_router, vis = "pub(super)")]
 impl HomunculusMcpHandler {
     /// Move the active character to a screen position.
@@ -276,4 +312,43 @@ impl HomunculusMcpHandler {
             Err(e) => format!("Error spinning character: {e}"),
         }
     }
+
+    /// Move the active character along a path of screen positions.
+    #[tool(
+        name = "tween_path",
+        description = "Move the active character along a path of screen positions at a constant speed. Use \"catmullRom\" for a smooth route through the points or \"bezier\" for arcs such as a hop. Supports looping, ping-pong and turning toward the direction of travel. Coordinates are in viewport pixels (0,0 = top-left of primary monitor).",
+        annotations(destructive_hint = false, open_world_hint = false)
+    )]
+    async fn tween_path(&self, params: Parameters<TweenPathParams>) -> String {
+        let args = params.0;
+
+        let entity = match self.resolve_character().await {
+            Ok(e) => e,
+            Err(e) => return format!("Error: {e}"),
+        };
+
+        let repeat = match args.passes.unwrap_or(1) {
+            0 => Repeat::Forever,
+            1 => Repeat::Never,
+            count => Repeat::Count { count },
+        };
+        let tween_args = TweenPathArgs {
+            points: args.points.iter().map(|p| Vec2::from_array(*p)).collect(),
+            curve: parse_curve(&args.curve),
+            space: PathSpace::Viewport,
+            z: None,
+            from_current: args.from_current.unwrap_or(false),
+            duration_ms: args.duration_ms,
+            easing: parse_easing(&args.easing),
+            orient_to_path: args.orient_to_path.unwrap_or(false),
+            repeat,
+            ping_pong: args.ping_pong.unwrap_or(false),
+            wait: args.wait.unwrap_or(false),
+        };
+
+        match self.entities_api.tween_path(entity, tween_args).await {
+            Ok(()) => format!("Moving character along {} points", args.points.len()),
+            Err(e) => format!("Error tweening along path: {e}"),
+        }
+    }
 }
@@ -2,8 +2,11 @@
 
 use super::super::HomunculusMcpHandler;
 use bevy::math::Vec2;
+use homunculus_api::emotion::EmotionArgs;
 use homunculus_api::entities::MoveTarget;
 use homunculus_api::persona::CreatePersona;
+use homunculus_api::vrm::{ApplyExpressionPreset, ExpressionTransition, LookAtPoint};
+use homunculus_core::prelude::GlobalViewport;
 use rmcp::handler::server::wrapper::Parameters;
 use rmcp::schemars;
 use rmcp::schemars::JsonSchema;
@@ -53,8 +56,32 @@ pub struct RemoveCharacterParams {
 pub struct SetExpressionParams {
     /// Map of expression names to weight values (0.0-1.0).
     pub expressions: Option<HashMap<String, f32>>,
+    /// Name of a saved expression preset to apply instead of `expressions`.
+    pub preset: Option<String>,
     /// Mode: "modify" (default, partial update), "set" (replace all), or "clear" (reset).
     pub mode: Option<String>,
+    /// Milliseconds to ease into the new weights. Defaults to 0 (instant).
+    pub duration_ms: Option<u64>,
+}
+
+/// Parameters for the `set_emotion` tool.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetEmotionParams {
+    /// Emotion name: "happy", "relaxed", "sad", "angry", "surprised", or "neutral" to clear.
+    pub emotion: Option<String>,
+    /// Pleasantness from -1.0 to 1.0, used instead of `emotion`.
+    pub valence: Option<f32>,
+    /// Activation from -1.0 (calm) to 1.0 (excited), used instead of `emotion`.
+    pub arousal: Option<f32>,
+    /// Strength of the emotion (0.0-1.0). Defaults to 1.0.
+    pub intensity: Option<f32>,
+    /// Seconds to stay at full strength before fading. Defaults to 0.
+    pub hold_secs: Option<f32>,
+    /// Seconds to fade back to neutral. Defaults to 3.
+    pub decay_secs: Option<f32>,
+    /// Keep the emotion until it is changed instead of fading. Defaults to false.
+    pub persist: Option<bool>,
 }
 
 /// Parameters for the `set_persona` tool.
@@ -71,8 +98,12 @@ pub struct SetPersonaParams {
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SetLookAtParams {
-    /// Look-at mode: "cursor" to follow the mouse, or "none" to disable.
+    /// Look-at mode: "cursor" to follow the mouse, "point" to look at a screen position,
+    /// "wander" to gaze idly around, or "none" to disable.
     pub mode: String,
+    /// Screen position `[x, y]` in global viewport coordinates, for the "point" mode.
+    #[serde(default)]
+    pub point: Option<[f32; 2]>,
 }
 
 #[rmcp::tool_router(router = vrm_tool_router, vis = "pub(super)")]
@@ -216,7 +247,7 @@ impl HomunculusMcpHandler {
     /// Set facial expression weights on the active character.
     #[tool(
         name = "set_expression",
-        description = "Set facial expression weights on the active character. Common expressions: happy, sad, angry, surprised, relaxed, neutral, aa, ih, ou, ee, oh, blink. Weights are 0.0-1.0. Modes: \"modify\" (default, partial update), \"set\" (replace all), \"clear\" (reset to animation control).",
+        description = "Set facial expression weights on the active character. Common expressions: happy, sad, angry, surprised, relaxed, neutral, aa, ih, ou, ee, oh, blink. Weights are 0.0-1.0. Modes: \"modify\" (default, partial update), \"set\" (replace all), \"clear\" (reset to animation control). Pass preset to apply a saved expression preset, and duration_ms to ease into the change instead of snapping.",
         annotations(
             destructive_hint = false,
             idempotent_hint = true,
@@ -231,23 +262,55 @@ impl HomunculusMcpHandler {
 
         let args = params.0;
         let mode = args.mode.as_deref().unwrap_or("modify");
+        let transition = ExpressionTransition {
+            duration_ms: args.duration_ms.unwrap_or(0),
+            ..Default::default()
+        };
+
+        if let Some(preset) = args.preset {
+            let apply = ApplyExpressionPreset {
+                duration_ms: transition.duration_ms,
+                easing: transition.easing,
+                replace: mode == "set",
+            };
+            return match self
+                .vrm_api
+                .apply_expression_preset(entity, preset.clone(), apply)
+                .await
+            {
+                Ok(()) => format!("Applied expression preset '{preset}'."),
+                Err(e) => format!("Error applying expression preset: {e}"),
+            };
+        }
 
         match mode {
             "set" => {
                 let expressions = args.expressions.unwrap_or_default();
-                match self.vrm_api.set_expressions(entity, expressions).await {
+                match self
+                    .vrm_api
+                    .set_expressions_with(entity, expressions, transition)
+                    .await
+                {
                     Ok(()) => "Expressions set.".to_string(),
                     Err(e) => format!("Error setting expressions: {e}"),
                 }
             }
             "modify" => {
                 let expressions = args.expressions.unwrap_or_default();
-                match self.vrm_api.modify_expressions(entity, expressions).await {
+                match self
+                    .vrm_api
+                    .modify_expressions_with(entity, expressions, transition)
+                    .await
+                {
                     Ok(()) => "Expressions modified.".to_string(),
                     Err(e) => format!("Error modifying expressions: {e}"),
                 }
             }
-            "clear" => match self.vrm_api.clear_expressions(entity).await {
+            "clear" => match self
+                .vrm_api
+                .clear_expressions_with(entity, transition)
+                .await
+            {
                 Ok(()) => "Expressions cleared.".to_string(),
                 Err(e) => format!("Error clearing expressions: {e}"),
             },
@@ -290,7 +353,7 @@ impl HomunculusMcpHandler {
     /// Control where the active character looks.
     #[tool(
         name = "set_look_at",
-        description = "Control where the active character looks. Use \"cursor\" to follow the mouse cursor, or \"none\" to disable look-at (character looks forward).",
+        description = "Control where the active character looks. Use \"cursor\" to follow the mouse cursor, \"point\" with `point` to look at a screen position (e.g. where a notification appears), \"wander\" to gaze idly around, or \"none\" to disable look-at (character looks forward).",
         annotations(
             destructive_hint = false,
             idempotent_hint = true,
@@ -309,12 +372,28 @@ impl HomunculusMcpHandler {
                 Ok(()) => format!("Set look-at mode to '{mode}'"),
                 Err(e) => format!("Error setting look-at: {e}"),
             },
+            "point" => {
+                let Some(point) = params.0.point else {
+                    return "Error: mode \"point\" requires `point`".to_string();
+                };
+                let point = LookAtPoint::Viewport(GlobalViewport(Vec2::from_array(point)));
+                match self.vrm_api.look_at_point(entity, point).await {
+                    Ok(()) => format!("Set look-at mode to '{mode}'"),
+                    Err(e) => format!("Error setting look-at: {e}"),
+                }
+            }
+            "wander" => match self.vrm_api.look_wander(entity).await {
+                Ok(()) => format!("Set look-at mode to '{mode}'"),
+                Err(e) => format!("Error setting look-at: {e}"),
+            },
             "none" => match self.vrm_api.unlook(entity).await {
                 Ok(()) => format!("Set look-at mode to '{mode}'"),
                 Err(e) => format!("Error setting look-at: {e}"),
             },
             other => {
-                format!("Unknown look-at mode '{other}'. Use \"cursor\" or \"none\".")
+                format!(
+                    "Unknown look-at mode '{other}'. Use \"cursor\", \"point\", \"wander\" or \"none\"."
+                )
             }
         }
     }
@@ -3,9 +3,10 @@
 //! Provides [`create_mcp_service`], which builds a [`StreamableHttpService`]
 //! suitable for mounting on the engine's Axum router via `nest_service`.
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use homunculus_api::prelude::ApiReactor;
+use homunculus_core::rpc_registry::RpcRegistry;
 use homunculus_utils::config::HomunculusConfig;
 use homunculus_utils::runtime::RuntimeResolver;
 use rmcp::transport::streamable_http_server::{
@@ -16,6 +17,8 @@ use crate::handler::HomunculusMcpHandler;
 
 /// Creates a [`StreamableHttpService`] backed by the given [`ApiReactor`].
 ///
+/// MOD RPC methods in `rpc_registry` are exposed as additional tools.
+///
 /// The returned service implements `tower::Service<Request<B>>` and can be
 /// mounted on an Axum router with `nest_service`.
 ///
@@ -25,6 +28,7 @@ pub fn create_mcp_service(
     reactor: ApiReactor,
     config: HomunculusConfig,
     runtime: RuntimeResolver,
+    rpc_registry: Arc<RwLock<RpcRegistry>>,
 ) -> StreamableHttpService<HomunculusMcpHandler, LocalSessionManager> {
     let server_config = StreamableHttpServerConfig::default();
     let session_manager = Arc::new(LocalSessionManager {
@@ -37,6 +41,7 @@ pub fn create_mcp_service(
                 reactor.clone(),
                 config.clone(),
                 runtime.clone(),
+                rpc_registry.clone(),
             ))
         },
         session_manager,
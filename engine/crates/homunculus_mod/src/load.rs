@@ -4,7 +4,7 @@ use crate::mod_service::ModService;
 use bevy::prelude::*;
 use homunculus_core::prelude::{
     AssetEntry, AssetId, AssetRegistry, HomunculusConfig, ModInfo, ModMenuMetadata,
-    ModMenuMetadataList, ModRegistry, create_dir_all_if_need,
+    ModMenuMetadataList, ModRegistry, create_dir_all_if_need, read_vrma_metadata,
 };
 use homunculus_utils::runtime::RuntimeResolver;
 
@@ -63,12 +63,16 @@ fn schedule_service(info: &ModInfo, commands: &mut Commands, mods_dir: &Path) {
 
 fn load_assets(info: &ModInfo, registry: &mut ResMut<AssetRegistry>) {
     for (asset_id, decl) in &info.assets {
+        let absolute_path = info.mod_dir.join(&decl.path);
         registry.register(AssetEntry {
             id: AssetId::new(asset_id.clone()),
             path: PathBuf::from(&decl.path),
-            absolute_path: info.mod_dir.join(&decl.path),
+            vrma: read_vrma_metadata(&decl.asset_type, &absolute_path),
+            license: None,
+            absolute_path,
             asset_type: decl.asset_type.clone(),
             description: decl.description.clone(),
+            tags: decl.tags.clone(),
             mod_name: info.name.clone(),
         });
     }
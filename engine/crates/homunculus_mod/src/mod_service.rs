@@ -87,8 +87,10 @@ pub fn append_pid_file(pid: u32) {
 /// Pre-registers the port in the RPC registry so the HTTP proxy can route
 /// requests even before the MOD service calls back to register its methods.
 fn pre_register_rpc_port(rpc_registry: &SharedRpcRegistry, mod_name: &str, rpc_port: u16) {
-    if let Ok(mut reg) = rpc_registry.write() {
-        reg.register(mod_name.to_string(), rpc_port, Default::default());
+    if let Ok(mut reg) = rpc_registry.write()
+        && let Err(e) = reg.register(mod_name.to_string(), rpc_port, Default::default())
+    {
+        error!("Failed to pre-register RPC port for mod '{mod_name}': {e}");
     }
 }
 
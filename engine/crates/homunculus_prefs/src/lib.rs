@@ -41,6 +41,7 @@ use homunculus_core::prelude::{Gender, Persona, PersonaId};
 use std::collections::HashMap;
 
 use homunculus_utils::path::homunculus_dir;
+use homunculus_utils::schema::vrm::VrmLicense;
 pub use rusqlite::types::Value as SqlValue;
 
 use serde::Serialize;
@@ -87,6 +88,72 @@ impl PrefsKeys {
         format!("transform::{asset_id}")
     }
 
+    /// Preferences key for a VRM's emotion-to-expression mapping, keyed by asset ID.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use homunculus_prefs::PrefsKeys;
+    /// assert_eq!(PrefsKeys::emotion_mapping("vrm:elmer"), "emotion_mapping::vrm:elmer");
+    /// ```
+    pub fn emotion_mapping(asset_id: &str) -> String {
+        format!("emotion_mapping::{asset_id}")
+    }
+
+    /// Preferences key for a persona's named expression presets, keyed by persona ID.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use homunculus_prefs::PrefsKeys;
+    /// assert_eq!(PrefsKeys::expression_presets("elmer"), "expression_presets::elmer");
+    /// ```
+    pub fn expression_presets(persona_id: &str) -> String {
+        format!("expression_presets::{persona_id}")
+    }
+
+    /// Preferences key for a persona's auto-blink and micro-expression settings.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use homunculus_prefs::PrefsKeys;
+    /// assert_eq!(PrefsKeys::auto_expressions("elmer"), "auto_expressions::elmer");
+    /// ```
+    pub fn auto_expressions(persona_id: &str) -> String {
+        format!("auto_expressions::{persona_id}")
+    }
+
+    /// Preferences key for a persona's named poses, keyed by persona ID.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use homunculus_prefs::PrefsKeys;
+    /// assert_eq!(PrefsKeys::pose_presets("elmer"), "pose_presets::elmer");
+    /// ```
+    pub fn pose_presets(persona_id: &str) -> String {
+        format!("pose_presets::{persona_id}")
+    }
+
+    /// Preferences key for a persona's spring bone overrides on one VRM asset.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use homunculus_prefs::PrefsKeys;
+    /// assert_eq!(
+    ///     PrefsKeys::spring_bones("elmer", "vrm:elmer"),
+    ///     "spring_bones::elmer::vrm:elmer"
+    /// );
+    /// ```
+    pub fn spring_bones(persona_id: &str, asset_id: &str) -> String {
+        format!("spring_bones::{persona_id}::{asset_id}")
+    }
+
+    /// Preferences key for the spring bone wind and reaction settings.
+    pub const SPRING_BONE_SETTINGS: &'static str = "spring_bones::settings";
+
     /// Preferences key for the shadow panel's alpha (opacity) value.
     pub const SHADOW_PANEL_ALPHA: &'static str = "shadow_panel::alpha";
 }
@@ -105,6 +172,28 @@ pub struct ImportedAsset {
     pub description: Option<String>,
     pub source_path: Option<String>,
     pub created_at: Option<String>,
+    /// License meta read from the file, for VRM models.
+    pub license: Option<VrmLicense>,
+}
+
+/// A signal retention policy stored in the `signal_retention` table.
+///
+/// Only signals whose retention is marked as persistent are stored; their
+/// retained messages live in `signal_history`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredSignalRetention {
+    pub signal: String,
+    pub max_messages: Option<u64>,
+    pub max_age_secs: Option<u64>,
+}
+
+/// A retained signal message stored in the `signal_history` table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredSignalMessage {
+    pub seq: u64,
+    /// Unix time in milliseconds.
+    pub timestamp: i64,
+    pub payload: serde_json::Value,
 }
 
 pub struct PrefsDatabase(pub rusqlite::Connection);
@@ -280,10 +369,24 @@ impl PrefsDatabase {
         Ok(())
     }
 
+    /// Stores (or clears with `None`) the license meta of an imported asset.
+    pub fn set_imported_asset_license(
+        &self,
+        id: &str,
+        license: Option<&VrmLicense>,
+    ) -> Result<(), rusqlite::Error> {
+        let license = license.and_then(|license| serde_json::to_string(license).ok());
+        self.0.execute(
+            "UPDATE imported_assets SET license = ?1 WHERE id = ?2",
+            rusqlite::params![license, id],
+        )?;
+        Ok(())
+    }
+
     /// Lists all imported asset records.
     pub fn list_imported_assets(&self) -> Result<Vec<ImportedAsset>, rusqlite::Error> {
         let mut stmt = self.0.prepare(
-            "SELECT id, persona_id, path, type, description, source_path, created_at
+            "SELECT id, persona_id, path, type, description, source_path, created_at, license
              FROM imported_assets",
         )?;
         let rows = stmt.query_map([], |row| {
@@ -295,6 +398,9 @@ impl PrefsDatabase {
                 description: row.get(4)?,
                 source_path: row.get(5)?,
                 created_at: row.get(6)?,
+                license: row
+                    .get::<_, Option<String>>(7)?
+                    .and_then(|json| serde_json::from_str(&json).ok()),
             })
         })?;
         rows.collect()
@@ -309,6 +415,114 @@ impl PrefsDatabase {
         Ok(())
     }
 
+    /// Inserts or updates the persisted retention policy of a signal.
+    pub fn save_signal_retention(
+        &self,
+        retention: &StoredSignalRetention,
+    ) -> Result<(), rusqlite::Error> {
+        self.0.execute(
+            "INSERT OR REPLACE INTO signal_retention (signal, max_messages, max_age_secs)
+             VALUES (?1, ?2, ?3)",
+            rusqlite::params![
+                retention.signal,
+                retention.max_messages.map(|v| v as i64),
+                retention.max_age_secs.map(|v| v as i64),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Lists all persisted signal retention policies.
+    pub fn list_signal_retentions(&self) -> Result<Vec<StoredSignalRetention>, rusqlite::Error> {
+        let mut stmt = self
+            .0
+            .prepare("SELECT signal, max_messages, max_age_secs FROM signal_retention")?;
+        let rows = stmt.query_map([], |row| {
+            let max_messages: Option<i64> = row.get(1)?;
+            let max_age_secs: Option<i64> = row.get(2)?;
+            Ok(StoredSignalRetention {
+                signal: row.get(0)?,
+                max_messages: max_messages.map(|v| v as u64),
+                max_age_secs: max_age_secs.map(|v| v as u64),
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Deletes the retention policy of a signal together with its stored history.
+    ///
+    /// Returns `Ok(())` even if the signal had no persisted retention.
+    pub fn delete_signal_retention(&self, signal: &str) -> Result<(), rusqlite::Error> {
+        self.0
+            .execute("DELETE FROM signal_retention WHERE signal = ?", [signal])?;
+        self.0
+            .execute("DELETE FROM signal_history WHERE signal = ?", [signal])?;
+        Ok(())
+    }
+
+    /// Appends messages to the stored history of a signal in a single transaction.
+    pub fn append_signal_messages(
+        &self,
+        signal: &str,
+        messages: &[StoredSignalMessage],
+    ) -> Result<(), rusqlite::Error> {
+        let tx = self.0.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO signal_history (signal, seq, timestamp, payload)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for message in messages {
+                let payload = serde_json::to_string(&message.payload).unwrap_or_default();
+                stmt.execute(rusqlite::params![
+                    signal,
+                    message.seq as i64,
+                    message.timestamp,
+                    payload
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Loads the stored history of a signal, ordered by sequence number.
+    pub fn load_signal_history(
+        &self,
+        signal: &str,
+    ) -> Result<Vec<StoredSignalMessage>, rusqlite::Error> {
+        let mut stmt = self.0.prepare(
+            "SELECT seq, timestamp, payload FROM signal_history
+             WHERE signal = ? ORDER BY seq",
+        )?;
+        let rows = stmt.query_map([signal], |row| {
+            let seq: i64 = row.get(0)?;
+            let timestamp: i64 = row.get(1)?;
+            let payload: String = row.get(2)?;
+            Ok((seq, timestamp, payload))
+        })?;
+        let mut messages = Vec::new();
+        for row_result in rows {
+            let (seq, timestamp, payload) = row_result?;
+            if let Ok(payload) = serde_json::from_str(&payload) {
+                messages.push(StoredSignalMessage {
+                    seq: seq as u64,
+                    timestamp,
+                    payload,
+                });
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Deletes stored messages of a signal whose sequence number is below `min_seq`.
+    pub fn prune_signal_history(&self, signal: &str, min_seq: u64) -> Result<(), rusqlite::Error> {
+        self.0.execute(
+            "DELETE FROM signal_history WHERE signal = ?1 AND seq < ?2",
+            rusqlite::params![signal, min_seq as i64],
+        )?;
+        Ok(())
+    }
+
     fn log_error(msg: &str) {
         #[cfg(feature = "bevy")]
         error!("{msg}");
@@ -529,6 +743,26 @@ fn create_tables(db: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
         )",
         [],
     )?;
+    // Add license column (idempotent — SQLite ignores if column already exists)
+    let _ = db.execute("ALTER TABLE imported_assets ADD COLUMN license TEXT", []);
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS signal_retention (
+            signal TEXT PRIMARY KEY,
+            max_messages INTEGER,
+            max_age_secs INTEGER
+        )",
+        [],
+    )?;
+    db.execute(
+        "CREATE TABLE IF NOT EXISTS signal_history (
+            signal TEXT NOT NULL,
+            seq INTEGER NOT NULL,
+            timestamp INTEGER NOT NULL,
+            payload TEXT NOT NULL,
+            PRIMARY KEY (signal, seq)
+        )",
+        [],
+    )?;
     Ok(())
 }
 
@@ -1331,6 +1565,26 @@ mod test {
         assert_eq!(assets[0].description.as_deref(), Some("updated"));
     }
 
+    #[test]
+    fn test_imported_asset_license() {
+        let db = PrefsDatabase::open_in_memory();
+        db.upsert_imported_asset("asset-1", None, "/model.vrm", "vrm", None, None)
+            .unwrap();
+        assert!(db.list_imported_assets().unwrap()[0].license.is_none());
+
+        let license = VrmLicense {
+            name: Some("Elmer".to_string()),
+            allow_redistribution: Some(false),
+            ..Default::default()
+        };
+        db.set_imported_asset_license("asset-1", Some(&license))
+            .unwrap();
+        assert_eq!(db.list_imported_assets().unwrap()[0].license, Some(license));
+
+        db.set_imported_asset_license("asset-1", None).unwrap();
+        assert!(db.list_imported_assets().unwrap()[0].license.is_none());
+    }
+
     #[test]
     fn test_imported_asset_cascade_delete() {
         use homunculus_core::prelude::{Persona, PersonaId};
@@ -1389,4 +1643,59 @@ mod test {
         assert_eq!(assets[0].id, "orphan-asset");
         assert!(assets[0].persona_id.is_none());
     }
+
+    #[test]
+    fn test_signal_retention_round_trip() {
+        let db = PrefsDatabase::open_in_memory();
+        let retention = crate::StoredSignalRetention {
+            signal: "chat".to_string(),
+            max_messages: Some(10),
+            max_age_secs: None,
+        };
+        db.save_signal_retention(&retention).unwrap();
+        assert_eq!(db.list_signal_retentions().unwrap(), vec![retention]);
+    }
+
+    #[test]
+    fn test_signal_history_append_and_prune() {
+        let db = PrefsDatabase::open_in_memory();
+        let messages: Vec<_> = (1..=3)
+            .map(|seq| crate::StoredSignalMessage {
+                seq,
+                timestamp: 1000 + seq as i64,
+                payload: serde_json::json!({"n": seq}),
+            })
+            .collect();
+        db.append_signal_messages("chat", &messages).unwrap();
+        db.prune_signal_history("chat", 2).unwrap();
+        let history = db.load_signal_history("chat").unwrap();
+        assert_eq!(
+            history.iter().map(|m| m.seq).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(history[1].payload, serde_json::json!({"n": 3}));
+    }
+
+    #[test]
+    fn test_delete_signal_retention_removes_history() {
+        let db = PrefsDatabase::open_in_memory();
+        db.save_signal_retention(&crate::StoredSignalRetention {
+            signal: "chat".to_string(),
+            max_messages: None,
+            max_age_secs: Some(60),
+        })
+        .unwrap();
+        db.append_signal_messages(
+            "chat",
+            &[crate::StoredSignalMessage {
+                seq: 1,
+                timestamp: 0,
+                payload: serde_json::json!("hi"),
+            }],
+        )
+        .unwrap();
+        db.delete_signal_retention("chat").unwrap();
+        assert!(db.list_signal_retentions().unwrap().is_empty());
+        assert!(db.load_signal_history("chat").unwrap().is_empty());
+    }
 }
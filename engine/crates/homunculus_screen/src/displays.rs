@@ -22,6 +22,10 @@ pub struct GlobalDisplay {
     pub title: String,
     #[cfg_attr(feature = "openapi", schema(value_type = Object))]
     pub frame: Rect,
+    /// The part of `frame` not covered by the menu bar, dock or taskbar.
+    #[serde(rename = "workArea")]
+    #[cfg_attr(feature = "openapi", schema(value_type = Object))]
+    pub work_area: Rect,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect, Deref)]
@@ -17,18 +17,43 @@ pub fn all_displays() -> Vec<GlobalDisplay> {
             let y = display.frame.origin.y as f32;
             let xo = x + display.frame.size.width as f32;
             let yo = y + display.frame.size.height as f32;
+            let frame = Rect::from_corners(Vec2::new(x, y), Vec2::new(xo, yo));
             GlobalDisplay {
                 id: DisplayId(display.display_id),
                 title: get_display_name(display.display_id),
-                frame: Rect::from_corners(Vec2::new(x, y), Vec2::new(xo, yo)),
+                frame,
+                work_area: get_work_area(display.display_id, frame),
             }
         })
         .collect()
 }
 
-fn get_display_name(display_id: CGDirectDisplayID) -> String {
+/// Shrinks `frame` by the menu bar and dock insets of the matching `NSScreen`.
+///
+/// `frame` is in top-left origin coordinates while `NSScreen` uses bottom-left origin
+/// ones, so only the insets are carried over.
+fn get_work_area(display_id: CGDirectDisplayID, frame: Rect) -> Rect {
+    unsafe {
+        let Some(screen) = find_screen(display_id) else {
+            return frame;
+        };
+        let full = NSScreen::frame(screen);
+        let visible = NSScreen::visibleFrame(screen);
+        let left = (visible.origin.x - full.origin.x) as f32;
+        let bottom = (visible.origin.y - full.origin.y) as f32;
+        let right =
+            ((full.origin.x + full.size.width) - (visible.origin.x + visible.size.width)) as f32;
+        let top =
+            ((full.origin.y + full.size.height) - (visible.origin.y + visible.size.height)) as f32;
+        Rect::from_corners(
+            frame.min + Vec2::new(left, top),
+            frame.max - Vec2::new(right, bottom),
+        )
+    }
+}
+
+fn find_screen(display_id: CGDirectDisplayID) -> Option<id> {
     unsafe {
-        // Get all screens
         let screens: id = NSScreen::screens(nil);
         let count: u64 = msg_send![screens, count];
 
@@ -37,22 +62,29 @@ fn get_display_name(display_id: CGDirectDisplayID) -> String {
             let device_description: id = msg_send![screen, deviceDescription];
             let display_id_number: id = msg_send![device_description, objectForKey: NSString::alloc(nil).init_str("NSScreenNumber")];
             let display_id_number: u32 = msg_send![display_id_number, unsignedIntValue];
-
             if display_id_number == display_id {
-                let localized_name: id = msg_send![screen, localizedName];
-                if localized_name == nil {
-                    return format!("Unknown Display {display_id}");
-                }
-                let name: *const i8 = msg_send![localized_name, UTF8String];
-                if name.is_null() {
-                    return format!("Unknown Display {display_id}");
-                }
-                return std::ffi::CStr::from_ptr(name)
-                    .to_string_lossy()
-                    .into_owned();
+                return Some(screen);
             }
         }
+        None
+    }
+}
 
-        format!("Unknown Display {display_id}")
+fn get_display_name(display_id: CGDirectDisplayID) -> String {
+    unsafe {
+        let Some(screen) = find_screen(display_id) else {
+            return format!("Unknown Display {display_id}");
+        };
+        let localized_name: id = msg_send![screen, localizedName];
+        if localized_name == nil {
+            return format!("Unknown Display {display_id}");
+        }
+        let name: *const i8 = msg_send![localized_name, UTF8String];
+        if name.is_null() {
+            return format!("Unknown Display {display_id}");
+        }
+        std::ffi::CStr::from_ptr(name)
+            .to_string_lossy()
+            .into_owned()
     }
 }
@@ -46,17 +46,21 @@ fn build_display(hmonitor: HMONITOR, index: u32) -> Option<GlobalDisplay> {
     let id = parse_display_id(&device_name).unwrap_or(index + 1000);
     let title = obtain_friendly_name(&device_name).unwrap_or(device_name);
     let scale = monitor_scale_factor(hmonitor);
-    let frame = Rect::from_corners(
-        Vec2::new(rc.left as f32 / scale, rc.top as f32 / scale),
-        Vec2::new(rc.right as f32 / scale, rc.bottom as f32 / scale),
-    );
     Some(GlobalDisplay {
         id: DisplayId(id),
         title,
-        frame,
+        frame: scaled_rect(&rc, scale),
+        work_area: scaled_rect(&info.monitorInfo.rcWork, scale),
     })
 }
 
+fn scaled_rect(rc: &RECT, scale: f32) -> Rect {
+    Rect::from_corners(
+        Vec2::new(rc.left as f32 / scale, rc.top as f32 / scale),
+        Vec2::new(rc.right as f32 / scale, rc.bottom as f32 / scale),
+    )
+}
+
 fn monitor_scale_factor(hmonitor: HMONITOR) -> f32 {
     let mut dpi_x: u32 = 96;
     let mut dpi_y: u32 = 96;
@@ -29,6 +29,22 @@ pub struct SttConfig {
     pub inference_energy_threshold: Option<f32>,
 }
 
+/// MOD service RPC configuration stored in `[rpc]` section of config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RpcConfig {
+    /// Validate MOD responses against each method's declared `outputSchema`.
+    /// Intended for MOD development; a mismatch is reported as a 502. Default: false.
+    pub validate_output: Option<bool>,
+}
+
+/// Session recording configuration stored in `[session]` section of config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SessionConfig {
+    /// Record every HTTP request, response and engine event to this JSONL file,
+    /// for replay with `hmcs replay`. The file is truncated at startup. Default: disabled.
+    pub record_path: Option<PathBuf>,
+}
+
 fn default_mods_dir() -> PathBuf {
     crate::path::mod_dir()
 }
@@ -52,6 +68,14 @@ pub struct HomunculusConfig {
     /// STT (Speech-to-Text) configuration.
     #[serde(default)]
     pub stt: SttConfig,
+
+    /// MOD service RPC configuration.
+    #[serde(default)]
+    pub rpc: RpcConfig,
+
+    /// API session recording configuration.
+    #[serde(default)]
+    pub session: SessionConfig,
 }
 
 impl Default for HomunculusConfig {
@@ -60,6 +84,8 @@ impl Default for HomunculusConfig {
             mods_dir: default_mods_dir(),
             port: default_port(),
             stt: SttConfig::default(),
+            rpc: RpcConfig::default(),
+            session: SessionConfig::default(),
         }
     }
 }
@@ -127,11 +153,16 @@ impl HomunculusConfig {
 /// Applies environment variable overrides to the config.
 ///
 /// - `HMCS_MODS_DIR`: overrides `mods_dir`.
+/// - `HMCS_RECORD_SESSION`: overrides `session.record_path`.
 fn apply_env_overrides(config: &mut HomunculusConfig) {
     if let Ok(dir) = std::env::var("HMCS_MODS_DIR") {
         log::info!("mods_dir overridden by HMCS_MODS_DIR: {dir}");
         config.mods_dir = PathBuf::from(dir);
     }
+    if let Ok(path) = std::env::var("HMCS_RECORD_SESSION") {
+        log::info!("session.record_path overridden by HMCS_RECORD_SESSION: {path}");
+        config.session.record_path = Some(PathBuf::from(path));
+    }
 }
 
 #[cfg(test)]
@@ -246,6 +277,16 @@ mod tests {
         assert_eq!(config.stt.inference_energy_threshold, Some(0.03));
     }
 
+    #[test]
+    fn test_config_with_rpc_section() {
+        let toml_str = r#"
+            [rpc]
+            validate_output = true
+        "#;
+        let config: HomunculusConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.rpc.validate_output, Some(true));
+    }
+
     #[test]
     fn test_config_without_stt_section() {
         let config: HomunculusConfig = toml::from_str("port = 3100").unwrap();
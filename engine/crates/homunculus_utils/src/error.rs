@@ -12,6 +12,8 @@ pub enum UtilError {
     Other(#[from] anyhow::Error),
     #[error("failed to execute child process: {0}")]
     ForkProcess(String),
+    #[error("invalid glTF binary: {0}")]
+    Glb(String),
 }
 
 #[derive(Debug, thiserror::Error)]
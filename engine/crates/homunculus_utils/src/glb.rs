@@ -0,0 +1,231 @@
+//! Minimal reader for binary glTF (`.glb`) files such as VRM and VRMA.
+//!
+//! Only the JSON chunk and the first binary chunk are read; this is enough to inspect
+//! metadata and animation keyframes without loading meshes or textures.
+
+use crate::error::{UtilError, UtilResult};
+use serde_json::Value;
+use std::io::Read;
+use std::path::Path;
+
+const MAGIC: u32 = 0x4654_6C67;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+const COMPONENT_FLOAT: u64 = 5126;
+
+/// The JSON document and binary buffer of a `.glb` file.
+#[derive(Debug, Clone)]
+pub struct Glb {
+    pub json: Value,
+    pub bin: Vec<u8>,
+}
+
+impl Glb {
+    /// Reads and parses a `.glb` file.
+    pub fn read(path: &Path) -> UtilResult<Self> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| UtilError::Glb(format!("failed to read {}: {e}", path.display())))?;
+        Self::parse(&bytes)
+    }
+
+    /// Reads only the JSON chunk of a `.glb` file, skipping its (possibly large) binary data.
+    pub fn read_json(path: &Path) -> UtilResult<Value> {
+        let error =
+            |e: std::io::Error| UtilError::Glb(format!("failed to read {}: {e}", path.display()));
+        let mut file = std::fs::File::open(path).map_err(error)?;
+        let mut header = [0; 20];
+        file.read_exact(&mut header).map_err(error)?;
+        if read_u32(&header, 0) != Some(MAGIC) || read_u32(&header, 16) != Some(CHUNK_JSON) {
+            return Err(UtilError::Glb("not a binary glTF file".to_string()));
+        }
+        // The chunk length comes from the file, so never allocate past its end.
+        let len = u64::from(read_u32(&header, 12).unwrap_or(0));
+        let file_len = file.metadata().map_err(error)?.len();
+        if file_len < 20 + len {
+            return Err(UtilError::Glb("truncated chunk".to_string()));
+        }
+        let mut json = vec![0; len as usize];
+        file.read_exact(&mut json).map_err(error)?;
+        serde_json::from_slice(&json)
+            .map_err(|e| UtilError::Glb(format!("invalid JSON chunk: {e}")))
+    }
+
+    /// Parses the bytes of a `.glb` file.
+    pub fn parse(bytes: &[u8]) -> UtilResult<Self> {
+        if read_u32(bytes, 0) != Some(MAGIC) {
+            return Err(UtilError::Glb("not a binary glTF file".to_string()));
+        }
+        let mut json = None;
+        let mut bin = Vec::new();
+        let mut offset = 12;
+        while let (Some(len), Some(kind)) = (read_u32(bytes, offset), read_u32(bytes, offset + 4)) {
+            let start = offset + 8;
+            let end = start + len as usize;
+            let Some(data) = bytes.get(start..end) else {
+                return Err(UtilError::Glb("truncated chunk".to_string()));
+            };
+            match kind {
+                CHUNK_JSON => {
+                    json = Some(
+                        serde_json::from_slice(data)
+                            .map_err(|e| UtilError::Glb(format!("invalid JSON chunk: {e}")))?,
+                    );
+                }
+                CHUNK_BIN if bin.is_empty() => bin = data.to_vec(),
+                _ => {}
+            }
+            offset = end;
+        }
+        let json = json.ok_or_else(|| UtilError::Glb("missing JSON chunk".to_string()))?;
+        Ok(Self { json, bin })
+    }
+
+    /// Reads a float accessor from the binary buffer, flattened to `count * components`
+    /// values. Returns `None` for sparse, non-float or out-of-range accessors.
+    pub fn accessor_floats(&self, index: usize) -> Option<Vec<f32>> {
+        let accessor = self.json["accessors"].get(index)?;
+        if accessor["componentType"].as_u64()? != COMPONENT_FLOAT {
+            return None;
+        }
+        let components = match accessor["type"].as_str()? {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" => 4,
+            _ => return None,
+        };
+        let count = usize::try_from(accessor["count"].as_u64()?).ok()?;
+        let view = self.json["bufferViews"].get(accessor["bufferView"].as_u64()? as usize)?;
+        if view["buffer"].as_u64().unwrap_or(0) != 0 {
+            return None;
+        }
+        let start = usize_of(&view["byteOffset"]).checked_add(usize_of(&accessor["byteOffset"]))?;
+        let stride = match usize_of(&view["byteStride"]) {
+            0 => components * 4,
+            stride => stride,
+        };
+        // Check the whole range up front so a bogus count cannot size the allocation.
+        if let Some(last) = count.checked_sub(1) {
+            let end = last
+                .checked_mul(stride)?
+                .checked_add(start)?
+                .checked_add(components * 4)?;
+            if self.bin.len() < end {
+                return None;
+            }
+        }
+        let mut values = Vec::with_capacity(count * components);
+        for i in 0..count {
+            for c in 0..components {
+                let at = start + i * stride + c * 4;
+                values.push(f32::from_le_bytes(
+                    self.bin.get(at..at + 4)?.try_into().ok()?,
+                ));
+            }
+        }
+        Some(values)
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn usize_of(value: &Value) -> usize {
+    value.as_u64().unwrap_or(0) as usize
+}
+
+/// Encodes a JSON document and binary buffer as `.glb` bytes.
+#[cfg(test)]
+pub(crate) fn encode(json: &Value, bin: &[u8]) -> Vec<u8> {
+    let mut json = serde_json::to_vec(json).unwrap();
+    json.resize(json.len().next_multiple_of(4), b' ');
+    let mut bin = bin.to_vec();
+    bin.resize(bin.len().next_multiple_of(4), 0);
+    let mut bytes = Vec::new();
+    bytes.extend(MAGIC.to_le_bytes());
+    bytes.extend(2u32.to_le_bytes());
+    bytes.extend(((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+    for (kind, data) in [(CHUNK_JSON, &json), (CHUNK_BIN, &bin)] {
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(kind.to_le_bytes());
+        bytes.extend(data.iter());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reads_float_accessors() {
+        let bin: Vec<u8> = [0.0f32, 0.5, 1.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let doc = json!({
+            "bufferViews": [{"buffer": 0, "byteOffset": 0, "byteLength": 12}],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR"},
+                {"bufferView": 0, "byteOffset": 4, "componentType": 5126, "count": 2,
+                 "type": "SCALAR"},
+            ],
+        });
+        let glb = Glb::parse(&encode(&doc, &bin)).unwrap();
+        assert_eq!(glb.json["accessors"][0]["count"], 3);
+        assert_eq!(glb.accessor_floats(0), Some(vec![0.0, 0.5, 1.0]));
+        assert_eq!(glb.accessor_floats(1), Some(vec![0.5, 1.0]));
+        assert_eq!(glb.accessor_floats(2), None);
+    }
+
+    #[test]
+    fn rejects_accessors_past_the_buffer() {
+        let doc = json!({
+            "bufferViews": [{"buffer": 0, "byteOffset": 0, "byteLength": 4}],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 1_000_000_000_000_000_000u64,
+                 "type": "VEC4"},
+                {"bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR"},
+                {"bufferView": 0, "byteOffset": u64::MAX, "componentType": 5126, "count": 1,
+                 "type": "SCALAR"},
+            ],
+        });
+        let glb = Glb::parse(&encode(&doc, &0.5f32.to_le_bytes())).unwrap();
+        assert_eq!(glb.accessor_floats(0), None);
+        assert_eq!(glb.accessor_floats(1), None);
+        assert_eq!(glb.accessor_floats(2), None);
+    }
+
+    #[test]
+    fn reads_only_the_json_chunk() {
+        let path = std::env::temp_dir().join("homunculus_glb_read_json.glb");
+        std::fs::write(
+            &path,
+            encode(&json!({"asset": {"version": "2.0"}}), &[1, 2, 3]),
+        )
+        .unwrap();
+        let json = Glb::read_json(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(json["asset"]["version"], "2.0");
+    }
+
+    #[test]
+    fn rejects_json_chunks_longer_than_the_file() {
+        let path = std::env::temp_dir().join("homunculus_glb_truncated.glb");
+        let mut bytes = encode(&json!({"asset": {"version": "2.0"}}), &[]);
+        bytes[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, bytes).unwrap();
+        let result = Glb::read_json(&path);
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(Glb::parse(b"{\"asset\":{}}").is_err());
+    }
+}
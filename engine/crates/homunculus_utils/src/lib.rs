@@ -6,11 +6,14 @@
 pub mod config;
 pub mod consts;
 pub mod error;
+pub mod glb;
 pub mod mods;
 pub mod path;
 pub mod process;
 pub mod runtime;
 pub mod schema;
+pub mod vrm;
+pub mod vrma;
 
 pub mod prelude {
     pub use crate::{config::*, consts::*, error::*, path::*, schema::prelude::*};
@@ -1,6 +1,8 @@
 pub mod asset;
 pub mod mods;
+pub mod session;
+pub mod vrm;
 
 pub mod prelude {
-    pub use crate::schema::{asset::*, mods::*};
+    pub use crate::schema::{asset::*, mods::*, session::*, vrm::*};
 }
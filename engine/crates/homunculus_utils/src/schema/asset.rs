@@ -1,3 +1,4 @@
+use crate::schema::vrm::VrmLicense;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -11,6 +12,8 @@ pub enum AssetType {
     Sound,
     Image,
     Html,
+    /// A persona choreography script (JSON, or YAML for `.yaml`/`.yml` files), played with `POST /personas/{id}/timelines/play`.
+    Timeline,
 }
 
 /// A single declared asset entry from a MOD's package.json.
@@ -25,6 +28,24 @@ pub struct AssetDeclaration {
     /// Human-readable description (used by LLM agents for asset selection).
     #[serde(default)]
     pub description: Option<String>,
+    /// Free-form labels for discovery, e.g. `["greeting", "idle"]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Facts about a VRMA animation, read from the file when it is registered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct VrmaMetadata {
+    /// Length of the animation in seconds.
+    pub duration_secs: f32,
+    /// Whether the last frame matches the first, so the animation repeats seamlessly.
+    pub loopable: bool,
+    /// Humanoid bones the animation moves, e.g. `["head", "rightUpperArm"]`.
+    pub bones: Vec<String>,
+    /// Whether the animation moves the hips across the floor.
+    pub root_motion: bool,
 }
 
 /// A resolved asset entry with full path information.
@@ -40,6 +61,12 @@ pub struct AssetEntry {
     pub asset_type: AssetType,
     /// Human-readable description.
     pub description: Option<String>,
+    /// Free-form labels for discovery.
+    pub tags: Vec<String>,
+    /// Animation facts, for VRMA assets that could be read.
+    pub vrma: Option<VrmaMetadata>,
+    /// License meta, for VRM assets that have been imported or attached.
+    pub license: Option<VrmLicense>,
     /// The MOD name this asset belongs to.
     pub mod_name: String,
 }
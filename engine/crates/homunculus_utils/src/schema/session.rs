@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One line of a recorded API session (JSONL).
+///
+/// Written by the engine's session recorder and read back by `hmcs replay`.
+/// A request and its response share the same `id`; events are interleaved in
+/// the order they were emitted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SessionEntry {
+    Request(RecordedRequest),
+    Response(RecordedResponse),
+    /// An engine event emitted while recording, as published on the event bus.
+    Event {
+        timestamp: i64,
+        event: Value,
+    },
+}
+
+/// An HTTP request received by the engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedRequest {
+    pub id: u64,
+    /// Unix time in milliseconds when the request was received.
+    pub timestamp: i64,
+    pub method: String,
+    /// Path and query, e.g. `/signals/chat/history?since=2`.
+    pub uri: String,
+    /// The JSON request body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// `true` if the request had a non-JSON body that was not recorded.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub body_omitted: bool,
+}
+
+/// The engine's response to a [`RecordedRequest`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedResponse {
+    pub id: u64,
+    /// Unix time in milliseconds when the response was produced.
+    pub timestamp: i64,
+    pub status: u16,
+    /// The JSON response body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// `true` if the response had a non-JSON or streaming body that was not recorded.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub body_omitted: bool,
+}
+
+/// Returns the current Unix time in milliseconds.
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_tagged_jsonl() {
+        let entry = SessionEntry::Request(RecordedRequest {
+            id: 1,
+            timestamp: 10,
+            method: "POST".to_string(),
+            uri: "/signals/chat".to_string(),
+            body: Some(serde_json::json!("hi")),
+            body_omitted: false,
+        });
+        let line = serde_json::to_string(&entry).unwrap();
+        assert_eq!(
+            line,
+            r#"{"type":"request","id":1,"timestamp":10,"method":"POST","uri":"/signals/chat","body":"hi"}"#
+        );
+        assert_eq!(serde_json::from_str::<SessionEntry>(&line).unwrap(), entry);
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+/// Report on a VRM file, produced by parsing it without rendering.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct VrmDiagnostics {
+    /// VRM specification version, e.g. `"1.0"` or `"0.0"`. `None` when the file is not a VRM.
+    pub spec_version: Option<String>,
+    /// Which humanoid bones the model maps.
+    pub humanoid: HumanoidCoverage,
+    /// Expressions the model defines, using VRM 1.0 preset names, e.g. `["aa", "blink"]`.
+    pub expressions: Vec<String>,
+    /// Mesh counts and vertex buffer size.
+    pub geometry: GeometryStats,
+    /// Texture counts and sizes.
+    pub textures: TextureStats,
+    /// Material counts by shader.
+    pub materials: MaterialStats,
+    /// Rough GPU memory needed for the geometry and decoded textures, in bytes.
+    pub estimated_memory_bytes: u64,
+    /// Author and usage terms from the VRM meta.
+    pub license: Option<VrmLicense>,
+    /// Problems that keep the model from loading or animating.
+    pub errors: Vec<String>,
+    /// Problems that degrade the model, such as missing lip sync expressions.
+    pub warnings: Vec<String>,
+}
+
+/// Humanoid bone mapping of a VRM.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct HumanoidCoverage {
+    /// Bones mapped to a node, e.g. `["hips", "spine", "head"]`.
+    pub bones: Vec<String>,
+    /// Bones VRM 1.0 requires that the model does not map.
+    pub missing_required: Vec<String>,
+}
+
+/// Mesh statistics of a VRM.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct GeometryStats {
+    pub meshes: u32,
+    pub primitives: u32,
+    pub vertices: u64,
+    pub triangles: u64,
+    /// Size of the vertex, index and morph target data, in bytes.
+    pub bytes: u64,
+}
+
+/// Texture statistics of a VRM.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TextureStats {
+    pub count: u32,
+    /// Width and height of the largest texture, when any could be measured.
+    pub largest: Option<[u32; 2]>,
+    /// Memory of the decoded RGBA textures including mipmaps, in bytes.
+    pub bytes: u64,
+}
+
+/// Material counts of a VRM.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MaterialStats {
+    pub count: u32,
+    pub mtoon: u32,
+    pub unlit: u32,
+}
+
+/// Author and usage terms from a VRM's meta, normalized across VRM 0.x and 1.0.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct VrmLicense {
+    /// Model name.
+    pub name: Option<String>,
+    pub authors: Vec<String>,
+    /// License name from VRM 0.x meta, e.g. `"CC_BY"`. VRM 1.0 names its license by URL.
+    pub license_name: Option<String>,
+    /// URL of the full license text.
+    pub license_url: Option<String>,
+    /// Who may use the avatar, e.g. `"onlyAuthor"`, `"onlySeparatelyLicensedPerson"` or
+    /// `"everyone"`.
+    pub avatar_permission: Option<String>,
+    /// Commercial usage terms, e.g. `"personalNonProfit"` or `"corporation"`.
+    pub commercial_usage: Option<String>,
+    /// Whether the file may be redistributed. `None` when the meta does not say.
+    pub allow_redistribution: Option<bool>,
+    /// Modification terms, e.g. `"prohibited"` or `"allowModification"`.
+    pub modification: Option<String>,
+    /// Whether users must credit the author: `"required"` or `"unnecessary"`.
+    pub credit_notation: Option<String>,
+}
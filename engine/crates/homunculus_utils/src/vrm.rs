@@ -0,0 +1,655 @@
+//! Validation of VRM models, read from their glTF data without rendering them.
+
+use crate::error::UtilResult;
+use crate::glb::Glb;
+use crate::schema::vrm::{
+    GeometryStats, HumanoidCoverage, MaterialStats, TextureStats, VrmDiagnostics, VrmLicense,
+};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Bones every VRM 1.0 humanoid must map.
+const REQUIRED_BONES: [&str; 15] = [
+    "hips",
+    "spine",
+    "head",
+    "leftUpperArm",
+    "leftLowerArm",
+    "leftHand",
+    "rightUpperArm",
+    "rightLowerArm",
+    "rightHand",
+    "leftUpperLeg",
+    "leftLowerLeg",
+    "leftFoot",
+    "rightUpperLeg",
+    "rightLowerLeg",
+    "rightFoot",
+];
+/// Expressions lip sync drives.
+const MOUTH_EXPRESSIONS: [&str; 5] = ["aa", "ih", "ou", "ee", "oh"];
+/// VRM 0.x expression presets and their VRM 1.0 names.
+const PRESETS_0X: [(&str, &str); 17] = [
+    ("a", "aa"),
+    ("i", "ih"),
+    ("u", "ou"),
+    ("e", "ee"),
+    ("o", "oh"),
+    ("joy", "happy"),
+    ("angry", "angry"),
+    ("sorrow", "sad"),
+    ("fun", "relaxed"),
+    ("neutral", "neutral"),
+    ("blink", "blink"),
+    ("blink_l", "blinkLeft"),
+    ("blink_r", "blinkRight"),
+    ("lookup", "lookUp"),
+    ("lookdown", "lookDown"),
+    ("lookleft", "lookLeft"),
+    ("lookright", "lookRight"),
+];
+/// glTF extensions a model cannot load without.
+const UNSUPPORTED_EXTENSIONS: [&str; 4] = [
+    "KHR_draco_mesh_compression",
+    "EXT_meshopt_compression",
+    "KHR_texture_basisu",
+    "EXT_texture_webp",
+];
+/// VRM 0.x shaders that render as intended.
+const SUPPORTED_SHADERS_0X: [&str; 6] = [
+    "VRM/MToon",
+    "VRM/UnlitTexture",
+    "VRM/UnlitCutout",
+    "VRM/UnlitTransparent",
+    "VRM/UnlitTransparentZWrite",
+    "VRM_USE_GLTFSHADER",
+];
+/// Texture side, in pixels, above which a texture is reported as oversized.
+const MAX_TEXTURE_SIZE: u32 = 4096;
+/// Estimated memory, in bytes, above which a model is reported as heavy.
+const MAX_MEMORY_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Reads and validates a `.vrm` file.
+pub fn read_vrm_diagnostics(path: &Path) -> std::io::Result<VrmDiagnostics> {
+    Ok(diagnose_vrm(&std::fs::read(path)?))
+}
+
+/// Reads the license meta of a `.vrm` file, or `None` when the file is not a VRM.
+///
+/// Only the glTF JSON is read, so this is cheap even for large models.
+pub fn read_vrm_license(path: &Path) -> UtilResult<Option<VrmLicense>> {
+    Ok(vrm_license(&Glb::read_json(path)?))
+}
+
+/// The license meta of a VRM's glTF JSON, converting VRM 0.x meta to VRM 1.0 terms.
+pub fn vrm_license(json: &Value) -> Option<VrmLicense> {
+    let extensions = &json["extensions"];
+    if extensions["VRMC_vrm"].is_object() {
+        Some(license_1x(&extensions["VRMC_vrm"]["meta"]))
+    } else if extensions["VRM"].is_object() {
+        Some(license_0x(&extensions["VRM"]["meta"]))
+    } else {
+        None
+    }
+}
+
+/// Validates the bytes of a `.vrm` file.
+///
+/// Never fails: files that cannot be parsed produce a report whose `errors` say why.
+pub fn diagnose_vrm(bytes: &[u8]) -> VrmDiagnostics {
+    let mut report = VrmDiagnostics::default();
+    let glb = match Glb::parse(bytes) {
+        Ok(glb) => glb,
+        Err(e) => {
+            report.errors.push(e.to_string());
+            return report;
+        }
+    };
+    let json = &glb.json;
+    let vrm1 = &json["extensions"]["VRMC_vrm"];
+    let vrm0 = &json["extensions"]["VRM"];
+    report.license = vrm_license(json);
+    let bones = if vrm1.is_object() {
+        report.spec_version = Some(vrm1["specVersion"].as_str().unwrap_or("1.0").to_string());
+        report.expressions = expressions_1x(vrm1);
+        bones_1x(vrm1)
+    } else if vrm0.is_object() {
+        report.spec_version = Some(vrm0["specVersion"].as_str().unwrap_or("0.0").to_string());
+        report.expressions = expressions_0x(vrm0);
+        report.errors.push(
+            "VRM 0.x models are not supported; convert the model to VRM 1.0 with UniVRM"
+                .to_string(),
+        );
+        bones_0x(vrm0)
+    } else {
+        report
+            .errors
+            .push("not a VRM: the file has no VRMC_vrm or VRM extension".to_string());
+        BTreeSet::new()
+    };
+
+    for extension in json["extensionsRequired"].as_array().into_iter().flatten() {
+        if let Some(name) = extension
+            .as_str()
+            .filter(|name| UNSUPPORTED_EXTENSIONS.contains(name))
+        {
+            report
+                .errors
+                .push(format!("requires the unsupported glTF extension {name}"));
+        }
+    }
+    report.geometry = geometry(json);
+    report.textures = textures(&glb, &mut report.warnings);
+    report.materials = materials(json, vrm0, &mut report.warnings);
+    report.estimated_memory_bytes = report.geometry.bytes + report.textures.bytes;
+    if report.spec_version.is_some() {
+        check_humanoid(&mut report, bones);
+        check_expressions(&mut report);
+    }
+    if MAX_MEMORY_BYTES < report.estimated_memory_bytes {
+        report.warnings.push(format!(
+            "the model needs about {} MiB of GPU memory; reduce texture sizes or polygons",
+            report.estimated_memory_bytes / (1024 * 1024)
+        ));
+    }
+    report
+}
+
+fn check_humanoid(report: &mut VrmDiagnostics, bones: BTreeSet<String>) {
+    let missing: Vec<String> = REQUIRED_BONES
+        .iter()
+        .filter(|bone| !bones.contains(**bone))
+        .map(|bone| bone.to_string())
+        .collect();
+    if !missing.is_empty() {
+        report.errors.push(format!(
+            "missing required humanoid bones: {}",
+            missing.join(", ")
+        ));
+    }
+    report.humanoid = HumanoidCoverage {
+        bones: bones.into_iter().collect(),
+        missing_required: missing,
+    };
+}
+
+fn check_expressions(report: &mut VrmDiagnostics) {
+    let has = |name: &str| report.expressions.iter().any(|e| e == name);
+    let missing_mouth: Vec<&str> = MOUTH_EXPRESSIONS
+        .into_iter()
+        .filter(|name| !has(name))
+        .collect();
+    let blink = has("blink");
+    if missing_mouth.len() == MOUTH_EXPRESSIONS.len() {
+        report.warnings.push(
+            "no lip sync expressions (aa, ih, ou, ee, oh); the mouth will not move while speaking"
+                .to_string(),
+        );
+    } else if !missing_mouth.is_empty() {
+        report.warnings.push(format!(
+            "missing lip sync expressions: {}",
+            missing_mouth.join(", ")
+        ));
+    }
+    if !blink {
+        report
+            .warnings
+            .push("no blink expression; the model will not blink".to_string());
+    }
+}
+
+fn bones_1x(vrm: &Value) -> BTreeSet<String> {
+    vrm["humanoid"]["humanBones"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(_, bone)| bone["node"].is_u64())
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Bone names of a VRM 0.x humanoid, renamed to VRM 1.0 where the thumbs differ.
+fn bones_0x(vrm: &Value) -> BTreeSet<String> {
+    vrm["humanoid"]["humanBones"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|bone| bone["node"].is_u64())
+        .filter_map(|bone| bone["bone"].as_str())
+        .map(|name| {
+            if let Some(side) = name.strip_suffix("ThumbProximal") {
+                format!("{side}ThumbMetacarpal")
+            } else if let Some(side) = name.strip_suffix("ThumbIntermediate") {
+                format!("{side}ThumbProximal")
+            } else {
+                name.to_string()
+            }
+        })
+        .collect()
+}
+
+fn expressions_1x(vrm: &Value) -> Vec<String> {
+    let expressions = &vrm["expressions"];
+    ["preset", "custom"]
+        .into_iter()
+        .filter_map(|group| expressions[group].as_object())
+        .flat_map(|group| group.keys().cloned())
+        .collect()
+}
+
+fn expressions_0x(vrm: &Value) -> Vec<String> {
+    vrm["blendShapeMaster"]["blendShapeGroups"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|group| {
+            let preset = group["presetName"].as_str().unwrap_or("unknown");
+            PRESETS_0X
+                .iter()
+                .find(|(name, _)| *name == preset)
+                .map(|(_, name)| name.to_string())
+                .or_else(|| group["name"].as_str().map(String::from))
+        })
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn license_1x(meta: &Value) -> VrmLicense {
+    VrmLicense {
+        name: text(&meta["name"]),
+        authors: meta["authors"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(text)
+            .collect(),
+        license_name: None,
+        license_url: text(&meta["licenseUrl"]),
+        avatar_permission: text(&meta["avatarPermission"]),
+        commercial_usage: text(&meta["commercialUsage"]),
+        allow_redistribution: meta["allowRedistribution"].as_bool(),
+        modification: text(&meta["modification"]),
+        credit_notation: text(&meta["creditNotation"]),
+    }
+}
+
+/// Converts VRM 0.x meta to VRM 1.0 terms, deriving redistribution, modification and
+/// credit from the license name.
+fn license_0x(meta: &Value) -> VrmLicense {
+    let license_name = text(&meta["licenseName"]);
+    let (allow_redistribution, modification) = match license_name.as_deref() {
+        Some("Redistribution_Prohibited") => (Some(false), Some("prohibited")),
+        Some("CC_BY_ND" | "CC_BY_NC_ND") => (Some(true), Some("prohibited")),
+        Some("CC0" | "CC_BY" | "CC_BY_NC" | "CC_BY_SA" | "CC_BY_NC_SA") => {
+            (Some(true), Some("allowModificationRedistribution"))
+        }
+        _ => (None, None),
+    };
+    let credit_notation = match license_name.as_deref() {
+        Some("CC0") => Some("unnecessary"),
+        Some(name) if name.starts_with("CC_BY") => Some("required"),
+        _ => None,
+    };
+    VrmLicense {
+        name: text(&meta["title"]),
+        authors: text(&meta["author"]).into_iter().collect(),
+        license_name,
+        license_url: text(&meta["otherLicenseUrl"]),
+        avatar_permission: meta["allowedUserName"]
+            .as_str()
+            .and_then(|user| match user {
+                "OnlyAuthor" => Some("onlyAuthor".to_string()),
+                "ExplicitlyLicensedPerson" => Some("onlySeparatelyLicensedPerson".to_string()),
+                "Everyone" => Some("everyone".to_string()),
+                _ => None,
+            }),
+        commercial_usage: meta["commercialUssageName"]
+            .as_str()
+            .and_then(|usage| match usage {
+                "Disallow" => Some("personalNonProfit".to_string()),
+                "Allow" => Some("corporation".to_string()),
+                _ => None,
+            }),
+        allow_redistribution,
+        modification: modification.map(String::from),
+        credit_notation: credit_notation.map(String::from),
+    }
+}
+
+fn geometry(json: &Value) -> GeometryStats {
+    let accessors = &json["accessors"];
+    let mut stats = GeometryStats::default();
+    for mesh in json["meshes"].as_array().into_iter().flatten() {
+        stats.meshes += 1;
+        for primitive in mesh["primitives"].as_array().into_iter().flatten() {
+            stats.primitives += 1;
+            let vertices = count(&accessors[index(&primitive["attributes"]["POSITION"])]);
+            let corners = match primitive["indices"].as_u64() {
+                Some(i) => count(&accessors[i as usize]),
+                None => vertices,
+            };
+            stats.vertices += vertices;
+            stats.triangles += match primitive["mode"].as_u64().unwrap_or(4) {
+                4 => corners / 3,
+                5 | 6 => corners.saturating_sub(2),
+                _ => 0,
+            };
+        }
+    }
+    let image_views: BTreeSet<u64> = json["images"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|image| image["bufferView"].as_u64())
+        .collect();
+    stats.bytes = json["bufferViews"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter(|(i, _)| !image_views.contains(&(*i as u64)))
+        .map(|(_, view)| view["byteLength"].as_u64().unwrap_or(0))
+        .sum();
+    stats
+}
+
+fn textures(glb: &Glb, warnings: &mut Vec<String>) -> TextureStats {
+    let mut stats = TextureStats::default();
+    for (i, image) in glb.json["images"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        stats.count += 1;
+        let Some([width, height]) = image_size(glb, image) else {
+            continue;
+        };
+        // Decoded RGBA8 plus a third more for mipmaps.
+        stats.bytes += u64::from(width) * u64::from(height) * 4 * 4 / 3;
+        if stats
+            .largest
+            .is_none_or(|[w, h]| u64::from(w) * u64::from(h) < u64::from(width) * u64::from(height))
+        {
+            stats.largest = Some([width, height]);
+        }
+        if MAX_TEXTURE_SIZE < width.max(height) {
+            let name = image["name"]
+                .as_str()
+                .map_or_else(|| i.to_string(), String::from);
+            warnings.push(format!(
+                "texture {name} is {width}x{height}; textures over {MAX_TEXTURE_SIZE} px use a \
+                 lot of GPU memory and may fail on some GPUs"
+            ));
+        }
+    }
+    stats
+}
+
+fn materials(json: &Value, vrm0: &Value, warnings: &mut Vec<String>) -> MaterialStats {
+    let shaders_0x = vrm0["materialProperties"].as_array();
+    let mut stats = MaterialStats::default();
+    for (i, material) in json["materials"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        stats.count += 1;
+        let name = material["name"].as_str();
+        let shader = shaders_0x
+            .and_then(|properties| {
+                properties
+                    .iter()
+                    .find(|p| name.is_some() && p["name"].as_str() == name)
+                    .or_else(|| properties.get(i))
+            })
+            .and_then(|p| p["shader"].as_str());
+        let extensions = &material["extensions"];
+        if extensions["VRMC_materials_mtoon"].is_object() || shader == Some("VRM/MToon") {
+            stats.mtoon += 1;
+        } else if extensions["KHR_materials_unlit"].is_object()
+            || shader.is_some_and(|s| s.starts_with("VRM/Unlit"))
+        {
+            stats.unlit += 1;
+        }
+        if let Some(shader) = shader.filter(|s| !SUPPORTED_SHADERS_0X.contains(s)) {
+            let name = name.map_or_else(|| i.to_string(), String::from);
+            warnings.push(format!(
+                "material {name} uses the unsupported shader {shader}; it renders as a standard \
+                 material"
+            ));
+        }
+    }
+    stats
+}
+
+/// Width and height of an embedded PNG or JPEG image.
+fn image_size(glb: &Glb, image: &Value) -> Option<[u32; 2]> {
+    let view = &glb.json["bufferViews"][image["bufferView"].as_u64()? as usize];
+    let start = view["byteOffset"].as_u64().unwrap_or(0) as usize;
+    let end = start.checked_add(view["byteLength"].as_u64()? as usize)?;
+    let bytes = glb.bin.get(start..end)?;
+    png_size(bytes).or_else(|| jpeg_size(bytes))
+}
+
+fn png_size(bytes: &[u8]) -> Option<[u32; 2]> {
+    if !bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return None;
+    }
+    let be = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+    Some([be(16)?, be(20)?])
+}
+
+fn jpeg_size(bytes: &[u8]) -> Option<[u32; 2]> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let be = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let mut at = 2;
+    loop {
+        if *bytes.get(at)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(at + 1)?;
+        match marker {
+            0xFF => at += 1,
+            0x01 | 0xD0..=0xD7 => at += 2,
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some([u32::from(be(at + 7)?), u32::from(be(at + 5)?)]);
+            }
+            _ => at += 2 + usize::from(be(at + 2)?),
+        }
+    }
+}
+
+fn index(value: &Value) -> usize {
+    value.as_u64().map_or(usize::MAX, |i| i as usize)
+}
+
+fn count(accessor: &Value) -> u64 {
+    accessor["count"].as_u64().unwrap_or(0)
+}
+
+fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glb::encode;
+    use serde_json::json;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend(width.to_be_bytes());
+        bytes.extend(height.to_be_bytes());
+        bytes
+    }
+
+    fn human_bones(skip: &str) -> Value {
+        REQUIRED_BONES
+            .iter()
+            .enumerate()
+            .filter(|(_, bone)| **bone != skip)
+            .map(|(i, bone)| (bone.to_string(), json!({"node": i})))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    /// A VRM 1.0 with one 8192x4096 texture and a 300-vertex mesh.
+    fn vrm1(skip_bone: &str, expressions: Value) -> Vec<u8> {
+        let image = png(8192, 4096);
+        let doc = json!({
+            "asset": {"version": "2.0"},
+            "extensions": {"VRMC_vrm": {
+                "specVersion": "1.0",
+                "meta": {
+                    "name": "Elmer",
+                    "authors": ["hmcs", ""],
+                    "licenseUrl": "https://vrm.dev/licenses/1.0/",
+                    "avatarPermission": "everyone",
+                    "commercialUsage": "personalNonProfit",
+                    "allowRedistribution": false,
+                    "modification": "prohibited",
+                    "creditNotation": "required",
+                },
+                "humanoid": {"humanBones": human_bones(skip_bone)},
+                "expressions": expressions,
+            }},
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 0, "byteLength": image.len()},
+                {"buffer": 0, "byteOffset": 24, "byteLength": 3600},
+            ],
+            "images": [{"name": "body", "bufferView": 0, "mimeType": "image/png"}],
+            "accessors": [
+                {"bufferView": 1, "componentType": 5126, "count": 300, "type": "VEC3"},
+            ],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0}}]}],
+            "materials": [{"name": "skin", "extensions": {"VRMC_materials_mtoon": {}}}],
+        });
+        encode(&doc, &image)
+    }
+
+    #[test]
+    fn reports_a_complete_vrm1() {
+        let expressions = json!({
+            "preset": {"aa": {}, "ih": {}, "ou": {}, "ee": {}, "oh": {}, "blink": {}},
+            "custom": {"smug": {}},
+        });
+        let report = diagnose_vrm(&vrm1("", expressions));
+        assert_eq!(report.spec_version.as_deref(), Some("1.0"));
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.humanoid.bones.len(), REQUIRED_BONES.len());
+        assert!(report.expressions.contains(&"smug".to_string()));
+        assert_eq!(report.geometry.vertices, 300);
+        assert_eq!(report.geometry.triangles, 100);
+        assert_eq!(report.geometry.bytes, 3600);
+        assert_eq!(report.textures.largest, Some([8192, 4096]));
+        assert_eq!(report.textures.bytes, 8192 * 4096 * 4 * 4 / 3);
+        assert_eq!(report.estimated_memory_bytes, report.textures.bytes + 3600);
+        assert_eq!(report.materials.mtoon, 1);
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert!(report.warnings[0].contains("8192x4096"));
+        let license = report.license.unwrap();
+        assert_eq!(license.name.as_deref(), Some("Elmer"));
+        assert_eq!(license.authors, vec!["hmcs".to_string()]);
+        assert_eq!(license.allow_redistribution, Some(false));
+        assert_eq!(license.credit_notation.as_deref(), Some("required"));
+    }
+
+    #[test]
+    fn reads_the_license_alone() {
+        let path = std::env::temp_dir().join("homunculus_vrm_license.vrm");
+        std::fs::write(&path, vrm1("", json!({}))).unwrap();
+        let license = read_vrm_license(&path);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(license.unwrap().unwrap().name.as_deref(), Some("Elmer"));
+        assert_eq!(vrm_license(&json!({"asset": {}})), None);
+    }
+
+    #[test]
+    fn reports_missing_bones_and_expressions() {
+        let report = diagnose_vrm(&vrm1("leftFoot", json!({"preset": {"aa": {}}})));
+        assert_eq!(
+            report.humanoid.missing_required,
+            vec!["leftFoot".to_string()]
+        );
+        assert!(report.errors[0].contains("leftFoot"));
+        assert!(report.warnings.iter().any(|w| w.contains("ih, ou, ee, oh")));
+        assert!(report.warnings.iter().any(|w| w.contains("blink")));
+    }
+
+    #[test]
+    fn converts_vrm0_meta_and_expressions() {
+        let doc = json!({
+            "extensions": {"VRM": {
+                "meta": {
+                    "title": "Old",
+                    "author": "someone",
+                    "allowedUserName": "OnlyAuthor",
+                    "commercialUssageName": "Disallow",
+                    "licenseName": "Redistribution_Prohibited",
+                },
+                "humanoid": {"humanBones": [
+                    {"bone": "hips", "node": 0},
+                    {"bone": "leftThumbProximal", "node": 1},
+                ]},
+                "blendShapeMaster": {"blendShapeGroups": [
+                    {"name": "A", "presetName": "a"},
+                    {"name": "Joy", "presetName": "joy"},
+                    {"name": "Smug", "presetName": "unknown"},
+                ]},
+                "materialProperties": [{"name": "skin", "shader": "Standard"}],
+            }},
+            "materials": [{"name": "skin"}],
+        });
+        let report = diagnose_vrm(&encode(&doc, &[]));
+        assert_eq!(report.spec_version.as_deref(), Some("0.0"));
+        assert!(report.errors[0].contains("VRM 0.x"));
+        assert_eq!(report.expressions, vec!["Smug", "aa", "happy"]);
+        assert!(
+            report
+                .humanoid
+                .bones
+                .contains(&"leftThumbMetacarpal".to_string())
+        );
+        assert!(report.warnings.iter().any(|w| w.contains("Standard")));
+        let license = report.license.unwrap();
+        assert_eq!(license.avatar_permission.as_deref(), Some("onlyAuthor"));
+        assert_eq!(
+            license.commercial_usage.as_deref(),
+            Some("personalNonProfit")
+        );
+        assert_eq!(license.allow_redistribution, Some(false));
+        assert_eq!(license.modification.as_deref(), Some("prohibited"));
+        assert_eq!(license.credit_notation, None);
+    }
+
+    #[test]
+    fn rejects_non_vrm_files() {
+        let report = diagnose_vrm(b"not a glb");
+        assert_eq!(report.spec_version, None);
+        assert_eq!(report.errors.len(), 1);
+
+        let doc = json!({"extensionsRequired": ["KHR_draco_mesh_compression"]});
+        let report = diagnose_vrm(&encode(&doc, &[]));
+        assert_eq!(report.errors.len(), 2, "{:?}", report.errors);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn reads_jpeg_size() {
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00];
+        jpeg.extend([0xFF, 0xC0, 0x00, 0x11, 0x08, 0x02, 0x00, 0x04, 0x00]);
+        assert_eq!(jpeg_size(&jpeg), Some([1024, 512]));
+        assert_eq!(png_size(&png(64, 32)), Some([64, 32]));
+    }
+}
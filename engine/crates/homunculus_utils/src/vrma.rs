@@ -0,0 +1,197 @@
+//! Facts about VRMA animations, read from their glTF data.
+
+use crate::error::{UtilError, UtilResult};
+use crate::glb::Glb;
+use crate::schema::asset::VrmaMetadata;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// Hips travel, in meters, above which an animation counts as moving across the floor.
+const ROOT_MOTION_DISTANCE: f32 = 0.1;
+/// Largest difference between the first and last keyframe of a looping channel.
+const LOOP_TOLERANCE: f32 = 0.01;
+
+/// Reads the metadata of a `.vrma` file.
+pub fn read_vrma_metadata(path: &Path) -> UtilResult<VrmaMetadata> {
+    vrma_metadata(&Glb::read(path)?)
+}
+
+/// Extracts the metadata of a parsed VRMA.
+///
+/// Only the first animation is inspected, since that is the one the engine plays.
+pub fn vrma_metadata(glb: &Glb) -> UtilResult<VrmaMetadata> {
+    let human_bones = glb.json["extensions"]["VRMC_vrm_animation"]["humanoid"]["humanBones"]
+        .as_object()
+        .ok_or_else(|| UtilError::Glb("missing VRMC_vrm_animation humanoid".to_string()))?;
+    let bone_nodes: HashMap<u64, &str> = human_bones
+        .iter()
+        .filter_map(|(name, bone)| Some((bone["node"].as_u64()?, name.as_str())))
+        .collect();
+    let hips = human_bones
+        .get("hips")
+        .and_then(|bone| bone["node"].as_u64());
+    let animation = &glb.json["animations"][0];
+    let samplers = animation["samplers"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut duration_secs = 0.0f32;
+    let mut loopable = true;
+    let mut root_motion = false;
+    let mut bones = BTreeSet::new();
+    for channel in animation["channels"].as_array().into_iter().flatten() {
+        let node = channel["target"]["node"].as_u64();
+        let path = channel["target"]["path"].as_str().unwrap_or_default();
+        if let Some(bone) = node.and_then(|node| bone_nodes.get(&node)) {
+            bones.insert(bone.to_string());
+        }
+        let Some(sampler) = channel["sampler"]
+            .as_u64()
+            .and_then(|i| samplers.get(i as usize))
+        else {
+            continue;
+        };
+        duration_secs = duration_secs.max(last_time(glb, sampler));
+        let Some(values) = output_keyframes(glb, sampler, path) else {
+            continue;
+        };
+        let (Some(first), Some(last)) = (values.first(), values.last()) else {
+            continue;
+        };
+        if !same_pose(path, first, last) {
+            loopable = false;
+        }
+        if node.is_some() && node == hips && path == "translation" {
+            root_motion |= values
+                .iter()
+                .any(|v| ROOT_MOTION_DISTANCE < (v[0] - first[0]).hypot(v[2] - first[2]));
+        }
+    }
+
+    Ok(VrmaMetadata {
+        duration_secs,
+        loopable,
+        bones: bones.into_iter().collect(),
+        root_motion,
+    })
+}
+
+/// The time of the sampler's last keyframe, from the accessor bounds or else its data.
+fn last_time(glb: &Glb, sampler: &Value) -> f32 {
+    let Some(input) = sampler["input"].as_u64().map(|i| i as usize) else {
+        return 0.0;
+    };
+    if let Some(max) = glb.json["accessors"][input]["max"][0].as_f64() {
+        return max as f32;
+    }
+    glb.accessor_floats(input)
+        .and_then(|times| times.into_iter().reduce(f32::max))
+        .unwrap_or(0.0)
+}
+
+/// The keyframe values of the sampler, one slice per keyframe. Cubic spline tangents
+/// are dropped.
+fn output_keyframes(glb: &Glb, sampler: &Value, path: &str) -> Option<Vec<Vec<f32>>> {
+    let components = match path {
+        "rotation" => 4,
+        "translation" | "scale" => 3,
+        _ => return None,
+    };
+    let values = glb.accessor_floats(sampler["output"].as_u64()? as usize)?;
+    let keyframes = values.chunks_exact(components).map(<[f32]>::to_vec);
+    Some(if sampler["interpolation"] == "CUBICSPLINE" {
+        keyframes.skip(1).step_by(3).collect()
+    } else {
+        keyframes.collect()
+    })
+}
+
+fn same_pose(path: &str, first: &[f32], last: &[f32]) -> bool {
+    if path == "rotation" {
+        let dot: f32 = first.iter().zip(last).map(|(a, b)| a * b).sum();
+        1.0 - dot.abs() < LOOP_TOLERANCE * LOOP_TOLERANCE
+    } else {
+        first
+            .iter()
+            .zip(last)
+            .all(|(a, b)| (a - b).abs() < LOOP_TOLERANCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::glb::encode;
+    use serde_json::json;
+
+    fn floats(values: &[f32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    /// A VRMA waving the right arm back to where it started, with hips moved by `hips_x`.
+    fn vrma(hips_x: f32) -> Glb {
+        let mut bin = floats(&[0.0, 0.5, 1.5]);
+        bin.extend(floats(&[
+            0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.38, 0.92, 0.0, 0.0, 0.0, 1.0,
+        ]));
+        bin.extend(floats(&[0.0, 1.0, 0.0, 0.0, 1.0, 0.0, hips_x, 1.0, 0.0]));
+        let doc = json!({
+            "extensions": {"VRMC_vrm_animation": {"humanoid": {"humanBones": {
+                "hips": {"node": 0},
+                "rightUpperArm": {"node": 1},
+                "head": {"node": 2},
+            }}}},
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 0, "byteLength": 12},
+                {"buffer": 0, "byteOffset": 12, "byteLength": 48},
+                {"buffer": 0, "byteOffset": 60, "byteLength": 36},
+            ],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR",
+                 "min": [0.0], "max": [1.5]},
+                {"bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC4"},
+                {"bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC3"},
+            ],
+            "animations": [{
+                "samplers": [
+                    {"input": 0, "output": 1, "interpolation": "LINEAR"},
+                    {"input": 0, "output": 2, "interpolation": "LINEAR"},
+                ],
+                "channels": [
+                    {"sampler": 0, "target": {"node": 1, "path": "rotation"}},
+                    {"sampler": 1, "target": {"node": 0, "path": "translation"}},
+                ],
+            }],
+        });
+        Glb::parse(&encode(&doc, &bin)).unwrap()
+    }
+
+    #[test]
+    fn reads_duration_bones_and_loops() {
+        let metadata = vrma_metadata(&vrma(0.0)).unwrap();
+        assert_eq!(
+            metadata,
+            VrmaMetadata {
+                duration_secs: 1.5,
+                loopable: true,
+                bones: vec!["hips".to_string(), "rightUpperArm".to_string()],
+                root_motion: false,
+            }
+        );
+    }
+
+    #[test]
+    fn detects_root_motion() {
+        let metadata = vrma_metadata(&vrma(0.8)).unwrap();
+        assert!(metadata.root_motion);
+        assert!(!metadata.loopable);
+    }
+
+    #[test]
+    fn rejects_plain_gltf() {
+        let glb = Glb::parse(&encode(&json!({"asset": {"version": "2.0"}}), &[])).unwrap();
+        assert!(vrma_metadata(&glb).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! Headless launch mode for automated integration testing.
+//!
+//! Runs the engine without rendering, windows, CEF, the tray icon or hit-testing,
+//! while keeping the ECS, [`HomunculusApiPlugin`], preferences, MOD loading, signals,
+//! RPC and the HTTP/MCP servers alive. This lets MOD services be exercised end-to-end
+//! against a real engine on a machine with no display or GPU.
+//!
+//! Enabled with the `headless` cargo feature and selected at launch with the
+//! `--headless` flag or the `HMCS_HEADLESS=1` environment variable:
+//!
+//! ```sh
+//! cargo run --features headless -- --headless
+//! ```
+//!
+//! Without the default `gui` feature, CEF, the tray icon, hit-testing and winit are not
+//! linked at all and the binary always runs headless:
+//!
+//! ```sh
+//! cargo run --no-default-features --features headless
+//! ```
+//!
+//! Since no render plugins are loaded, VRM models are not loaded either: personas can be
+//! created and driven through the API, but `vrm-attached` never fires.
+
+use crate::{custom_layer, resolve_asset_path};
+use bevy::app::{App, ScheduleRunnerPlugin};
+use bevy::asset::UnapprovedPathMode;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::window::{ExitCondition, WindowPlugin};
+use bevy_flurx::FlurxPlugin;
+use bevy_tweening::TweeningPlugin;
+use homunculus_api::HomunculusApiPlugin;
+use homunculus_api::prelude::{ShadowPanelApiPlugin, WebviewApiPlugin};
+use homunculus_core::HomunculusCorePlugin;
+use homunculus_http_server::HomunculusHttpServerPlugin;
+use homunculus_mod::HomunculusModPlugin;
+use homunculus_prefs::HomunculusPrefsPlugin;
+use homunculus_utils::config::HomunculusConfig;
+use homunculus_utils::runtime::RuntimeResolver;
+use std::time::Duration;
+
+/// Environment variable that selects headless mode when set to `1` or `true`.
+#[cfg(feature = "gui")]
+const HEADLESS_ENV: &str = "HMCS_HEADLESS";
+
+/// How often the headless main loop ticks.
+const TICK_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// Returns `true` if the engine was launched with `--headless` or `HMCS_HEADLESS=1`.
+#[cfg(feature = "gui")]
+pub fn requested() -> bool {
+    std::env::args().skip(1).any(|arg| arg == "--headless")
+        || std::env::var(HEADLESS_ENV).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+/// Builds and runs the headless engine until the process is terminated.
+pub fn run(config: HomunculusConfig, runtime: RuntimeResolver) {
+    info!("Starting Desktop Homunculus in headless mode");
+    build(config, runtime).run();
+}
+
+/// Builds the headless engine without starting its main loop.
+fn build(config: HomunculusConfig, runtime: RuntimeResolver) -> App {
+    let mut app = App::new();
+    app.insert_resource(runtime)
+        .insert_resource(config)
+        .add_plugins((
+            // Registers the `asset://` source, so it must precede `AssetPlugin`.
+            HomunculusModPlugin,
+            MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(TICK_INTERVAL)),
+            LogPlugin {
+                custom_layer,
+                ..default()
+            },
+            TransformPlugin,
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                ..default()
+            },
+            AssetPlugin {
+                file_path: resolve_asset_path(),
+                unapproved_path_mode: UnapprovedPathMode::Allow,
+                ..default()
+            },
+            ImagePlugin::default_linear(),
+        ))
+        .add_plugins((FlurxPlugin, TweeningPlugin))
+        .add_plugins((
+            HomunculusCorePlugin,
+            HomunculusApiPlugin
+                .build()
+                .disable::<ShadowPanelApiPlugin>()
+                .disable::<WebviewApiPlugin>(),
+            HomunculusPrefsPlugin,
+            HomunculusHttpServerPlugin,
+        ));
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homunculus_prefs::PrefsDatabase;
+
+    #[test]
+    fn headless_app_updates() {
+        let mods_dir = std::env::temp_dir().join("homunculus_headless_smoke");
+        std::fs::create_dir_all(&mods_dir).unwrap();
+        let config = HomunculusConfig {
+            mods_dir,
+            // Any free port; the server is not exercised here.
+            port: 0,
+            ..default()
+        };
+        let mut app = build(config, RuntimeResolver::detect());
+        app.insert_non_send_resource(PrefsDatabase::open_in_memory());
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(app.should_exit().is_none());
+    }
+}
@@ -4,46 +4,22 @@
     windows_subsystem = "windows"
 )]
 
-mod cef_fetch;
+#[cfg(not(any(feature = "gui", feature = "headless")))]
+compile_error!("enable the `gui` feature, the `headless` feature, or both");
 
-use crate::cef_fetch::CefFetchPlugin;
+#[cfg(feature = "gui")]
+mod cef_fetch;
+#[cfg(feature = "gui")]
+mod gui;
+#[cfg(feature = "headless")]
+mod headless;
 
-/// Marker component indicating a webview's V8 context is ready for HostEmitEvent.
-/// Inserted on `LoadingStateChanged(is_loading=false)`, removed on `is_loading=true`.
-#[derive(Component)]
-struct WebviewReady;
-use bevy::DefaultPlugins;
-use bevy::app::{App, PluginGroup};
-use bevy::asset::UnapprovedPathMode;
-use bevy::input::common_conditions::{input_just_pressed, input_pressed};
+use bevy::app::App;
+use bevy::log::BoxedLayer;
 use bevy::log::tracing_subscriber::Layer;
-use bevy::log::{BoxedLayer, LogPlugin};
-use bevy::prelude::*;
-use bevy::window::{ExitCondition, WindowLevel, WindowPlugin, WindowResolution};
-use bevy_cef::CefPlugin;
-use bevy_cef::prelude::*;
-use bevy_flurx::FlurxPlugin;
-use bevy_tweening::TweeningPlugin;
-use bevy_vrm1::vrm::VrmPlugin;
-use bevy_vrm1::vrma::VrmaPlugin;
-use homunculus_api::HomunculusApiPlugin;
-use homunculus_audio::HomunculusAudioPlugin;
-use homunculus_core::HomunculusCorePlugin;
 use homunculus_core::prelude::homunculus_dir;
-use homunculus_drag::HomunculusDragPlugin;
-use homunculus_hit_test::HomunculusHitTestPlugin;
-use homunculus_http_server::HomunculusHttpServerPlugin;
-use homunculus_mod::HomunculusModPlugin;
-use homunculus_power_saver::HomunculusPowerSaverPlugin;
-use homunculus_prefs::HomunculusPrefsPlugin;
-use homunculus_screen::HomunculusScreenPlugin;
-use homunculus_shadow_panel::HomunculusShadowPanelPlugin;
-use homunculus_sitting::HomunculusSittingPlugin;
-use homunculus_speech::HomunculusSpeechPlugin;
-use homunculus_tray::HomunculusTrayPlugin;
 use homunculus_utils::config::HomunculusConfig;
 use homunculus_utils::runtime::RuntimeResolver;
-use homunculus_windows::HomunculusWindowsPlugin;
 use std::sync::OnceLock;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling;
@@ -61,133 +37,16 @@ fn main() {
         eprintln!("Node.js version check: {e}");
     }
 
-    let mut app = App::new();
-    app.insert_resource(runtime)
-        .insert_resource(config)
-        .insert_resource(ClearColor(Color::NONE))
-        .add_plugins((
-            HomunculusModPlugin,
-            DefaultPlugins
-                .set(LogPlugin {
-                    #[cfg(debug_assertions)]
-                    level: bevy::log::Level::INFO,
-                    #[cfg(not(debug_assertions))]
-                    level: bevy::log::Level::ERROR,
-                    custom_layer,
-                    #[cfg(target_os = "windows")]
-                    filter: "wgpu_hal=off,naga::back::spv::writer=off".to_string(),
-                    #[cfg(not(target_os = "windows"))]
-                    filter: format!(
-                        "{},naga::back::spv::writer=off",
-                        LogPlugin::default().filter
-                    ),
-                    ..default()
-                })
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        resolution: WindowResolution::new(0, 0),
-                        decorations: false,
-                        transparent: true,
-                        skip_taskbar: true,
-                        window_level: WindowLevel::AlwaysOnTop,
-                        ..default()
-                    }),
-                    exit_condition: ExitCondition::OnAllClosed,
-                    ..default()
-                })
-                .set(AssetPlugin {
-                    file_path: resolve_asset_path(),
-                    unapproved_path_mode: UnapprovedPathMode::Allow,
-                    ..default()
-                }),
-            MeshPickingPlugin,
-            #[cfg(feature = "develop")]
-            bevy_egui::EguiPlugin::default(),
-            #[cfg(feature = "develop")]
-            bevy_inspector_egui::quick::WorldInspectorPlugin::default(),
-        ))
-        .add_plugins((FlurxPlugin, TweeningPlugin, VrmPlugin, VrmaPlugin))
-        .add_plugins(HomunculusTrayPlugin)
-        .add_plugins((
-            HomunculusCorePlugin,
-            HomunculusAudioPlugin,
-            HomunculusDragPlugin,
-            HomunculusWindowsPlugin,
-            HomunculusPowerSaverPlugin,
-            HomunculusScreenPlugin,
-            HomunculusSittingPlugin,
-            HomunculusShadowPanelPlugin,
-            HomunculusSpeechPlugin,
-            HomunculusApiPlugin,
-            HomunculusHitTestPlugin,
-            HomunculusPrefsPlugin,
-            HomunculusHttpServerPlugin,
-            CefPlugin {
-                command_line_config: CommandLineConfig::default()
-                    .with_switch("disable-web-security"),
-                extensions: CefExtensions::new().add("cef-fetch", include_str!("./cef_fetch.js")),
-                root_cache_path: Some(
-                    homunculus_dir()
-                        .join("cef_data")
-                        .to_string_lossy()
-                        .into_owned(),
-                ),
-            },
-            CefFetchPlugin,
-        ))
-        .add_observer(|trigger: On<LoadingStateChanged>, mut commands: Commands| {
-            if trigger.is_loading {
-                // V8 context is not ready during load start — remove ready marker
-                // and skip HostEmitEvent to avoid render process crash.
-                commands.entity(trigger.webview).remove::<WebviewReady>();
-                return;
-            }
-            // Page load complete — V8 is stable, mark as ready.
-            commands.entity(trigger.webview).try_insert(WebviewReady);
-            commands.trigger(HostEmitEvent::new(
-                trigger.webview,
-                "loading-state-changed",
-                &serde_json::json!({
-                    "isLoading": trigger.is_loading,
-                    "canGoBack": trigger.can_go_back,
-                    "canGoForward": trigger.can_go_forward,
-                }),
-            ));
-        })
-        .add_observer(
-            |trigger: On<AddressChanged>,
-             mut commands: Commands,
-             ready: Query<(), With<WebviewReady>>| {
-                // Only emit when V8 context is ready — during initial load or
-                // cross-document navigation, WebviewReady is absent.
-                if ready.get(trigger.webview).is_err() {
-                    return;
-                }
-                commands.trigger(HostEmitEvent::new(
-                    trigger.webview,
-                    "navigation-state-changed",
-                    &serde_json::json!({
-                        "canGoBack": trigger.can_go_back,
-                        "canGoForward": trigger.can_go_forward,
-                    }),
-                ));
-            },
-        )
-        .add_systems(
-            Update,
-            (
-                webview_navigate_back.run_if(input_just_pressed(MouseButton::Back).or(
-                    input_pressed(KeyCode::SuperLeft).and(input_just_pressed(KeyCode::BracketLeft)),
-                )),
-                webview_navigate_forward.run_if(
-                    input_just_pressed(MouseButton::Forward).or(input_pressed(KeyCode::SuperLeft)
-                        .and(input_just_pressed(KeyCode::BracketRight))),
-                ),
-                show_devtool.run_if(input_just_pressed(KeyCode::F1)),
-                close_devtool.run_if(input_just_pressed(KeyCode::F2)),
-            ),
-        )
-        .run();
+    #[cfg(all(feature = "gui", feature = "headless"))]
+    if headless::requested() {
+        headless::run(config, runtime);
+        return;
+    }
+
+    #[cfg(feature = "gui")]
+    gui::run(config, runtime);
+    #[cfg(not(feature = "gui"))]
+    headless::run(config, runtime);
 }
 
 /// Resolves the asset directory path based on the current platform and build mode.
@@ -294,27 +153,3 @@ fn custom_layer(_app: &mut App) -> Option<BoxedLayer> {
             .boxed(),
     )
 }
-
-fn webview_navigate_back(mut commands: Commands, webviews: Query<Entity, With<WebviewSource>>) {
-    for webview in webviews.iter() {
-        commands.trigger(RequestGoBack { webview });
-    }
-}
-
-fn webview_navigate_forward(mut commands: Commands, webviews: Query<Entity, With<WebviewSource>>) {
-    for webview in webviews.iter() {
-        commands.trigger(RequestGoForward { webview });
-    }
-}
-
-fn show_devtool(mut commands: Commands, webviews: Query<Entity, With<WebviewSource>>) {
-    for webview in webviews.iter() {
-        commands.trigger(RequestShowDevTool { webview });
-    }
-}
-
-fn close_devtool(mut commands: Commands, webviews: Query<Entity, With<WebviewSource>>) {
-    for webview in webviews.iter() {
-        commands.trigger(RequestCloseDevtool { webview });
-    }
-}
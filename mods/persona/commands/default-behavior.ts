@@ -29,7 +29,18 @@ console.log(`[default-behavior] Subscribing to events...`);
 
 events.on('state-change', async (e) => {
   console.log(`[default-behavior] EVENT state-change: ${e.state}`);
+  const previousState = currentState;
   currentState = e.state;
+  if (previousState === 'landing' && currentState === 'idle') {
+    // The idle animation already started on touchdown.
+    try {
+      await persona.vrm().clearExpressions({ durationMs: 300 });
+      await persona.vrm().lookAtCursor();
+    } catch (err) {
+      console.error('[default-behavior] lookAtCursor after landing failed:', err);
+    }
+    return;
+  }
   await applyBehaviorWithLogging(persona, currentState, animations, 'state-change');
 });
 events.on('vrm-attached', async (e) => {
@@ -61,6 +72,11 @@ async function applyBehavior(p: Persona, state: string, anims: BehaviorAnimation
   } else if (state === 'drag') {
     await vrm.unlook();
     await vrm.playVrma({ asset: anims.drag, ...option, resetSpringBones: true });
+  } else if (state === 'falling') {
+    await vrm.playVrma({ asset: anims.drag, ...option });
+  } else if (state === 'landing') {
+    await vrm.playVrma({ asset: anims.idle, repeat: repeat.forever(), transitionSecs: 0.2 });
+    await vrm.setExpressions({ surprised: 1.0 }, { durationMs: 100 });
   } else if (state === 'sitting') {
     await vrm.playVrma({ asset: anims.sitting, ...option });
     await sleep(500);
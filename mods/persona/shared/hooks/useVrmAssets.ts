@@ -37,12 +37,15 @@ export function useVrmAssets(): UseVrmAssetsReturn {
 
       try {
         const assetId = `vrm:local:${personaId}`;
-        await assets.importAsset({
+        const { diagnostics } = await assets.importAsset({
           sourcePath: path,
           assetId,
           assetType: 'vrm',
           description: `Imported VRM for ${personaId}`,
         });
+        for (const problem of [...(diagnostics?.errors ?? []), ...(diagnostics?.warnings ?? [])]) {
+          console.warn(`VRM ${assetId}: ${problem}`);
+        }
         await fetchAssets();
         return assetId;
       } catch (e) {
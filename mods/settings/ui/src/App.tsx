@@ -3,7 +3,8 @@ import { GeneralTab } from './components/GeneralTab';
 import { useSettings } from './hooks/useSettings';
 
 export function App() {
-  const { loading, fps, setFps, alpha, setAlpha, handleClose } = useSettings();
+  const { loading, fps, setFps, alpha, setAlpha, physics, setPhysics, handleClose } =
+    useSettings();
 
   if (loading) {
     return (
@@ -26,7 +27,14 @@ export function App() {
       <Toolbar title="Settings" onClose={handleClose} />
 
       <div className="settings-content">
-        <GeneralTab fps={fps} setFps={setFps} alpha={alpha} setAlpha={setAlpha} />
+        <GeneralTab
+          fps={fps}
+          setFps={setFps}
+          alpha={alpha}
+          setAlpha={setAlpha}
+          physics={physics}
+          setPhysics={setPhysics}
+        />
       </div>
     </div>
   );
@@ -1,11 +1,22 @@
+import type { PhysicsSettings } from '@hmcs/sdk';
+
 interface GeneralTabProps {
   fps: number;
   setFps: (v: number) => void;
   alpha: number;
   setAlpha: (v: number) => void;
+  physics: PhysicsSettings | null;
+  setPhysics: (v: PhysicsSettings) => void;
 }
 
-export function GeneralTab({ fps, setFps, alpha, setAlpha }: GeneralTabProps) {
+export function GeneralTab({
+  fps,
+  setFps,
+  alpha,
+  setAlpha,
+  physics,
+  setPhysics,
+}: GeneralTabProps) {
   return (
     <div className="settings-section">
       <label className="settings-label">
@@ -45,6 +56,36 @@ export function GeneralTab({ fps, setFps, alpha, setAlpha }: GeneralTabProps) {
           Controls the transparency of the shadow panel overlay behind the character.
         </span>
       </label>
+
+      {physics && (
+        <label className="settings-label">
+          Gravity
+          <div className="settings-slider-row">
+            <input
+              type="checkbox"
+              checked={physics.enabled}
+              onChange={(e) => setPhysics({ ...physics, enabled: e.target.checked })}
+            />
+            <input
+              type="range"
+              className="settings-slider"
+              min={0}
+              max={1}
+              step={0.05}
+              value={physics.bounciness}
+              disabled={!physics.enabled}
+              onChange={(e) => setPhysics({ ...physics, bounciness: Number(e.target.value) })}
+            />
+            <span className="settings-slider-value">
+              {Math.round(physics.bounciness * 100)}% bounce
+            </span>
+          </div>
+          <span className="settings-description">
+            Characters dropped away from a window fall to the bottom of the screen or onto a
+            window, and can be thrown.
+          </span>
+        </label>
+      )}
     </div>
   );
 }
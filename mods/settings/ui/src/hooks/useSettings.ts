@@ -1,9 +1,10 @@
-import { audio, settings, shadowPanel, Webview } from '@hmcs/sdk';
+import { audio, type PhysicsSettings, settings, shadowPanel, Webview } from '@hmcs/sdk';
 import { useCallback, useEffect, useRef, useState } from 'react';
 
 export function useSettings() {
   const [fps, setFps] = useState(60);
   const [alpha, setAlpha] = useState(0.5);
+  const [physics, setPhysics] = useState<PhysicsSettings | null>(null);
   const [loading, setLoading] = useState(true);
   const initialised = useRef(false);
 
@@ -11,10 +12,15 @@ export function useSettings() {
     let cancelled = false;
 
     (async () => {
-      const [currentFps, currentAlpha] = await Promise.all([settings.fps(), shadowPanel.alpha()]);
+      const [currentFps, currentAlpha, currentPhysics] = await Promise.all([
+        settings.fps(),
+        shadowPanel.alpha(),
+        settings.physics(),
+      ]);
       if (cancelled) return;
       setFps(currentFps);
       setAlpha(currentAlpha);
+      setPhysics(currentPhysics);
       setLoading(false);
       initialised.current = true;
     })();
@@ -40,10 +46,18 @@ export function useSettings() {
     return () => clearTimeout(id);
   }, [alpha]);
 
+  useEffect(() => {
+    if (!initialised.current || !physics) return;
+    const id = setTimeout(() => {
+      settings.setPhysics(physics).catch(console.error);
+    }, 500);
+    return () => clearTimeout(id);
+  }, [physics]);
+
   const handleClose = useCallback(() => {
     audio.se.play('se:close');
     Webview.current()?.close();
   }, []);
 
-  return { loading, fps, setFps, alpha, setAlpha, handleClose };
+  return { loading, fps, setFps, alpha, setAlpha, physics, setPhysics, handleClose };
 }
@@ -21,7 +21,7 @@ import { host } from './host';
  */
 export namespace assets {
   /** The type of an asset. */
-  export type AssetType = 'vrm' | 'vrma' | 'sound' | 'image' | 'html';
+  export type AssetType = 'vrm' | 'vrma' | 'sound' | 'image' | 'html' | 'timeline';
 
   /** Information about a registered asset. */
   export interface AssetInfo {
@@ -33,6 +33,24 @@ export namespace assets {
     mod: string;
     /** Optional description of the asset. */
     description?: string;
+    /** Labels declared by the mod, e.g. `["greeting"]`. */
+    tags?: string[];
+    /** Facts about VRMA animations, read when the asset is registered. */
+    vrma?: VrmaMetadata;
+    /** License meta of VRM models, known once they are imported or first attached. */
+    license?: VrmLicense;
+  }
+
+  /** Facts about a VRMA animation. */
+  export interface VrmaMetadata {
+    /** Length of the animation in seconds. */
+    durationSecs: number;
+    /** Whether the last frame matches the first, so the animation repeats seamlessly. */
+    loopable: boolean;
+    /** Humanoid bones the animation moves, e.g. `["head", "rightUpperArm"]`. */
+    bones: string[];
+    /** Whether the animation moves the hips across the floor. */
+    rootMotion: boolean;
   }
 
   /** Filter options for listing assets. */
@@ -41,10 +59,12 @@ export namespace assets {
     type?: AssetType;
     /** Filter by mod name. */
     mod?: string;
+    /** Filter by tag. */
+    tag?: string;
   }
 
   /**
-   * Lists available assets, optionally filtered by type and/or mod.
+   * Lists available assets, optionally filtered by type, mod and/or tag.
    *
    * @param filter - Optional filter criteria
    * @returns Array of matching asset info objects
@@ -62,6 +82,9 @@ export namespace assets {
    *
    * // Combine filters
    * const sounds = await assets.list({ type: "sound", mod: "my-mod" });
+   *
+   * // Find greeting animations
+   * const greetings = await assets.list({ type: "vrma", tag: "greeting" });
    * ```
    */
   export async function list(filter?: AssetFilter): Promise<AssetInfo[]> {
@@ -85,6 +108,70 @@ export namespace assets {
   export interface ImportAssetResult {
     /** The registered asset ID */
     assetId: string;
+    /** Validation report, for VRM models. */
+    diagnostics?: VrmDiagnostics;
+  }
+
+  /** Validation report for a VRM model, produced without rendering it. */
+  export interface VrmDiagnostics {
+    /** VRM spec version, e.g. `"1.0"` or `"0.0"`. `null` when the file is not a VRM. */
+    specVersion: string | null;
+    /** Humanoid bone mapping. */
+    humanoid: {
+      /** Mapped bones, e.g. `["hips", "spine", "head"]`. */
+      bones: string[];
+      /** Bones VRM 1.0 requires that the model does not map. */
+      missingRequired: string[];
+    };
+    /** Expressions the model defines, using VRM 1.0 preset names, e.g. `["aa", "blink"]`. */
+    expressions: string[];
+    /** Mesh counts and the size of the vertex data in bytes. */
+    geometry: {
+      meshes: number;
+      primitives: number;
+      vertices: number;
+      triangles: number;
+      bytes: number;
+    };
+    /** Texture count, largest `[width, height]` and decoded size in bytes. */
+    textures: {
+      count: number;
+      largest: [number, number] | null;
+      bytes: number;
+    };
+    /** Material counts by shader. */
+    materials: {
+      count: number;
+      mtoon: number;
+      unlit: number;
+    };
+    /** Rough GPU memory needed for the geometry and decoded textures, in bytes. */
+    estimatedMemoryBytes: number;
+    /** Author and usage terms from the VRM meta. */
+    license: VrmLicense | null;
+    /** Problems that keep the model from loading or animating. */
+    errors: string[];
+    /** Problems that degrade the model, such as missing lip sync expressions. */
+    warnings: string[];
+  }
+
+  /** Author and usage terms from a VRM's meta, normalized across VRM 0.x and 1.0. */
+  export interface VrmLicense {
+    name: string | null;
+    authors: string[];
+    /** License name from VRM 0.x meta, e.g. `"CC_BY"`. */
+    licenseName: string | null;
+    licenseUrl: string | null;
+    /** `"onlyAuthor"`, `"onlySeparatelyLicensedPerson"` or `"everyone"`. */
+    avatarPermission: string | null;
+    /** `"personalNonProfit"`, `"personalProfit"` or `"corporation"`. */
+    commercialUsage: string | null;
+    /** Whether the file may be redistributed. `null` when the meta does not say. */
+    allowRedistribution: boolean | null;
+    /** `"prohibited"`, `"allowModification"` or `"allowModificationRedistribution"`. */
+    modification: string | null;
+    /** Whether users must credit the author: `"required"` or `"unnecessary"`. */
+    creditNotation: string | null;
   }
 
   /**
@@ -104,6 +191,9 @@ export namespace assets {
    *   description: "Alice's custom model",
    * });
    * console.log("Imported:", result.assetId);
+   * for (const warning of result.diagnostics?.warnings ?? []) {
+   *   console.warn(warning);
+   * }
    * ```
    */
   export async function importAsset(params: ImportAssetParams): Promise<ImportAssetResult> {
@@ -140,4 +230,28 @@ export namespace assets {
     const response = await host.get(host.createUrl('assets/file', { id }));
     return await response.blob();
   }
+
+  /**
+   * Validates a VRM asset without rendering it.
+   *
+   * Reports the spec version, humanoid bone coverage, expressions, vertex and
+   * texture counts with a memory estimate, license meta, errors and warnings.
+   *
+   * @param id - VRM asset ID (e.g. `"vrm:local:alice"`)
+   * @throws {HomunculusApiError} 400 if the asset is not a VRM, 404 if the asset
+   *         or underlying file is missing.
+   *
+   * @example
+   * ```typescript
+   * const report = await assets.diagnostics("vrm:local:alice");
+   * if (report.errors.length > 0) {
+   *   console.error("Model will not work:", report.errors);
+   * }
+   * console.log(`${report.geometry.vertices} vertices, ${report.textures.count} textures`);
+   * ```
+   */
+  export async function diagnostics(id: string): Promise<VrmDiagnostics> {
+    const response = await host.get(host.createUrl(`assets/${encodeURIComponent(id)}/diagnostics`));
+    return (await response.json()) as VrmDiagnostics;
+  }
 }
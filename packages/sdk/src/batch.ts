@@ -0,0 +1,145 @@
+/**
+ * Batched API operations.
+ *
+ * Runs an ordered list of operations in one request and one engine task, instead
+ * of one HTTP round trip per step. In `sameFrame` mode every step is applied in the
+ * same frame, so a choreographed reaction appears at once.
+ *
+ * @example
+ * ```typescript
+ * import { batch } from "@hmcs/sdk";
+ *
+ * const result = await batch.run(
+ *   [
+ *     { op: "expressions.modify", args: { persona: "elmer", weights: { happy: 1 } } },
+ *     { op: "vrma.play", args: { persona: "elmer", asset: "vrma:wave" } },
+ *     { id: "panel", op: "webview.open", args: { source: { type: "url", url: "my-mod:ui" } } },
+ *     { op: "se.play", args: { asset: "se:pop" } },
+ *   ],
+ *   { mode: "sameFrame" },
+ * );
+ * const panel = result.results[2].result as number;
+ * ```
+ *
+ * @packageDocumentation
+ */
+
+import { host } from './host';
+import type { VrmaRepeat } from './persona';
+import type { WebviewOpenOptions } from './webviews';
+
+/**
+ * A reference to the result of an earlier step, by its `id`.
+ *
+ * A dotted path (`"panel.entity"`) selects a field of an object result.
+ */
+export interface BatchRef {
+  $ref: string;
+}
+
+/** A value that may be given literally or as a {@link BatchRef}. */
+export type Ref<T> = T | BatchRef;
+
+/** Arguments of each batch operation, keyed by operation name. */
+export interface BatchOperations {
+  /** Replace all expression weights. */
+  'expressions.set': { persona: Ref<string>; weights: Record<string, number> };
+  /** Merge expression weights into the current ones. */
+  'expressions.modify': { persona: Ref<string>; weights: Record<string, number> };
+  'expressions.clear': { persona: Ref<string> };
+  'vrma.play': {
+    persona: Ref<string>;
+    asset: Ref<string>;
+    repeat?: VrmaRepeat;
+    transitionSecs?: number;
+    resetSpringBones?: boolean;
+    /** Wait until the animation finishes. Sequential mode only. */
+    waitForCompletion?: boolean;
+  };
+  'vrma.stop': { persona: Ref<string>; asset: Ref<string> };
+  /** Open a webview. The step result is the webview entity. */
+  'webview.open': WebviewOpenOptions;
+  'webview.close': { entity: Ref<number> };
+  'se.play': { asset: Ref<string>; volume?: number; speed?: number; panning?: number };
+  'signal.send': { signal: string; payload?: unknown };
+  /** Pause the batch. Sequential mode only. */
+  wait: { ms: number };
+}
+
+/** One step of a batch. */
+export type BatchStep = {
+  [K in keyof BatchOperations]: {
+    /** Name later steps use to reference this step's result. */
+    id?: string;
+    op: K;
+    args: BatchOperations[K];
+  };
+}[keyof BatchOperations];
+
+/** Options for {@link batch.run}. */
+export interface BatchOptions {
+  /**
+   * `sequential` (default) applies steps one after another and honors waits.
+   * `sameFrame` applies every step in the same frame.
+   */
+  mode?: 'sequential' | 'sameFrame';
+  /** `stop` (default) skips the steps after a failure; `continue` runs them anyway. */
+  onError?: 'stop' | 'continue';
+}
+
+/** Result of one step, in request order. */
+export interface BatchStepResult {
+  id?: string;
+  op: keyof BatchOperations;
+  status: 'ok' | 'error' | 'skipped';
+  /** The step's result, e.g. the entity of an opened webview. */
+  result?: unknown;
+  /** Why the step failed. */
+  error?: string;
+}
+
+/** Response of {@link batch.run}. */
+export interface BatchResult {
+  /** `true` if every step succeeded. */
+  ok: boolean;
+  results: BatchStepResult[];
+}
+
+export namespace batch {
+  /**
+   * Runs `steps` in order in a single engine task.
+   *
+   * Step failures do not reject the promise; check `ok` and each step's `status`.
+   *
+   * @throws {HomunculusApiError} status 400 — no steps, or duplicate step ids
+   *
+   * @example
+   * ```typescript
+   * await batch.run([
+   *   { op: "vrma.play", args: { persona: "elmer", asset: "vrma:bow", waitForCompletion: true } },
+   *   { op: "wait", args: { ms: 500 } },
+   *   { op: "signal.send", args: { signal: "greeting-done" } },
+   * ]);
+   * ```
+   */
+  export async function run(steps: BatchStep[], options?: BatchOptions): Promise<BatchResult> {
+    const response = await host.post(host.createUrl('batch'), { ...options, steps });
+    return (await response.json()) as BatchResult;
+  }
+
+  /**
+   * Builds a reference to the result of the step with the given `id`.
+   *
+   * @example
+   * ```typescript
+   * await batch.run([
+   *   { id: "panel", op: "webview.open", args: { source: { type: "url", url: "my-mod:ui" } } },
+   *   { op: "wait", args: { ms: 3000 } },
+   *   { op: "webview.close", args: { entity: batch.ref("panel") } },
+   * ]);
+   * ```
+   */
+  export function ref(id: string): BatchRef {
+    return { $ref: id };
+  }
+}
@@ -83,6 +83,8 @@ export interface GlobalDisplay {
   title: string;
   /** Display frame rectangle in screen coordinates. */
   frame: Rect;
+  /** Part of `frame` not covered by the menu bar, dock or taskbar. */
+  workArea: Rect;
 }
 
 /** Global viewport coordinates (screen-space position) as [x, y]. */
@@ -1,5 +1,6 @@
 import { host } from './host';
 import type { Transform, Vec2 } from './math';
+import type { VrmaRepeat } from './persona';
 
 /**
  * Entities API namespace for managing ECS (Entity Component System) entities.
@@ -367,4 +368,66 @@ export namespace entities {
   export async function tweenScale(entityId: number, request: TweenScaleRequest): Promise<void> {
     await host.post(host.createUrl(`entities/${entityId}/tween/scale`), request);
   }
+
+  /**
+   * How a path passes through its points.
+   *
+   * - `polyline`: straight lines between consecutive points
+   * - `catmullRom`: a smooth curve through every point
+   * - `bezier`: cubic Bézier segments (anchor, control, control, anchor, ...); needs 3n+1 points
+   */
+  export type PathCurve = 'polyline' | 'catmullRom' | 'bezier';
+
+  /**
+   * Request parameters for moving an entity along a path.
+   */
+  export interface TweenPathRequest {
+    /** Path points in `space` coordinates */
+    points: Vec2[];
+    /** Path shape (default: "polyline") */
+    curve?: PathCurve;
+    /** Coordinate space of the points (default: "world") */
+    space?: 'world' | 'viewport';
+    /** World z of the path (default: the entity's current z; ignored for viewport paths) */
+    z?: number;
+    /** Start at the entity's current position instead of the first point (default: false) */
+    fromCurrent?: boolean;
+    /** Duration of one pass along the path in milliseconds */
+    durationMs: number;
+    /** Easing applied to the distance travelled (default: "linear") */
+    easing?: EasingFunction;
+    /** Turn the entity's front toward the direction of travel (default: false) */
+    orientToPath?: boolean;
+    /** How many passes to make (default: a single pass) */
+    repeat?: VrmaRepeat;
+    /** Travel back along the path on every other pass (default: false) */
+    pingPong?: boolean;
+    /** Whether to wait for the last pass to finish before returning (default: false) */
+    wait?: boolean;
+  }
+
+  /**
+   * Move an entity along a path at a constant speed.
+   *
+   * Progress is published on the event bus as `path-tween-loop` and `path-tween-finish`.
+   *
+   * @param entityId - The entity ID to move
+   * @param request - Path parameters
+   * @returns Promise that resolves when the request completes (or when the last pass finishes if wait=true)
+   *
+   * @example
+   * ```typescript
+   * // Hop to the right of the current position
+   * await entities.tweenPath(vrmEntity, {
+   *   points: [[0, 0.5], [0.4, 0.5], [0.4, 0]],
+   *   curve: "bezier",
+   *   fromCurrent: true,
+   *   durationMs: 800,
+   *   wait: true,
+   * });
+   * ```
+   */
+  export async function tweenPath(entityId: number, request: TweenPathRequest): Promise<void> {
+    await host.post(host.createUrl(`entities/${entityId}/tween/path`), request);
+  }
 }
@@ -0,0 +1,154 @@
+/**
+ * Unified engine event stream.
+ *
+ * Every engine event (pointer, drag, state, expression, VRMA and persona lifecycle
+ * events) and every user signal is published on a single bus, exposed over the
+ * `/events/ws` WebSocket. Each event carries a bus-wide sequence number, so a jump
+ * in `seq` on an unfiltered subscription means events were missed.
+ *
+ * @example
+ * ```typescript
+ * import { events } from "@hmcs/sdk";
+ *
+ * const sub = events.subscribe({ events: ["drag-start", "drag-end"] }, (event) => {
+ *   console.log(event.seq, event.event, event.personaId);
+ * });
+ *
+ * // Later, stop receiving events
+ * sub.close();
+ * ```
+ *
+ * @packageDocumentation
+ */
+
+import { host } from './host';
+import type { Subscription } from './signals';
+
+/** Event names carried by the bus, besides `"signal"` for user signals. */
+export type EngineEventName =
+  | 'drag-start'
+  | 'drag'
+  | 'drag-end'
+  | 'pointer-press'
+  | 'pointer-click'
+  | 'pointer-move'
+  | 'pointer-release'
+  | 'pointer-over'
+  | 'pointer-out'
+  | 'pointer-cancel'
+  | 'state-change'
+  | 'expression-change'
+  | 'vrma-play'
+  | 'vrma-finish'
+  | 'vrm-attached'
+  | 'vrm-detached'
+  | 'persona-change'
+  | 'persona-spawned'
+  | 'persona-despawned'
+  | 'persona-deleted'
+  | 'proximity-enter'
+  | 'proximity-leave'
+  | 'walk-arrive'
+  | 'walk-cancel'
+  | 'timeline-play'
+  | 'timeline-cue'
+  | 'timeline-finish'
+  | 'path-tween-loop'
+  | 'path-tween-finish'
+  | 'emotion-change'
+  | 'signal';
+
+/** Selects which events a subscription receives. Omitted fields match everything. */
+export interface EventFilter {
+  /** Event names to receive. */
+  events?: EngineEventName[];
+  /** Only receive events concerning these personas. Events without a persona are excluded. */
+  personas?: string[];
+  /** Signal channel names or wildcard patterns to receive `signal` events for. */
+  signals?: string[];
+}
+
+/** An event delivered by the engine event bus. */
+export interface EngineEvent<V = unknown> {
+  /** Bus-wide sequence number, increasing by one per published event. */
+  seq: number;
+  /** Unix time in milliseconds when the event was published. */
+  timestamp: number;
+  /** The event name. */
+  event: EngineEventName;
+  /** The persona the event concerns, if any. */
+  personaId?: string;
+  /** The signal channel name, for `signal` events. */
+  signal?: string;
+  /** The event payload. */
+  data: V;
+}
+
+/** Options for {@link events.subscribe}. */
+export interface EventSubscribeOptions {
+  /** Called when the engine dropped events because this subscriber fell behind. */
+  onLagged?: (missed: number) => void;
+  /** Called when the engine rejects the filter. */
+  onError?: (message: string) => void;
+}
+
+export namespace events {
+  /**
+   * Subscribe to engine events matching `filter`.
+   *
+   * Opens a dedicated WebSocket to `/events/ws`. Returns a {@link Subscription}
+   * handle; call `.close()` to disconnect.
+   *
+   * @param filter - Which events to receive; `{}` receives everything
+   * @param callback - Called for each matching event
+   * @param options - Optional lag and error handlers
+   */
+  export function subscribe(
+    filter: EventFilter,
+    callback: (event: EngineEvent) => void | Promise<void>,
+    options: EventSubscribeOptions = {},
+  ): Subscription {
+    // Node.js: use `ws` package; Browser: use native WebSocket
+    const WS =
+      typeof globalThis.WebSocket !== 'undefined'
+        ? globalThis.WebSocket
+        : // eslint-disable-next-line @typescript-eslint/no-require-imports
+          (require('ws') as typeof WebSocket);
+
+    const ws = new WS(`${host.base().replace(/^http/, 'ws')}/events/ws`);
+    ws.addEventListener('open', () => {
+      ws.send(JSON.stringify({ type: 'subscribe', ...filter }));
+    });
+    ws.addEventListener('message', (message: MessageEvent) => {
+      try {
+        const msg = JSON.parse(
+          typeof message.data === 'string' ? message.data : message.data.toString(),
+        );
+        switch (msg.type) {
+          case 'event': {
+            const { type: _, ...event } = msg;
+            const result = callback(event as EngineEvent);
+            if (result instanceof Promise) {
+              result.catch((e) => console.error(`Error processing event ${event.event}:`, e));
+            }
+            break;
+          }
+          case 'lagged':
+            options.onLagged?.(msg.missed);
+            break;
+          case 'error':
+            options.onError?.(msg.message);
+            break;
+        }
+      } catch (e) {
+        console.error('events: failed to process WS message', e);
+      }
+    });
+
+    return {
+      close() {
+        ws.close();
+      },
+    };
+  }
+}
@@ -41,11 +41,13 @@
 export * from './app';
 export * from './assets';
 export * from './audio';
+export * from './batch';
 export * from './coordinates';
 export * from './dialog';
 export * from './displays';
 export * from './effects';
 export * from './entities';
+export * from './events';
 export * as fileDialog from './file-dialog.js';
 export * from './host';
 export * from './math';
@@ -58,5 +60,6 @@ export * from './shadowPanel';
 export * from './signals';
 export * from './speech';
 export * from './stt';
+export * from './timelines';
 export * from './utils';
 export * from './webviews';
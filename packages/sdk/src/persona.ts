@@ -1,6 +1,10 @@
 import { EventSource } from 'eventsource';
+import type { assets } from './assets';
+import type { entities } from './entities';
 import { host } from './host';
-import type { Transform } from './math';
+import type { Transform, Vec3 } from './math';
+import type { PhysicsSettings, SpringBoneSettings, WindSettings } from './settings';
+import { PersonaTimelines } from './timelines';
 
 // --- Persona types ---
 
@@ -33,7 +37,7 @@ export interface PersonaSnapshot {
   profile: string;
   /** Free-text personality description for agent prompts. */
   personality?: string | null;
-  /** Current ephemeral state (e.g. "idle", "sitting", "drag"). */
+  /** Current ephemeral state (e.g. "idle", "sitting", "drag", "falling"). */
   state: string;
   /** The asset ID of the currently attached VRM, or null. */
   vrmAssetId?: string | null;
@@ -110,6 +114,8 @@ export interface PersonaFullSnapshot {
   transform: Transform;
   /** Entity IDs of linked webviews. */
   linkedWebviews: string[];
+  /** Current emotion, after fading. */
+  emotion: EmotionState;
   /** Attached VRM state, or null if no VRM is attached. */
   vrm: {
     assetId: string;
@@ -117,6 +123,8 @@ export interface PersonaFullSnapshot {
     animations: unknown[];
     lookAt: unknown | null;
     springBones: unknown;
+    /** License meta of the model, or null if it has not been read. */
+    license: assets.VrmLicense | null;
   } | null;
 }
 
@@ -160,8 +168,136 @@ export type PersonaEventMap = {
   'vrma-play': unknown;
   /** VRMA animation finished. */
   'vrma-finish': unknown;
+  /** Another persona came within the proximity radius. */
+  'proximity-enter': { other: string; distance: number };
+  /** A nearby persona moved out of the proximity radius. */
+  'proximity-leave': { other: string };
+  /** A walk reached its target. */
+  'walk-arrive': { position: Vec3 };
+  /** A walk ended early: `stopped`, `replaced` by another walk, or `interrupted` by a drag. */
+  'walk-cancel': { reason: 'stopped' | 'replaced' | 'interrupted'; position: Vec3 };
+  /** The emotion was set, or faded back to neutral (empty `emotions`). */
+  'emotion-change': EmotionState;
 };
 
+// --- Interaction types ---
+
+/** Options for {@link Persona.approach}. */
+export interface ApproachOptions {
+  /** Horizontal distance to stop at, in world units. Defaults to `0.6`. */
+  distance?: number;
+  /** Duration of the move in milliseconds. Defaults to `1000`; `0` moves instantly. */
+  durationMs?: number;
+  easing?: entities.EasingFunction;
+  /** Turn toward the other persona before moving. Defaults to `true`. */
+  face?: boolean;
+  /** Resolve only after the move finishes. */
+  wait?: boolean;
+}
+
+/** Options for {@link Persona.face}. */
+export interface FaceOptions {
+  /** Duration of the turn in milliseconds. Defaults to `300`; `0` turns instantly. */
+  durationMs?: number;
+  easing?: entities.EasingFunction;
+  /** Resolve only after the turn finishes. */
+  wait?: boolean;
+}
+
+/** Options for {@link Persona.formation}. */
+export interface FormationOptions {
+  /** Persona IDs from left to right. Defaults to every persona with a VRM, ordered by ID. */
+  personas?: string[];
+  /** Distance from the bottom edge of the screen in pixels. */
+  margin?: number;
+  /** Duration of the move in milliseconds. Defaults to `1000`; `0` moves instantly. */
+  durationMs?: number;
+  easing?: entities.EasingFunction;
+  /** Resolve only after the move finishes. */
+  wait?: boolean;
+}
+
+/** Options for {@link Persona.walkTo}. */
+export interface WalkOptions {
+  /** Walking speed in world units per second. Defaults to `0.8`. */
+  speed?: number;
+  /** VRMA looped while walking. Defaults to `"vrma:walk"`. */
+  walkVrma?: string;
+  /** VRMA blended to on arrival. Defaults to `"vrma:idle-maid"`. */
+  idleVrma?: string;
+  /** Blend time between the walk and idle animations in seconds. Defaults to `0.3`. */
+  transitionSecs?: number;
+  /** Resolve only once the walk ends. */
+  wait?: boolean;
+}
+
+/** A procedural gesture, played without a VRMA asset. */
+export type Gesture = 'nod' | 'headShake' | 'shrug' | 'wave' | 'bow' | 'jump' | 'tilt';
+
+/** Options for {@link Persona.gesture}. */
+export interface GestureOptions {
+  /** How pronounced the gesture is (0.0-2.0). Defaults to `1`. */
+  intensity?: number;
+  /** Length of the gesture in milliseconds (100-10000). Defaults to the gesture's own. */
+  durationMs?: number;
+  /** Resolve only once the gesture ends. */
+  wait?: boolean;
+}
+
+// --- Emotion types ---
+
+/**
+ * Options for {@link Persona.setEmotion}.
+ *
+ * Set either `emotion` or `valence`/`arousal`.
+ */
+export interface EmotionOptions {
+  /**
+   * `happy`, `relaxed`, `sad`, `angry`, `surprised`, an emotion from the model's mapping,
+   * or `neutral` to clear the emotion.
+   */
+  emotion?: string;
+  /** Pleasantness, from -1.0 (unpleasant) to 1.0 (pleasant). */
+  valence?: number;
+  /** Activation, from -1.0 (calm) to 1.0 (excited). */
+  arousal?: number;
+  /** Strength of the emotion (0.0-1.0). Defaults to `1.0`. */
+  intensity?: number;
+  /** Seconds the emotion stays at full strength before fading. Defaults to `0`. */
+  holdSecs?: number;
+  /**
+   * Seconds to fade back to neutral; `null` keeps the emotion until it is changed.
+   * Defaults to `3`.
+   */
+  decaySecs?: number | null;
+}
+
+/** A persona's current emotion. */
+export interface EmotionState {
+  /** Current intensity of each emotion (0.0-1.0). Empty when neutral. */
+  emotions: Record<string, number>;
+  /** Pleasantness, from -1.0 (unpleasant) to 1.0 (pleasant). */
+  valence: number;
+  /** Activation, from -1.0 (calm) to 1.0 (excited). */
+  arousal: number;
+}
+
+/** Emotion name to expression weights, e.g. `{ happy: { happy: 1.0 } }`. */
+export type EmotionMapping = Record<string, Record<string, number>>;
+
+/** Engine-wide settings for persona separation and proximity events. */
+export interface InteractionSettings {
+  /** Push overlapping personas apart. */
+  separation: boolean;
+  /** Horizontal gap separation keeps between personas, in world units. */
+  personalSpace: number;
+  /**
+   * Distance at which `proximity-enter` fires, in world units. `proximity-leave` fires
+   * once the personas are 10% further apart.
+   */
+  proximityRadius: number;
+}
+
 /**
  * SSE event source for receiving persona-related events.
  *
@@ -218,6 +354,97 @@ export class PersonaEventSource implements Disposable {
   }
 }
 
+// --- Expression types ---
+
+/** How expression weights move to their new values. */
+export interface ExpressionTransition {
+  /** Duration of the transition in milliseconds. Defaults to `0` (instant). */
+  durationMs?: number;
+  easing?: entities.EasingFunction;
+}
+
+/** Options for {@link PersonaVrm.applyExpressionPreset}. */
+export interface ApplyExpressionPresetOptions extends ExpressionTransition {
+  /** Release every other expression override, like `set`. Defaults to `false`. */
+  replace?: boolean;
+}
+
+/** Auto-blink and micro-expression settings for one persona. */
+export interface AutoExpressionSettings {
+  /** Blink at random intervals. Defaults to `false`. */
+  blink: boolean;
+  /** Average number of blinks per minute (0-120). Defaults to `15`. */
+  blinksPerMinute: number;
+  /** How long one blink takes, in milliseconds (50-1000). Defaults to `150`. */
+  blinkDurationMs: number;
+  /** Flash faint expressions at random intervals. Defaults to `false`. */
+  microExpressions: boolean;
+  /** Average number of micro-expressions per minute (0-120). Defaults to `4`. */
+  microExpressionsPerMinute: number;
+  /** Peak weight of a micro-expression (0.0-1.0). Defaults to `0.25`. */
+  microExpressionIntensity: number;
+  /** Expressions a micro-expression picks from. Defaults to `happy`, `relaxed`, `surprised`. */
+  microExpressionNames: string[];
+}
+
+/** A point for a persona to look at: world space, or global viewport coordinates. */
+export type LookAtPoint = { world: [number, number, number] } | { viewport: [number, number] };
+
+/** How a persona's gaze moves. */
+export interface LookAtSettings {
+  /** Largest horizontal gaze angle, in degrees (0-180). Defaults to `60`. */
+  maxYawDeg: number;
+  /** Largest vertical gaze angle, in degrees (0-90). Defaults to `35`. */
+  maxPitchDeg: number;
+  /** How quickly the gaze follows its target, per second (0-100); `0` snaps. Defaults to `10`. */
+  speed: number;
+  /** Make small, quick eye jumps while looking at something. Defaults to `true`. */
+  saccades: boolean;
+  /** Share of the gaze angle the head turns by (0.0-1.0). Defaults to `0`. */
+  headWeight: number;
+  /** Share of the gaze angle the neck turns by (0.0-1.0). Defaults to `0`. */
+  neckWeight: number;
+}
+
+/** Where a hand or foot reaches to: world space, or global viewport coordinates. */
+export type IkPoint = { world: [number, number, number] } | { viewport: [number, number] };
+
+/** An inverse kinematics target for one hand or foot. */
+export interface IkTarget {
+  /** Where the hand or foot reaches to. Viewport points lie at the depth of the shoulder or hip. */
+  position: IkPoint;
+  /** How much of the solved pose is blended over the animation (0.0-1.0). Defaults to `1`. */
+  weight?: number;
+  /** World-space point the elbow or knee bends toward. Defaults to the way it already bends. */
+  pole?: [number, number, number] | null;
+}
+
+/** Inverse kinematics targets of a persona; limbs without a target follow the animation. */
+export interface IkTargets {
+  leftHand?: IkTarget | null;
+  rightHand?: IkTarget | null;
+  leftFoot?: IkTarget | null;
+  rightFoot?: IkTarget | null;
+}
+
+/** A rotation layered on one humanoid bone. */
+export interface BoneRotation {
+  /** Degrees around the persona's X (pitch), Y (yaw) and Z (roll) axes. */
+  rotation: [number, number, number];
+  /** How much of the rotation is applied (0.0-1.0). Defaults to `1`. */
+  weight?: number;
+}
+
+/** Humanoid bone name (e.g. `head`, `rightUpperArm`) to its rotation. */
+export type BonePose = Record<string, BoneRotation>;
+
+/** How bones move to a new rotation. */
+export interface PoseTransition {
+  /** Duration of the transition in milliseconds. Defaults to `0` (instant). */
+  durationMs?: number;
+  easing?: entities.EasingFunction;
+}
+
 // --- PersonaVrm class ---
 
 /**
@@ -236,8 +463,8 @@ export class PersonaEventSource implements Disposable {
 export class PersonaVrm {
   constructor(private readonly personaId: string) {}
 
-  private url(path: string): URL {
-    return host.createUrl(`personas/${encodeURIComponent(this.personaId)}/vrm/${path}`);
+  private url(path: string, params?: object): URL {
+    return host.createUrl(`personas/${encodeURIComponent(this.personaId)}/vrm/${path}`, params);
   }
 
   /**
@@ -259,26 +486,121 @@ export class PersonaVrm {
    * Modifies specific expression weights (merges with current weights).
    *
    * @param expressions - Expression name-to-weight map (0.0-1.0)
+   * @param transition - Ease from the current weights instead of snapping
    *
    * @example
    * ```typescript
    * await p.vrm().setExpressions({ happy: 1.0, blink: 0.5 });
+   * await p.vrm().setExpressions({ sad: 0.8 }, { durationMs: 400, easing: "sineInOut" });
    * ```
    */
-  async setExpressions(expressions: Record<string, number>): Promise<void> {
-    await host.patch(this.url('expressions'), { weights: expressions });
+  async setExpressions(
+    expressions: Record<string, number>,
+    transition?: ExpressionTransition,
+  ): Promise<void> {
+    await host.patch(this.url('expressions'), { weights: expressions, ...transition });
   }
 
   /**
    * Clears all expression overrides, returning control to VRMA animation.
    *
+   * @param transition - Fade the weights to zero first instead of snapping
+   *
    * @example
    * ```typescript
    * await p.vrm().clearExpressions();
+   * await p.vrm().clearExpressions({ durationMs: 500 });
+   * ```
+   */
+  async clearExpressions(transition?: ExpressionTransition): Promise<void> {
+    await host.deleteMethod(this.url('expressions', transition));
+  }
+
+  /**
+   * Gets the persona's expression presets, by name.
+   */
+  async expressionPresets(): Promise<Record<string, Record<string, number>>> {
+    const response = await host.get(this.url('expressions/presets'));
+    return (await response.json()) as Record<string, Record<string, number>>;
+  }
+
+  /**
+   * Saves a named combination of expression weights for the persona, replacing any
+   * preset with the same name.
+   *
+   * @returns All presets after saving
+   * @throws {HomunculusApiError} 400 if the name is empty or a weight is out of range
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().saveExpressionPreset("smug", { happy: 0.4, blinkLeft: 1.0 });
    * ```
    */
-  async clearExpressions(): Promise<void> {
-    await host.deleteMethod(this.url('expressions'));
+  async saveExpressionPreset(
+    name: string,
+    weights: Record<string, number>,
+  ): Promise<Record<string, Record<string, number>>> {
+    const response = await host.put(
+      this.url(`expressions/presets/${encodeURIComponent(name)}`),
+      { weights },
+    );
+    return (await response.json()) as Record<string, Record<string, number>>;
+  }
+
+  /**
+   * Deletes one of the persona's expression presets.
+   *
+   * @returns The remaining presets
+   * @throws {HomunculusApiError} 404 if the preset does not exist
+   */
+  async deleteExpressionPreset(name: string): Promise<Record<string, Record<string, number>>> {
+    const response = await host.deleteMethod(
+      this.url(`expressions/presets/${encodeURIComponent(name)}`),
+    );
+    return (await response.json()) as Record<string, Record<string, number>>;
+  }
+
+  /**
+   * Applies one of the persona's expression presets.
+   *
+   * @throws {HomunculusApiError} 404 if the preset does not exist
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().applyExpressionPreset("smug", { durationMs: 300, easing: "quadraticOut" });
+   * ```
+   */
+  async applyExpressionPreset(name: string, options?: ApplyExpressionPresetOptions): Promise<void> {
+    await host.post(this.url(`expressions/presets/${encodeURIComponent(name)}/apply`), {
+      ...options,
+    });
+  }
+
+  /**
+   * Gets the persona's auto-blink and micro-expression settings.
+   */
+  async autoExpressions(): Promise<AutoExpressionSettings> {
+    const response = await host.get(this.url('expressions/auto'));
+    return (await response.json()) as AutoExpressionSettings;
+  }
+
+  /**
+   * Replaces and persists the persona's auto-blink and micro-expression settings.
+   *
+   * Fields left out take their defaults.
+   *
+   * @throws {HomunculusApiError} 400 if a rate, duration or intensity is out of range
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().setAutoExpressions({ blink: true, blinksPerMinute: 20 });
+   * ```
+   */
+  async setAutoExpressions(
+    settings: Partial<AutoExpressionSettings>,
+  ): Promise<AutoExpressionSettings> {
+    const response = await host.put(this.url('expressions/auto'), settings);
+    return (await response.json()) as AutoExpressionSettings;
   }
 
   /**
@@ -388,6 +710,227 @@ export class PersonaVrm {
     await host.put(this.url(`look/target/${encodeURIComponent(target)}`));
   }
 
+  /**
+   * Makes the VRM look at a fixed point in the world or on the screen.
+   *
+   * @param point - `{ world: [x, y, z] }` or `{ viewport: [x, y] }` in global viewport coordinates
+   *
+   * @example
+   * ```typescript
+   * // Glance at where a notification webview appears.
+   * await p.vrm().lookAtPoint({ viewport: [1600, 80] });
+   * ```
+   */
+  async lookAtPoint(point: LookAtPoint): Promise<void> {
+    await host.put(this.url('look/point'), point);
+  }
+
+  /**
+   * Lets the VRM's gaze wander idly around.
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().lookWander();
+   * ```
+   */
+  async lookWander(): Promise<void> {
+    await host.put(this.url('look/wander'));
+  }
+
+  /**
+   * Gets the angular limits, smoothing, saccades and head/neck weights of the VRM's gaze.
+   *
+   * @example
+   * ```typescript
+   * const settings = await p.vrm().lookAtSettings();
+   * ```
+   */
+  async lookAtSettings(): Promise<LookAtSettings> {
+    const response = await host.get(this.url('look/settings'));
+    return (await response.json()) as LookAtSettings;
+  }
+
+  /**
+   * Replaces the settings of the VRM's gaze. Fields left out take their defaults.
+   *
+   * @throws {HomunculusApiError} 400 if a limit, speed or weight is out of range,
+   * or the head and neck weights add up to more than 1
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().setLookAtSettings({ maxYawDeg: 45, headWeight: 0.3, neckWeight: 0.2 });
+   * ```
+   */
+  async setLookAtSettings(settings: Partial<LookAtSettings>): Promise<LookAtSettings> {
+    const response = await host.put(this.url('look/settings'), settings);
+    return (await response.json()) as LookAtSettings;
+  }
+
+  /**
+   * Gets the inverse kinematics targets of the VRM's hands and feet.
+   *
+   * @example
+   * ```typescript
+   * const targets = await p.vrm().ik();
+   * ```
+   */
+  async ik(): Promise<IkTargets> {
+    const response = await host.get(this.url('ik'));
+    return (await response.json()) as IkTargets;
+  }
+
+  /**
+   * Replaces the inverse kinematics targets of the VRM's hands and feet.
+   *
+   * Targets are layered on top of the playing VRMA. Limbs left out return to the animation.
+   *
+   * @throws {HomunculusApiError} 400 if a weight is out of range or a position is not finite
+   *
+   * @example
+   * ```typescript
+   * // Point at a spot on the screen with the right hand.
+   * await p.vrm().setIk({ rightHand: { position: { viewport: [1200, 400] }, weight: 0.8 } });
+   * ```
+   */
+  async setIk(targets: IkTargets): Promise<IkTargets> {
+    const response = await host.put(this.url('ik'), targets);
+    return (await response.json()) as IkTargets;
+  }
+
+  /**
+   * Releases every inverse kinematics target, returning the limbs to the animation.
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().clearIk();
+   * ```
+   */
+  async clearIk(): Promise<void> {
+    await host.deleteMethod(this.url('ik'));
+  }
+
+  /**
+   * Gets the bone rotations set on the VRM.
+   */
+  async pose(): Promise<BonePose> {
+    const response = await host.get(this.url('pose'));
+    return (await response.json()) as BonePose;
+  }
+
+  /**
+   * Rotates one humanoid bone on top of the playing VRMA.
+   *
+   * @param bone - Humanoid bone name, e.g. `head` or `rightUpperArm`
+   * @returns The bone rotations after the change
+   * @throws {HomunculusApiError} 400 if the weight is out of range
+   * @throws {HomunculusApiError} 404 if the model has no such bone
+   *
+   * @example
+   * ```typescript
+   * // Tilt the head over 300 ms.
+   * await p.vrm().setBoneRotation('head', { rotation: [0, 0, 15] }, { durationMs: 300 });
+   * ```
+   */
+  async setBoneRotation(
+    bone: string,
+    rotation: BoneRotation,
+    transition?: PoseTransition,
+  ): Promise<BonePose> {
+    const response = await host.put(this.url(`bone/${encodeURIComponent(bone)}`), {
+      ...rotation,
+      ...transition,
+    });
+    return (await response.json()) as BonePose;
+  }
+
+  /**
+   * Returns one bone to the animation.
+   *
+   * @returns The bone rotations after the change
+   */
+  async clearBoneRotation(bone: string, transition?: PoseTransition): Promise<BonePose> {
+    const response = await host.deleteMethod(
+      this.url(`bone/${encodeURIComponent(bone)}`, transition),
+    );
+    return (await response.json()) as BonePose;
+  }
+
+  /**
+   * Replaces every bone rotation of the VRM. Bones left out return to the animation.
+   *
+   * @throws {HomunculusApiError} 400 if a weight is out of range
+   * @throws {HomunculusApiError} 404 if the model lacks one of the bones
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().setPose(
+   *   { leftUpperArm: { rotation: [0, 0, -70] }, rightUpperArm: { rotation: [0, 0, 70] } },
+   *   { durationMs: 500, easing: 'sineInOut' },
+   * );
+   * ```
+   */
+  async setPose(bones: BonePose, transition?: PoseTransition): Promise<BonePose> {
+    const response = await host.put(this.url('pose'), { bones, ...transition });
+    return (await response.json()) as BonePose;
+  }
+
+  /**
+   * Returns every bone to the animation.
+   */
+  async clearPose(transition?: PoseTransition): Promise<void> {
+    await host.deleteMethod(this.url('pose', transition));
+  }
+
+  /**
+   * Gets the persona's named poses.
+   */
+  async posePresets(): Promise<Record<string, BonePose>> {
+    const response = await host.get(this.url('pose/presets'));
+    return (await response.json()) as Record<string, BonePose>;
+  }
+
+  /**
+   * Saves a named pose for the persona, replacing any pose with the same name.
+   *
+   * @returns All poses after saving
+   * @throws {HomunculusApiError} 400 if the name is empty or a weight is out of range
+   */
+  async savePosePreset(name: string, bones: BonePose): Promise<Record<string, BonePose>> {
+    const response = await host.put(this.url(`pose/presets/${encodeURIComponent(name)}`), {
+      bones,
+    });
+    return (await response.json()) as Record<string, BonePose>;
+  }
+
+  /**
+   * Deletes one of the persona's named poses.
+   *
+   * @returns The remaining poses
+   * @throws {HomunculusApiError} 404 if the pose does not exist
+   */
+  async deletePosePreset(name: string): Promise<Record<string, BonePose>> {
+    const response = await host.deleteMethod(this.url(`pose/presets/${encodeURIComponent(name)}`));
+    return (await response.json()) as Record<string, BonePose>;
+  }
+
+  /**
+   * Takes one of the persona's named poses, replacing the current bone rotations.
+   *
+   * @throws {HomunculusApiError} 404 if the pose or one of its bones does not exist
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().applyPosePreset('shrug', { durationMs: 400 });
+   * ```
+   */
+  async applyPosePreset(name: string, transition?: PoseTransition): Promise<BonePose> {
+    const response = await host.post(
+      this.url(`pose/presets/${encodeURIComponent(name)}/apply`),
+      { ...transition },
+    );
+    return (await response.json()) as BonePose;
+  }
+
   /**
    * Disables the VRM's look-at functionality.
    *
@@ -414,7 +957,8 @@ export class PersonaVrm {
   }
 
   /**
-   * Updates spring bone properties for a chain.
+   * Updates spring bone properties for a chain. The change is remembered for the
+   * persona's VRM model and applied again when it loads.
    *
    * @param chainId - The chain entity ID
    * @param props - Partial properties to update
@@ -428,6 +972,43 @@ export class PersonaVrm {
     await host.patch(this.url(`spring-bones/${chainId}`), props);
   }
 
+  /**
+   * Applies a built-in spring bone preset to every chain, scaling the model's own
+   * stiffness, drag and gravity. The preset is remembered for the persona's VRM model
+   * and replaces earlier chain changes; `default` forgets them.
+   *
+   * @param preset - `default`, `stiff`, `soft` or `floaty`
+   * @returns The spring bone chains after applying
+   * @throws {HomunculusApiError} 404 if the preset does not exist
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().applySpringBonePreset('floaty');
+   * ```
+   */
+  async applySpringBonePreset(preset: string): Promise<unknown> {
+    const response = await host.post(
+      this.url(`spring-bones/presets/${encodeURIComponent(preset)}`),
+    );
+    return await response.json();
+  }
+
+  /**
+   * Overrides the wind blowing on this persona's spring bones.
+   *
+   * @param wind - The persona's own wind, or `null` to follow the global wind
+   * @returns The updated spring bone settings
+   *
+   * @example
+   * ```typescript
+   * await p.vrm().setWind({ enabled: true, direction: [-1, 0, 0], strength: 0.6 });
+   * ```
+   */
+  async setWind(wind: Partial<WindSettings> | null): Promise<SpringBoneSettings> {
+    const response = await host.put(this.url('spring-bones/wind'), { wind });
+    return (await response.json()) as SpringBoneSettings;
+  }
+
   /**
    * Speaks using pre-generated audio with a timeline of expression keyframes.
    *
@@ -735,6 +1316,161 @@ export class Persona {
     await host.put(this.url('transform'), transform);
   }
 
+  /**
+   * Moves the persona next to another persona, on the side it is already on.
+   *
+   * @param other - The persona ID to approach
+   * @throws {HomunculusApiError} 404 if either persona is not spawned
+   *
+   * @example
+   * ```typescript
+   * await p.approach("luna", { distance: 0.5, wait: true });
+   * ```
+   */
+  async approach(other: string, options?: ApproachOptions): Promise<void> {
+    await host.post(this.url('approach'), { target: other, ...options });
+  }
+
+  /**
+   * Turns the persona toward another persona.
+   *
+   * @param other - The persona ID to face
+   * @throws {HomunculusApiError} 404 if either persona is not spawned
+   *
+   * @example
+   * ```typescript
+   * await p.face("luna");
+   * ```
+   */
+  async face(other: string, options?: FaceOptions): Promise<void> {
+    await host.post(this.url('face'), { target: other, ...options });
+  }
+
+  /**
+   * Walks the persona to a position instead of moving it instantly.
+   *
+   * The persona turns toward the direction of travel and is in the `walking` state
+   * until it arrives. Walks across monitors pass through the edge the monitors share.
+   * Listen for `walk-arrive` and `walk-cancel` to follow progress.
+   *
+   * @param target - World or global viewport position to walk to
+   * @throws {HomunculusApiError} 400 if `speed` is not positive
+   *
+   * @example
+   * ```typescript
+   * await p.walkTo(
+   *   { type: "viewport", position: [800, 900] },
+   *   { walkVrma: "vrma:walk", idleVrma: "vrma:idle-maid", wait: true },
+   * );
+   * ```
+   */
+  async walkTo(target: entities.MoveTarget, options?: WalkOptions): Promise<void> {
+    await host.post(this.url('walk'), { target, ...options });
+  }
+
+  /**
+   * Stops the persona where it is and returns it to `idle`.
+   *
+   * @throws {HomunculusApiError} 409 if the persona is not walking
+   */
+  async stopWalking(): Promise<void> {
+    await host.post(this.url('walk/stop'));
+  }
+
+  /**
+   * Performs a short procedural gesture, layered over the playing animation.
+   *
+   * Bones return to where they were when the gesture ends. A new gesture replaces one
+   * in progress.
+   *
+   * @throws {HomunculusApiError} 400 if `intensity` or `durationMs` is out of range
+   *
+   * @example
+   * ```typescript
+   * await p.gesture('nod');
+   * await p.gesture('wave', { intensity: 1.5, durationMs: 2500, wait: true });
+   * ```
+   */
+  async gesture(gesture: Gesture, options?: GestureOptions): Promise<void> {
+    await host.post(this.url(`gestures/${gesture}`), { ...options });
+  }
+
+  /**
+   * Overrides whether this persona falls when released from a drag.
+   *
+   * @param enabled - `true` or `false` to override, `null` to follow the global setting
+   * @returns The updated physics settings
+   *
+   * @example
+   * ```typescript
+   * await p.setPhysics(true);
+   * ```
+   */
+  async setPhysics(enabled: boolean | null): Promise<PhysicsSettings> {
+    const response = await host.put(this.url('physics'), { enabled });
+    return (await response.json()) as PhysicsSettings;
+  }
+
+  /**
+   * Sets the persona's emotion, replacing the current one.
+   *
+   * The emotion drives the persona's expressions through the VRM model's emotion mapping,
+   * holds for `holdSecs`, then fades back to neutral over `decaySecs`.
+   *
+   * @returns The emotion as set
+   * @throws {HomunculusApiError} 400 if the emotion is unknown or a value is out of range
+   *
+   * @example
+   * ```typescript
+   * await p.setEmotion({ emotion: "happy", holdSecs: 2 });
+   * await p.setEmotion({ valence: -0.4, arousal: 0.6, intensity: 0.5 });
+   * ```
+   */
+  async setEmotion(options: EmotionOptions): Promise<EmotionState> {
+    const response = await host.post(this.url('emotion'), options);
+    return (await response.json()) as EmotionState;
+  }
+
+  /**
+   * Gets the persona's current emotion, after fading.
+   *
+   * @example
+   * ```typescript
+   * const { emotions, valence } = await p.emotion();
+   * ```
+   */
+  async emotion(): Promise<EmotionState> {
+    const response = await host.get(this.url('emotion'));
+    return (await response.json()) as EmotionState;
+  }
+
+  /**
+   * Gets the emotion-to-expression mapping of the persona's VRM model, including defaults.
+   */
+  async emotionMapping(): Promise<EmotionMapping> {
+    const response = await host.get(this.url('emotion/mapping'));
+    return (await response.json()) as EmotionMapping;
+  }
+
+  /**
+   * Stores an emotion-to-expression mapping for the persona's VRM model.
+   *
+   * Emotions in `mapping` replace their defaults and can add new emotions.
+   *
+   * @returns The updated mapping, including defaults
+   * @throws {HomunculusApiError} 409 if no VRM is attached
+   *
+   * @example
+   * ```typescript
+   * await p.setEmotionMapping({ sleepy: { relaxed: 0.6, blink: 0.8 } });
+   * await p.setEmotion({ emotion: "sleepy", decaySecs: null });
+   * ```
+   */
+  async setEmotionMapping(mapping: EmotionMapping): Promise<EmotionMapping> {
+    const response = await host.put(this.url('emotion/mapping'), mapping);
+    return (await response.json()) as EmotionMapping;
+  }
+
   /**
    * Applies a partial update to the persona, only modifying specified fields.
    *
@@ -930,6 +1666,18 @@ export class Persona {
     return new PersonaVrm(this.id);
   }
 
+  /**
+   * Returns a {@link PersonaTimelines} accessor for playing timeline scripts.
+   *
+   * @example
+   * ```typescript
+   * await p.timelines().play({ asset: "my-mod:greeting" });
+   * ```
+   */
+  timelines(): PersonaTimelines {
+    return new PersonaTimelines(this.id);
+  }
+
   /**
    * Returns an SSE event source for receiving events related to this persona.
    *
@@ -1032,6 +1780,47 @@ export class Persona {
     const response = await host.get(host.createUrl('personas'));
     return (await response.json()) as PersonaSnapshot[];
   }
+
+  /**
+   * Spreads personas evenly along the bottom of the primary monitor.
+   *
+   * @example
+   * ```typescript
+   * await Persona.formation({ personas: ["alice", "luna"], margin: 40 });
+   * ```
+   */
+  static async formation(options?: FormationOptions): Promise<void> {
+    await host.post(host.createUrl('interaction/formation'), options ?? {});
+  }
+
+  /**
+   * Gets the separation and proximity settings.
+   *
+   * @example
+   * ```typescript
+   * const settings = await Persona.interactionSettings();
+   * ```
+   */
+  static async interactionSettings(): Promise<InteractionSettings> {
+    const response = await host.get(host.createUrl('interaction/settings'));
+    return (await response.json()) as InteractionSettings;
+  }
+
+  /**
+   * Replaces the separation and proximity settings.
+   *
+   * @example
+   * ```typescript
+   * const settings = await Persona.interactionSettings();
+   * await Persona.setInteractionSettings({ ...settings, separation: false });
+   * ```
+   */
+  static async setInteractionSettings(
+    settings: InteractionSettings,
+  ): Promise<InteractionSettings> {
+    const response = await host.put(host.createUrl('interaction/settings'), settings);
+    return (await response.json()) as InteractionSettings;
+  }
 }
 
 // --- VrmaRepeat & repeat helpers ---
@@ -77,6 +77,7 @@ export namespace rpc {
    * @returns The parsed JSON response from the MOD method handler
    * @throws {HomunculusApiError} status 503 — MOD not registered
    * @throws {HomunculusApiError} status 404 — method not found
+   * @throws {HomunculusApiError} status 422 — body does not match the method's input schema
    * @throws {HomunculusApiError} status 504 — timeout exceeded
    * @throws {HomunculusApiError} status 502 — MOD service unreachable
    *
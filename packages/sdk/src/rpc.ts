@@ -67,6 +67,11 @@ export interface RpcMethodDef<I = unknown, O = unknown> {
   timeout?: number;
   /** Zod schema used to validate incoming request bodies. */
   input?: ZodType<I>;
+  /**
+   * Optional Zod schema describing the response. Published to the engine as
+   * `outputSchema` for client code generation and debug-mode validation.
+   */
+  output?: ZodType<O>;
   /** Async function called with the validated input. */
   handler: (params: I) => Promise<O>;
   /**
@@ -302,6 +307,9 @@ function buildMethodsMeta(
         ...(entry.input !== undefined
           ? { inputSchema: convertZodToObjectSchema(entry.input) }
           : {}),
+        ...(entry.output !== undefined
+          ? { outputSchema: zodToJsonSchema(entry.output, { $refStrategy: 'none' }) }
+          : {}),
         ...(entry.meta !== undefined ? { meta: entry.meta } : {}),
       };
     } else {
@@ -5,6 +5,47 @@ export interface SetFpsBody {
   fps: number;
 }
 
+/** Gravity and throwing for personas released from a drag. */
+export interface PhysicsSettings {
+  /** Whether released personas fall. Entries in `personas` take precedence. */
+  enabled: boolean;
+  /** Downward acceleration in world units per second squared. Defaults to `9.8`. */
+  gravity: number;
+  /** Fraction of the speed kept on each bounce, from `0` to `1`. Defaults to `0.3`. */
+  bounciness: number;
+  /** Upper bound of the throw speed in world units per second. Defaults to `10`. */
+  maxThrowSpeed: number;
+  /** Per-persona overrides of `enabled`, keyed by persona ID. */
+  personas: Record<string, boolean>;
+}
+
+/** Wind blowing on a persona's spring bones (hair, clothing). */
+export interface WindSettings {
+  /** Defaults to `false`. */
+  enabled: boolean;
+  /** Direction the wind blows toward, in world space. Defaults to `[1, 0, 0]`. */
+  direction: [number, number, number];
+  /** Average force of the wind, from `0` to `5`. Defaults to `0.3`. */
+  strength: number;
+  /** How far gusts push the force around `strength`, from `0` to `1`. Defaults to `0.5`. */
+  gustiness: number;
+  /** Rate of gusts per second, from `0` to `10`. Defaults to `0.4`. */
+  gustFrequency: number;
+}
+
+/** Wind and motion reactions for spring bones. */
+export interface SpringBoneSettings {
+  /** Wind for personas without their own. */
+  wind: WindSettings;
+  /** Per-persona wind, keyed by persona ID. */
+  personas: Record<string, WindSettings>;
+  /**
+   * How strongly spring bones swing when a persona is dragged or lands, from `0` (off)
+   * to `1`. Defaults to `0.03`.
+   */
+  reaction: number;
+}
+
 /**
  * Settings API namespace for controlling application-level configuration.
  *
@@ -44,4 +85,66 @@ export namespace settings {
   export async function setFps(fps: number): Promise<void> {
     await host.put(host.createUrl('settings/fps'), { fps });
   }
+
+  /**
+   * Gets the gravity and throwing settings for released personas.
+   *
+   * @example
+   * ```typescript
+   * const { enabled } = await settings.physics();
+   * ```
+   */
+  export async function physics(): Promise<PhysicsSettings> {
+    const response = await host.get(host.createUrl('settings/physics'));
+    return (await response.json()) as PhysicsSettings;
+  }
+
+  /**
+   * Replaces the gravity and throwing settings. Persists and applies to the next release.
+   *
+   * @throws {HomunculusApiError} 400 if `gravity` is not positive, `bounciness` is outside
+   * `0..1` or `maxThrowSpeed` is negative
+   *
+   * @example
+   * ```typescript
+   * const current = await settings.physics();
+   * await settings.setPhysics({ ...current, enabled: true });
+   * ```
+   */
+  export async function setPhysics(physics: PhysicsSettings): Promise<PhysicsSettings> {
+    const response = await host.put(host.createUrl('settings/physics'), physics);
+    return (await response.json()) as PhysicsSettings;
+  }
+
+  /**
+   * Gets the wind and reaction settings for spring bones.
+   *
+   * @example
+   * ```typescript
+   * const { wind } = await settings.springBones();
+   * ```
+   */
+  export async function springBones(): Promise<SpringBoneSettings> {
+    const response = await host.get(host.createUrl('settings/spring-bones'));
+    return (await response.json()) as SpringBoneSettings;
+  }
+
+  /**
+   * Replaces the wind and reaction settings for spring bones. Persists and applies
+   * immediately.
+   *
+   * @throws {HomunculusApiError} 400 if a wind value or `reaction` is out of range
+   *
+   * @example
+   * ```typescript
+   * const current = await settings.springBones();
+   * await settings.setSpringBones({ ...current, wind: { ...current.wind, enabled: true } });
+   * ```
+   */
+  export async function setSpringBones(
+    springBones: SpringBoneSettings,
+  ): Promise<SpringBoneSettings> {
+    const response = await host.put(host.createUrl('settings/spring-bones'), springBones);
+    return (await response.json()) as SpringBoneSettings;
+  }
 }
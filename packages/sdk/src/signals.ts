@@ -32,6 +32,40 @@ export interface SignalChannelInfo {
   signal: string;
   /** The number of active subscribers. */
   subscribers: number;
+  /** The number of active subscribers to wildcard patterns matching this channel. */
+  patternSubscribers: number;
+  /** The retention policy, if history is kept for this channel. */
+  retention?: SignalRetention;
+}
+
+/** Opt-in history retention for a signal channel. At least one limit must be set. */
+export interface SignalRetention {
+  /** Keep at most this many messages (max 10,000). */
+  maxMessages?: number;
+  /** Keep messages for at most this many seconds. */
+  maxAgeSecs?: number;
+  /** Store the history in the preferences database so it survives restarts. */
+  persist?: boolean;
+}
+
+/** A request delivered to subscribers by `signals.request()`. */
+export interface SignalRequestMessage<V = unknown> {
+  /** Identifies the request; must be echoed back in the reply. */
+  correlationId: string;
+  /** The signal channel the reply must be sent to. */
+  replyTo: string;
+  /** The request payload. */
+  payload: V;
+}
+
+/** A retained signal message. */
+export interface SignalRecord<V = unknown> {
+  /** Sequence number, increasing by one per message on the same channel. */
+  seq: number;
+  /** Unix time in milliseconds when the message was sent. */
+  timestamp: number;
+  /** The message payload. */
+  payload: V;
 }
 
 /** A handle to an active signal subscription. Call `.close()` to unsubscribe. */
@@ -43,11 +77,13 @@ export interface Subscription {
 export namespace signals {
   // --- Connection state ---
 
-  type Callback = (payload: unknown) => void | Promise<void>;
+  type Callback = (payload: unknown, channel: string) => void | Promise<void>;
 
   let ws: WebSocket | null = null;
   let connectPromise: Promise<void> | null = null;
   const listeners = new Map<string, Set<Callback>>();
+  /** Last received sequence number per channel, used to replay missed messages on reconnect. */
+  const lastSeqs = new Map<string, number>();
   const pendingSubscribes: string[] = [];
   let reconnectTimer: ReturnType<typeof setTimeout> | null = null;
   let reconnectDelay = 1000;
@@ -90,9 +126,9 @@ export namespace signals {
         }
         pendingSubscribes.length = 0;
 
-        // Re-subscribe all active channels
+        // Re-subscribe all active channels, replaying retained messages missed while disconnected
         for (const channel of listeners.keys()) {
-          sendFrame({ type: 'subscribe', channel });
+          sendFrame({ type: 'subscribe', channel, since: lastSeqs.get(channel) });
         }
 
         resolve();
@@ -103,7 +139,14 @@ export namespace signals {
       try {
         const msg = JSON.parse(typeof event.data === 'string' ? event.data : event.data.toString());
         if (msg.channel && 'data' in msg) {
-          dispatch(msg.channel, msg.data);
+          if (msg.pattern) {
+            dispatch(msg.pattern, msg.channel, msg.data);
+            return;
+          }
+          if (typeof msg.seq === 'number') {
+            lastSeqs.set(msg.channel, msg.seq);
+          }
+          dispatch(msg.channel, msg.channel, msg.data);
         }
       } catch (e) {
         console.error('signals: failed to parse WS message', e);
@@ -139,12 +182,12 @@ export namespace signals {
     }
   }
 
-  function dispatch(channel: string, data: unknown): void {
-    const cbs = listeners.get(channel);
+  function dispatch(subscription: string, channel: string, data: unknown): void {
+    const cbs = listeners.get(subscription);
     if (!cbs) return;
     for (const cb of cbs) {
       try {
-        const result = cb(data);
+        const result = cb(data, channel);
         if (result instanceof Promise) {
           result.catch((e) => console.error(`Error processing signal ${channel}:`, e));
         }
@@ -180,8 +223,13 @@ export namespace signals {
    * Returns a {@link Subscription} handle. Call `.close()` to unsubscribe.
    * Internally multiplexed over a single WebSocket connection per JS runtime.
    *
+   * `signal` may be a wildcard pattern: channel names are split into segments at
+   * `.`, `/` and `:`, `*` matches one segment and a trailing `#` matches any number
+   * of segments (e.g. `persona.*`, `stt/#`). The callback receives the name of the
+   * channel each message was sent on.
+   *
    * @typeParam V - Expected payload type (documentation-level safety)
-   * @param signal - Signal channel name to subscribe to
+   * @param signal - Signal channel name or pattern to subscribe to
    * @param f - Callback invoked for each received event
    * @returns A subscription handle with `.close()` method
    *
@@ -193,9 +241,17 @@ export namespace signals {
    *
    * // Later, unsubscribe
    * sub.close();
+   *
+   * // Every persona event
+   * signals.stream("persona.*", (data, channel) => {
+   *   console.log(channel, data);
+   * });
    * ```
    */
-  export function stream<V>(signal: string, f: (payload: V) => void | Promise<void>): Subscription {
+  export function stream<V>(
+    signal: string,
+    f: (payload: V, channel: string) => void | Promise<void>,
+  ): Subscription {
     const callback = f as Callback;
     let closed = false;
 
@@ -228,6 +284,7 @@ export namespace signals {
           channelCbs.delete(callback);
           if (channelCbs.size === 0) {
             listeners.delete(signal);
+            lastSeqs.delete(signal);
             sendFrame({ type: 'unsubscribe', channel: signal });
           }
         }
@@ -252,4 +309,82 @@ export namespace signals {
   export async function send<V>(signal: string, payload: V): Promise<void> {
     await host.post(host.createUrl(`signals/${signal}`), payload);
   }
+
+  /**
+   * Get the retained messages of a signal channel, oldest first.
+   *
+   * Only channels with a retention policy (see {@link setRetention}) keep history.
+   *
+   * @param signal - Signal channel name
+   * @param since - Only return messages with a greater sequence number
+   *
+   * @example
+   * ```typescript
+   * const missed = await signals.history<{ state: string }>("openclaw:status", lastSeq);
+   * ```
+   */
+  export async function history<V>(signal: string, since?: number): Promise<SignalRecord<V>[]> {
+    const response = await host.get(
+      host.createUrl(`signals/${signal}/history`, since === undefined ? undefined : { since }),
+    );
+    return (await response.json()) as SignalRecord<V>[];
+  }
+
+  /**
+   * Enable history retention for a signal channel, replacing any existing policy.
+   *
+   * @example
+   * ```typescript
+   * await signals.setRetention("openclaw:status", { maxMessages: 50, persist: true });
+   * ```
+   */
+  export async function setRetention(signal: string, retention: SignalRetention): Promise<void> {
+    await host.put(host.createUrl(`signals/${signal}/retention`), retention);
+  }
+
+  /** Disable history retention for a signal channel and discard its history. */
+  export async function clearRetention(signal: string): Promise<void> {
+    await host.deleteMethod(host.createUrl(`signals/${signal}/retention`));
+  }
+
+  /**
+   * Send a request on a signal channel and wait for the first reply.
+   *
+   * Subscribers receive a {@link SignalRequestMessage} and answer with {@link reply}.
+   * Rejects with a 504 error if no reply arrives within `timeoutMs` (default 30 s).
+   *
+   * @example
+   * ```typescript
+   * const answer = await signals.request<{ text: string }, { ok: boolean }>(
+   *   "my-mod:confirm",
+   *   { text: "Proceed?" },
+   *   { timeoutMs: 10_000 },
+   * );
+   * ```
+   */
+  export async function request<Req, Res>(
+    signal: string,
+    payload: Req,
+    options?: { timeoutMs?: number },
+  ): Promise<Res> {
+    const response = await host.post(host.createUrl(`signals/${signal}/request`), {
+      payload,
+      timeoutMs: options?.timeoutMs,
+    });
+    return (await response.json()) as Res;
+  }
+
+  /**
+   * Reply to a request received through {@link request}.
+   *
+   * @example
+   * ```typescript
+   * signals.stream<SignalRequestMessage<{ text: string }>>("my-mod:confirm", async (req) => {
+   *   await signals.reply(req, { ok: true });
+   * });
+   * ```
+   */
+  export async function reply<Res>(request: SignalRequestMessage, payload: Res): Promise<void> {
+    await send(request.replyTo, { correlationId: request.correlationId, payload });
+  }
 }
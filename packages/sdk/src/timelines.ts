@@ -0,0 +1,213 @@
+/**
+ * Timeline scripts: declarative choreography for personas.
+ *
+ * A timeline lists cues at offsets in seconds. The engine fires every cue on its own
+ * clock, so tracks run in parallel and stay in sync with the frame. Scripts are passed
+ * inline or registered as `timeline` assets in a MOD's `package.json`.
+ *
+ * @example
+ * ```typescript
+ * import { Persona } from "@hmcs/sdk";
+ *
+ * const p = await Persona.load("elmer");
+ * await p.timelines().play({
+ *   timeline: {
+ *     tracks: [
+ *       { name: "body", cues: [
+ *         { at: 0, type: "vrma", asset: "vrma:wave" },
+ *         { at: 1.2, type: "move", viewport: [800, 600], durationMs: 600, easing: "backOut" },
+ *       ] },
+ *       { name: "face", cues: [{ at: 0.3, type: "expressions", weights: { happy: 1 } }] },
+ *     ],
+ *   },
+ * });
+ * ```
+ *
+ * @packageDocumentation
+ */
+
+import type { entities } from './entities';
+import { host } from './host';
+import type { VrmaRepeat } from './persona';
+import type { TimelineKeyframe } from './speech';
+
+/**
+ * What a cue does when it fires, discriminated by `type`.
+ *
+ * `expressions` merges weights into the current ones unless `replace` is set. `speak`
+ * plays a WAV `sound` asset with lip-sync keyframes. `move` takes exactly one of a world
+ * `position` or a `viewport` position.
+ */
+export type TimelineAction =
+  | {
+      type: 'vrma';
+      asset: string;
+      repeat?: VrmaRepeat;
+      transitionSecs?: number;
+      resetSpringBones?: boolean;
+    }
+  | { type: 'stopVrma'; asset: string }
+  | { type: 'expressions'; weights: Record<string, number>; replace?: boolean }
+  | { type: 'clearExpressions' }
+  | { type: 'speak'; asset: string; keyframes: TimelineKeyframe[]; transitionDuration?: number }
+  | {
+      type: 'stamp';
+      asset: string;
+      options?: {
+        display?: number;
+        bounds?: { min: [number, number]; max: [number, number] };
+        size?: [number, number];
+        durationSecs?: number;
+      };
+    }
+  | { type: 'sound'; asset: string; volume?: number; speed?: number; panning?: number }
+  | {
+      type: 'move';
+      position?: [number, number, number];
+      viewport?: [number, number];
+      durationMs: number;
+      easing?: entities.EasingFunction;
+    }
+  | {
+      type: 'rotate';
+      target: [number, number, number, number];
+      durationMs: number;
+      easing?: entities.EasingFunction;
+    }
+  | {
+      type: 'scale';
+      target: [number, number, number];
+      durationMs: number;
+      easing?: entities.EasingFunction;
+    }
+  | { type: 'signal'; signal: string; payload?: unknown };
+
+/** An action fired `at` seconds after the timeline starts. */
+export type TimelineCue = { at: number } & TimelineAction;
+
+/** A named group of cues. */
+export interface TimelineTrack {
+  /** Name reported in `timeline-cue` events. */
+  name?: string;
+  cues: TimelineCue[];
+}
+
+/** A timeline script, inline or as the content of a `timeline` asset. */
+export interface TimelineScript {
+  /** Total length in seconds. Defaults to the end of the last cue. */
+  duration?: number;
+  tracks: TimelineTrack[];
+}
+
+/** Options for {@link PersonaTimelines.play}. Give exactly one of `asset` or `timeline`. */
+export interface PlayTimelineOptions {
+  /** A registered `timeline` asset. */
+  asset?: string;
+  /** An inline timeline script. */
+  timeline?: TimelineScript;
+  /** Restart from the beginning each time the timeline ends. */
+  loop?: boolean;
+}
+
+/** Playback state of a persona's timeline. */
+export interface TimelineStatus {
+  /** The asset ID of the timeline, or `"inline"`. */
+  timeline: string;
+  /** Seconds since the start of the timeline (or of the current loop). */
+  elapsed: number;
+  /** Length of the timeline in seconds. */
+  duration: number;
+  paused: boolean;
+  loop: boolean;
+}
+
+/**
+ * Accessor for timeline playback on a persona.
+ *
+ * Obtained via {@link Persona.timelines}. Progress is published on the event bus as
+ * `timeline-play`, `timeline-cue` and `timeline-finish` events.
+ */
+export class PersonaTimelines {
+  constructor(private readonly personaId: string) {}
+
+  private url(path?: string): URL {
+    const base = `personas/${encodeURIComponent(this.personaId)}/timelines`;
+    return host.createUrl(path ? `${base}/${path}` : base);
+  }
+
+  /**
+   * Plays a timeline, replacing the one the persona is playing.
+   *
+   * Resolves once the VRMA animations and speech audio it uses are loaded and the
+   * timeline has started.
+   *
+   * @throws {HomunculusApiError} 400 for an invalid script, 404 if an asset is missing
+   *
+   * @example
+   * ```typescript
+   * await p.timelines().play({ asset: "my-mod:greeting" });
+   * ```
+   */
+  async play(options: PlayTimelineOptions): Promise<TimelineStatus> {
+    const response = await host.post(this.url('play'), options);
+    return (await response.json()) as TimelineStatus;
+  }
+
+  /**
+   * Gets the playback state, or `null` if no timeline is playing.
+   *
+   * @example
+   * ```typescript
+   * const status = await p.timelines().status();
+   * ```
+   */
+  async status(): Promise<TimelineStatus | null> {
+    const response = await host.get(this.url());
+    return (await response.json()) as TimelineStatus | null;
+  }
+
+  /**
+   * Stops the timeline. Actions already started run to completion.
+   *
+   * @throws {HomunculusApiError} 409 if no timeline is playing
+   */
+  async stop(): Promise<void> {
+    await host.post(this.url('stop'), {});
+  }
+
+  /**
+   * Pauses the timeline clock and the VRMA animations the timeline started.
+   *
+   * @throws {HomunculusApiError} 409 if no timeline is playing
+   */
+  async pause(): Promise<TimelineStatus> {
+    const response = await host.post(this.url('pause'), {});
+    return (await response.json()) as TimelineStatus;
+  }
+
+  /**
+   * Resumes a paused timeline.
+   *
+   * @throws {HomunculusApiError} 409 if no timeline is playing
+   */
+  async resume(): Promise<TimelineStatus> {
+    const response = await host.post(this.url('resume'), {});
+    return (await response.json()) as TimelineStatus;
+  }
+
+  /**
+   * Moves the timeline to `at` seconds. Cues before it are skipped; cues from it on
+   * fire again.
+   *
+   * @throws {HomunculusApiError} 400 if `at` is outside the timeline, 409 if none is playing
+   *
+   * @example
+   * ```typescript
+   * await p.timelines().seek(1.5);
+   * ```
+   */
+  async seek(at: number): Promise<TimelineStatus> {
+    const response = await host.post(this.url('seek'), { at });
+    return (await response.json()) as TimelineStatus;
+  }
+}